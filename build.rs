@@ -56,7 +56,7 @@ mod cdk {
             writeln!(
                 file,
                 "static {static_attrs}: phf::Map<&str, &Property> = {};",
-                properties.build()
+                attributes.build()
             )?;
             writeln!(
                 file,
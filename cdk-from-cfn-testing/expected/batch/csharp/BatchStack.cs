@@ -24,6 +24,9 @@ namespace BatchStack
 
         public BatchStack(Construct scope, string id, BatchStackProps props = null) : base(scope, id, props)
         {
+            // Template options
+            TemplateOptions.TemplateFormatVersion = "2010-09-09";
+
 
             // Resources
             var batchServiceRole = new CfnRole(this, "BatchServiceRole", new CfnRoleProps
@@ -28,6 +28,8 @@ func NewBatchStack(scope constructs.Construct, id string, props *BatchStackProps
 	}
 	stack := cdk.NewStack(scope, &id, &sprops)
 
+	stack.TemplateOptions().SetTemplateFormatVersion(jsii.String("2010-09-09"))
+
 	batchServiceRole := iam.NewCfnRole(
 		stack,
 		jsii.String("BatchServiceRole"),
@@ -85,7 +87,7 @@ func NewBatchStack(scope constructs.Construct, id string, props *BatchStackProps
 		jsii.String("JobDefinition"),
 		&batch.CfnJobDefinitionProps{
 			Type: jsii.String("container"),
-			ContainerProperties: &ContainerPropertiesProperty{
+			ContainerProperties: &batch.CfnJobDefinition_ContainerPropertiesProperty{
 				Image: cdk.Fn_Join(jsii.String(""), &[]*string{
 					jsii.String("137112412989.dkr.ecr."),
 					stack.Region(),
@@ -98,7 +100,7 @@ func NewBatchStack(scope constructs.Construct, id string, props *BatchStackProps
 					jsii.String("Hello world"),
 				},
 			},
-			RetryStrategy: &RetryStrategyProperty{
+			RetryStrategy: &batch.CfnJobDefinition_RetryStrategyProperty{
 				Attempts: jsii.Number(1),
 			},
 		},
@@ -163,7 +165,7 @@ func NewBatchStack(scope constructs.Construct, id string, props *BatchStackProps
 		jsii.String("ComputeEnvironment"),
 		&batch.CfnComputeEnvironmentProps{
 			Type: jsii.String("MANAGED"),
-			ComputeResources: &ComputeResourcesProperty{
+			ComputeResources: &batch.CfnComputeEnvironment_ComputeResourcesProperty{
 				Type: jsii.String("EC2"),
 				MinvCpus: jsii.Number(0),
 				DesiredvCpus: jsii.Number(0),
@@ -208,7 +210,7 @@ func NewBatchStack(scope constructs.Construct, id string, props *BatchStackProps
 		&batch.CfnJobQueueProps{
 			Priority: jsii.Number(1),
 			ComputeEnvironmentOrder: &[]interface{}{
-				&ComputeEnvironmentOrderProperty{
+				&batch.CfnJobQueue_ComputeEnvironmentOrderProperty{
 					Order: jsii.Number(1),
 					ComputeEnvironment: computeEnvironment.Ref(),
 				},
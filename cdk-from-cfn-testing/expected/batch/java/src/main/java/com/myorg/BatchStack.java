@@ -39,6 +39,8 @@ class BatchStack extends Stack {
     public BatchStack(final Construct scope, final String id, final StackProps props) {
         super(scope, id, props);
 
+        this.getTemplateOptions().setTemplateFormatVersion("2010-09-09");
+
         CfnRole batchServiceRole = CfnRole.Builder.create(this, "BatchServiceRole")
                 .assumeRolePolicyDocument(Map.of("Version", "2012-10-17",
                 "Statement", Arrays.asList(
@@ -12,6 +12,9 @@ class BatchStack(Stack):
   def __init__(self, scope: Construct, construct_id: str, **kwargs) -> None:
     super().__init__(scope, construct_id, **kwargs)
 
+    # Template options
+    self.template_options.template_format_version = '2010-09-09'
+
     # Resources
     batchServiceRole = iam.CfnRole(self, 'BatchServiceRole',
           assume_role_policy_document = {
@@ -17,6 +17,9 @@ export class BatchStack extends cdk.Stack {
   public constructor(scope: cdk.App, id: string, props: BatchStackProps = {}) {
     super(scope, id, props);
 
+    // Template options
+    this.templateOptions.templateFormatVersion = '2010-09-09';
+
     // Resources
     const batchServiceRole = new iam.CfnRole(this, 'BatchServiceRole', {
       assumeRolePolicyDocument: {
@@ -22,6 +22,18 @@ namespace CloudwatchStack
             props ??= new CloudwatchStackProps();
             props.EnvironmentName ??= "dev";
 
+            // Validating props
+            if (props.EnvironmentName != null && !Token.IsUnresolved(props.EnvironmentName))
+            {
+                if (!new List<string> { "dev", "stage", "prod" }.Contains(props.EnvironmentName))
+                {
+                    throw new System.ArgumentException("environmentName must be one of: dev, stage, prod");
+                }
+            }
+
+            // Template options
+            TemplateOptions.TemplateFormatVersion = "2010-09-09";
+
 
             // Resources
             var myApi5xxErrorsAlarm = new CfnAlarm(this, "MyApi5xxErrorsAlarm", new CfnAlarmProps
@@ -26,6 +26,18 @@ func NewCloudwatchStack(scope constructs.Construct, id string, props *Cloudwatch
 	}
 	stack := cdk.NewStack(scope, &id, &sprops)
 
+	if props != nil {
+		if value := props.EnvironmentName; value != nil && !*cdk.Token_IsUnresolved(value) {
+			switch *value {
+			case "dev", "stage", "prod":
+			default:
+				panic("environmentName must be one of: dev, stage, prod")
+			}
+		}
+	}
+
+	stack.TemplateOptions().SetTemplateFormatVersion(jsii.String("2010-09-09"))
+
 	cloud_watch.NewCfnAlarm(
 		stack,
 		jsii.String("MyApi5xxErrorsAlarm"),
@@ -33,7 +45,7 @@ func NewCloudwatchStack(scope constructs.Construct, id string, props *Cloudwatch
 			AlarmDescription: jsii.String("Example alarm"),
 			Namespace: jsii.String("AWS/ApiGateway"),
 			Dimensions: &[]interface{}{
-				&DimensionProperty{
+				&cloudwatch.CfnAlarm_DimensionProperty{
 					Name: jsii.String("ApiName"),
 					Value: jsii.String("MyApi"),
 				},
@@ -27,6 +27,14 @@ class CloudwatchStack extends Stack {
         environmentName = Optional.ofNullable(environmentName).isPresent() ? environmentName
                 : "dev";
 
+        if (environmentName != null && !Token.isUnresolved(environmentName)) {
+            if (!Arrays.asList("dev", "stage", "prod").contains(environmentName)) {
+                throw new IllegalArgumentException("environmentName must be one of: dev, stage, prod");
+            }
+        }
+
+        this.getTemplateOptions().setTemplateFormatVersion("2010-09-09");
+
         CfnAlarm myApi5xxErrorsAlarm = CfnAlarm.Builder.create(this, "MyApi5xxErrorsAlarm")
                 .alarmDescription("Example alarm")
                 .namespace("AWS/ApiGateway")
@@ -12,6 +12,14 @@ class CloudwatchStack(Stack):
       'environmentName': kwargs.get('environmentName', 'dev'),
     }
 
+    # Validating props
+    if props['environmentName'] is not None and not cdk.Token.is_unresolved(props['environmentName']):
+      if props['environmentName'] not in ['dev', 'stage', 'prod']:
+        raise ValueError('environmentName must be one of: dev, stage, prod')
+
+    # Template options
+    self.template_options.template_format_version = '2010-09-09'
+
     # Resources
     myApi5xxErrorsAlarm = cloudwatch.CfnAlarm(self, 'MyApi5xxErrorsAlarm',
           alarm_description = 'Example alarm',
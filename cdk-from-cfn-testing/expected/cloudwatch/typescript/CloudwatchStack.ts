@@ -19,6 +19,16 @@ export class CloudwatchStack extends cdk.Stack {
       environmentName: props.environmentName ?? 'dev',
     };
 
+    // Validating props
+    if (!cdk.Token.isUnresolved(props.environmentName)) {
+      if (!['dev', 'stage', 'prod'].includes(props.environmentName!)) {
+        throw new Error('environmentName must be one of: dev, stage, prod');
+      }
+    }
+
+    // Template options
+    this.templateOptions.templateFormatVersion = '2010-09-09';
+
     // Resources
     const myApi5xxErrorsAlarm = new cloudwatch.CfnAlarm(this, 'MyApi5xxErrorsAlarm', {
       alarmDescription: 'Example alarm',
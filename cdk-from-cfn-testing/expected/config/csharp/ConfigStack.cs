@@ -36,6 +36,9 @@ namespace ConfigStack
             props.Ec2VolumeAutoEnableIo ??= false;
             props.Ec2VolumeTagKey ??= "CostCenter";
 
+            // Template options
+            TemplateOptions.TemplateFormatVersion = "2010-09-09";
+
 
             // Resources
             var configBucket = new CfnBucket(this, "ConfigBucket", new CfnBucketProps
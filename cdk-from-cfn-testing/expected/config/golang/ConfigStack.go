@@ -35,6 +35,8 @@ func NewConfigStack(scope constructs.Construct, id string, props *ConfigStackPro
 	}
 	stack := cdk.NewStack(scope, &id, &sprops)
 
+	stack.TemplateOptions().SetTemplateFormatVersion(jsii.String("2010-09-09"))
+
 	configBucket := s3.NewCfnBucket(
 		stack,
 		jsii.String("ConfigBucket"),
@@ -87,7 +89,7 @@ func NewConfigStack(scope constructs.Construct, id string, props *ConfigStackPro
 				},
 			},
 			Policies: &[]interface{}{
-				&PolicyProperty{
+				&iam.CfnRole_PolicyProperty{
 					PolicyName: jsii.String("root"),
 					PolicyDocument: map[string]interface{} {
 						"Version": jsii.String("2012-10-17"),
@@ -132,7 +134,7 @@ func NewConfigStack(scope constructs.Construct, id string, props *ConfigStackPro
 				jsii.String("arn:aws:iam::aws:policy/service-role/AWS_ConfigRole"),
 			},
 			Policies: &[]interface{}{
-				&PolicyProperty{
+				&iam.CfnRole_PolicyProperty{
 					PolicyName: jsii.String("root"),
 					PolicyDocument: map[string]interface{} {
 						"Version": jsii.String("2012-10-17"),
@@ -157,7 +159,7 @@ func NewConfigStack(scope constructs.Construct, id string, props *ConfigStackPro
 								}),
 								"Condition": map[string]interface{} {
 									"StringEquals": map[string]interface{} {
-										"S3XAmzAcl": jsii.String("bucket-owner-full-control"),
+										"s3:x-amz-acl": jsii.String("bucket-owner-full-control"),
 									},
 								},
 							},
@@ -201,7 +203,7 @@ func NewConfigStack(scope constructs.Construct, id string, props *ConfigStackPro
 		stack,
 		jsii.String("DeliveryChannel"),
 		&config.CfnDeliveryChannelProps{
-			ConfigSnapshotDeliveryProperties: &ConfigSnapshotDeliveryPropertiesProperty{
+			ConfigSnapshotDeliveryProperties: &config.CfnDeliveryChannel_ConfigSnapshotDeliveryPropertiesProperty{
 				DeliveryFrequency: jsii.String("Six_Hours"),
 			},
 			S3BucketName: configBucket.Ref(),
@@ -213,7 +215,7 @@ func NewConfigStack(scope constructs.Construct, id string, props *ConfigStackPro
 		stack,
 		jsii.String("VolumeAutoEnableIOComplianceCheck"),
 		&lambda.CfnFunctionProps{
-			Code: &CodeProperty{
+			Code: &lambda.CfnFunction_CodeProperty{
 				ZipFile: cdk.Fn_Join(jsii.String("\n"), &[]*string{
 					jsii.String("var aws  = require('aws-sdk');"),
 					jsii.String("var config = new aws.ConfigService();"),
@@ -271,7 +273,7 @@ func NewConfigStack(scope constructs.Construct, id string, props *ConfigStackPro
 		jsii.String("ConfigRecorder"),
 		&config.CfnConfigurationRecorderProps{
 			Name: jsii.String("default"),
-			RecordingGroup: &RecordingGroupProperty{
+			RecordingGroup: &config.CfnConfigurationRecorder_RecordingGroupProperty{
 				ResourceTypes: &[]*string{
 					jsii.String("AWS::EC2::Volume"),
 				},
@@ -285,16 +287,16 @@ func NewConfigStack(scope constructs.Construct, id string, props *ConfigStackPro
 		jsii.String("ConfigRuleForVolumeAutoEnableIO"),
 		&config.CfnConfigRuleProps{
 			ConfigRuleName: jsii.String("ConfigRuleForVolumeAutoEnableIO"),
-			Scope: &ScopeProperty{
+			Scope: &config.CfnConfigRule_ScopeProperty{
 				ComplianceResourceId: ec2Volume.Ref(),
 				ComplianceResourceTypes: &[]*string{
 					jsii.String("AWS::EC2::Volume"),
 				},
 			},
-			Source: &SourceProperty{
+			Source: &config.CfnConfigRule_SourceProperty{
 				Owner: jsii.String("CUSTOM_LAMBDA"),
 				SourceDetails: &[]interface{}{
-					&SourceDetailProperty{
+					&config.CfnConfigRule_SourceDetailProperty{
 						EventSource: jsii.String("aws.config"),
 						MessageType: jsii.String("ConfigurationItemChangeNotification"),
 					},
@@ -309,14 +311,14 @@ func NewConfigStack(scope constructs.Construct, id string, props *ConfigStackPro
 		jsii.String("ConfigRuleForVolumeTags"),
 		&config.CfnConfigRuleProps{
 			InputParameters: map[string]interface{} {
-				"Tag1Key": jsii.String("CostCenter"),
+				"tag1Key": jsii.String("CostCenter"),
 			},
-			Scope: &ScopeProperty{
+			Scope: &config.CfnConfigRule_ScopeProperty{
 				ComplianceResourceTypes: &[]*string{
 					jsii.String("AWS::EC2::Volume"),
 				},
 			},
-			Source: &SourceProperty{
+			Source: &config.CfnConfigRule_SourceProperty{
 				Owner: jsii.String("AWS"),
 				SourceIdentifier: jsii.String("REQUIRED_TAGS"),
 			},
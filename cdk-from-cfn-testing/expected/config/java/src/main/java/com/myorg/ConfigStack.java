@@ -53,6 +53,8 @@ class ConfigStack extends Stack {
         ec2VolumeTagKey = Optional.ofNullable(ec2VolumeTagKey).isPresent() ? ec2VolumeTagKey
                 : "CostCenter";
 
+        this.getTemplateOptions().setTemplateFormatVersion("2010-09-09");
+
         CfnBucket configBucket = CfnBucket.Builder.create(this, "ConfigBucket")
                 .bucketName("cdk-from-cfn-e2e-test-config-bucket-" + this.getAccount() + "-" + this.getRegion())
                 .build();
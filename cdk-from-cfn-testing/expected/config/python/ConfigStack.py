@@ -21,6 +21,9 @@ class ConfigStack(Stack):
       'ec2VolumeTagKey': kwargs.get('ec2VolumeTagKey', 'CostCenter'),
     }
 
+    # Template options
+    self.template_options.template_format_version = '2010-09-09'
+
     # Resources
     configBucket = s3.CfnBucket(self, 'ConfigBucket',
           bucket_name = f"""cdk-from-cfn-e2e-test-config-bucket-{self.account}-{self.region}""",
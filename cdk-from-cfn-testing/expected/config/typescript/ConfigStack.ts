@@ -8,7 +8,7 @@ import * as sns from 'aws-cdk-lib/aws-sns';
 
 export interface ConfigStackProps extends cdk.StackProps {
   /**
-   * @default 'false'
+   * @default false
    */
   readonly ec2VolumeAutoEnableIo?: boolean;
   /**
@@ -35,6 +35,9 @@ export class ConfigStack extends cdk.Stack {
       ec2VolumeTagKey: props.ec2VolumeTagKey ?? 'CostCenter',
     };
 
+    // Template options
+    this.templateOptions.templateFormatVersion = '2010-09-09';
+
     // Resources
     const configBucket = new s3.CfnBucket(this, 'ConfigBucket', {
       bucketName: `cdk-from-cfn-e2e-test-config-bucket-${this.account}-${this.region}`,
@@ -58,6 +58,10 @@ namespace DocumentDbStack
                 Type = "String",
                 Default = props.MasterUser ?? "MainUser",
                 Description = "The database admin account username",
+                AllowedPattern = "[a-zA-Z][a-zA-Z0-9]*",
+                ConstraintDescription = "Must begin with a letter and contain only alphanumeric characters.",
+                MinLength = 1,
+                MaxLength = 16,
                 NoEcho = true,
             }).ValueAsString;
             props.MasterPassword = new CfnParameter(this, "MasterPassword", new CfnParameterProps
@@ -65,10 +69,53 @@ namespace DocumentDbStack
                 Type = "String",
                 Default = props.MasterPassword ?? "password",
                 Description = "The database admin account password",
+                AllowedPattern = "[a-zA-Z0-9]+",
+                ConstraintDescription = "must contain only alphanumeric characters.",
+                MinLength = 1,
+                MaxLength = 41,
                 NoEcho = true,
             }).ValueAsString;
             props.DbInstanceClass ??= "db.t3.medium";
 
+            // Validating props
+            if (props.DbClusterName != null && !Token.IsUnresolved(props.DbClusterName))
+            {
+                if (!System.Text.RegularExpressions.Regex.IsMatch(props.DbClusterName, "^(?:[a-zA-Z][a-zA-Z0-9]*(-[a-zA-Z0-9]+)*)$"))
+                {
+                    throw new System.ArgumentException("dbClusterName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.");
+                }
+                if (props.DbClusterName.Length < 1)
+                {
+                    throw new System.ArgumentException("dbClusterName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.");
+                }
+                if (props.DbClusterName.Length > 64)
+                {
+                    throw new System.ArgumentException("dbClusterName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.");
+                }
+            }
+            if (props.DbInstanceName != null && !Token.IsUnresolved(props.DbInstanceName))
+            {
+                if (!System.Text.RegularExpressions.Regex.IsMatch(props.DbInstanceName, "^(?:[a-zA-Z][a-zA-Z0-9]*(-[a-zA-Z0-9]+)*)$"))
+                {
+                    throw new System.ArgumentException("dbInstanceName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.");
+                }
+                if (props.DbInstanceName.Length < 1)
+                {
+                    throw new System.ArgumentException("dbInstanceName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.");
+                }
+                if (props.DbInstanceName.Length > 64)
+                {
+                    throw new System.ArgumentException("dbInstanceName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.");
+                }
+            }
+            if (props.DbInstanceClass != null && !Token.IsUnresolved(props.DbInstanceClass))
+            {
+                if (!new List<string> { "db.t3.medium", "db.r5.large", "db.r5.xlarge", "db.r5.2xlarge", "db.r5.4xlarge", "db.r5.12xlarge", "db.r5.24xlarge" }.Contains(props.DbInstanceClass))
+                {
+                    throw new System.ArgumentException("dbInstanceClass failed to satisfy constraint: Instance type must be of the ones supported for the region. Please refer to: https://docs.aws.amazon.com/documentdb/latest/developerguide/db-instance-classes.html#db-instance-classes-by-region");
+                }
+            }
+
 
             // Resources
             var dbCluster = new CfnDBCluster(this, "DBCluster", new CfnDBClusterProps
@@ -1,6 +1,8 @@
 package main
 
 import (
+	"regexp"
+
 	cdk "github.com/aws/aws-cdk-go/awscdk/v2"
 	docdb "github.com/aws/aws-cdk-go/awscdk/v2/awsdocdb"
 	"github.com/aws/constructs-go/constructs/v10"
@@ -37,16 +39,71 @@ func NewDocumentDbStack(scope constructs.Construct, id string, props *DocumentDb
 	}
 	stack := cdk.NewStack(scope, &id, &sprops)
 
+	if props != nil {
+		if value := props.DbClusterName; value != nil && !*cdk.Token_IsUnresolved(value) {
+			if !regexp.MustCompile(`^(?:[a-zA-Z][a-zA-Z0-9]*(-[a-zA-Z0-9]+)*)$`).MatchString(*value) {
+				panic("dbClusterName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.")
+			}
+			if len([]rune(*value)) < 1 {
+				panic("dbClusterName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.")
+			}
+			if len([]rune(*value)) > 64 {
+				panic("dbClusterName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.")
+			}
+		}
+		if value := props.DbInstanceName; value != nil && !*cdk.Token_IsUnresolved(value) {
+			if !regexp.MustCompile(`^(?:[a-zA-Z][a-zA-Z0-9]*(-[a-zA-Z0-9]+)*)$`).MatchString(*value) {
+				panic("dbInstanceName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.")
+			}
+			if len([]rune(*value)) < 1 {
+				panic("dbInstanceName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.")
+			}
+			if len([]rune(*value)) > 64 {
+				panic("dbInstanceName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.")
+			}
+		}
+		if value := props.DbInstanceClass; value != nil && !*cdk.Token_IsUnresolved(value) {
+			switch *value {
+			case "db.t3.medium", "db.r5.large", "db.r5.xlarge", "db.r5.2xlarge", "db.r5.4xlarge", "db.r5.12xlarge", "db.r5.24xlarge":
+			default:
+				panic("dbInstanceClass failed to satisfy constraint: Instance type must be of the ones supported for the region. Please refer to: https://docs.aws.amazon.com/documentdb/latest/developerguide/db-instance-classes.html#db-instance-classes-by-region")
+			}
+		}
+	}
+
+	masterUser := cdk.NewCfnParameter(stack, jsii.String("MasterUser"), &cdk.CfnParameterProps{
+		Type: jsii.String("String"),
+		Default: props.MasterUser,
+		Description: jsii.String("The database admin account username"),
+		AllowedPattern: jsii.String("[a-zA-Z][a-zA-Z0-9]*"),
+		ConstraintDescription: jsii.String("Must begin with a letter and contain only alphanumeric characters."),
+		MinLength: jsii.Number(1),
+		MaxLength: jsii.Number(16),
+		NoEcho: jsii.Bool(true),
+	}).ValueAsString()
+
+	masterPassword := cdk.NewCfnParameter(stack, jsii.String("MasterPassword"), &cdk.CfnParameterProps{
+		Type: jsii.String("String"),
+		Default: props.MasterPassword,
+		Description: jsii.String("The database admin account password"),
+		AllowedPattern: jsii.String("[a-zA-Z0-9]+"),
+		ConstraintDescription: jsii.String("must contain only alphanumeric characters."),
+		MinLength: jsii.Number(1),
+		MaxLength: jsii.Number(41),
+		NoEcho: jsii.Bool(true),
+	}).ValueAsString()
+
 	dbCluster := doc_db.NewCfnDBCluster(
 		stack,
 		jsii.String("DBCluster"),
 		&doc_db.CfnDBClusterProps{
 			DbClusterIdentifier: props.DbClusterName,
-			MasterUsername: props.MasterUser,
-			MasterUserPassword: props.MasterPassword,
+			MasterUsername: masterUser,
+			MasterUserPassword: masterPassword,
 			EngineVersion: jsii.String("4.0.0"),
 		},
 	)
+	dbCluster.CfnOptions().SetDeletionPolicy(cdk.CfnDeletionPolicy_DELETE)
 
 	doc_db.NewCfnDBInstance(
 		stack,
@@ -61,6 +61,10 @@ class DocumentDbStack extends Stack {
                 : CfnParameter.Builder.create(this, "MasterUser")
                         .type("String")
                         .defaultValue("MainUser")
+                        .allowedPattern("[a-zA-Z][a-zA-Z0-9]*")
+                        .constraintDescription("Must begin with a letter and contain only alphanumeric characters.")
+                        .minLength(1)
+                        .maxLength(16)
                         .noEcho(true)
                         .build()
                         .getValueAsString();
@@ -70,6 +74,10 @@ class DocumentDbStack extends Stack {
                 : CfnParameter.Builder.create(this, "MasterPassword")
                         .type("String")
                         .defaultValue("password")
+                        .allowedPattern("[a-zA-Z0-9]+")
+                        .constraintDescription("must contain only alphanumeric characters.")
+                        .minLength(1)
+                        .maxLength(41)
                         .noEcho(true)
                         .build()
                         .getValueAsString();
@@ -77,6 +85,36 @@ class DocumentDbStack extends Stack {
         dbInstanceClass = Optional.ofNullable(dbInstanceClass).isPresent() ? dbInstanceClass
                 : "db.t3.medium";
 
+        if (dbClusterName != null && !Token.isUnresolved(dbClusterName)) {
+            if (!dbClusterName.matches("[a-zA-Z][a-zA-Z0-9]*(-[a-zA-Z0-9]+)*")) {
+                throw new IllegalArgumentException("dbClusterName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.");
+            }
+            if (dbClusterName.length() < 1) {
+                throw new IllegalArgumentException("dbClusterName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.");
+            }
+            if (dbClusterName.length() > 64) {
+                throw new IllegalArgumentException("dbClusterName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.");
+            }
+        }
+
+        if (dbInstanceName != null && !Token.isUnresolved(dbInstanceName)) {
+            if (!dbInstanceName.matches("[a-zA-Z][a-zA-Z0-9]*(-[a-zA-Z0-9]+)*")) {
+                throw new IllegalArgumentException("dbInstanceName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.");
+            }
+            if (dbInstanceName.length() < 1) {
+                throw new IllegalArgumentException("dbInstanceName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.");
+            }
+            if (dbInstanceName.length() > 64) {
+                throw new IllegalArgumentException("dbInstanceName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.");
+            }
+        }
+
+        if (dbInstanceClass != null && !Token.isUnresolved(dbInstanceClass)) {
+            if (!Arrays.asList("db.t3.medium", "db.r5.large", "db.r5.xlarge", "db.r5.2xlarge", "db.r5.4xlarge", "db.r5.12xlarge", "db.r5.24xlarge").contains(dbInstanceClass)) {
+                throw new IllegalArgumentException("dbInstanceClass failed to satisfy constraint: Instance type must be of the ones supported for the region. Please refer to: https://docs.aws.amazon.com/documentdb/latest/developerguide/db-instance-classes.html#db-instance-classes-by-region");
+            }
+        }
+
         CfnDBCluster dbCluster = CfnDBCluster.Builder.create(this, "DBCluster")
                 .dbClusterIdentifier(dbClusterName)
                 .masterUsername(masterUser)
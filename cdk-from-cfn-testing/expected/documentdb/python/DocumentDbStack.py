@@ -2,6 +2,7 @@ from aws_cdk import Stack
 import aws_cdk as cdk
 import aws_cdk.aws_docdb as docdb
 from constructs import Construct
+import re
 
 """
   AWS CloudFormation Sample Template DocumentDB_Quick_Create: Sample template showing how to create a DocumentDB DB cluster and DB instance. **WARNING** This template creates an Amazon DocumentDB resources and you will be billed for the AWS resources used if you create a stack from this template.
@@ -14,26 +15,53 @@ class DocumentDbStack(Stack):
     props = {
       'dbClusterName': kwargs.get('dbClusterName', 'MyCluster'),
       'dbInstanceName': kwargs.get('dbInstanceName', 'MyInstance'),
-      'masterUser': cdk.CfnParameter(self, 'masterUser', 
+      'masterUser': cdk.CfnParameter(self, 'MasterUser',
         type = 'String',
         default = str(kwargs.get('masterUser', 'MainUser')),
         description = 'The database admin account username',
+        allowed_pattern = '[a-zA-Z][a-zA-Z0-9]*',
+        constraint_description = 'Must begin with a letter and contain only alphanumeric characters.',
+        min_length = 1,
+        max_length = 16,
         no_echo = True,
-      ),
-      'masterPassword': cdk.CfnParameter(self, 'masterPassword', 
+      ).value_as_string,
+      'masterPassword': cdk.CfnParameter(self, 'MasterPassword',
         type = 'String',
         default = str(kwargs.get('masterPassword', 'password')),
         description = 'The database admin account password',
+        allowed_pattern = '[a-zA-Z0-9]+',
+        constraint_description = 'must contain only alphanumeric characters.',
+        min_length = 1,
+        max_length = 41,
         no_echo = True,
-      ),
+      ).value_as_string,
       'dbInstanceClass': kwargs.get('dbInstanceClass', 'db.t3.medium'),
     }
 
+    # Validating props
+    if props['dbClusterName'] is not None and not cdk.Token.is_unresolved(props['dbClusterName']):
+      if not re.fullmatch('[a-zA-Z][a-zA-Z0-9]*(-[a-zA-Z0-9]+)*', props['dbClusterName']):
+        raise ValueError('dbClusterName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.')
+      if len(props['dbClusterName']) < 1:
+        raise ValueError('dbClusterName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.')
+      if len(props['dbClusterName']) > 64:
+        raise ValueError('dbClusterName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.')
+    if props['dbInstanceName'] is not None and not cdk.Token.is_unresolved(props['dbInstanceName']):
+      if not re.fullmatch('[a-zA-Z][a-zA-Z0-9]*(-[a-zA-Z0-9]+)*', props['dbInstanceName']):
+        raise ValueError('dbInstanceName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.')
+      if len(props['dbInstanceName']) < 1:
+        raise ValueError('dbInstanceName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.')
+      if len(props['dbInstanceName']) > 64:
+        raise ValueError('dbInstanceName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.')
+    if props['dbInstanceClass'] is not None and not cdk.Token.is_unresolved(props['dbInstanceClass']):
+      if props['dbInstanceClass'] not in ['db.t3.medium', 'db.r5.large', 'db.r5.xlarge', 'db.r5.2xlarge', 'db.r5.4xlarge', 'db.r5.12xlarge', 'db.r5.24xlarge']:
+        raise ValueError('dbInstanceClass failed to satisfy constraint: Instance type must be of the ones supported for the region. Please refer to: https://docs.aws.amazon.com/documentdb/latest/developerguide/db-instance-classes.html#db-instance-classes-by-region')
+
     # Resources
     dbCluster = docdb.CfnDBCluster(self, 'DBCluster',
           db_cluster_identifier = props['dbClusterName'],
-          master_username = props['masterUser'].value_as_string,
-          master_user_password = props['masterPassword'].value_as_string,
+          master_username = props['masterUser'],
+          master_user_password = props['masterPassword'],
           engine_version = '4.0.0',
         )
     dbCluster.cfn_options.deletion_policy = cdk.CfnDeletionPolicy.DELETE
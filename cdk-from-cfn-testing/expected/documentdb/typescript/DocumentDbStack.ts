@@ -50,17 +50,54 @@ export class DocumentDbStack extends cdk.Stack {
         type: 'String',
         default: props.masterUser?.toString() ?? 'MainUser',
         description: 'The database admin account username',
+        allowedPattern: '[a-zA-Z][a-zA-Z0-9]*',
+        constraintDescription: 'Must begin with a letter and contain only alphanumeric characters.',
+        minLength: 1,
+        maxLength: 16,
         noEcho: true,
       }).valueAsString,
       masterPassword: new cdk.CfnParameter(this, 'MasterPassword', {
         type: 'String',
         default: props.masterPassword?.toString() ?? 'password',
         description: 'The database admin account password',
+        allowedPattern: '[a-zA-Z0-9]+',
+        constraintDescription: 'must contain only alphanumeric characters.',
+        minLength: 1,
+        maxLength: 41,
         noEcho: true,
       }).valueAsString,
       dbInstanceClass: props.dbInstanceClass ?? 'db.t3.medium',
     };
 
+    // Validating props
+    if (!cdk.Token.isUnresolved(props.dbClusterName)) {
+      if (!/^(?:[a-zA-Z][a-zA-Z0-9]*(-[a-zA-Z0-9]+)*)$/.test(props.dbClusterName!)) {
+        throw new Error('dbClusterName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.');
+      }
+      if (props.dbClusterName!.length < 1) {
+        throw new Error('dbClusterName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.');
+      }
+      if (props.dbClusterName!.length > 64) {
+        throw new Error('dbClusterName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.');
+      }
+    }
+    if (!cdk.Token.isUnresolved(props.dbInstanceName)) {
+      if (!/^(?:[a-zA-Z][a-zA-Z0-9]*(-[a-zA-Z0-9]+)*)$/.test(props.dbInstanceName!)) {
+        throw new Error('dbInstanceName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.');
+      }
+      if (props.dbInstanceName!.length < 1) {
+        throw new Error('dbInstanceName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.');
+      }
+      if (props.dbInstanceName!.length > 64) {
+        throw new Error('dbInstanceName failed to satisfy constraint: Must begin with a letter and contain only alphanumeric characters.');
+      }
+    }
+    if (!cdk.Token.isUnresolved(props.dbInstanceClass)) {
+      if (!['db.t3.medium', 'db.r5.large', 'db.r5.xlarge', 'db.r5.2xlarge', 'db.r5.4xlarge', 'db.r5.12xlarge', 'db.r5.24xlarge'].includes(props.dbInstanceClass!)) {
+        throw new Error('dbInstanceClass failed to satisfy constraint: Instance type must be of the ones supported for the region. Please refer to: https://docs.aws.amazon.com/documentdb/latest/developerguide/db-instance-classes.html#db-instance-classes-by-region');
+      }
+    }
+
     // Resources
     const dbCluster = new docdb.CfnDBCluster(this, 'DBCluster', {
       dbClusterIdentifier: props.dbClusterName!,
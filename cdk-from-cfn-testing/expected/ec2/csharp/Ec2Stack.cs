@@ -13,6 +13,9 @@ namespace Ec2Stack
     {
         public Ec2Stack(Construct scope, string id, Ec2StackProps props = null) : base(scope, id, props)
         {
+            // Template options
+            TemplateOptions.TemplateFormatVersion = "2010-09-09";
+
 
             // Resources
             var testVpc = new CfnVPC(this, "TestVPC", new CfnVPCProps
@@ -22,6 +22,8 @@ func NewEc2Stack(scope constructs.Construct, id string, props *Ec2StackProps) *E
 	}
 	stack := cdk.NewStack(scope, &id, &sprops)
 
+	stack.TemplateOptions().SetTemplateFormatVersion(jsii.String("2010-09-09"))
+
 	testVpc := ec2.NewCfnVPC(
 		stack,
 		jsii.String("TestVPC"),
@@ -37,7 +39,7 @@ func NewEc2Stack(scope constructs.Construct, id string, props *Ec2StackProps) *E
 			GroupDescription: jsii.String("SG2"),
 			VpcId: testVpc.Ref(),
 			SecurityGroupEgress: &[]interface{}{
-				&EgressProperty{
+				&ec2.CfnSecurityGroup_EgressProperty{
 					IpProtocol: jsii.String("TCP"),
 					FromPort: jsii.Number(10000),
 					ToPort: jsii.Number(10000),
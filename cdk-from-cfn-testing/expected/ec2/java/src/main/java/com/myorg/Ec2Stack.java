@@ -19,6 +19,8 @@ class Ec2Stack extends Stack {
     public Ec2Stack(final Construct scope, final String id, final StackProps props) {
         super(scope, id, props);
 
+        this.getTemplateOptions().setTemplateFormatVersion("2010-09-09");
+
         CfnVPC testVpc = CfnVPC.Builder.create(this, "TestVPC")
                 .cidrBlock("10.0.0.0/16")
                 .build();
@@ -7,6 +7,9 @@ class Ec2Stack(Stack):
   def __init__(self, scope: Construct, construct_id: str, **kwargs) -> None:
     super().__init__(scope, construct_id, **kwargs)
 
+    # Template options
+    self.template_options.template_format_version = '2010-09-09'
+
     # Resources
     testVpc = ec2.CfnVPC(self, 'TestVPC',
           cidr_block = '10.0.0.0/16',
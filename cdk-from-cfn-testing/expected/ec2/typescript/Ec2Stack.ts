@@ -8,6 +8,9 @@ export class Ec2Stack extends cdk.Stack {
   public constructor(scope: cdk.App, id: string, props: Ec2StackProps = {}) {
     super(scope, id, props);
 
+    // Template options
+    this.templateOptions.templateFormatVersion = '2010-09-09';
+
     // Resources
     const testVpc = new ec2.CfnVPC(this, 'TestVPC', {
       cidrBlock: '10.0.0.0/16',
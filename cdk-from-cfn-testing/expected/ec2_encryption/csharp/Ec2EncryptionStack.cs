@@ -37,6 +37,32 @@ namespace Ec2EncryptionStack
             props.SubnetType ??= "Private1";
             props.EnableMonitoringParameter ??= false;
 
+            // Validating props
+            if (props.Environment != null && !Token.IsUnresolved(props.Environment))
+            {
+                if (!new List<string> { "dev", "test", "prod" }.Contains(props.Environment))
+                {
+                    throw new System.ArgumentException("environment must be one of: dev, test, prod");
+                }
+            }
+            if (props.DatabaseType != null && !Token.IsUnresolved(props.DatabaseType))
+            {
+                if (!new List<string> { "mysql", "postgresql" }.Contains(props.DatabaseType))
+                {
+                    throw new System.ArgumentException("databaseType must be one of: mysql, postgresql");
+                }
+            }
+            if (props.SubnetType != null && !Token.IsUnresolved(props.SubnetType))
+            {
+                if (!new List<string> { "Private1", "Private2" }.Contains(props.SubnetType))
+                {
+                    throw new System.ArgumentException("subnetType must be one of: Private1, Private2");
+                }
+            }
+
+            // Template options
+            TemplateOptions.TemplateFormatVersion = "2010-09-09";
+
             // Mappings
             var regionToAmi = new Dictionary<string, Dictionary<string,string>> 
             {
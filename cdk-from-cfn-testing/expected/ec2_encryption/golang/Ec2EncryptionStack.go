@@ -47,6 +47,32 @@ func NewEc2EncryptionStack(scope constructs.Construct, id string, props *Ec2Encr
 	}
 	stack := cdk.NewStack(scope, &id, &sprops)
 
+	if props != nil {
+		if value := props.Environment; value != nil && !*cdk.Token_IsUnresolved(value) {
+			switch *value {
+			case "dev", "test", "prod":
+			default:
+				panic("environment must be one of: dev, test, prod")
+			}
+		}
+		if value := props.DatabaseType; value != nil && !*cdk.Token_IsUnresolved(value) {
+			switch *value {
+			case "mysql", "postgresql":
+			default:
+				panic("databaseType must be one of: mysql, postgresql")
+			}
+		}
+		if value := props.SubnetType; value != nil && !*cdk.Token_IsUnresolved(value) {
+			switch *value {
+			case "Private1", "Private2":
+			default:
+				panic("subnetType must be one of: Private1, Private2")
+			}
+		}
+	}
+
+	stack.TemplateOptions().SetTemplateFormatVersion(jsii.String("2010-09-09"))
+
 	hasDatabase := props.DatabaseType == jsii.String("mysql")
 
 	isProduction := props.Environment == jsii.String("prod")
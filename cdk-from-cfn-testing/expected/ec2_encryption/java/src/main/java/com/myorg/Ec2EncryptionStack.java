@@ -44,6 +44,26 @@ class Ec2EncryptionStack extends Stack {
                 : "Private1";
         enableMonitoringParameter = Optional.ofNullable(enableMonitoringParameter).isPresent() ? enableMonitoringParameter
                 : false;
+
+        if (environment != null && !Token.isUnresolved(environment)) {
+            if (!Arrays.asList("dev", "test", "prod").contains(environment)) {
+                throw new IllegalArgumentException("environment must be one of: dev, test, prod");
+            }
+        }
+
+        if (databaseType != null && !Token.isUnresolved(databaseType)) {
+            if (!Arrays.asList("mysql", "postgresql").contains(databaseType)) {
+                throw new IllegalArgumentException("databaseType must be one of: mysql, postgresql");
+            }
+        }
+
+        if (subnetType != null && !Token.isUnresolved(subnetType)) {
+            if (!Arrays.asList("Private1", "Private2").contains(subnetType)) {
+                throw new IllegalArgumentException("subnetType must be one of: Private1, Private2");
+            }
+        }
+
+        this.getTemplateOptions().setTemplateFormatVersion("2010-09-09");
         // Mappings
         final CfnMapping regionToAmi = new CfnMapping(this, "regionToAmi");
         regionToAmi.setValue("us-east-1", "AMI", "ami-0c02fb55956c7d316");
@@ -18,6 +18,20 @@ class Ec2EncryptionStack(Stack):
       'enableMonitoringParameter': kwargs.get('enableMonitoringParameter', False),
     }
 
+    # Validating props
+    if props['environment'] is not None and not cdk.Token.is_unresolved(props['environment']):
+      if props['environment'] not in ['dev', 'test', 'prod']:
+        raise ValueError('environment must be one of: dev, test, prod')
+    if props['databaseType'] is not None and not cdk.Token.is_unresolved(props['databaseType']):
+      if props['databaseType'] not in ['mysql', 'postgresql']:
+        raise ValueError('databaseType must be one of: mysql, postgresql')
+    if props['subnetType'] is not None and not cdk.Token.is_unresolved(props['subnetType']):
+      if props['subnetType'] not in ['Private1', 'Private2']:
+        raise ValueError('subnetType must be one of: Private1, Private2')
+
+    # Template options
+    self.template_options.template_format_version = '2010-09-09'
+
     # Mappings
     regionToAmi = {
       'us-east-1': {
@@ -11,7 +11,7 @@ export interface Ec2EncryptionStackProps extends cdk.StackProps {
    */
   readonly databaseType?: string;
   /**
-   * @default 'false'
+   * @default false
    */
   readonly useEncryption?: boolean;
   /**
@@ -27,7 +27,7 @@ export interface Ec2EncryptionStackProps extends cdk.StackProps {
    */
   readonly subnetType?: string;
   /**
-   * @default 'false'
+   * @default false
    */
   readonly enableMonitoringParameter?: boolean;
 }
@@ -48,6 +48,26 @@ export class Ec2EncryptionStack extends cdk.Stack {
       enableMonitoringParameter: props.enableMonitoringParameter ?? false,
     };
 
+    // Validating props
+    if (!cdk.Token.isUnresolved(props.environment)) {
+      if (!['dev', 'test', 'prod'].includes(props.environment!)) {
+        throw new Error('environment must be one of: dev, test, prod');
+      }
+    }
+    if (!cdk.Token.isUnresolved(props.databaseType)) {
+      if (!['mysql', 'postgresql'].includes(props.databaseType!)) {
+        throw new Error('databaseType must be one of: mysql, postgresql');
+      }
+    }
+    if (!cdk.Token.isUnresolved(props.subnetType)) {
+      if (!['Private1', 'Private2'].includes(props.subnetType!)) {
+        throw new Error('subnetType must be one of: Private1, Private2');
+      }
+    }
+
+    // Template options
+    this.templateOptions.templateFormatVersion = '2010-09-09';
+
     // Mappings
     const regionToAmi: Record<string, Record<string, string>> = {
       'us-east-1': {
@@ -14,6 +14,9 @@ namespace EcsStack
     {
         public EcsStack(Construct scope, string id, EcsStackProps props = null) : base(scope, id, props)
         {
+            // Template options
+            TemplateOptions.TemplateFormatVersion = "2010-09-09";
+
 
             // Resources
             var backendEcsTaskRole = new CfnRole(this, "BackendECSTaskRole", new CfnRoleProps
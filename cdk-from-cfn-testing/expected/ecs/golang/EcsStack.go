@@ -23,6 +23,8 @@ func NewEcsStack(scope constructs.Construct, id string, props *EcsStackProps) *E
 	}
 	stack := cdk.NewStack(scope, &id, &sprops)
 
+	stack.TemplateOptions().SetTemplateFormatVersion(jsii.String("2010-09-09"))
+
 	backendEcsTaskRole := iam.NewCfnRole(
 		stack,
 		jsii.String("BackendECSTaskRole"),
@@ -80,10 +82,10 @@ func NewEcsStack(scope constructs.Construct, id string, props *EcsStackProps) *E
 			ExecutionRoleArn: ecsTaskExecutionRole.AttrArn(),
 			TaskRoleArn: backendEcsTaskRole.AttrArn(),
 			ContainerDefinitions: &[]interface{}{
-				&ContainerDefinitionProperty{
+				&ecs.CfnTaskDefinition_ContainerDefinitionProperty{
 					Name: jsii.String("main"),
 					Image: jsii.String("nginx"),
-					LogConfiguration: &LogConfigurationProperty{
+					LogConfiguration: &ecs.CfnTaskDefinition_LogConfigurationProperty{
 						Options: map[string]interface{} {
 							"awslogs-group": jsii.String("/aws/ecs/test/main"),
 							"awslogs-region": jsii.String("ap-northeast-1"),
@@ -20,6 +20,8 @@ class EcsStack extends Stack {
     public EcsStack(final Construct scope, final String id, final StackProps props) {
         super(scope, id, props);
 
+        this.getTemplateOptions().setTemplateFormatVersion("2010-09-09");
+
         CfnRole backendEcsTaskRole = CfnRole.Builder.create(this, "BackendECSTaskRole")
                 .path("/")
                 .assumeRolePolicyDocument(Map.of("Statement", Arrays.asList(
@@ -8,6 +8,9 @@ class EcsStack(Stack):
   def __init__(self, scope: Construct, construct_id: str, **kwargs) -> None:
     super().__init__(scope, construct_id, **kwargs)
 
+    # Template options
+    self.template_options.template_format_version = '2010-09-09'
+
     # Resources
     backendEcsTaskRole = iam.CfnRole(self, 'BackendECSTaskRole',
           path = '/',
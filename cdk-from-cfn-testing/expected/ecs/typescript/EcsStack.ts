@@ -9,6 +9,9 @@ export class EcsStack extends cdk.Stack {
   public constructor(scope: cdk.App, id: string, props: EcsStackProps = {}) {
     super(scope, id, props);
 
+    // Template options
+    this.templateOptions.templateFormatVersion = '2010-09-09';
+
     // Resources
     const backendEcsTaskRole = new iam.CfnRole(this, 'BackendECSTaskRole', {
       path: '/',
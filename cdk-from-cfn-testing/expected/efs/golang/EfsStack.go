@@ -1,6 +1,8 @@
 package main
 
 import (
+	"regexp"
+
 	cdk "github.com/aws/aws-cdk-go/awscdk/v2"
 	ec2 "github.com/aws/aws-cdk-go/awscdk/v2/awsec2"
 	efs "github.com/aws/aws-cdk-go/awscdk/v2/awsefs"
@@ -280,6 +282,39 @@ func NewEfsStack(scope constructs.Construct, id string, props *EfsStackProps) *E
 	}
 	stack := cdk.NewStack(scope, &id, &sprops)
 
+	if props != nil {
+		if value := props.InstanceType; value != nil && !*cdk.Token_IsUnresolved(value) {
+			switch *value {
+			case "t1.micro", "t2.nano", "t2.micro", "t2.small", "t2.medium", "t2.large", "m1.small", "m1.medium", "m1.large", "m1.xlarge", "m2.xlarge", "m2.2xlarge", "m2.4xlarge", "m3.medium", "m3.large", "m3.xlarge", "m3.2xlarge", "m4.large", "m4.xlarge", "m4.2xlarge", "m4.4xlarge", "m4.10xlarge", "c1.medium", "c1.xlarge", "c3.large", "c3.xlarge", "c3.2xlarge", "c3.4xlarge", "c3.8xlarge", "c4.large", "c4.xlarge", "c4.2xlarge", "c4.4xlarge", "c4.8xlarge", "g2.2xlarge", "g2.8xlarge", "r3.large", "r3.xlarge", "r3.2xlarge", "r3.4xlarge", "r3.8xlarge", "i2.xlarge", "i2.2xlarge", "i2.4xlarge", "i2.8xlarge", "d2.xlarge", "d2.2xlarge", "d2.4xlarge", "d2.8xlarge", "hi1.4xlarge", "hs1.8xlarge", "cr1.8xlarge", "cc2.8xlarge", "cg1.4xlarge":
+			default:
+				panic("instanceType failed to satisfy constraint: must be a valid EC2 instance type.")
+			}
+		}
+		if value := props.SshLocation; value != nil && !*cdk.Token_IsUnresolved(value) {
+			if !regexp.MustCompile(`^(?:(\d{1,3})\.(\d{1,3})\.(\d{1,3})\.(\d{1,3})/(\d{1,2}))$`).MatchString(*value) {
+				panic("sshLocation failed to satisfy constraint: must be a valid IP CIDR range of the form x.x.x.x/x.")
+			}
+			if len([]rune(*value)) < 9 {
+				panic("sshLocation failed to satisfy constraint: must be a valid IP CIDR range of the form x.x.x.x/x.")
+			}
+			if len([]rune(*value)) > 18 {
+				panic("sshLocation failed to satisfy constraint: must be a valid IP CIDR range of the form x.x.x.x/x.")
+			}
+		}
+		if value := props.VolumeName; value != nil && !*cdk.Token_IsUnresolved(value) {
+			if len([]rune(*value)) < 1 {
+				panic("volumeName must have a length of at least 1")
+			}
+		}
+		if value := props.MountPoint; value != nil && !*cdk.Token_IsUnresolved(value) {
+			if len([]rune(*value)) < 1 {
+				panic("mountPoint must have a length of at least 1")
+			}
+		}
+	}
+
+	stack.TemplateOptions().SetTemplateFormatVersion(jsii.String("2010-09-09"))
+
 	cloudWatchPutMetricsRole := iam.NewCfnRole(
 		stack,
 		jsii.String("CloudWatchPutMetricsRole"),
@@ -309,7 +344,7 @@ func NewEfsStack(scope constructs.Construct, id string, props *EfsStackProps) *E
 		&efs.CfnFileSystemProps{
 			PerformanceMode: jsii.String("generalPurpose"),
 			FileSystemTags: &[]interface{}{
-				&ElasticFileSystemTagProperty{
+				&efs.CfnFileSystem_ElasticFileSystemTagProperty{
 					Key: jsii.String("Name"),
 					Value: props.VolumeName,
 				},
@@ -403,13 +438,13 @@ func NewEfsStack(scope constructs.Construct, id string, props *EfsStackProps) *E
 			VpcId: vpc.Ref(),
 			GroupDescription: jsii.String("Enable SSH access via port 22"),
 			SecurityGroupIngress: &[]interface{}{
-				&IngressProperty{
+				&ec2.CfnSecurityGroup_IngressProperty{
 					IpProtocol: jsii.String("tcp"),
 					FromPort: jsii.Number(22),
 					ToPort: jsii.Number(22),
 					CidrIp: props.SshLocation,
 				},
-				&IngressProperty{
+				&ec2.CfnSecurityGroup_IngressProperty{
 					IpProtocol: jsii.String("tcp"),
 					FromPort: jsii.Number(80),
 					ToPort: jsii.Number(80),
@@ -426,7 +461,7 @@ func NewEfsStack(scope constructs.Construct, id string, props *EfsStackProps) *E
 			VpcId: vpc.Ref(),
 			GroupDescription: jsii.String("Security group for mount target"),
 			SecurityGroupIngress: &[]interface{}{
-				&IngressProperty{
+				&ec2.CfnSecurityGroup_IngressProperty{
 					IpProtocol: jsii.String("tcp"),
 					FromPort: jsii.Number(2049),
 					ToPort: jsii.Number(2049),
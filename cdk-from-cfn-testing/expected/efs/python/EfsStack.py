@@ -4,6 +4,7 @@ import aws_cdk.aws_ec2 as ec2
 import aws_cdk.aws_efs as efs
 import aws_cdk.aws_iam as iam
 from constructs import Construct
+import re
 
 """
   This template creates an Amazon EFS file system and mount target and associates it with Amazon EC2 instances in an Auto Scaling group. **WARNING** This template creates Amazon EC2 instances and related resources. You will be billed for the AWS resources used if you create a stack from this template.
@@ -21,6 +22,27 @@ class EfsStack(Stack):
       'mountPoint': kwargs.get('mountPoint', 'myEFSvolume'),
     }
 
+    # Validating props
+    if props['instanceType'] is not None and not cdk.Token.is_unresolved(props['instanceType']):
+      if props['instanceType'] not in ['t1.micro', 't2.nano', 't2.micro', 't2.small', 't2.medium', 't2.large', 'm1.small', 'm1.medium', 'm1.large', 'm1.xlarge', 'm2.xlarge', 'm2.2xlarge', 'm2.4xlarge', 'm3.medium', 'm3.large', 'm3.xlarge', 'm3.2xlarge', 'm4.large', 'm4.xlarge', 'm4.2xlarge', 'm4.4xlarge', 'm4.10xlarge', 'c1.medium', 'c1.xlarge', 'c3.large', 'c3.xlarge', 'c3.2xlarge', 'c3.4xlarge', 'c3.8xlarge', 'c4.large', 'c4.xlarge', 'c4.2xlarge', 'c4.4xlarge', 'c4.8xlarge', 'g2.2xlarge', 'g2.8xlarge', 'r3.large', 'r3.xlarge', 'r3.2xlarge', 'r3.4xlarge', 'r3.8xlarge', 'i2.xlarge', 'i2.2xlarge', 'i2.4xlarge', 'i2.8xlarge', 'd2.xlarge', 'd2.2xlarge', 'd2.4xlarge', 'd2.8xlarge', 'hi1.4xlarge', 'hs1.8xlarge', 'cr1.8xlarge', 'cc2.8xlarge', 'cg1.4xlarge']:
+        raise ValueError('instanceType failed to satisfy constraint: must be a valid EC2 instance type.')
+    if props['sshLocation'] is not None and not cdk.Token.is_unresolved(props['sshLocation']):
+      if not re.fullmatch('(\\d{1,3})\\.(\\d{1,3})\\.(\\d{1,3})\\.(\\d{1,3})/(\\d{1,2})', props['sshLocation']):
+        raise ValueError('sshLocation failed to satisfy constraint: must be a valid IP CIDR range of the form x.x.x.x/x.')
+      if len(props['sshLocation']) < 9:
+        raise ValueError('sshLocation failed to satisfy constraint: must be a valid IP CIDR range of the form x.x.x.x/x.')
+      if len(props['sshLocation']) > 18:
+        raise ValueError('sshLocation failed to satisfy constraint: must be a valid IP CIDR range of the form x.x.x.x/x.')
+    if props['volumeName'] is not None and not cdk.Token.is_unresolved(props['volumeName']):
+      if len(props['volumeName']) < 1:
+        raise ValueError('volumeName must have a length of at least 1')
+    if props['mountPoint'] is not None and not cdk.Token.is_unresolved(props['mountPoint']):
+      if len(props['mountPoint']) < 1:
+        raise ValueError('mountPoint must have a length of at least 1')
+
+    # Template options
+    self.template_options.template_format_version = '2010-09-09'
+
     # Mappings
     awsInstanceType2Arch = {
       't1.micro': {
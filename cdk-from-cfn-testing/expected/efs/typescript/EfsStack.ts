@@ -57,6 +57,37 @@ export class EfsStack extends cdk.Stack {
       mountPoint: props.mountPoint ?? 'myEFSvolume',
     };
 
+    // Validating props
+    if (!cdk.Token.isUnresolved(props.instanceType)) {
+      if (!['t1.micro', 't2.nano', 't2.micro', 't2.small', 't2.medium', 't2.large', 'm1.small', 'm1.medium', 'm1.large', 'm1.xlarge', 'm2.xlarge', 'm2.2xlarge', 'm2.4xlarge', 'm3.medium', 'm3.large', 'm3.xlarge', 'm3.2xlarge', 'm4.large', 'm4.xlarge', 'm4.2xlarge', 'm4.4xlarge', 'm4.10xlarge', 'c1.medium', 'c1.xlarge', 'c3.large', 'c3.xlarge', 'c3.2xlarge', 'c3.4xlarge', 'c3.8xlarge', 'c4.large', 'c4.xlarge', 'c4.2xlarge', 'c4.4xlarge', 'c4.8xlarge', 'g2.2xlarge', 'g2.8xlarge', 'r3.large', 'r3.xlarge', 'r3.2xlarge', 'r3.4xlarge', 'r3.8xlarge', 'i2.xlarge', 'i2.2xlarge', 'i2.4xlarge', 'i2.8xlarge', 'd2.xlarge', 'd2.2xlarge', 'd2.4xlarge', 'd2.8xlarge', 'hi1.4xlarge', 'hs1.8xlarge', 'cr1.8xlarge', 'cc2.8xlarge', 'cg1.4xlarge'].includes(props.instanceType!)) {
+        throw new Error('instanceType failed to satisfy constraint: must be a valid EC2 instance type.');
+      }
+    }
+    if (!cdk.Token.isUnresolved(props.sshLocation)) {
+      if (!/^(?:(\d{1,3})\.(\d{1,3})\.(\d{1,3})\.(\d{1,3})\/(\d{1,2}))$/.test(props.sshLocation!)) {
+        throw new Error('sshLocation failed to satisfy constraint: must be a valid IP CIDR range of the form x.x.x.x/x.');
+      }
+      if (props.sshLocation!.length < 9) {
+        throw new Error('sshLocation failed to satisfy constraint: must be a valid IP CIDR range of the form x.x.x.x/x.');
+      }
+      if (props.sshLocation!.length > 18) {
+        throw new Error('sshLocation failed to satisfy constraint: must be a valid IP CIDR range of the form x.x.x.x/x.');
+      }
+    }
+    if (!cdk.Token.isUnresolved(props.volumeName)) {
+      if (props.volumeName!.length < 1) {
+        throw new Error('volumeName must have a length of at least 1');
+      }
+    }
+    if (!cdk.Token.isUnresolved(props.mountPoint)) {
+      if (props.mountPoint!.length < 1) {
+        throw new Error('mountPoint must have a length of at least 1');
+      }
+    }
+
+    // Template options
+    this.templateOptions.templateFormatVersion = '2010-09-09';
+
     // Mappings
     const awsInstanceType2Arch: Record<string, Record<string, string>> = {
       't1.micro': {
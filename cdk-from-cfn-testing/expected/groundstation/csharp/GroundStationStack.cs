@@ -71,11 +71,12 @@ namespace GroundStationStack
             props.SatelliteName ??= "JPSS1";
             props.SoftwareS3Bucket ??= "your-software-bucket";
             props.SshCidrBlock ??= "15.16.17.18/32";
-            props.SshKeyName = new CfnParameter(this, "SshKeyName", new CfnParameterProps
+            props.SshKeyName = new CfnParameter(this, "SSHKeyName", new CfnParameterProps
             {
                 Type = "AWS::EC2::KeyPair::KeyName",
                 Default = props.SshKeyName ?? "",
                 Description = "Name of the ssh key used to access ec2 hosts. Set this up ahead of time.",
+                ConstraintDescription = "must be the name of an existing EC2 KeyPair.",
             }).ValueAsString;
             props.VpcId = new CfnParameter(this, "VpcId", new CfnParameterProps
             {
@@ -90,6 +91,61 @@ namespace GroundStationStack
                 Description = "Subnet to launch instances in",
             }).ValueAsString;
 
+            // Validating props
+            if (props.GroundStationS3DataDeliveryBucketName != null && !Token.IsUnresolved(props.GroundStationS3DataDeliveryBucketName))
+            {
+                if (!System.Text.RegularExpressions.Regex.IsMatch(props.GroundStationS3DataDeliveryBucketName, "^(?:^aws-groundstation-[a-z0-9-.]+)$"))
+                {
+                    throw new System.ArgumentException("groundStationS3DataDeliveryBucketName must match pattern ^aws-groundstation-[a-z0-9-.]+");
+                }
+            }
+            if (props.NotificationEmail != null && !Token.IsUnresolved(props.NotificationEmail))
+            {
+                if (!System.Text.RegularExpressions.Regex.IsMatch(props.NotificationEmail, "^(?:^[a-zA-Z0-9_.+-]+@[a-zA-Z0-9-]+\\.[a-zA-Z0-9-.]+$)$"))
+                {
+                    throw new System.ArgumentException("notificationEmail failed to satisfy constraint: Must be a valid email adress");
+                }
+            }
+            if (props.SatelliteName != null && !Token.IsUnresolved(props.SatelliteName))
+            {
+                if (!new List<string> { "JPSS1" }.Contains(props.SatelliteName))
+                {
+                    throw new System.ArgumentException("satelliteName must be one of: JPSS1");
+                }
+            }
+            if (props.SshCidrBlock != null && !Token.IsUnresolved(props.SshCidrBlock))
+            {
+                if (!System.Text.RegularExpressions.Regex.IsMatch(props.SshCidrBlock, "^(?:((\\d{1,3})\\.){3}\\d{1,3}/\\d{1,2})$"))
+                {
+                    throw new System.ArgumentException("sshCidrBlock failed to satisfy constraint: must be a valid CIDR range of the form x.x.x.x/x, for example \"15.16.17.18/16\".");
+                }
+            }
+
+            // Template options
+            TemplateOptions.TemplateFormatVersion = "2010-09-09";
+            TemplateOptions.Metadata = new Dictionary<string, object>
+            {
+                { "AWS::CloudFormation::Interface", new Dictionary<string, object>
+                {
+                    { "ParameterGroups", new []
+                    {
+                        new Dictionary<string, object>
+                        {
+                            { "Label", new Dictionary<string, object>
+                            {
+                                { "default", "RT-STPS instance configuration"},
+                            }},
+                            { "Parameters", new []
+                            {
+                                "VpcId",
+                                "SubnetId",
+                                "SSHKeyName",
+                            }},
+                        },
+                    }},
+                }},
+            };
+
             // Transforms
             AddTransform("AWS::Serverless-2016-10-31");
             // Mappings
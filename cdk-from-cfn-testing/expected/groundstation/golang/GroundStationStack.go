@@ -2,6 +2,7 @@ package main
 
 import (
 	"fmt"
+	"regexp"
 
 	cdk "github.com/aws/aws-cdk-go/awscdk/v2"
 	ec2 "github.com/aws/aws-cdk-go/awscdk/v2/awsec2"
@@ -28,11 +29,11 @@ type GroundStationStackProps struct {
 	/// The CIDR Block that the security group will allow ssh access to an instance. The CIDR Block has the form x.x.x.x/x.
 	SshCidrBlock *string
 	/// Name of the ssh key used to access ec2 hosts. Set this up ahead of time.
-	SshKeyName interface{/* AWS::EC2::KeyPair::KeyName */}
+	SshKeyName *string
 	/// VPC to launch instances in.
-	VpcId interface{/* AWS::EC2::VPC::Id */}
+	VpcId *string
 	/// Subnet to launch instances in
-	SubnetId interface{/* AWS::EC2::Subnet::Id */}
+	SubnetId *string
 }
 
 /// Ground Station S3 Data Delivery stack for JPSS1
@@ -87,6 +88,68 @@ func NewGroundStationStack(scope constructs.Construct, id string, props *GroundS
 	}
 	stack := cdk.NewStack(scope, &id, &sprops)
 
+	if props != nil {
+		if value := props.GroundStationS3DataDeliveryBucketName; value != nil && !*cdk.Token_IsUnresolved(value) {
+			if !regexp.MustCompile(`^(?:^aws-groundstation-[a-z0-9-.]+)$`).MatchString(*value) {
+				panic("groundStationS3DataDeliveryBucketName must match pattern ^aws-groundstation-[a-z0-9-.]+")
+			}
+		}
+		if value := props.NotificationEmail; value != nil && !*cdk.Token_IsUnresolved(value) {
+			if !regexp.MustCompile(`^(?:^[a-zA-Z0-9_.+-]+@[a-zA-Z0-9-]+\.[a-zA-Z0-9-.]+$)$`).MatchString(*value) {
+				panic("notificationEmail failed to satisfy constraint: Must be a valid email adress")
+			}
+		}
+		if value := props.SatelliteName; value != nil && !*cdk.Token_IsUnresolved(value) {
+			switch *value {
+			case "JPSS1":
+			default:
+				panic("satelliteName must be one of: JPSS1")
+			}
+		}
+		if value := props.SshCidrBlock; value != nil && !*cdk.Token_IsUnresolved(value) {
+			if !regexp.MustCompile(`^(?:((\d{1,3})\.){3}\d{1,3}/\d{1,2})$`).MatchString(*value) {
+				panic("sshCidrBlock failed to satisfy constraint: must be a valid CIDR range of the form x.x.x.x/x, for example \"15.16.17.18/16\".")
+			}
+		}
+	}
+
+	sshKeyName := cdk.NewCfnParameter(stack, jsii.String("SSHKeyName"), &cdk.CfnParameterProps{
+		Type: jsii.String("AWS::EC2::KeyPair::KeyName"),
+		Default: props.SshKeyName,
+		Description: jsii.String("Name of the ssh key used to access ec2 hosts. Set this up ahead of time."),
+		ConstraintDescription: jsii.String("must be the name of an existing EC2 KeyPair."),
+	}).ValueAsString()
+
+	vpcId := cdk.NewCfnParameter(stack, jsii.String("VpcId"), &cdk.CfnParameterProps{
+		Type: jsii.String("AWS::EC2::VPC::Id"),
+		Default: props.VpcId,
+		Description: jsii.String("VPC to launch instances in."),
+	}).ValueAsString()
+
+	subnetId := cdk.NewCfnParameter(stack, jsii.String("SubnetId"), &cdk.CfnParameterProps{
+		Type: jsii.String("AWS::EC2::Subnet::Id"),
+		Default: props.SubnetId,
+		Description: jsii.String("Subnet to launch instances in"),
+	}).ValueAsString()
+
+	stack.TemplateOptions().SetTemplateFormatVersion(jsii.String("2010-09-09"))
+	stack.TemplateOptions().SetMetadata(&map[string]interface{} {
+		"AWS::CloudFormation::Interface": map[string]interface{} {
+			"ParameterGroups": &[]interface{}{
+				map[string]interface{} {
+					"Label": map[string]interface{} {
+						"default": jsii.String("RT-STPS instance configuration"),
+					},
+					"Parameters": &[]interface{}{
+						jsii.String("VpcId"),
+						jsii.String("SubnetId"),
+						jsii.String("SSHKeyName"),
+					},
+				},
+			},
+		},
+	})
+
 	stack.AddTransform(jsii.String("AWS::Serverless-2016-10-31"))
 
 	groundStationS3DataDeliveryBucket := s3.NewCfnBucket(
@@ -96,6 +159,7 @@ func NewGroundStationStack(scope constructs.Construct, id string, props *GroundS
 			BucketName: props.GroundStationS3DataDeliveryBucketName,
 		},
 	)
+	groundStationS3DataDeliveryBucket.CfnOptions().SetDeletionPolicy(cdk.CfnDeletionPolicy_RETAIN)
 
 	groundStationS3DataDeliveryRole := iam.NewCfnRole(
 		stack,
@@ -115,10 +179,10 @@ func NewGroundStationStack(scope constructs.Construct, id string, props *GroundS
 						},
 						"Condition": map[string]interface{} {
 							"StringEquals": map[string]interface{} {
-								"Aws:SourceAccount": stack.Account(),
+								"aws:SourceAccount": stack.Account(),
 							},
 							"ArnLike": map[string]interface{} {
-								"Aws:SourceArn": jsii.String(fmt.Sprintf("arn:aws:groundstation:%v:%v:config/s3-recording/*", stack.Region(), stack.Account())),
+								"aws:SourceArn": jsii.String(fmt.Sprintf("arn:aws:groundstation:%v:%v:config/s3-recording/*", stack.Region(), stack.Account())),
 							},
 						},
 					},
@@ -168,9 +232,9 @@ func NewGroundStationStack(scope constructs.Construct, id string, props *GroundS
 		jsii.String("InstanceSecurityGroup"),
 		&ec2.CfnSecurityGroupProps{
 			GroupDescription: jsii.String("AWS Ground Station receiver instance security group."),
-			VpcId: props.VpcId,
+			VpcId: vpcId,
 			SecurityGroupIngress: &[]interface{}{
-				&IngressProperty{
+				&ec2.CfnSecurityGroup_IngressProperty{
 					IpProtocol: jsii.String("tcp"),
 					FromPort: jsii.Number(22),
 					ToPort: jsii.Number(22),
@@ -186,23 +250,23 @@ func NewGroundStationStack(scope constructs.Construct, id string, props *GroundS
 		jsii.String("SnppJpssDownlinkDemodDecodeAntennaConfig"),
 		&ground_station.CfnConfigProps{
 			Name: jsii.String("JPSS1 Downlink Demod Decode Antenna Config"),
-			ConfigData: &ConfigDataProperty{
-				AntennaDownlinkDemodDecodeConfig: &AntennaDownlinkDemodDecodeConfigProperty{
-					SpectrumConfig: &SpectrumConfigProperty{
-						CenterFrequency: &FrequencyProperty{
+			ConfigData: &groundstation.CfnConfig_ConfigDataProperty{
+				AntennaDownlinkDemodDecodeConfig: &groundstation.CfnConfig_AntennaDownlinkDemodDecodeConfigProperty{
+					SpectrumConfig: &groundstation.CfnConfig_SpectrumConfigProperty{
+						CenterFrequency: &groundstation.CfnConfig_FrequencyProperty{
 							Value: jsii.Number(7812),
 							Units: jsii.String("MHz"),
 						},
 						Polarization: jsii.String("RIGHT_HAND"),
-						Bandwidth: &FrequencyBandwidthProperty{
+						Bandwidth: &groundstation.CfnConfig_FrequencyBandwidthProperty{
 							Value: jsii.Number(30),
 							Units: jsii.String("MHz"),
 						},
 					},
-					DemodulationConfig: &DemodulationConfigProperty{
+					DemodulationConfig: &groundstation.CfnConfig_DemodulationConfigProperty{
 						UnvalidatedJson: jsii.String("{ \"type\":\"QPSK\", \"qpsk\":{ \"carrierFrequencyRecovery\":{ \"centerFrequency\":{ \"value\":7812, \"units\":\"MHz\" }, \"range\":{ \"value\":250, \"units\":\"kHz\" } }, \"symbolTimingRecovery\":{ \"symbolRate\":{ \"value\":15, \"units\":\"Msps\" }, \"range\":{ \"value\":0.75, \"units\":\"ksps\" }, \"matchedFilter\":{ \"type\":\"ROOT_RAISED_COSINE\", \"rolloffFactor\":0.5 } } } }"),
 					},
-					DecodeConfig: &DecodeConfigProperty{
+					DecodeConfig: &groundstation.CfnConfig_DecodeConfigProperty{
 						UnvalidatedJson: jsii.String("{ \"edges\":[ { \"from\":\"I-Ingress\", \"to\":\"IQ-Recombiner\" }, { \"from\":\"Q-Ingress\", \"to\":\"IQ-Recombiner\" }, { \"from\":\"IQ-Recombiner\", \"to\":\"CcsdsViterbiDecoder\" }, { \"from\":\"CcsdsViterbiDecoder\", \"to\":\"NrzmDecoder\" }, { \"from\":\"NrzmDecoder\", \"to\":\"UncodedFramesEgress\" } ], \"nodeConfigs\":{ \"I-Ingress\":{ \"type\":\"CODED_SYMBOLS_INGRESS\", \"codedSymbolsIngress\":{ \"source\":\"I\" } }, \"Q-Ingress\":{ \"type\":\"CODED_SYMBOLS_INGRESS\", \"codedSymbolsIngress\":{ \"source\":\"Q\" } }, \"IQ-Recombiner\":{ \"type\":\"IQ_RECOMBINER\" }, \"CcsdsViterbiDecoder\":{ \"type\":\"CCSDS_171_133_VITERBI_DECODER\", \"ccsds171133ViterbiDecoder\":{ \"codeRate\":\"ONE_HALF\" } }, \"NrzmDecoder\":{ \"type\":\"NRZ_M_DECODER\" }, \"UncodedFramesEgress\":{ \"type\":\"UNCODED_FRAMES_EGRESS\" } } }"),
 					},
 				},
@@ -215,8 +279,8 @@ func NewGroundStationStack(scope constructs.Construct, id string, props *GroundS
 		jsii.String("TrackingConfig"),
 		&ground_station.CfnConfigProps{
 			Name: jsii.String("JPSS1 Tracking Config"),
-			ConfigData: &ConfigDataProperty{
-				TrackingConfig: &TrackingConfigProperty{
+			ConfigData: &groundstation.CfnConfig_ConfigDataProperty{
+				TrackingConfig: &groundstation.CfnConfig_TrackingConfigProperty{
 					Autotrack: jsii.String("PREFERRED"),
 				},
 			},
@@ -232,7 +296,7 @@ func NewGroundStationStack(scope constructs.Construct, id string, props *GroundS
 				props.SatelliteName,
 			}),
 			Subscription: &[]interface{}{
-				&SubscriptionProperty{
+				&sns.CfnTopic_SubscriptionProperty{
 					Endpoint: props.NotificationEmail,
 					Protocol: jsii.String("email"),
 				},
@@ -426,7 +490,7 @@ func NewGroundStationStack(scope constructs.Construct, id string, props *GroundS
 			GroupSet: &[]*string{
 				instanceSecurityGroup.Ref(),
 			},
-			SubnetId: props.SubnetId,
+			SubnetId: subnetId,
 		},
 	)
 
@@ -447,19 +511,19 @@ func NewGroundStationStack(scope constructs.Construct, id string, props *GroundS
 			IamInstanceProfile: generalInstanceProfile.Ref(),
 			ImageId: amiMap[stack.Region()][jsii.String("ami")],
 			InstanceType: jsii.String("c5.4xlarge"),
-			KeyName: props.SshKeyName,
+			KeyName: sshKeyName,
 			Monitoring: jsii.Bool(true),
 			NetworkInterfaces: &[]interface{}{
-				&NetworkInterfaceProperty{
+				&ec2.CfnInstance_NetworkInterfaceProperty{
 					NetworkInterfaceId: receiverInstanceNetworkInterfacePublic.Ref(),
 					DeviceIndex: jsii.Number(0),
 					DeleteOnTermination: jsii.Bool(false),
 				},
 			},
 			BlockDeviceMappings: &[]interface{}{
-				&BlockDeviceMappingProperty{
+				&ec2.CfnInstance_BlockDeviceMappingProperty{
 					DeviceName: jsii.String("/dev/xvda"),
-					Ebs: &EbsProperty{
+					Ebs: &ec2.CfnInstance_EbsProperty{
 						VolumeType: jsii.String("gp2"),
 						VolumeSize: jsii.Number(100),
 					},
@@ -474,7 +538,7 @@ func NewGroundStationStack(scope constructs.Construct, id string, props *GroundS
 					}),
 				},
 			},
-			UserData: cdk.Fn_Base64(jsii.String(fmt.Sprintf("#!/bin/bash\n\nexec > >(tee /var/log/user-data.log|logger -t user-data -s 2>/dev/console) 2>&1\necho `date +'%F %R:%S'` \"INFO: Logging Setup\" >&2\n\necho \"Setting instance hostname\"\nexport INSTANCE=$(curl -s http://169.254.169.254/latest/meta-data/instance-id)\nexport HOSTNAME=$(aws ec2 describe-tags --filters \"Name=resource-id,Values=$INSTANCE\" \"Name=key,Values=Name\" --region=%v --output=text |cut -f5)\necho $HOSTNAME > /etc/hostname\nhostname $HOSTNAME\n\necho \"Installing RT-STPS pre-reqs\"\nyum update -y && yum install -y wget java python3\n\nGROUND_STATION_DIR=\"/opt/aws/groundstation\"\nGROUND_STATION_BIN_DIR=\"$GROUND_STATION_DIR/bin\"\nPROCESS_SCRIPT=\"$GROUND_STATION_BIN_DIR/rt-stps-process.sh\"\n\necho \"Creating $GROUND_STATION_BIN_DIR\"\nmkdir -p \"$GROUND_STATION_BIN_DIR\"\n\necho \"Getting Assets from S3\"\naws s3 cp --region %v \"s3://%v/software/RT-STPS/rt-stps-process.sh\" \"$PROCESS_SCRIPT\"\nchmod +x \"$PROCESS_SCRIPT\"\nchown ec2-user:ec2-user \"$PROCESS_SCRIPT\"\n\necho \"Adding call to $PROCESS_SCRIPT into /etc/rc.local\"\necho \"TIMESTR=\\$(date '+%Y%m%d-%H%M')\" >> /etc/rc.local\necho \"$PROCESS_SCRIPT %v %v %v 2>&1 | tee $GROUND_STATION_BIN_DIR/data-capture_\\$TIMESTR.log\" >> /etc/rc.local\nchmod +x /etc/rc.d/rc.local\n\necho \"Creating /opt/aws/groundstation/bin/getSNSTopic.sh\"\necho \"export SNS_TOPIC=%v\" > /opt/aws/groundstation/bin/getSNSTopic.sh\nchmod +x /opt/aws/groundstation/bin/getSNSTopic.sh\n\necho \"Sending completion SNS notification\"\nexport MESSAGE=\"GroundStation setup is complete for Satellite: %v.  The RT-STPS processor EC2 instance is all setup and ready to go! It will be automatically started after data from a satellite pass has been deposited in your S3 bucket.  Data will be processed using RT-STPS, then copied to the following S3 Bucket: %v.  A summary of the contact will be emailed to %v. The EC2 instance will now be stopped.\"\naws sns publish --topic-arn %v --message \"$MESSAGE\" --region %v\n\necho \"Shutting down the EC2 instance\"\nshutdown -h now\n\nexit 0\n", stack.Region(), stack.Region(), props.SoftwareS3Bucket, props.SatelliteName, props.SoftwareS3Bucket, props.GroundStationS3DataDeliveryBucketName, snsTopic.Ref(), props.SatelliteName, props.GroundStationS3DataDeliveryBucketName, props.NotificationEmail, snsTopic.Ref(), stack.Region()))),
+			UserData: cdk.Fn_Base64(jsii.String(fmt.Sprintf("#!/bin/bash\n\nexec > >(tee /var/log/user-data.log|logger -t user-data -s 2>/dev/console) 2>&1\necho `date +'%%F %%R:%%S'` \"INFO: Logging Setup\" >&2\n\necho \"Setting instance hostname\"\nexport INSTANCE=$(curl -s http://169.254.169.254/latest/meta-data/instance-id)\nexport HOSTNAME=$(aws ec2 describe-tags --filters \"Name=resource-id,Values=$INSTANCE\" \"Name=key,Values=Name\" --region=%v --output=text |cut -f5)\necho $HOSTNAME > /etc/hostname\nhostname $HOSTNAME\n\necho \"Installing RT-STPS pre-reqs\"\nyum update -y && yum install -y wget java python3\n\nGROUND_STATION_DIR=\"/opt/aws/groundstation\"\nGROUND_STATION_BIN_DIR=\"$GROUND_STATION_DIR/bin\"\nPROCESS_SCRIPT=\"$GROUND_STATION_BIN_DIR/rt-stps-process.sh\"\n\necho \"Creating $GROUND_STATION_BIN_DIR\"\nmkdir -p \"$GROUND_STATION_BIN_DIR\"\n\necho \"Getting Assets from S3\"\naws s3 cp --region %v \"s3://%v/software/RT-STPS/rt-stps-process.sh\" \"$PROCESS_SCRIPT\"\nchmod +x \"$PROCESS_SCRIPT\"\nchown ec2-user:ec2-user \"$PROCESS_SCRIPT\"\n\necho \"Adding call to $PROCESS_SCRIPT into /etc/rc.local\"\necho \"TIMESTR=\\$(date '+%%Y%%m%%d-%%H%%M')\" >> /etc/rc.local\necho \"$PROCESS_SCRIPT %v %v %v 2>&1 | tee $GROUND_STATION_BIN_DIR/data-capture_\\$TIMESTR.log\" >> /etc/rc.local\nchmod +x /etc/rc.d/rc.local\n\necho \"Creating /opt/aws/groundstation/bin/getSNSTopic.sh\"\necho \"export SNS_TOPIC=%v\" > /opt/aws/groundstation/bin/getSNSTopic.sh\nchmod +x /opt/aws/groundstation/bin/getSNSTopic.sh\n\necho \"Sending completion SNS notification\"\nexport MESSAGE=\"GroundStation setup is complete for Satellite: %v.  The RT-STPS processor EC2 instance is all setup and ready to go! It will be automatically started after data from a satellite pass has been deposited in your S3 bucket.  Data will be processed using RT-STPS, then copied to the following S3 Bucket: %v.  A summary of the contact will be emailed to %v. The EC2 instance will now be stopped.\"\naws sns publish --topic-arn %v --message \"$MESSAGE\" --region %v\n\necho \"Shutting down the EC2 instance\"\nshutdown -h now\n\nexit 0\n", stack.Region(), stack.Region(), props.SoftwareS3Bucket, props.SatelliteName, props.SoftwareS3Bucket, props.GroundStationS3DataDeliveryBucketName, snsTopic.Ref(), props.SatelliteName, props.GroundStationS3DataDeliveryBucketName, props.NotificationEmail, snsTopic.Ref(), stack.Region()))),
 		},
 	)
 
@@ -483,8 +547,8 @@ func NewGroundStationStack(scope constructs.Construct, id string, props *GroundS
 		jsii.String("S3RecordingConfig"),
 		&ground_station.CfnConfigProps{
 			Name: jsii.String("JPSS1 Recording Config"),
-			ConfigData: &ConfigDataProperty{
-				S3RecordingConfig: &S3RecordingConfigProperty{
+			ConfigData: &groundstation.CfnConfig_ConfigDataProperty{
+				S3RecordingConfig: &groundstation.CfnConfig_S3RecordingConfigProperty{
 					BucketArn: cdk.Fn_Join(jsii.String(""), &[]*string{
 						jsii.String("arn:aws:s3:::"),
 						props.GroundStationS3DataDeliveryBucketName,
@@ -575,7 +639,7 @@ func NewGroundStationStack(scope constructs.Construct, id string, props *GroundS
 			MinimumViableContactDurationSeconds: jsii.Number(180),
 			TrackingConfigArn: trackingConfig.Ref(),
 			DataflowEdges: &[]interface{}{
-				&DataflowEdgeProperty{
+				&groundstation.CfnMissionProfile_DataflowEdgeProperty{
 					Source: cdk.Fn_Join(jsii.String("/"), &[]*string{
 						snppJpssDownlinkDemodDecodeAntennaConfig.Ref(),
 						jsii.String("UncodedFramesEgress"),
@@ -616,7 +680,7 @@ func NewGroundStationStack(scope constructs.Construct, id string, props *GroundS
 		stack,
 		jsii.String("LambdaFunctionStartRtstps"),
 		&lambda.CfnFunctionProps{
-			Environment: &EnvironmentProperty{
+			Environment: &lambda.CfnFunction_EnvironmentProperty{
 				Variables: map[string]interface{} {
 					"RtstpsInstance": receiverInstance.Ref(),
 				},
@@ -626,7 +690,7 @@ func NewGroundStationStack(scope constructs.Construct, id string, props *GroundS
 			MemorySize: jsii.Number(512),
 			Timeout: jsii.Number(300),
 			Role: groundStationS3ddLambdaRole.AttrArn(),
-			Code: &CodeProperty{
+			Code: &lambda.CfnFunction_CodeProperty{
 				S3Bucket: props.SoftwareS3Bucket,
 				S3Key: jsii.String("software/RT-STPS/lambda.zip"),
 			},
@@ -639,16 +703,16 @@ func NewGroundStationStack(scope constructs.Construct, id string, props *GroundS
 		&events.CfnRuleProps{
 			Description: jsii.String("Triggered when all files have been uploaded for a Ground Station S3 data delivery contact"),
 			EventPattern: map[string]interface{} {
-				"Source": &[]interface{}{
+				"source": &[]interface{}{
 					jsii.String("aws.groundstation"),
 				},
-				"DetailType": &[]interface{}{
+				"detail-type": &[]interface{}{
 					jsii.String("Ground Station S3 Upload Complete"),
 				},
 			},
 			State: jsii.String("ENABLED"),
 			Targets: &[]interface{}{
-				&TargetProperty{
+				&events.CfnRule_TargetProperty{
 					Arn: lambdaFunctionStartRtstps.AttrArn(),
 					Id: jsii.String("LambdaFunctionStartRtstps"),
 				},
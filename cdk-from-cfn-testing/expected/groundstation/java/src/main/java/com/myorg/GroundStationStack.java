@@ -55,9 +55,10 @@ class GroundStationStack extends Stack {
                 : "15.16.17.18/32";
         sshKeyName = Optional.ofNullable(sshKeyName).isPresent()
                 ? sshKeyName
-                : CfnParameter.Builder.create(this, "SshKeyName")
+                : CfnParameter.Builder.create(this, "SSHKeyName")
                         .type("AWS::EC2::KeyPair::KeyName")
                         .defaultValue("")
+                        .constraintDescription("must be the name of an existing EC2 KeyPair.")
                         .build()
                         .getValueAsString();
 
@@ -78,6 +79,38 @@ class GroundStationStack extends Stack {
                         .getValueAsString();
 
 
+        if (groundStationS3DataDeliveryBucketName != null && !Token.isUnresolved(groundStationS3DataDeliveryBucketName)) {
+            if (!groundStationS3DataDeliveryBucketName.matches("^aws-groundstation-[a-z0-9-.]+")) {
+                throw new IllegalArgumentException("groundStationS3DataDeliveryBucketName must match pattern ^aws-groundstation-[a-z0-9-.]+");
+            }
+        }
+
+        if (notificationEmail != null && !Token.isUnresolved(notificationEmail)) {
+            if (!notificationEmail.matches("^[a-zA-Z0-9_.+-]+@[a-zA-Z0-9-]+\\.[a-zA-Z0-9-.]+$")) {
+                throw new IllegalArgumentException("notificationEmail failed to satisfy constraint: Must be a valid email adress");
+            }
+        }
+
+        if (satelliteName != null && !Token.isUnresolved(satelliteName)) {
+            if (!Arrays.asList("JPSS1").contains(satelliteName)) {
+                throw new IllegalArgumentException("satelliteName must be one of: JPSS1");
+            }
+        }
+
+        if (sshCidrBlock != null && !Token.isUnresolved(sshCidrBlock)) {
+            if (!sshCidrBlock.matches("((\\d{1,3})\\.){3}\\d{1,3}/\\d{1,2}")) {
+                throw new IllegalArgumentException("sshCidrBlock failed to satisfy constraint: must be a valid CIDR range of the form x.x.x.x/x, for example \"15.16.17.18/16\".");
+            }
+        }
+
+        this.getTemplateOptions().setTemplateFormatVersion("2010-09-09");
+        this.getTemplateOptions().setMetadata(Map.of("AWS::CloudFormation::Interface", Map.of("ParameterGroups", Arrays.asList(
+                Map.of("Label", Map.of("default", "RT-STPS instance configuration"),
+                "Parameters", Arrays.asList(
+                        "VpcId",
+                        "SubnetId",
+                        "SSHKeyName"))))));
+
         this.addTransform("AWS::Serverless-2016-10-31");
         // Mappings
         final CfnMapping amiMap = new CfnMapping(this, "amiMap");
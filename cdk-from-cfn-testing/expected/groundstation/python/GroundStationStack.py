@@ -8,6 +8,7 @@ import aws_cdk.aws_lambda as aws_lambda
 import aws_cdk.aws_s3 as s3
 import aws_cdk.aws_sns as sns
 from constructs import Construct
+import re
 
 """
   Ground Station S3 Data Delivery stack for JPSS1
@@ -23,21 +24,55 @@ class GroundStationStack(Stack):
       'satelliteName': kwargs.get('satelliteName', 'JPSS1'),
       'softwareS3Bucket': kwargs.get('softwareS3Bucket', 'your-software-bucket'),
       'sshCidrBlock': kwargs.get('sshCidrBlock', '15.16.17.18/32'),
-      'sshKeyName': cdk.CfnParameter(self, 'sshKeyName', 
+      'sshKeyName': cdk.CfnParameter(self, 'SSHKeyName',
         type = 'AWS::EC2::KeyPair::KeyName',
         default = str(kwargs.get('sshKeyName', '')),
         description = 'Name of the ssh key used to access ec2 hosts. Set this up ahead of time.',
-      ),
-      'vpcId': cdk.CfnParameter(self, 'vpcId', 
+        constraint_description = 'must be the name of an existing EC2 KeyPair.',
+      ).value_as_string,
+      'vpcId': cdk.CfnParameter(self, 'VpcId',
         type = 'AWS::EC2::VPC::Id',
         default = str(kwargs.get('vpcId', '')),
         description = 'VPC to launch instances in.',
-      ),
-      'subnetId': cdk.CfnParameter(self, 'subnetId', 
+      ).value_as_string,
+      'subnetId': cdk.CfnParameter(self, 'SubnetId',
         type = 'AWS::EC2::Subnet::Id',
         default = str(kwargs.get('subnetId', '')),
         description = 'Subnet to launch instances in',
-      ),
+      ).value_as_string,
+    }
+
+    # Validating props
+    if props['groundStationS3DataDeliveryBucketName'] is not None and not cdk.Token.is_unresolved(props['groundStationS3DataDeliveryBucketName']):
+      if not re.fullmatch('^aws-groundstation-[a-z0-9-.]+', props['groundStationS3DataDeliveryBucketName']):
+        raise ValueError('groundStationS3DataDeliveryBucketName must match pattern ^aws-groundstation-[a-z0-9-.]+')
+    if props['notificationEmail'] is not None and not cdk.Token.is_unresolved(props['notificationEmail']):
+      if not re.fullmatch('^[a-zA-Z0-9_.+-]+@[a-zA-Z0-9-]+\\.[a-zA-Z0-9-.]+$', props['notificationEmail']):
+        raise ValueError('notificationEmail failed to satisfy constraint: Must be a valid email adress')
+    if props['satelliteName'] is not None and not cdk.Token.is_unresolved(props['satelliteName']):
+      if props['satelliteName'] not in ['JPSS1']:
+        raise ValueError('satelliteName must be one of: JPSS1')
+    if props['sshCidrBlock'] is not None and not cdk.Token.is_unresolved(props['sshCidrBlock']):
+      if not re.fullmatch('((\\d{1,3})\\.){3}\\d{1,3}/\\d{1,2}', props['sshCidrBlock']):
+        raise ValueError('sshCidrBlock failed to satisfy constraint: must be a valid CIDR range of the form x.x.x.x/x, for example "15.16.17.18/16".')
+
+    # Template options
+    self.template_options.template_format_version = '2010-09-09'
+    self.template_options.metadata = {
+      'AWS::CloudFormation::Interface': {
+        'ParameterGroups': [
+          {
+            'Label': {
+              'default': 'RT-STPS instance configuration',
+            },
+            'Parameters': [
+              'VpcId',
+              'SubnetId',
+              'SSHKeyName',
+            ],
+          },
+        ],
+      },
     }
 
     # Transforms
@@ -435,8 +470,8 @@ class GroundStationStack(Stack):
           chown ec2-user:ec2-user "$PROCESS_SCRIPT"
 
           echo "Adding call to $PROCESS_SCRIPT into /etc/rc.local"
-          echo "TIMESTR=\$(date '+%Y%m%d-%H%M')" >> /etc/rc.local
-          echo "$PROCESS_SCRIPT {props['satelliteName']} {props['softwareS3Bucket']} {props['groundStationS3DataDeliveryBucketName']} 2>&1 | tee $GROUND_STATION_BIN_DIR/data-capture_\$TIMESTR.log" >> /etc/rc.local
+          echo "TIMESTR=\\$(date '+%Y%m%d-%H%M')" >> /etc/rc.local
+          echo "$PROCESS_SCRIPT {props['satelliteName']} {props['softwareS3Bucket']} {props['groundStationS3DataDeliveryBucketName']} 2>&1 | tee $GROUND_STATION_BIN_DIR/data-capture_\\$TIMESTR.log" >> /etc/rc.local
           chmod +x /etc/rc.d/rc.local
 
           echo "Creating /opt/aws/groundstation/bin/getSNSTopic.sh"
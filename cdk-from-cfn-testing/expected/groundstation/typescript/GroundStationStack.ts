@@ -67,10 +67,11 @@ export class GroundStationStack extends cdk.Stack {
       satelliteName: props.satelliteName ?? 'JPSS1',
       softwareS3Bucket: props.softwareS3Bucket ?? 'your-software-bucket',
       sshCidrBlock: props.sshCidrBlock ?? '15.16.17.18/32',
-      sshKeyName: new cdk.CfnParameter(this, 'SshKeyName', {
+      sshKeyName: new cdk.CfnParameter(this, 'SSHKeyName', {
         type: 'AWS::EC2::KeyPair::KeyName',
         default: props.sshKeyName?.toString() ?? '',
         description: 'Name of the ssh key used to access ec2 hosts. Set this up ahead of time.',
+        constraintDescription: 'must be the name of an existing EC2 KeyPair.',
       }).valueAsString,
       vpcId: new cdk.CfnParameter(this, 'VpcId', {
         type: 'AWS::EC2::VPC::Id',
@@ -84,6 +85,47 @@ export class GroundStationStack extends cdk.Stack {
       }).valueAsString,
     };
 
+    // Validating props
+    if (!cdk.Token.isUnresolved(props.groundStationS3DataDeliveryBucketName)) {
+      if (!/^(?:^aws-groundstation-[a-z0-9-.]+)$/.test(props.groundStationS3DataDeliveryBucketName!)) {
+        throw new Error('groundStationS3DataDeliveryBucketName must match pattern ^aws-groundstation-[a-z0-9-.]+');
+      }
+    }
+    if (!cdk.Token.isUnresolved(props.notificationEmail)) {
+      if (!/^(?:^[a-zA-Z0-9_.+-]+@[a-zA-Z0-9-]+\.[a-zA-Z0-9-.]+$)$/.test(props.notificationEmail!)) {
+        throw new Error('notificationEmail failed to satisfy constraint: Must be a valid email adress');
+      }
+    }
+    if (!cdk.Token.isUnresolved(props.satelliteName)) {
+      if (!['JPSS1'].includes(props.satelliteName!)) {
+        throw new Error('satelliteName must be one of: JPSS1');
+      }
+    }
+    if (!cdk.Token.isUnresolved(props.sshCidrBlock)) {
+      if (!/^(?:((\d{1,3})\.){3}\d{1,3}\/\d{1,2})$/.test(props.sshCidrBlock!)) {
+        throw new Error('sshCidrBlock failed to satisfy constraint: must be a valid CIDR range of the form x.x.x.x/x, for example \"15.16.17.18/16\".');
+      }
+    }
+
+    // Template options
+    this.templateOptions.templateFormatVersion = '2010-09-09';
+    this.templateOptions.metadata = {
+      'AWS::CloudFormation::Interface': {
+        ParameterGroups: [
+          {
+            Label: {
+              default: 'RT-STPS instance configuration',
+            },
+            Parameters: [
+              'VpcId',
+              'SubnetId',
+              'SSHKeyName',
+            ],
+          },
+        ],
+      },
+    };
+
     // Transforms
     this.addTransform('AWS::Serverless-2016-10-31');
 
@@ -455,7 +497,7 @@ export class GroundStationStack extends cdk.Stack {
       userData: cdk.Fn.base64(`#!/bin/bash
 
       exec > >(tee /var/log/user-data.log|logger -t user-data -s 2>/dev/console) 2>&1
-      echo `date +'%F %R:%S'` "INFO: Logging Setup" >&2
+      echo \`date +'%F %R:%S'\` "INFO: Logging Setup" >&2
 
       echo "Setting instance hostname"
       export INSTANCE=$(curl -s http://169.254.169.254/latest/meta-data/instance-id)
@@ -479,8 +521,8 @@ export class GroundStationStack extends cdk.Stack {
       chown ec2-user:ec2-user "$PROCESS_SCRIPT"
 
       echo "Adding call to $PROCESS_SCRIPT into /etc/rc.local"
-      echo "TIMESTR=\$(date '+%Y%m%d-%H%M')" >> /etc/rc.local
-      echo "$PROCESS_SCRIPT ${props.satelliteName!} ${props.softwareS3Bucket!} ${props.groundStationS3DataDeliveryBucketName!} 2>&1 | tee $GROUND_STATION_BIN_DIR/data-capture_\$TIMESTR.log" >> /etc/rc.local
+      echo "TIMESTR=\\$(date '+%Y%m%d-%H%M')" >> /etc/rc.local
+      echo "$PROCESS_SCRIPT ${props.satelliteName!} ${props.softwareS3Bucket!} ${props.groundStationS3DataDeliveryBucketName!} 2>&1 | tee $GROUND_STATION_BIN_DIR/data-capture_\\$TIMESTR.log" >> /etc/rc.local
       chmod +x /etc/rc.d/rc.local
 
       echo "Creating /opt/aws/groundstation/bin/getSNSTopic.sh"
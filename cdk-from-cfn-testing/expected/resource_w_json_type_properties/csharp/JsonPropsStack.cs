@@ -14,6 +14,9 @@ namespace JsonPropsStack
     {
         public JsonPropsStack(Construct scope, string id, JsonPropsStackProps props = null) : base(scope, id, props)
         {
+            // Template options
+            TemplateOptions.TemplateFormatVersion = "2010-09-09";
+
 
             // Resources
             var myQueue1 = new CfnQueue(this, "MyQueue1", new CfnQueueProps
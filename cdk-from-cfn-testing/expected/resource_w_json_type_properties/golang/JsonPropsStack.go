@@ -23,6 +23,8 @@ func NewJsonPropsStack(scope constructs.Construct, id string, props *JsonPropsSt
 	}
 	stack := cdk.NewStack(scope, &id, &sprops)
 
+	stack.TemplateOptions().SetTemplateFormatVersion(jsii.String("2010-09-09"))
+
 	myQueue1 := sqs.NewCfnQueue(
 		stack,
 		jsii.String("MyQueue1"),
@@ -42,7 +44,7 @@ func NewJsonPropsStack(scope constructs.Construct, id string, props *JsonPropsSt
 		jsii.String("MyRDMessageQueueGroup"),
 		&iam.CfnGroupProps{
 			Policies: &[]interface{}{
-				&PolicyProperty{
+				&iam.CfnGroup_PolicyProperty{
 					PolicyName: jsii.String("MyQueueGroupPolicy"),
 					PolicyDocument: map[string]interface{} {
 						"Statement": &[]interface{}{
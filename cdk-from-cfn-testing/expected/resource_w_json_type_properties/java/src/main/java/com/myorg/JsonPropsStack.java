@@ -20,6 +20,8 @@ class JsonPropsStack extends Stack {
     public JsonPropsStack(final Construct scope, final String id, final StackProps props) {
         super(scope, id, props);
 
+        this.getTemplateOptions().setTemplateFormatVersion("2010-09-09");
+
         CfnQueue myQueue1 = CfnQueue.Builder.create(this, "MyQueue1")
                 .build();
 
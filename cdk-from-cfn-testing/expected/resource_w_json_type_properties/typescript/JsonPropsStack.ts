@@ -9,6 +9,9 @@ export class JsonPropsStack extends cdk.Stack {
   public constructor(scope: cdk.App, id: string, props: JsonPropsStackProps = {}) {
     super(scope, id, props);
 
+    // Template options
+    this.templateOptions.templateFormatVersion = '2010-09-09';
+
     // Resources
     const myQueue1 = new sqs.CfnQueue(this, 'MyQueue1', {
     });
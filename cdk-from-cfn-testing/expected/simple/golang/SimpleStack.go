@@ -12,7 +12,7 @@ type SimpleStackProps struct {
 	cdk.StackProps
 	/// The prefix for the bucket name
 	BucketNamePrefix *string
-	LogDestinationBucketName interface{/* AWS::SSM::Parameter::Value<String> */}
+	LogDestinationBucketName *string
 }
 
 /// An example stack that uses many of the syntax elements permitted in a
@@ -95,6 +95,11 @@ func NewSimpleStack(scope constructs.Construct, id string, props *SimpleStackPro
 	}
 	stack := cdk.NewStack(scope, &id, &sprops)
 
+	logDestinationBucketName := cdk.NewCfnParameter(stack, jsii.String("LogDestinationBucketName"), &cdk.CfnParameterProps{
+		Type: jsii.String("AWS::SSM::Parameter::Value<String>"),
+		Default: props.LogDestinationBucketName,
+	}).ValueAsString()
+
 	isUs := cdk.Fn_Select(jsii.Number(0), cdk.Fn_Split(jsii.String("-"), stack.Region())) == jsii.String("us")
 
 	isUsEast1 := stack.Region() == jsii.String("us-east-1")
@@ -117,17 +122,18 @@ func NewSimpleStack(scope constructs.Construct, id string, props *SimpleStackPro
 			VisibilityTimeout: jsii.Number(120),
 		},
 	)
+	queue.CfnOptions().SetDeletionPolicy(cdk.CfnDeletionPolicy_RETAIN_EXCEPT_ON_CREATE)
 
 	bucket := s3.NewCfnBucket(
 		stack,
 		jsii.String("Bucket"),
 		&s3.CfnBucketProps{
 			AccessControl: jsii.String("Private"),
-			LoggingConfiguration: &LoggingConfigurationProperty{
-				DestinationBucketName: props.LogDestinationBucketName,
+			LoggingConfiguration: &s3.CfnBucket_LoggingConfigurationProperty{
+				DestinationBucketName: logDestinationBucketName,
 			},
-			WebsiteConfiguration: &WebsiteConfigurationProperty{
-				RedirectAllRequestsTo: &RedirectAllRequestsToProperty{
+			WebsiteConfiguration: &s3.CfnBucket_WebsiteConfigurationProperty{
+				RedirectAllRequestsTo: &s3.CfnBucket_RedirectAllRequestsToProperty{
 					HostName: jsii.String("example.com"),
 					Protocol: jsii.String("https"),
 				},
@@ -144,6 +150,7 @@ func NewSimpleStack(scope constructs.Construct, id string, props *SimpleStackPro
 			},
 		},
 	)
+	bucket.CfnOptions().SetDeletionPolicy(cdk.CfnDeletionPolicy_DELETE)
 
 	cdk.NewCfnOutput(stack, jsii.String("CfnOutputBucketArn"), &cdk.CfnOutputProps{
 		Key: jsii.String("BucketArn"),
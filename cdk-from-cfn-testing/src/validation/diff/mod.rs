@@ -5,4 +5,4 @@ mod stack;
 mod template;
 
 pub use stack::StackDiff;
-pub use template::TemplateDiff;
+pub use template::TemplateComparison;
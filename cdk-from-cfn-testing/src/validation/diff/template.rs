@@ -1,6 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use cdk_from_cfn::verify::TemplateDiff;
 use serde_json::Value;
 
 use crate::{
@@ -8,192 +9,13 @@ use crate::{
     validation::json::JSON,
 };
 
-/// Advanced JSON diff utility for CloudFormation template comparison.
+/// CloudFormation template comparison for the snapshot tests.
 /// 
-/// Provides semantic comparison of JSON structures with detailed reporting
-/// of differences, acceptable diff management, and multi-template validation.
-pub struct TemplateDiff;
-
-impl TemplateDiff {
-    /// Generates a detailed diff report showing specific differences between JSON structures.
-    /// 
-    /// Recursively compares JSON values and produces human-readable output
-    /// highlighting missing keys, extra keys, and value mismatches.
-    /// 
-    /// # Arguments
-    /// * `expected` - Expected JSON structure
-    /// * `actual` - Actual JSON structure
-    /// 
-    /// # Returns
-    /// Formatted string describing all differences found
-    pub fn report(expected: &Value, actual: &Value) -> String {
-        let mut diff_lines = Vec::new();
-        Self::find_differences(expected, actual, "", &mut diff_lines);
-
-        if diff_lines.is_empty() {
-            "No differences found".to_string()
-        } else {
-            format!("Differences found:\n{}", diff_lines.join("\n\n"))
-        }
-    }
-
-    /// Recursively traverses JSON structures to identify all differences.
-    /// 
-    /// Handles objects, arrays, and primitive values, tracking the path
-    /// to each difference for detailed reporting.
-    /// 
-    /// # Arguments
-    /// * `expected` - Expected JSON value
-    /// * `actual` - Actual JSON value
-    /// * `path` - Current path in the JSON structure
-    /// * `diff_lines` - Accumulator for difference descriptions
-    fn find_differences(
-        expected: &Value,
-        actual: &Value,
-        path: &str,
-        diff_lines: &mut Vec<String>,
-    ) {
-        match (expected, actual) {
-            (Value::Object(exp_map), Value::Object(act_map)) => {
-                for (key, exp_val) in exp_map {
-                    let current_path = Self::build_path(path, key);
-                    match act_map.get(key) {
-                        Some(act_val) => {
-                            Self::find_differences(exp_val, act_val, &current_path, diff_lines)
-                        }
-                        None => {
-                            Self::add_missing_key_diff(&current_path, exp_val, diff_lines);
-                        }
-                    }
-                }
-                for key in act_map.keys() {
-                    if !exp_map.contains_key(key) {
-                        let current_path = Self::build_path(path, key);
-                        Self::add_extra_key_diff(
-                            &current_path,
-                            act_map.get(key).unwrap(),
-                            diff_lines,
-                        );
-                    }
-                }
-            }
-            (Value::Array(exp_arr), Value::Array(act_arr)) => {
-                if exp_arr.len() != act_arr.len() {
-                    diff_lines.push(format!(
-                        "Array length mismatch at {}: expected {}, got {}",
-                        path,
-                        exp_arr.len(),
-                        act_arr.len()
-                    ));
-                }
-                for (i, (exp_item, act_item)) in exp_arr.iter().zip(act_arr.iter()).enumerate() {
-                    let current_path = format!("{}[{}]", path, i);
-                    Self::find_differences(exp_item, act_item, &current_path, diff_lines);
-                }
-            }
-            (exp, act) if exp != act => {
-                Self::add_value_mismatch_diff(path, exp, act, diff_lines);
-            }
-            _ => {}
-        }
-    }
-
-    /// Constructs a dot-notation path for nested JSON keys.
-    /// 
-    /// # Arguments
-    /// * `base` - Base path (empty for root level)
-    /// * `key` - Key to append to the path
-    /// 
-    /// # Returns
-    /// Dot-notation path string
-    fn build_path(base: &str, key: &str) -> String {
-        if base.is_empty() {
-            key.to_string()
-        } else {
-            format!("{}.{}", base, key)
-        }
-    }
-
-    /// Formats JSON values for human-readable output.
-    /// 
-    /// # Arguments
-    /// * `value` - JSON value to format
-    /// 
-    /// # Returns
-    /// Pretty-printed JSON string with fallback to debug format
-    fn pretty_print_json(value: &Value) -> String {
-        serde_json::to_string_pretty(value).unwrap_or_else(|_| format!("{:?}", value))
-    }
-
-    /// Adds consistent indentation to multi-line text.
-    /// 
-    /// # Arguments
-    /// * `text` - Text to indent
-    /// * `spaces` - Number of spaces for indentation
-    /// 
-    /// # Returns
-    /// Indented text with specified spacing
-    fn indent(text: &str, spaces: usize) -> String {
-        let indent_str = " ".repeat(spaces);
-        text.lines()
-            .map(|line| format!("{}{}", indent_str, line))
-            .collect::<Vec<_>>()
-            .join("\n")
-    }
-
-    /// Records a missing key difference in the diff report.
-    /// 
-    /// # Arguments
-    /// * `path` - Path to the missing key
-    /// * `value` - Expected value that was missing
-    /// * `diff_lines` - Accumulator for difference descriptions
-    fn add_missing_key_diff(path: &str, value: &Value, diff_lines: &mut Vec<String>) {
-        let pretty = Self::pretty_print_json(value);
-        diff_lines.push(format!(
-            "- Missing key: {}\n  Expected:\n{}",
-            path,
-            Self::indent(&pretty, 4)
-        ));
-    }
-
-    /// Records an extra key difference in the diff report.
-    /// 
-    /// # Arguments
-    /// * `path` - Path to the extra key
-    /// * `value` - Actual value that was unexpected
-    /// * `diff_lines` - Accumulator for difference descriptions
-    fn add_extra_key_diff(path: &str, value: &Value, diff_lines: &mut Vec<String>) {
-        let pretty = Self::pretty_print_json(value);
-        diff_lines.push(format!(
-            "+ Extra key: {}\n  Actual:\n{}",
-            path,
-            Self::indent(&pretty, 4)
-        ));
-    }
-
-    /// Records a value mismatch difference in the diff report.
-    /// 
-    /// # Arguments
-    /// * `path` - Path to the mismatched value
-    /// * `expected` - Expected value
-    /// * `actual` - Actual value
-    /// * `diff_lines` - Accumulator for difference descriptions
-    fn add_value_mismatch_diff(
-        path: &str,
-        expected: &Value,
-        actual: &Value,
-        diff_lines: &mut Vec<String>,
-    ) {
-        let exp_pretty = Self::pretty_print_json(expected);
-        let act_pretty = Self::pretty_print_json(actual);
-        diff_lines.push(format!(
-            "Value mismatch at {}:\n  Expected:\n{}\n  Actual:\n{}",
-            path,
-            Self::indent(&exp_pretty, 4),
-            Self::indent(&act_pretty, 4)
-        ));
-    }
+/// Compares templates with the semantic diff of `cdk_from_cfn::verify::TemplateDiff`,
+/// adding acceptable diff management and multi-template validation.
+pub struct TemplateComparison;
 
+impl TemplateComparison {
     /// Performs semantic comparison of CloudFormation templates with acceptable diff support.
     /// 
     /// Normalizes both templates and compares them semantically. Supports acceptable
@@ -254,9 +76,9 @@ impl TemplateDiff {
             None => return,
         };
 
-        let first_json = TemplateDiff::normalize_content(first_template, first_lang);
+        let first_json = Self::normalize_content(first_template, first_lang);
         for (lang, template) in templates.iter().skip(1) {
-            let current_json = TemplateDiff::normalize_content(template, lang);
+            let current_json = Self::normalize_content(template, lang);
 
             assert!(
                 first_json == current_json,
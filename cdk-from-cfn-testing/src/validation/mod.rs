@@ -5,7 +5,7 @@ mod diff;
 mod json;
 mod templates;
 
-use diff::TemplateDiff;
+use diff::TemplateComparison;
 pub use templates::Templates;
 
 pub use diff::StackDiff;
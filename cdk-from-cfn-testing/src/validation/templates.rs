@@ -1,7 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use super::TemplateDiff;
+use super::TemplateComparison;
 use crate::{
     filesystem::{Files, Zip},
     Scope,
@@ -33,7 +33,7 @@ impl Templates {
             "  ✨ Synthesized App {}::{} passed ({})",
             scope.test,
             scope.lang,
-            TemplateDiff::compare(
+            TemplateComparison::compare(
                 expected_content.as_str(),
                 actual_content.as_str(),
                 &scope.test,
@@ -55,7 +55,7 @@ impl Templates {
     /// Panics if templates differ between languages
     pub fn validate_all(languages: &Vec<String>, scope: &Scope, stack_name: &str) {
         let templates = Self::get_synthesized_templates(languages, scope, stack_name);
-        TemplateDiff::compare_multiple_templates(&templates);
+        TemplateComparison::compare_multiple_templates(&templates);
         eprintln!(
             "  ✨ Synthesized App {}::[{}] matches all other apps",
             scope.test,
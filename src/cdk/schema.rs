@@ -168,6 +168,14 @@ impl<V> Map<V> {
             Map::HashMap(map) => map.get(key),
         }
     }
+
+    // Iterates over the entries of the map, in no particular order.
+    fn iter(&self) -> Box<dyn Iterator<Item = (&str, &V)> + '_> {
+        match self {
+            Map::PhfMap(map) => Box::new(map.entries().map(|(key, value)| (*key, *value))),
+            Map::HashMap(map) => Box::new(map.iter().map(|(key, value)| (key.as_str(), value))),
+        }
+    }
 }
 
 impl<V> From<&'static phf::Map<&'static str, &'static V>> for Map<V> {
@@ -185,6 +193,12 @@ impl<V> From<HashMap<String, V, Hasher>> for Map<V> {
 pub trait PropertyBag {
     // Retrieves the property with the provided CloudFormation name, if any.
     fn property(&self, name: &str) -> Option<Property>;
+
+    // Lists the properties declared by this bag, by CloudFormation name, in no particular order.
+    // Bags accepting any name (such as maps) declare none.
+    fn properties(&self) -> Vec<(&str, &Property)> {
+        Vec::new()
+    }
 }
 
 // Information about an AWS CDK construct class.
@@ -228,6 +242,10 @@ impl PropertyBag for CfnResource {
     fn property(&self, name: &str) -> Option<Property> {
         self.properties.get(name).cloned()
     }
+
+    fn properties(&self) -> Vec<(&str, &Property)> {
+        self.properties.iter().collect()
+    }
 }
 
 // Information about an AWS CDK struct.
@@ -257,6 +275,10 @@ impl PropertyBag for DataType {
     fn property(&self, name: &str) -> Option<Property> {
         self.properties.get(name).cloned()
     }
+
+    fn properties(&self) -> Vec<(&str, &Property)> {
+        self.properties.iter().collect()
+    }
 }
 
 // A multi-language aware type name.
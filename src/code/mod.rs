@@ -40,6 +40,17 @@ impl CodeBuffer {
         content.push(CodeBufferContent::String(text.into(), false));
     }
 
+    /// Adds each of `comments` as a line comment starting with `marker` (e.g: `//`).
+    pub fn comments(&self, marker: &str, comments: &[String]) {
+        for comment in comments {
+            if comment.is_empty() {
+                self.line(marker.to_string());
+            } else {
+                self.line(format!("{marker} {comment}"));
+            }
+        }
+    }
+
     /// Creates a new indented sub-buffer at the current position.
     #[inline]
     pub fn indent(&self, indent: Cow<'static, str>) -> Rc<CodeBuffer> {
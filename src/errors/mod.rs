@@ -2,6 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 use thiserror::Error;
 
+use crate::ir::diagnostics::Diagnostics;
+use crate::parser::span::Span;
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("{message}")]
@@ -30,6 +33,81 @@ pub enum Error {
     TypeReferenceError { message: String },
     #[error("{message}")]
     PrimitiveError { message: String },
+    #[error("cyclic dependency detected: {}", cycle.join(" -> "))]
+    CyclicDependency { cycle: Vec<String> },
+    #[error("{from} references {to}, which is not defined in the template")]
+    UnresolvedReference { from: String, to: String },
+    #[error("{intrinsic} is not supported in {location}")]
+    UnsupportedIntrinsic { intrinsic: String, location: String },
+    #[error("{value:?} is not a valid default value for a {parameter_type} parameter")]
+    InvalidParameterDefault {
+        value: String,
+        parameter_type: String,
+    },
+    #[error("{path}: {source}")]
+    LocatedError { path: String, source: Box<Error> },
+    #[error("{source}")]
+    SpannedError { span: Span, source: Box<Error> },
+    #[error("{diagnostics}")]
+    InvalidTemplate { diagnostics: Diagnostics },
+}
+
+impl Error {
+    // Records that this error happened within the entry named `key` of an object.
+    pub(crate) fn at_key(self, key: &str) -> Self {
+        match self {
+            Self::LocatedError { path, source } => Self::LocatedError {
+                path: if path.starts_with('[') {
+                    format!("{key}{path}")
+                } else {
+                    format!("{key}.{path}")
+                },
+                source,
+            },
+            other => Self::LocatedError {
+                path: key.to_string(),
+                source: Box::new(other),
+            },
+        }
+    }
+
+    // Records where this error happened in the template, unless a more precise position is known.
+    pub(crate) fn at_span(self, span: Option<Span>) -> Self {
+        match span {
+            Some(span) if self.span().is_none() => Self::SpannedError {
+                span,
+                source: Box::new(self),
+            },
+            _ => self,
+        }
+    }
+
+    /// The position in the template this error happened at, if it is known.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Self::SpannedError { span, .. } => Some(*span),
+            Self::LocatedError { source, .. } => source.span(),
+            _ => None,
+        }
+    }
+
+    // Records that this error happened within the item at `index` of a list.
+    pub(crate) fn at_index(self, index: usize) -> Self {
+        match self {
+            Self::LocatedError { path, source } => Self::LocatedError {
+                path: if path.starts_with('[') {
+                    format!("[{index}]{path}")
+                } else {
+                    format!("[{index}].{path}")
+                },
+                source,
+            },
+            other => Self::LocatedError {
+                path: format!("[{index}]"),
+                source: Box::new(other),
+            },
+        }
+    }
 }
 
 #[cfg(test)]
@@ -72,3 +72,61 @@ fn test_primitive_error() {
     };
     assert_eq!(error.to_string(), "Primitive error");
 }
+
+#[test]
+fn test_cyclic_dependency() {
+    let error = crate::Error::CyclicDependency {
+        cycle: vec!["A".to_string(), "B".to_string(), "A".to_string()],
+    };
+    assert_eq!(error.to_string(), "cyclic dependency detected: A -> B -> A");
+}
+
+#[test]
+fn test_unresolved_reference() {
+    let error = crate::Error::UnresolvedReference {
+        from: "Bucket".to_string(),
+        to: "Missing".to_string(),
+    };
+    assert_eq!(
+        error.to_string(),
+        "Bucket references Missing, which is not defined in the template"
+    );
+}
+
+#[test]
+fn test_unsupported_intrinsic() {
+    let error = crate::Error::UnsupportedIntrinsic {
+        intrinsic: "Fn::If".to_string(),
+        location: "Conditions.IsProd".to_string(),
+    };
+    assert_eq!(
+        error.to_string(),
+        "Fn::If is not supported in Conditions.IsProd"
+    );
+}
+
+#[test]
+fn test_located_error() {
+    let error = crate::Error::ResourceTranslationError {
+        message: "invalid digit found in string".to_string(),
+    }
+    .at_key("Key")
+    .at_index(2)
+    .at_key("Tags");
+    assert_eq!(
+        error.to_string(),
+        "Tags[2].Key: invalid digit found in string"
+    );
+}
+
+#[test]
+fn test_invalid_template() {
+    let mut diagnostics = crate::ir::diagnostics::Diagnostics::default();
+    diagnostics.error("Resources.A", "first");
+    diagnostics.warning("Outputs.B", "second");
+    let error = crate::Error::InvalidTemplate { diagnostics };
+    assert_eq!(
+        error.to_string(),
+        "error: Resources.A: first\nwarning: Outputs.B: second"
+    );
+}
@@ -1,13 +1,18 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
+use std::collections::{BTreeSet, HashSet};
+
 use indexmap::IndexMap;
 use topological_sort::TopologicalSort;
 
+use super::diagnostics::Diagnostics;
+use super::find_cycle;
 use super::reference::PseudoParameter;
 
 use crate::ir::reference::{Origin, Reference};
 use crate::parser::condition::{ConditionFunction, ConditionValue};
 use crate::util::Hasher;
+use crate::Error;
 
 // ConditionInstructions are simple assignment + boolean
 // clauses, as conditions are based on those composite values.
@@ -21,18 +26,33 @@ pub struct ConditionInstruction {
 }
 
 impl ConditionInstruction {
-    pub(super) fn from(mut parse_tree: IndexMap<String, ConditionFunction, Hasher>) -> Vec<Self> {
-        let order: Vec<String> = determine_order(&parse_tree)
-            .into_iter()
-            .map(ToString::to_string)
-            .collect();
+    pub(super) fn from(
+        mut parse_tree: IndexMap<String, ConditionFunction, Hasher>,
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<Self> {
+        let order: Vec<String> = match determine_order(&parse_tree) {
+            Ok(order) => order.into_iter().map(ToString::to_string).collect(),
+            Err(err) => {
+                let path = match &err {
+                    Error::UnresolvedReference { from, .. } => format!("Conditions.{from}"),
+                    _ => "Conditions".into(),
+                };
+                diagnostics.report(path, err);
+                return Vec::new();
+            }
+        };
 
         order
             .into_iter()
-            .map(|name| {
-                let value = parse_tree.shift_remove(&name).unwrap().into_ir();
-                ConditionInstruction { name, value }
-            })
+            .filter_map(
+                |name| match parse_tree.shift_remove(&name).unwrap().into_ir(&name) {
+                    Ok(value) => Some(ConditionInstruction { name, value }),
+                    Err(err) => {
+                        diagnostics.report(format!("Conditions.{name}"), err);
+                        None
+                    }
+                },
+            )
             .collect()
     }
 }
@@ -64,48 +84,59 @@ impl ConditionIr {
 }
 
 impl ConditionFunction {
-    fn into_ir(self) -> ConditionIr {
-        match self {
+    fn into_ir(self, logical_id: &str) -> Result<ConditionIr, Error> {
+        Ok(match self {
             Self::And(x) => {
-                let and_list = x.into_iter().map(ConditionValue::into_ir).collect();
+                let and_list = x
+                    .into_iter()
+                    .map(|val| val.into_ir(logical_id))
+                    .collect::<Result<_, _>>()?;
                 ConditionIr::And(and_list)
             }
             Self::Equals(x, y) => {
-                let x = x.into_ir();
-                let y = y.into_ir();
+                let x = x.into_ir(logical_id)?;
+                let y = y.into_ir(logical_id)?;
 
                 ConditionIr::Equals(Box::new(x), Box::new(y))
             }
             Self::Not(x) => {
-                let x = x.into_ir();
+                let x = x.into_ir(logical_id)?;
                 ConditionIr::Not(Box::new(x))
             }
             Self::Or(x) => {
-                let or_list = x.into_iter().map(ConditionValue::into_ir).collect();
+                let or_list = x
+                    .into_iter()
+                    .map(|val| val.into_ir(logical_id))
+                    .collect::<Result<_, _>>()?;
                 ConditionIr::Or(or_list)
             }
             Self::Condition(x) => ConditionIr::Condition(x),
-            Self::If { .. } => unimplemented!(),
-        }
+            Self::If { .. } => {
+                return Err(Error::UnsupportedIntrinsic {
+                    intrinsic: "Fn::If".into(),
+                    location: format!("Conditions.{logical_id}"),
+                })
+            }
+        })
     }
 }
 
 impl ConditionValue {
-    fn into_ir(self) -> ConditionIr {
-        match self {
-            Self::Function(function) => function.into_ir(),
+    fn into_ir(self, logical_id: &str) -> Result<ConditionIr, Error> {
+        Ok(match self {
+            Self::Function(function) => function.into_ir(logical_id)?,
             Self::FindInMap(name, x, y) => {
-                let x = x.into_ir();
-                let y = y.into_ir();
+                let x = x.into_ir(logical_id)?;
+                let y = y.into_ir(logical_id)?;
 
                 ConditionIr::Map(name, Box::new(x), Box::new(y))
             }
             Self::Split(delimiter, x) => {
-                let x = x.into_ir();
+                let x = x.into_ir(logical_id)?;
                 ConditionIr::Split(delimiter, Box::new(x))
             }
             Self::Select(index, x) => {
-                let x = x.into_ir();
+                let x = x.into_ir(logical_id)?;
                 ConditionIr::Select(index, Box::new(x))
             }
             Self::String(x) => ConditionIr::Str(x),
@@ -122,7 +153,7 @@ impl ConditionValue {
                 origin: Origin::Condition,
                 name,
             }),
-        }
+        })
     }
 }
 
@@ -131,81 +162,92 @@ impl ConditionValue {
  */
 pub fn determine_order<S>(
     conditions: &indexmap::IndexMap<String, ConditionFunction, S>,
-) -> Vec<&str> {
-    let mut topo: TopologicalSort<&str> = TopologicalSort::new();
+) -> Result<Vec<&str>, Error> {
     // Identify condition dependencies
+    let mut dependencies: IndexMap<&str, BTreeSet<&str>, Hasher> =
+        IndexMap::with_capacity_and_hasher(conditions.len(), Hasher::default());
     for (name, value) in conditions {
-        topo.insert(name.as_str());
-        value.find_dependencies(name, &mut topo);
+        let mut deps = BTreeSet::default();
+        value.find_dependencies(&mut deps);
+        dependencies.insert(name.as_str(), deps);
+    }
+
+    let mut topo: TopologicalSort<&str> = TopologicalSort::new();
+    for (&name, deps) in &dependencies {
+        topo.insert(name);
+        for &dep in deps {
+            if !dependencies.contains_key(dep) {
+                return Err(Error::UnresolvedReference {
+                    from: name.to_string(),
+                    to: dep.to_string(),
+                });
+            }
+            topo.add_dependency(dep, name);
+        }
     }
 
     let mut sorted = Vec::with_capacity(conditions.len());
     while !topo.is_empty() {
         let mut list = topo.pop_all();
         if list.is_empty() {
-            panic!("There are cyclic deps in the conditions clauses")
+            let sorted: HashSet<&str> = sorted.iter().copied().collect();
+            return Err(Error::CyclicDependency {
+                cycle: find_cycle(&dependencies, &sorted),
+            });
         }
         // Ensure consistent ordering in generated code...
         list.sort();
         sorted.extend(list);
     }
 
-    sorted
+    Ok(sorted)
 }
 
 impl ConditionFunction {
-    fn find_dependencies<'a>(
-        &'a self,
-        logical_id: &'a str,
-        topo_sort: &'_ mut TopologicalSort<&'a str>,
-    ) {
+    fn find_dependencies<'a>(&'a self, deps: &'_ mut BTreeSet<&'a str>) {
         match self {
-            Self::And(list) | Self::Or(list) => list
-                .iter()
-                .for_each(|val| val.find_dependencies(logical_id, topo_sort)),
+            Self::And(list) | Self::Or(list) => {
+                list.iter().for_each(|val| val.find_dependencies(deps))
+            }
             Self::Equals(a, b) => {
-                a.find_dependencies(logical_id, topo_sort);
-                b.find_dependencies(logical_id, topo_sort);
+                a.find_dependencies(deps);
+                b.find_dependencies(deps);
             }
             Self::Condition(x) => {
-                topo_sort.add_dependency(x.as_str(), logical_id);
+                deps.insert(x.as_str());
             }
-            Self::Not(cond) => cond.find_dependencies(logical_id, topo_sort),
+            Self::Not(cond) => cond.find_dependencies(deps),
             Self::If {
                 condition_name,
                 if_true,
                 if_false,
                 ..
             } => {
-                topo_sort.add_dependency(condition_name.as_str(), logical_id);
-                if_true.find_dependencies(logical_id, topo_sort);
-                if_false.find_dependencies(logical_id, topo_sort);
+                deps.insert(condition_name.as_str());
+                if_true.find_dependencies(deps);
+                if_false.find_dependencies(deps);
             }
         }
     }
 }
 
 impl ConditionValue {
-    fn find_dependencies<'a>(
-        &'a self,
-        logical_id: &'a str,
-        topo_sort: &'_ mut TopologicalSort<&'a str>,
-    ) {
+    fn find_dependencies<'a>(&'a self, deps: &'_ mut BTreeSet<&'a str>) {
         match self {
             Self::Condition(cond) => {
-                topo_sort.add_dependency(cond.as_str(), logical_id);
+                deps.insert(cond.as_str());
             }
             Self::FindInMap(_, key1, key2) => {
-                key1.find_dependencies(logical_id, topo_sort);
-                key2.find_dependencies(logical_id, topo_sort);
+                key1.find_dependencies(deps);
+                key2.find_dependencies(deps);
             }
             Self::Split(_, key1) => {
-                key1.find_dependencies(logical_id, topo_sort);
+                key1.find_dependencies(deps);
             }
             Self::Select(_, key1) => {
-                key1.find_dependencies(logical_id, topo_sort);
+                key1.find_dependencies(deps);
             }
-            Self::Function(func) => func.find_dependencies(logical_id, topo_sort),
+            Self::Function(func) => func.find_dependencies(deps),
             Self::Ref(_) | Self::String(_) => {}
         }
     }
@@ -5,6 +5,7 @@ use indexmap::IndexMap;
 use crate::ir::conditions::{determine_order, ConditionIr};
 use crate::ir::reference::{Origin, PseudoParameter, Reference};
 use crate::parser::condition::{ConditionFunction, ConditionValue};
+use crate::Error;
 
 #[test]
 fn test_eq_translation() {
@@ -13,7 +14,7 @@ fn test_eq_translation() {
         ConditionValue::Ref("AWS::Region".into()),
     );
 
-    let condition_ir = condition_structure.into_ir();
+    let condition_ir = condition_structure.into_ir("Test").unwrap();
     assert_eq!(
         ConditionIr::Equals(
             Box::new(ConditionIr::Str("us-west-2".into())),
@@ -36,15 +37,55 @@ fn test_sorting() {
     let b = ConditionFunction::Not(ConditionValue::Condition("A".into()));
 
     let hash = IndexMap::from([("A".into(), a), ("B".into(), b)]);
-    let ordered = determine_order(&hash);
+    let ordered = determine_order(&hash).unwrap();
 
     assert_eq!(ordered, vec!["A", "B"]);
 }
 
+#[test]
+fn test_sorting_cycle() {
+    let a = ConditionFunction::Not(ConditionValue::Condition("B".into()));
+    let b = ConditionFunction::Not(ConditionValue::Condition("A".into()));
+
+    let hash = IndexMap::from([("A".into(), a), ("B".into(), b)]);
+    match determine_order(&hash) {
+        Err(Error::CyclicDependency { cycle }) => assert_eq!(cycle, vec!["A", "B", "A"]),
+        other => panic!("expected a cyclic dependency error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_sorting_undefined_condition() {
+    let a = ConditionFunction::Not(ConditionValue::Condition("Missing".into()));
+
+    let hash = IndexMap::from([("A".into(), a)]);
+    match determine_order(&hash) {
+        Err(Error::UnresolvedReference { from, to }) => {
+            assert_eq!(from, "A");
+            assert_eq!(to, "Missing");
+        }
+        other => panic!("expected an unresolved reference error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_if_is_unsupported() {
+    let condition_structure = ConditionFunction::If {
+        condition_name: "Other".into(),
+        if_true: ConditionValue::String("a".into()),
+        if_false: ConditionValue::String("b".into()),
+    };
+    let error = condition_structure.into_ir("IsProd").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Fn::If is not supported in Conditions.IsProd"
+    );
+}
+
 #[test]
 fn test_condition_translation() {
     let condition_structure: ConditionValue = ConditionValue::Condition("other".into());
-    let condition_ir = condition_structure.into_ir();
+    let condition_ir = condition_structure.into_ir("Test").unwrap();
     assert_eq!(
         (ConditionIr::Ref(Reference::new("other", Origin::Condition))),
         condition_ir
@@ -55,6 +96,6 @@ fn test_condition_translation() {
 fn test_simple() {
     assert_eq!(
         ConditionIr::Str("hi".into()),
-        ConditionValue::String("hi".into()).into_ir()
+        ConditionValue::String("hi".into()).into_ir("Test").unwrap()
     );
 }
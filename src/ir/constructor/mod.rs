@@ -1,62 +1,413 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
-use crate::{parser::parameters::Parameter, Hasher};
+use std::fmt;
+
+use crate::parser::parameters::{Parameter, ParameterDefault, ParameterType};
+use crate::{Error, Hasher};
 use indexmap::IndexMap;
 use voca_rs::case::camel_case;
 
-#[derive(Debug, Default)]
+use super::diagnostics::Diagnostics;
+
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct Constructor {
     pub inputs: Vec<ConstructorParameter>,
 }
 
 impl Constructor {
-    pub(super) fn from(parse_tree: IndexMap<String, Parameter, Hasher>) -> Self {
+    /// The logical ID of the CfnParameter of the input named `name`.
+    pub fn logical_id(&self, name: &str) -> String {
+        self.inputs
+            .iter()
+            .find(|input| input.name == name)
+            .map_or_else(|| name.to_string(), |input| input.logical_id.clone())
+    }
+
+    pub(super) fn from(
+        parse_tree: IndexMap<String, Parameter, Hasher>,
+        diagnostics: &mut Diagnostics,
+    ) -> Self {
         Self {
             inputs: parse_tree
                 .into_iter()
-                .map(|(name, param)| ConstructorParameter {
-                    name: camel_case(&name),
-                    description: param.description,
-                    constructor_type: match param.parameter_type {
-                        crate::parser::parameters::ParameterType::String => {
-                            if param.allowed_values.is_some() {
-                                let values = param.allowed_values.clone().unwrap();
-                                if values.len() == 2 {
-                                    if (values[0].to_lowercase() == "true"
-                                        && values[1].to_lowercase() == "false")
-                                        || (values[0].to_lowercase() == "false"
-                                            && values[1].to_lowercase() == "true")
-                                    {
-                                        crate::parser::parameters::ParameterType::Bool.to_string()
-                                    } else {
-                                        param.parameter_type.to_string()
-                                    }
-                                } else {
-                                    param.parameter_type.to_string()
-                                }
-                            } else {
-                                param.parameter_type.to_string()
-                            }
+                .map(|(name, param)| {
+                    let constructor_type = match &param.allowed_values {
+                        // String parameters that only allow true and false are booleans.
+                        Some(values)
+                            if param.parameter_type == ParameterType::String
+                                && values.len() == 2
+                                && values.iter().any(|v| v.eq_ignore_ascii_case("true"))
+                                && values.iter().any(|v| v.eq_ignore_ascii_case("false")) =>
+                        {
+                            ConstructorType::Boolean
                         }
-                        _ => param.parameter_type.to_string(),
-                    },
-                    default_value: param.default,
-                    allowed_values: param.allowed_values,
-                    no_echo: param.no_echo,
+                        _ => ConstructorType::from(&param.parameter_type),
+                    };
+                    let default_value = param.default.and_then(|default| {
+                        DefaultValue::resolve(default, &constructor_type)
+                            .map_err(|err| {
+                                let err = err.at_span(param.span);
+                                diagnostics.report(format!("Parameters.{name}.Default"), err)
+                            })
+                            .ok()
+                    });
+                    ConstructorParameter {
+                        name: camel_case(&name),
+                        logical_id: name,
+                        description: param.description,
+                        constructor_type,
+                        default_value,
+                        allowed_values: param.allowed_values,
+                        no_echo: param.no_echo,
+                        allowed_pattern: param.allowed_pattern,
+                        constraint_description: param.constraint_description,
+                        min_length: param.min_length,
+                        max_length: param.max_length,
+                        min_value: param.min_value,
+                        max_value: param.max_value,
+                        ssm_lookup: false,
+                        comments: param.comments,
+                    }
                 })
                 .collect(),
         }
     }
+
+    /// The checks the generated code runs on the props when the stack is constructed. Props that
+    /// do not become a CfnParameter are never seen by CloudFormation, so their constraints are
+    /// checked by the construct instead. Tokens can only be checked at deploy time, so the
+    /// synthesizers skip the checks for them.
+    pub fn validations(&self) -> Vec<PropValidation<'_>> {
+        self.inputs
+            .iter()
+            .filter(|param| !param.is_cfn_parameter())
+            .filter_map(|param| {
+                let checks = param
+                    .constraints()
+                    .into_iter()
+                    .map(|constraint| ConstraintCheck {
+                        message: param.constraint_message(&constraint),
+                        constraint,
+                    })
+                    .collect::<Vec<_>>();
+                (!checks.is_empty()).then_some(PropValidation { param, checks })
+            })
+            .collect()
+    }
+}
+
+/// The constraints a prop is checked against, see `Constructor::validations`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropValidation<'a> {
+    pub param: &'a ConstructorParameter,
+    pub checks: Vec<ConstraintCheck>,
 }
 
-#[derive(Debug, Default)]
+/// A constraint along with the error message raised when a prop value violates it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConstraintCheck {
+    pub constraint: ParameterConstraint,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct ConstructorParameter {
     pub name: String,
+    /// The name of the parameter in the template, which its CfnParameter keeps as logical ID.
+    pub logical_id: String,
     pub description: Option<String>,
-    pub constructor_type: String,
-    pub default_value: Option<String>,
+    pub constructor_type: ConstructorType,
+    pub default_value: Option<DefaultValue>,
     pub allowed_values: Option<Vec<String>>,
     pub no_echo: Option<String>,
+    pub allowed_pattern: Option<String>,
+    pub constraint_description: Option<String>,
+    pub min_length: Option<u64>,
+    pub max_length: Option<u64>,
+    pub min_value: Option<f64>,
+    pub max_value: Option<f64>,
+    /// Set by `CloudformationProgramIr::lookup_ssm_parameters`: the parameter holds the name of an
+    /// SSM parameter, which the generated code resolves using
+    /// `ssm.StringParameter.valueForStringParameter` instead of declaring a CfnParameter.
+    pub ssm_lookup: bool,
+    /// The comments of the parameter in a YAML template, emitted along with its description.
+    pub comments: Vec<String>,
+}
+
+impl ConstructorParameter {
+    /// Whether the parameter is declared as a CfnParameter, so that CloudFormation validates (or
+    /// resolves) its value, or keeps it out of the stack events.
+    pub fn is_cfn_parameter(&self) -> bool {
+        self.constructor_type.is_aws_specific()
+            || self
+                .no_echo
+                .as_ref()
+                .is_some_and(|x| x.eq_ignore_ascii_case("true"))
+    }
+
+    /// The constraints the generated code must check itself when this parameter becomes a plain
+    /// prop, since CloudFormation only enforces them on a `CfnParameter`. Only `String` and
+    /// `Number` parameters support them.
+    fn constraints(&self) -> Vec<ParameterConstraint> {
+        let mut constraints = Vec::new();
+        let is_string = match self.constructor_type {
+            ConstructorType::String => true,
+            ConstructorType::Number => false,
+            _ => return constraints,
+        };
+        if let Some(values) = self.allowed_values.as_ref().filter(|v| !v.is_empty()) {
+            constraints.push(ParameterConstraint::AllowedValues(values.clone()));
+        }
+        if is_string {
+            if let Some(pattern) = &self.allowed_pattern {
+                constraints.push(ParameterConstraint::AllowedPattern(pattern.clone()));
+            }
+            if let Some(min) = self.min_length {
+                constraints.push(ParameterConstraint::MinLength(min));
+            }
+            if let Some(max) = self.max_length {
+                constraints.push(ParameterConstraint::MaxLength(max));
+            }
+        } else {
+            if let Some(min) = self.min_value {
+                constraints.push(ParameterConstraint::MinValue(min));
+            }
+            if let Some(max) = self.max_value {
+                constraints.push(ParameterConstraint::MaxValue(max));
+            }
+        }
+        constraints
+    }
+
+    /// The error message raised when a prop value violates the given constraint. This uses the
+    /// `ConstraintDescription` when the template has one, like CloudFormation does.
+    fn constraint_message(&self, constraint: &ParameterConstraint) -> String {
+        let name = &self.name;
+        if let Some(description) = &self.constraint_description {
+            return format!("{name} failed to satisfy constraint: {description}");
+        }
+        match constraint {
+            ParameterConstraint::AllowedValues(values) => {
+                format!("{name} must be one of: {}", values.join(", "))
+            }
+            ParameterConstraint::AllowedPattern(pattern) => {
+                format!("{name} must match pattern {pattern}")
+            }
+            ParameterConstraint::MinLength(min) => {
+                format!("{name} must have a length of at least {min}")
+            }
+            ParameterConstraint::MaxLength(max) => {
+                format!("{name} must have a length of at most {max}")
+            }
+            ParameterConstraint::MinValue(min) => format!("{name} must be at least {min}"),
+            ParameterConstraint::MaxValue(max) => format!("{name} must be at most {max}"),
+        }
+    }
+}
+
+// DefaultValue is the Default of a parameter, interpreted according to the parameter's type, so
+// that synthesizers do not need to re-parse it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DefaultValue {
+    String(String),
+    Number(f64),
+    Bool(bool),
+    List(Vec<DefaultValue>),
+}
+
+impl DefaultValue {
+    fn resolve(
+        default: ParameterDefault,
+        constructor_type: &ConstructorType,
+    ) -> Result<Self, Error> {
+        let invalid = |value: &str| Error::InvalidParameterDefault {
+            value: value.into(),
+            parameter_type: constructor_type.to_string(),
+        };
+        let number = |value: &str| {
+            value
+                .trim()
+                .parse()
+                .map(DefaultValue::Number)
+                .map_err(|_| invalid(value))
+        };
+        // The items of list parameters are whitespace-trimmed by CloudFormation.
+        let items = |default: ParameterDefault| match default {
+            ParameterDefault::Scalar(value) => value
+                .split(',')
+                .map(|item| item.trim().to_string())
+                .collect::<Vec<_>>(),
+            ParameterDefault::List(items) => items
+                .into_iter()
+                .map(|item| item.trim().to_string())
+                .collect(),
+        };
+
+        match (constructor_type, default) {
+            (ConstructorType::Number, ParameterDefault::Scalar(value)) => number(&value),
+            (ConstructorType::Boolean, ParameterDefault::Scalar(value)) => match value.trim() {
+                v if v.eq_ignore_ascii_case("true") => Ok(DefaultValue::Bool(true)),
+                v if v.eq_ignore_ascii_case("false") => Ok(DefaultValue::Bool(false)),
+                _ => Err(invalid(&value)),
+            },
+            (ConstructorType::ListOfNumbers, default) => Ok(DefaultValue::List(
+                items(default)
+                    .iter()
+                    .map(|item| number(item))
+                    .collect::<Result<_, _>>()?,
+            )),
+            (t, default) if t.is_list() => Ok(DefaultValue::List(
+                items(default)
+                    .into_iter()
+                    .map(DefaultValue::String)
+                    .collect(),
+            )),
+            (_, ParameterDefault::Scalar(value)) => Ok(DefaultValue::String(value)),
+            (_, ParameterDefault::List(items)) => Err(invalid(&items.join(","))),
+        }
+    }
+}
+
+// Renders the value the way CloudFormation expects it, e.g: as the default of a CfnParameter.
+impl fmt::Display for DefaultValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DefaultValue::String(value) => write!(f, "{value}"),
+            DefaultValue::Number(value) => write!(f, "{value}"),
+            DefaultValue::Bool(value) => write!(f, "{value}"),
+            DefaultValue::List(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+// ConstructorType is the type of a parameter. AWS-specific and SSM parameter types are resolved or
+// validated by CloudFormation against the account the stack is deployed to, so they can only be
+// surfaced as a CfnParameter.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum ConstructorType {
+    #[default]
+    String,
+    Number,
+    Boolean,
+    ListOfNumbers,
+    CommaDelimitedList,
+    /// e.g: `AWS::EC2::VPC::Id` or `AWS::EC2::KeyPair::KeyName`
+    Aws(String),
+    /// e.g: `List<AWS::EC2::Subnet::Id>`, holding the item type
+    AwsList(String),
+    /// `AWS::SSM::Parameter::Name`
+    SsmParameterName,
+    /// `AWS::SSM::Parameter::Value<T>`, holding the type of the SSM parameter's value
+    SsmParameterValue(Box<ConstructorType>),
+    Other(String),
+}
+
+impl ConstructorType {
+    fn parse(value: &str) -> Self {
+        match value {
+            "String" => Self::String,
+            "Number" => Self::Number,
+            "Boolean" => Self::Boolean,
+            "List<Number>" => Self::ListOfNumbers,
+            "CommaDelimitedList" => Self::CommaDelimitedList,
+            "AWS::SSM::Parameter::Name" => Self::SsmParameterName,
+            _ => {
+                if let Some(value_type) = value
+                    .strip_prefix("AWS::SSM::Parameter::Value<")
+                    .and_then(|t| t.strip_suffix('>'))
+                {
+                    Self::SsmParameterValue(Box::new(Self::parse(value_type)))
+                } else if let Some(item_type) = value
+                    .strip_prefix("List<")
+                    .and_then(|t| t.strip_suffix('>'))
+                    .filter(|t| t.starts_with("AWS::"))
+                {
+                    Self::AwsList(item_type.into())
+                } else if value.starts_with("AWS::") {
+                    Self::Aws(value.into())
+                } else {
+                    Self::Other(value.into())
+                }
+            }
+        }
+    }
+
+    /// Whether the parameter value is a list (for SSM parameters, the value of the SSM parameter).
+    pub fn is_list(&self) -> bool {
+        match self {
+            Self::ListOfNumbers | Self::CommaDelimitedList | Self::AwsList(_) => true,
+            Self::SsmParameterValue(value_type) => value_type.is_list(),
+            Self::Other(other) => other.starts_with("List<"),
+            Self::String | Self::Number | Self::Boolean | Self::Aws(_) | Self::SsmParameterName => {
+                false
+            }
+        }
+    }
+
+    pub fn is_aws_specific(&self) -> bool {
+        matches!(
+            self,
+            Self::Aws(_) | Self::AwsList(_) | Self::SsmParameterName | Self::SsmParameterValue(_)
+        )
+    }
+
+    /// Whether the parameter is an SSM parameter holding a single string, which can be resolved
+    /// using `ssm.StringParameter.valueForStringParameter`.
+    pub fn is_ssm_string(&self) -> bool {
+        matches!(self, Self::SsmParameterValue(value_type) if !value_type.is_list())
+    }
+}
+
+impl From<&ParameterType> for ConstructorType {
+    fn from(parameter_type: &ParameterType) -> Self {
+        match parameter_type {
+            ParameterType::String => Self::String,
+            ParameterType::Number => Self::Number,
+            ParameterType::ListOfNumbers => Self::ListOfNumbers,
+            ParameterType::CommaDelimitedList => Self::CommaDelimitedList,
+            ParameterType::Bool => Self::Boolean,
+            ParameterType::Other(other) => Self::parse(other),
+        }
+    }
+}
+
+// Renders the type the way CloudFormation expects it, e.g: as the type of a CfnParameter.
+impl fmt::Display for ConstructorType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::String => write!(f, "String"),
+            Self::Number => write!(f, "Number"),
+            Self::Boolean => write!(f, "Boolean"),
+            Self::ListOfNumbers => write!(f, "List<Number>"),
+            Self::CommaDelimitedList => write!(f, "CommaDelimitedList"),
+            Self::Aws(aws) => write!(f, "{aws}"),
+            Self::AwsList(item_type) => write!(f, "List<{item_type}>"),
+            Self::SsmParameterName => write!(f, "AWS::SSM::Parameter::Name"),
+            Self::SsmParameterValue(value_type) => {
+                write!(f, "AWS::SSM::Parameter::Value<{value_type}>")
+            }
+            Self::Other(other) => write!(f, "{other}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParameterConstraint {
+    AllowedValues(Vec<String>),
+    /// The pattern must match the whole value, as with Java's `String::matches`.
+    AllowedPattern(String),
+    MinLength(u64),
+    MaxLength(u64),
+    MinValue(f64),
+    MaxValue(f64),
 }
 
 #[cfg(test)]
@@ -3,7 +3,7 @@
 use super::*;
 
 use crate::parser;
-use parser::parameters::ParameterType;
+use parser::parameters::{ParameterDefault, ParameterType};
 
 #[test]
 fn test_constructor_from() {
@@ -12,24 +12,40 @@ fn test_constructor_from() {
         "param1".to_string(),
         Parameter {
             allowed_values: None,
-            default: Some("default1".to_string()),
+            default: Some(ParameterDefault::Scalar("default1".to_string())),
             description: Some("description1".to_string()),
             parameter_type: ParameterType::String,
             no_echo: None,
+            allowed_pattern: None,
+            constraint_description: None,
+            min_length: None,
+            max_length: None,
+            min_value: None,
+            max_value: None,
+            span: None,
+            comments: Vec::new(),
         },
     );
     parse_tree.insert(
-        "param2".to_string(),
+        "Param2".to_string(),
         Parameter {
             allowed_values: None,
             default: None,
             description: Some("description2".to_string()),
             parameter_type: ParameterType::Number,
             no_echo: None,
+            allowed_pattern: None,
+            constraint_description: None,
+            min_length: None,
+            max_length: None,
+            min_value: None,
+            max_value: None,
+            span: None,
+            comments: Vec::new(),
         },
     );
 
-    let constructor = Constructor::from(parse_tree);
+    let constructor = Constructor::from(parse_tree, &mut Diagnostics::default());
 
     assert_eq!(constructor.inputs.len(), 2);
 
@@ -38,19 +54,27 @@ fn test_constructor_from() {
         constructor.inputs[0].description,
         Some("description1".to_string())
     );
-    assert_eq!(constructor.inputs[0].constructor_type, "String");
+    assert_eq!(
+        constructor.inputs[0].constructor_type,
+        ConstructorType::String
+    );
     assert_eq!(
         constructor.inputs[0].default_value,
-        Some("default1".to_string())
+        Some(DefaultValue::String("default1".to_string()))
     );
     assert_eq!(constructor.inputs[0].allowed_values, None);
 
     assert_eq!(constructor.inputs[1].name, "param2");
+    assert_eq!(constructor.inputs[1].logical_id, "Param2");
+    assert_eq!(constructor.logical_id("param2"), "Param2");
     assert_eq!(
         constructor.inputs[1].description,
         Some("description2".to_string())
     );
-    assert_eq!(constructor.inputs[1].constructor_type, "Number");
+    assert_eq!(
+        constructor.inputs[1].constructor_type,
+        ConstructorType::Number
+    );
     assert_eq!(constructor.inputs[1].default_value, None);
     assert_eq!(constructor.inputs[1].allowed_values, None);
 }
@@ -60,18 +84,289 @@ fn test_constructor_parameter() {
     let param = ConstructorParameter {
         name: "Param1".to_string(),
         description: Some("description1".to_string()),
-        constructor_type: "String".to_string(),
-        default_value: Some("default1".to_string()),
+        constructor_type: ConstructorType::String,
+        default_value: Some(DefaultValue::String("default1".to_string())),
         allowed_values: Some(vec!["true".to_string(), "false".to_string()]),
         no_echo: None,
+        ..Default::default()
     };
 
     assert_eq!(param.name, "Param1");
     assert_eq!(param.description, Some("description1".to_string()));
-    assert_eq!(param.constructor_type, "String");
-    assert_eq!(param.default_value, Some("default1".to_string()));
+    assert_eq!(param.constructor_type, ConstructorType::String);
+    assert_eq!(
+        param.default_value,
+        Some(DefaultValue::String("default1".to_string()))
+    );
     assert_eq!(
         param.allowed_values,
         Some(vec!["true".to_string(), "false".to_string()])
     );
 }
+
+#[test]
+fn test_constructor_parameter_constraints() {
+    let parse_tree: IndexMap<String, Parameter, Hasher> = serde_yaml::from_str(
+        r#"
+        ClusterName:
+          Type: String
+          MinLength: 1
+          MaxLength: 64
+          AllowedPattern: '[a-zA-Z][a-zA-Z0-9]*'
+          ConstraintDescription: Must begin with a letter.
+        Port:
+          Type: Number
+          MinValue: 1
+          MaxValue: 65535
+          MinLength: 2
+        Subnets:
+          Type: CommaDelimitedList
+          AllowedPattern: subnet-.*
+        "#,
+    )
+    .unwrap();
+
+    let constructor = Constructor::from(parse_tree, &mut Diagnostics::default());
+
+    let cluster_name = &constructor.inputs[0];
+    assert_eq!(cluster_name.min_length, Some(1));
+    assert_eq!(
+        cluster_name.constraints(),
+        vec![
+            ParameterConstraint::AllowedPattern("[a-zA-Z][a-zA-Z0-9]*".into()),
+            ParameterConstraint::MinLength(1),
+            ParameterConstraint::MaxLength(64),
+        ]
+    );
+    assert_eq!(
+        cluster_name.constraint_message(&ParameterConstraint::MinLength(1)),
+        "clusterName failed to satisfy constraint: Must begin with a letter."
+    );
+
+    let port = &constructor.inputs[1];
+    assert_eq!(
+        port.constraints(),
+        vec![
+            ParameterConstraint::MinValue(1.0),
+            ParameterConstraint::MaxValue(65535.0),
+        ]
+    );
+    assert_eq!(
+        port.constraint_message(&ParameterConstraint::MaxValue(65535.0)),
+        "port must be at most 65535"
+    );
+
+    let subnets = &constructor.inputs[2];
+    assert_eq!(subnets.allowed_pattern.as_deref(), Some("subnet-.*"));
+    assert!(subnets.constraints().is_empty());
+}
+
+#[test]
+fn test_constructor_parameter_default_values() {
+    let parse_tree: IndexMap<String, Parameter, Hasher> = serde_yaml::from_str(
+        r#"
+        Name:
+          Type: String
+          Default: 5
+        Port:
+          Type: Number
+          Default: ' 8080 '
+        Enabled:
+          Type: Boolean
+          Default: TRUE
+        Subnets:
+          Type: CommaDelimitedList
+          Default: 'subnet-a, subnet-b ,subnet-c'
+        Zones:
+          Type: List<AWS::EC2::AvailabilityZone::Name>
+          Default: [us-east-1a, ' us-east-1b']
+        Ports:
+          Type: List<Number>
+          Default: 80, 443
+        "#,
+    )
+    .unwrap();
+
+    let mut diagnostics = Diagnostics::default();
+    let constructor = Constructor::from(parse_tree, &mut diagnostics);
+    assert!(diagnostics.is_empty(), "{diagnostics}");
+
+    let defaults = constructor
+        .inputs
+        .iter()
+        .map(|input| input.default_value.clone().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        defaults,
+        vec![
+            DefaultValue::String("5".into()),
+            DefaultValue::Number(8080.0),
+            DefaultValue::Bool(true),
+            DefaultValue::List(vec![
+                DefaultValue::String("subnet-a".into()),
+                DefaultValue::String("subnet-b".into()),
+                DefaultValue::String("subnet-c".into()),
+            ]),
+            DefaultValue::List(vec![
+                DefaultValue::String("us-east-1a".into()),
+                DefaultValue::String("us-east-1b".into()),
+            ]),
+            DefaultValue::List(vec![
+                DefaultValue::Number(80.0),
+                DefaultValue::Number(443.0)
+            ]),
+        ]
+    );
+    assert_eq!(defaults[3].to_string(), "subnet-a,subnet-b,subnet-c");
+    assert_eq!(defaults[5].to_string(), "80,443");
+}
+
+#[test]
+fn test_constructor_parameter_invalid_default_value() {
+    let parse_tree: IndexMap<String, Parameter, Hasher> = serde_yaml::from_str(
+        r#"
+        Port:
+          Type: Number
+          Default: eighty
+        Name:
+          Type: String
+          Default: [a, b]
+        "#,
+    )
+    .unwrap();
+
+    let mut diagnostics = Diagnostics::default();
+    let constructor = Constructor::from(parse_tree, &mut diagnostics);
+
+    assert_eq!(constructor.inputs[0].default_value, None);
+    let messages = diagnostics
+        .iter()
+        .map(|d| (d.path.as_str(), d.message.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            (
+                "Parameters.Port.Default",
+                "\"eighty\" is not a valid default value for a Number parameter"
+            ),
+            (
+                "Parameters.Name.Default",
+                "\"a,b\" is not a valid default value for a String parameter"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_constructor_types() {
+    let parse_tree: IndexMap<String, Parameter, Hasher> = serde_yaml::from_str(
+        r#"
+        Enabled:
+          Type: String
+          AllowedValues: ['False', 'True']
+        Vpc:
+          Type: AWS::EC2::VPC::Id
+        Subnets:
+          Type: List<AWS::EC2::Subnet::Id>
+        AmiName:
+          Type: AWS::SSM::Parameter::Name
+        Ami:
+          Type: AWS::SSM::Parameter::Value<AWS::EC2::Image::Id>
+        Zones:
+          Type: AWS::SSM::Parameter::Value<List<String>>
+        Custom:
+          Type: Custom::Type
+        "#,
+    )
+    .unwrap();
+
+    let constructor = Constructor::from(parse_tree, &mut Diagnostics::default());
+    let types = constructor
+        .inputs
+        .iter()
+        .map(|input| input.constructor_type.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        types,
+        vec![
+            ConstructorType::Boolean,
+            ConstructorType::Aws("AWS::EC2::VPC::Id".into()),
+            ConstructorType::AwsList("AWS::EC2::Subnet::Id".into()),
+            ConstructorType::SsmParameterName,
+            ConstructorType::SsmParameterValue(Box::new(ConstructorType::Aws(
+                "AWS::EC2::Image::Id".into()
+            ))),
+            ConstructorType::SsmParameterValue(Box::new(ConstructorType::Other(
+                "List<String>".into()
+            ))),
+            ConstructorType::Other("Custom::Type".into()),
+        ]
+    );
+    assert_eq!(
+        types.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        vec![
+            "Boolean",
+            "AWS::EC2::VPC::Id",
+            "List<AWS::EC2::Subnet::Id>",
+            "AWS::SSM::Parameter::Name",
+            "AWS::SSM::Parameter::Value<AWS::EC2::Image::Id>",
+            "AWS::SSM::Parameter::Value<List<String>>",
+            "Custom::Type",
+        ]
+    );
+    assert_eq!(
+        types
+            .iter()
+            .map(ConstructorType::is_list)
+            .collect::<Vec<_>>(),
+        vec![false, false, true, false, false, true, false]
+    );
+    assert_eq!(
+        types
+            .iter()
+            .map(ConstructorType::is_ssm_string)
+            .collect::<Vec<_>>(),
+        vec![false, false, false, false, true, false, false]
+    );
+    assert!(!constructor.inputs[0].is_cfn_parameter());
+    assert!(constructor.inputs[1..6]
+        .iter()
+        .all(|p| p.is_cfn_parameter()));
+    assert!(!constructor.inputs[6].is_cfn_parameter());
+}
+
+#[test]
+fn test_constructor_validations() {
+    let parse_tree: IndexMap<String, Parameter, Hasher> = serde_yaml::from_str(
+        r#"
+        Port:
+          Type: Number
+          MaxValue: 65535
+        Password:
+          Type: String
+          NoEcho: true
+          MinLength: 8
+        Subnet:
+          Type: AWS::EC2::Subnet::Id
+          AllowedValues: [subnet-1]
+        Name:
+          Type: String
+        "#,
+    )
+    .unwrap();
+
+    let constructor = Constructor::from(parse_tree, &mut Diagnostics::default());
+
+    // CfnParameters are validated by CloudFormation, and unconstrained props are not checked.
+    assert_eq!(
+        constructor.validations(),
+        vec![PropValidation {
+            param: &constructor.inputs[0],
+            checks: vec![ConstraintCheck {
+                constraint: ParameterConstraint::MaxValue(65535.0),
+                message: "port must be at most 65535".into(),
+            }],
+        }]
+    );
+}
@@ -0,0 +1,967 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+use indexmap::IndexMap;
+use voca_rs::case::camel_case;
+
+use crate::cdk::{Primitive, TypeReference};
+use crate::ir::importer::ImportInstruction;
+use crate::ir::reference::{Origin, Reference};
+use crate::ir::resources::{find_references, ResourceIr, ResourceType};
+use crate::ir::CloudformationProgramIr;
+use crate::parser::resource::{DeletionPolicy, UpdateReplacePolicy};
+use crate::Hasher;
+
+// L2Construct is a resource emitted as an L2 construct of its service's module (e.g: `s3.Bucket`)
+// instead of its `Cfn*` class. The L1 resource of the construct remains reachable as its default
+// child (the "escape hatch"), which keeps the resource's logical ID and is used for everything the
+// construct can't express.
+#[derive(Clone, Debug, PartialEq)]
+pub struct L2Construct {
+    // The class of the construct, in the module of the resource's service.
+    pub class: String,
+    // The props of the construct, by their camelCase name.
+    pub props: IndexMap<String, L2Value, Hasher>,
+    // The properties set on the L1 resource with `addPropertyOverride`, by their CloudFormation
+    // name. Their values are untyped, so that they are emitted as CloudFormation expects them.
+    pub overrides: IndexMap<String, ResourceIr, Hasher>,
+}
+
+impl L2Construct {
+    // The name the L1 resource of the construct emitted for the given resource goes by: references
+    // to attributes the construct doesn't expose, and dependencies, use it as a logical ID.
+    pub fn escape_hatch(logical_id: &str) -> String {
+        format!("Cfn{logical_id}")
+    }
+
+    // The values of the props and overrides of the construct.
+    pub fn values(&self) -> Vec<&ResourceIr> {
+        let mut values: Vec<_> = self.props.values().flat_map(L2Value::values).collect();
+        values.extend(self.overrides.values());
+        values
+    }
+
+    pub fn values_mut(&mut self) -> Vec<&mut ResourceIr> {
+        let mut values: Vec<_> = self
+            .props
+            .values_mut()
+            .flat_map(L2Value::values_mut)
+            .collect();
+        values.extend(self.overrides.values_mut());
+        values
+    }
+}
+
+// L2Value is the value of an L2 prop. Besides values shaped like the properties of L1 resources,
+// L2 props take instances of the classes and enums of the construct library.
+#[derive(Clone, Debug, PartialEq)]
+pub enum L2Value {
+    // A value emitted like a property of an L1 resource
+    Value(ResourceIr),
+    // A `Duration` of this many seconds, which is a literal number
+    Seconds(ResourceIr),
+    // A member of an enum of the module of `service`
+    Enum {
+        service: String,
+        name: String,
+        member: String,
+    },
+    // An instance of a struct of the module of `service`, with camelCase fields
+    Struct {
+        service: String,
+        name: String,
+        fields: IndexMap<String, L2Value, Hasher>,
+    },
+    // A new instance of a class of the module of `service`. `options` is set when the constructor
+    // also takes a struct of options, which is left out.
+    New {
+        service: String,
+        class: String,
+        args: Vec<L2Value>,
+        options: bool,
+    },
+    // A call to a static (camelCase) method of a class of the module of `service`, which may also
+    // take a struct of options, like `New`.
+    Call {
+        service: String,
+        class: String,
+        method: String,
+        args: Vec<L2Value>,
+        options: bool,
+    },
+    // The stack, as the scope of a construct
+    Scope,
+    // The L2 construct emitted for another resource, by logical ID
+    Construct(String),
+}
+
+impl L2Value {
+    fn string(value: &str) -> Self {
+        Self::Value(ResourceIr::String(value.into()))
+    }
+
+    // The services whose module the value uses.
+    fn services<'a>(&'a self, services: &mut Vec<&'a str>) {
+        match self {
+            Self::Value(_) | Self::Seconds(_) | Self::Scope | Self::Construct(_) => {}
+            Self::Enum { service, .. } => services.push(service),
+            Self::Struct {
+                service, fields, ..
+            } => {
+                services.push(service);
+                for field in fields.values() {
+                    field.services(services);
+                }
+            }
+            Self::New { service, args, .. } | Self::Call { service, args, .. } => {
+                services.push(service);
+                for arg in args {
+                    arg.services(services);
+                }
+            }
+        }
+    }
+
+    // The logical IDs of the resources the value refers to.
+    fn references(&self, references: &mut Vec<String>) {
+        match self {
+            Self::Value(value) | Self::Seconds(value) => references.extend(find_references(value)),
+            Self::Enum { .. } | Self::Scope => {}
+            Self::Construct(name) => references.push(name.clone()),
+            Self::Struct { fields, .. } => {
+                for field in fields.values() {
+                    field.references(references);
+                }
+            }
+            Self::New { args, .. } | Self::Call { args, .. } => {
+                for arg in args {
+                    arg.references(references);
+                }
+            }
+        }
+    }
+
+    fn values(&self) -> Vec<&ResourceIr> {
+        match self {
+            Self::Value(value) | Self::Seconds(value) => vec![value],
+            Self::Enum { .. } | Self::Scope | Self::Construct(_) => Vec::new(),
+            Self::Struct { fields, .. } => fields.values().flat_map(Self::values).collect(),
+            Self::New { args, .. } | Self::Call { args, .. } => {
+                args.iter().flat_map(Self::values).collect()
+            }
+        }
+    }
+
+    fn values_mut(&mut self) -> Vec<&mut ResourceIr> {
+        match self {
+            Self::Value(value) | Self::Seconds(value) => vec![value],
+            Self::Enum { .. } | Self::Scope | Self::Construct(_) => Vec::new(),
+            Self::Struct { fields, .. } => fields.values_mut().flat_map(Self::values_mut).collect(),
+            Self::New { args, .. } | Self::Call { args, .. } => {
+                args.iter_mut().flat_map(Self::values_mut).collect()
+            }
+        }
+    }
+}
+
+/// Translates the resources of a CloudFormation type into an L2 construct. See [`L2Mappings`].
+pub trait L2Mapping {
+    /// The class of the construct, in the module of the resource's service.
+    fn class(&self) -> &str;
+
+    /// Translates the properties of a resource into props of the construct, removing the
+    /// properties it translated. The remaining properties are set on the construct's L1 resource
+    /// as overrides. Returns `None` when the construct can't be used for the resource, e.g:
+    /// because one of its required props can't be derived from the properties.
+    fn props(
+        &self,
+        properties: &mut IndexMap<String, ResourceIr, Hasher>,
+        context: &MappingContext,
+    ) -> Option<IndexMap<String, L2Value, Hasher>>;
+
+    /// The (camelCase) attribute of the construct holding the given attribute of the resource, or
+    /// its `Ref` value when `attribute` is `None`. Other attributes are read from the L1 resource.
+    fn attribute(&self, attribute: Option<&str>) -> Option<&str>;
+
+    /// Whether the construct retains its resource by default, unlike CloudFormation. If so, the
+    /// resource is given explicit deletion policies when the template has none.
+    fn retained_by_default(&self) -> bool {
+        false
+    }
+}
+
+/// What an [`L2Mapping`] knows of the template besides the resource it translates.
+pub struct MappingContext<'a> {
+    /// The logical ID of the resource.
+    pub logical_id: &'a str,
+    constructs: &'a HashSet<String>,
+}
+
+impl MappingContext<'_> {
+    /// The logical ID of the resource whose L2 construct `value` refers to, if it is a reference
+    /// to `attribute` of a resource emitted as an L2 construct (or its `Ref` if `None`).
+    pub fn construct(&self, value: &ResourceIr, attribute: Option<&str>) -> Option<String> {
+        let ResourceIr::Ref(reference) = value else {
+            return None;
+        };
+        let matches = match (&reference.origin, attribute) {
+            (Origin::LogicalId { .. }, None) => true,
+            (Origin::GetAttribute { attribute, .. }, Some(expected)) => attribute == expected,
+            _ => false,
+        };
+        (matches && self.constructs.contains(&reference.name)).then(|| reference.name.clone())
+    }
+}
+
+/// The L2 constructs resources are emitted as, by CloudFormation resource type (see
+/// [`CloudformationProgramIr::use_l2_constructs`]).
+#[derive(Default)]
+pub struct L2Mappings {
+    mappings: HashMap<String, Box<dyn L2Mapping>>,
+}
+
+impl L2Mappings {
+    /// The constructs of the most common resource types.
+    pub fn builtin() -> Self {
+        let mut mappings = Self::default();
+        mappings.register("AWS::S3::Bucket", Bucket);
+        mappings.register("AWS::SQS::Queue", Queue);
+        mappings.register("AWS::SNS::Topic", Topic);
+        mappings.register("AWS::DynamoDB::Table", Table);
+        mappings.register("AWS::Lambda::Function", Function);
+        mappings.register("AWS::IAM::Role", Role);
+        mappings
+    }
+
+    /// Emits the resources of `resource_type` as described by `mapping`, replacing any mapping
+    /// registered for that type.
+    pub fn register(
+        &mut self,
+        resource_type: impl Into<String>,
+        mapping: impl L2Mapping + 'static,
+    ) {
+        self.mappings
+            .insert(resource_type.into(), Box::new(mapping));
+    }
+
+    fn get(&self, resource_type: &ResourceType) -> Option<&dyn L2Mapping> {
+        match resource_type {
+            ResourceType::AWS { .. } => self
+                .mappings
+                .get(&resource_type.to_string())
+                .map(AsRef::as_ref),
+            _ => None,
+        }
+    }
+}
+
+impl CloudformationProgramIr {
+    // use_l2_constructs emits the resources that have a mapping as L2 constructs. Conditional
+    // resources remain L1 resources, as the constructs are not all able to be conditional. The
+    // references to their attributes use the attributes of the constructs when possible.
+    pub fn use_l2_constructs(&mut self, mappings: &L2Mappings) {
+        for resource in &mut self.resources {
+            if let Some(nested) = &mut resource.nested_stack {
+                nested.program.use_l2_constructs(mappings);
+            }
+        }
+
+        let names: HashSet<String> = self.resources.iter().map(|r| r.name.clone()).collect();
+        let mut constructs: HashMap<String, &dyn L2Mapping> = HashMap::new();
+        let mut names_of_constructs = HashSet::new();
+        for resource in &mut self.resources {
+            let Some(mapping) = mappings.get(&resource.resource_type) else {
+                continue;
+            };
+            if resource.nested_stack.is_some()
+                || resource.l2_construct.is_some()
+                || resource.condition.is_some()
+                || names.contains(&L2Construct::escape_hatch(&resource.name))
+            {
+                continue;
+            }
+            let context = MappingContext {
+                logical_id: &resource.name,
+                constructs: &names_of_constructs,
+            };
+            let mut properties = resource.properties.clone();
+            let Some(props) = mapping.props(&mut properties, &context) else {
+                continue;
+            };
+
+            if mapping.retained_by_default() {
+                resource
+                    .deletion_policy
+                    .get_or_insert(DeletionPolicy::Delete);
+                resource
+                    .update_replace_policy
+                    .get_or_insert(UpdateReplacePolicy::Delete);
+            }
+            // The comments of the properties the construct translated go with the prop of the same
+            // name, or above the construct when there is none.
+            for (name, comments) in mem::take(&mut resource.property_comments) {
+                let prop = camel_case(&name);
+                if properties.contains_key(&name) {
+                    resource.property_comments.insert(name, comments);
+                } else if props.contains_key(&prop) {
+                    resource.property_comments.insert(prop, comments);
+                } else {
+                    resource.comments.extend(comments);
+                }
+            }
+            resource.properties.clear();
+            resource.l2_construct = Some(L2Construct {
+                class: mapping.class().to_string(),
+                props,
+                overrides: properties
+                    .into_iter()
+                    .map(|(name, value)| (name, untyped(value)))
+                    .collect(),
+            });
+            constructs.insert(resource.name.clone(), mapping);
+            names_of_constructs.insert(resource.name.clone());
+        }
+        if constructs.is_empty() {
+            return;
+        }
+
+        // The references of the resources are to logical IDs, so they are found before references
+        // to the escape hatches are renamed.
+        for resource in &mut self.resources {
+            let Some(construct) = &resource.l2_construct else {
+                continue;
+            };
+            let mut references = resource.dependencies.to_vec();
+            for value in construct.props.values() {
+                value.references(&mut references);
+            }
+            for value in construct.overrides.values() {
+                references.extend(find_references(value));
+            }
+            resource.references = references.into_iter().collect();
+        }
+
+        let rewrite = |value: &mut ResourceIr| {
+            value.visit_references(&mut |reference| {
+                let Some(mapping) = constructs.get(&reference.name) else {
+                    return;
+                };
+                let attribute = match &reference.origin {
+                    Origin::LogicalId { .. } => mapping.attribute(None),
+                    Origin::GetAttribute { attribute, .. } => mapping.attribute(Some(attribute)),
+                    _ => return,
+                };
+                match attribute {
+                    Some(attribute) => {
+                        reference.origin = Origin::ConstructAttribute {
+                            attribute: attribute.to_string(),
+                        }
+                    }
+                    None => reference.name = L2Construct::escape_hatch(&reference.name),
+                }
+            })
+        };
+        for resource in &mut self.resources {
+            for value in resource.properties.values_mut() {
+                rewrite(value);
+            }
+            for value in [&mut resource.metadata, &mut resource.update_policy]
+                .into_iter()
+                .flatten()
+            {
+                rewrite(value);
+            }
+            if let Some(nested) = &mut resource.nested_stack {
+                for value in nested.parameters.values_mut() {
+                    rewrite(value);
+                }
+            }
+            if let Some(construct) = &mut resource.l2_construct {
+                for value in construct.values_mut() {
+                    rewrite(value);
+                }
+            }
+        }
+        for output in &mut self.outputs {
+            rewrite(&mut output.value);
+            if let Some(export) = &mut output.export {
+                rewrite(export);
+            }
+        }
+
+        // Dependencies are between L1 resources.
+        for resource in &mut self.resources {
+            for dependency in &mut resource.dependencies {
+                if constructs.contains_key(dependency) {
+                    *dependency = L2Construct::escape_hatch(dependency);
+                }
+            }
+        }
+
+        // The props of the constructs may use the modules of other services.
+        let mut services = Vec::new();
+        for construct in self
+            .resources
+            .iter()
+            .filter_map(|resource| resource.l2_construct.as_ref())
+        {
+            for value in construct.props.values() {
+                value.services(&mut services);
+            }
+        }
+        for service in services {
+            let import = ImportInstruction {
+                organization: "AWS".into(),
+                service: Some(service.into()),
+            };
+            if !self.imports.contains(&import) {
+                self.imports.push(import);
+            }
+        }
+        self.imports
+            .sort_by(|left, right| left.service.cmp(&right.service));
+    }
+}
+
+impl ResourceIr {
+    pub(super) fn visit_references(&mut self, visit: &mut impl FnMut(&mut Reference)) {
+        match self {
+            ResourceIr::Null
+            | ResourceIr::Bool(_)
+            | ResourceIr::Number(_)
+            | ResourceIr::Double(_)
+            | ResourceIr::String(_) => {}
+            ResourceIr::Ref(reference) => visit(reference),
+            ResourceIr::Array(_, items) | ResourceIr::Join(_, items) | ResourceIr::Sub(items) => {
+                for item in items {
+                    item.visit_references(visit);
+                }
+            }
+            ResourceIr::Object(_, entries) => {
+                for value in entries.values_mut() {
+                    value.visit_references(visit);
+                }
+            }
+            ResourceIr::If(_, x, y) | ResourceIr::Map(_, x, y) => {
+                x.visit_references(visit);
+                y.visit_references(visit);
+            }
+            ResourceIr::Split(_, x)
+            | ResourceIr::Base64(x)
+            | ResourceIr::GetAZs(x)
+            | ResourceIr::Select(_, x)
+            | ResourceIr::ImportValue(x)
+            | ResourceIr::Length(x)
+            | ResourceIr::ToJsonString(x) => x.visit_references(visit),
+            ResourceIr::Cidr(x, y, z) => {
+                x.visit_references(visit);
+                y.visit_references(visit);
+                z.visit_references(visit);
+            }
+        }
+    }
+}
+
+// Property overrides are set as CloudFormation expects them, so their objects keep the names of
+// their keys.
+fn untyped(value: ResourceIr) -> ResourceIr {
+    let json = || TypeReference::Primitive(Primitive::Json);
+    match value {
+        ResourceIr::Array(_, items) => {
+            ResourceIr::Array(json(), items.into_iter().map(untyped).collect())
+        }
+        ResourceIr::Object(_, entries) => ResourceIr::Object(
+            json(),
+            entries
+                .into_iter()
+                .map(|(key, value)| (key, untyped(value)))
+                .collect(),
+        ),
+        ResourceIr::If(condition, x, y) => {
+            ResourceIr::If(condition, Box::new(untyped(*x)), Box::new(untyped(*y)))
+        }
+        value => value,
+    }
+}
+
+// The literal string value of a property, if it has one.
+fn literal(value: Option<&ResourceIr>) -> Option<&str> {
+    match value {
+        Some(ResourceIr::String(value)) => Some(value),
+        _ => None,
+    }
+}
+
+// Moves the properties that are passed to the construct as is (or as a number of seconds) into
+// `props`.
+fn take(
+    properties: &mut IndexMap<String, ResourceIr, Hasher>,
+    props: &mut IndexMap<String, L2Value, Hasher>,
+    names: &[(&str, &str)],
+    seconds: &[(&str, &str)],
+) {
+    for (property, prop) in names {
+        if let Some(value) = properties.shift_remove(*property) {
+            props.insert(prop.to_string(), L2Value::Value(value));
+        }
+    }
+    // Durations are made of numbers, so other values are left to the L1 resource.
+    for (property, prop) in seconds {
+        if let Some(value @ ResourceIr::Number(_)) = properties.get(*property) {
+            props.insert(prop.to_string(), L2Value::Seconds(value.clone()));
+            properties.shift_remove(*property);
+        }
+    }
+}
+
+// `s3.Bucket`
+struct Bucket;
+
+impl L2Mapping for Bucket {
+    fn class(&self) -> &str {
+        "Bucket"
+    }
+
+    fn props(
+        &self,
+        properties: &mut IndexMap<String, ResourceIr, Hasher>,
+        _: &MappingContext,
+    ) -> Option<IndexMap<String, L2Value, Hasher>> {
+        let mut props = IndexMap::default();
+        take(properties, &mut props, &[("BucketName", "bucketName")], &[]);
+        if let Some(ResourceIr::Object(_, versioning)) = properties.get("VersioningConfiguration") {
+            if versioning.len() == 1 && literal(versioning.get("Status")) == Some("Enabled") {
+                properties.shift_remove("VersioningConfiguration");
+                props.insert("versioned".into(), L2Value::Value(ResourceIr::Bool(true)));
+            }
+        }
+        Some(props)
+    }
+
+    fn attribute(&self, attribute: Option<&str>) -> Option<&str> {
+        match attribute {
+            None => Some("bucketName"),
+            Some("Arn") => Some("bucketArn"),
+            Some("DomainName") => Some("bucketDomainName"),
+            Some("DualStackDomainName") => Some("bucketDualStackDomainName"),
+            Some("RegionalDomainName") => Some("bucketRegionalDomainName"),
+            Some("WebsiteURL") => Some("bucketWebsiteUrl"),
+            Some(_) => None,
+        }
+    }
+
+    fn retained_by_default(&self) -> bool {
+        true
+    }
+}
+
+// `sqs.Queue`
+struct Queue;
+
+impl L2Mapping for Queue {
+    fn class(&self) -> &str {
+        "Queue"
+    }
+
+    fn props(
+        &self,
+        properties: &mut IndexMap<String, ResourceIr, Hasher>,
+        _: &MappingContext,
+    ) -> Option<IndexMap<String, L2Value, Hasher>> {
+        let mut props = IndexMap::default();
+        take(
+            properties,
+            &mut props,
+            &[
+                ("QueueName", "queueName"),
+                ("FifoQueue", "fifo"),
+                ("ContentBasedDeduplication", "contentBasedDeduplication"),
+                ("MaximumMessageSize", "maxMessageSizeBytes"),
+            ],
+            &[
+                ("DelaySeconds", "deliveryDelay"),
+                ("MessageRetentionPeriod", "retentionPeriod"),
+                ("ReceiveMessageWaitTimeSeconds", "receiveMessageWaitTime"),
+                ("VisibilityTimeout", "visibilityTimeout"),
+            ],
+        );
+        Some(props)
+    }
+
+    fn attribute(&self, attribute: Option<&str>) -> Option<&str> {
+        match attribute {
+            None | Some("QueueUrl") => Some("queueUrl"),
+            Some("Arn") => Some("queueArn"),
+            Some("QueueName") => Some("queueName"),
+            Some(_) => None,
+        }
+    }
+}
+
+// `sns.Topic`
+struct Topic;
+
+impl L2Mapping for Topic {
+    fn class(&self) -> &str {
+        "Topic"
+    }
+
+    fn props(
+        &self,
+        properties: &mut IndexMap<String, ResourceIr, Hasher>,
+        _: &MappingContext,
+    ) -> Option<IndexMap<String, L2Value, Hasher>> {
+        let mut props = IndexMap::default();
+        take(
+            properties,
+            &mut props,
+            &[
+                ("TopicName", "topicName"),
+                ("DisplayName", "displayName"),
+                ("FifoTopic", "fifo"),
+                ("ContentBasedDeduplication", "contentBasedDeduplication"),
+            ],
+            &[],
+        );
+        Some(props)
+    }
+
+    fn attribute(&self, attribute: Option<&str>) -> Option<&str> {
+        match attribute {
+            None | Some("TopicArn") => Some("topicArn"),
+            Some("TopicName") => Some("topicName"),
+            Some(_) => None,
+        }
+    }
+}
+
+// `dynamodb.Table`, whose keys must be literal.
+struct Table;
+
+impl L2Mapping for Table {
+    fn class(&self) -> &str {
+        "Table"
+    }
+
+    fn props(
+        &self,
+        properties: &mut IndexMap<String, ResourceIr, Hasher>,
+        _: &MappingContext,
+    ) -> Option<IndexMap<String, L2Value, Hasher>> {
+        let entries = |value: Option<&ResourceIr>| -> Option<Vec<(String, String)>> {
+            let Some(ResourceIr::Array(_, items)) = value else {
+                return None;
+            };
+            items
+                .iter()
+                .map(|item| match item {
+                    ResourceIr::Object(_, fields) if fields.len() == 2 => {
+                        let mut values = fields.values().map(|value| literal(Some(value)));
+                        Some((values.next()??.to_string(), values.next()??.to_string()))
+                    }
+                    _ => None,
+                })
+                .collect()
+        };
+        let keys = entries(properties.get("KeySchema"))?;
+        let definitions = entries(properties.get("AttributeDefinitions"))?;
+
+        let mut props = IndexMap::default();
+        for (name, key_type) in &keys {
+            let prop = match key_type.as_str() {
+                "HASH" => "partitionKey",
+                "RANGE" => "sortKey",
+                _ => return None,
+            };
+            let member = match definitions.iter().find(|(n, _)| n == name)?.1.as_str() {
+                "S" => "STRING",
+                "N" => "NUMBER",
+                "B" => "BINARY",
+                _ => return None,
+            };
+            let fields = IndexMap::from_iter([
+                ("name".to_string(), L2Value::string(name)),
+                (
+                    "type".to_string(),
+                    L2Value::Enum {
+                        service: "DynamoDB".into(),
+                        name: "AttributeType".into(),
+                        member: member.into(),
+                    },
+                ),
+            ]);
+            props.insert(
+                prop.to_string(),
+                L2Value::Struct {
+                    service: "DynamoDB".into(),
+                    name: "Attribute".into(),
+                    fields,
+                },
+            );
+        }
+
+        // The construct provisions 5 read & write capacity units unless told otherwise.
+        match literal(properties.get("BillingMode")) {
+            Some(mode @ ("PAY_PER_REQUEST" | "PROVISIONED")) => {
+                props.insert(
+                    "billingMode".into(),
+                    L2Value::Enum {
+                        service: "DynamoDB".into(),
+                        name: "BillingMode".into(),
+                        member: mode.into(),
+                    },
+                );
+            }
+            Some(_) => return None,
+            None if properties.contains_key("BillingMode") => return None,
+            None => {}
+        }
+        match properties.get("ProvisionedThroughput") {
+            Some(ResourceIr::Object(_, throughput)) => {
+                for (property, prop) in [
+                    ("ReadCapacityUnits", "readCapacity"),
+                    ("WriteCapacityUnits", "writeCapacity"),
+                ] {
+                    props.insert(
+                        prop.into(),
+                        L2Value::Value(throughput.get(property)?.clone()),
+                    );
+                }
+            }
+            Some(_) => return None,
+            None => {}
+        }
+        properties.shift_remove("BillingMode");
+        properties.shift_remove("ProvisionedThroughput");
+        properties.shift_remove("KeySchema");
+        // The construct only defines the attributes of the table's keys, not those of its indexes.
+        if definitions.len() == keys.len() {
+            properties.shift_remove("AttributeDefinitions");
+        }
+        take(properties, &mut props, &[("TableName", "tableName")], &[]);
+        Some(props)
+    }
+
+    fn attribute(&self, attribute: Option<&str>) -> Option<&str> {
+        match attribute {
+            None => Some("tableName"),
+            Some("Arn") => Some("tableArn"),
+            Some(_) => None,
+        }
+    }
+
+    fn retained_by_default(&self) -> bool {
+        true
+    }
+}
+
+// `lambda.Function`, for functions whose code is inline.
+struct Function;
+
+impl L2Mapping for Function {
+    fn class(&self) -> &str {
+        "Function"
+    }
+
+    fn props(
+        &self,
+        properties: &mut IndexMap<String, ResourceIr, Hasher>,
+        context: &MappingContext,
+    ) -> Option<IndexMap<String, L2Value, Hasher>> {
+        let code = match properties.get("Code") {
+            Some(ResourceIr::Object(_, code)) if code.len() == 1 => code.get("ZipFile")?.clone(),
+            _ => return None,
+        };
+        let runtime = literal(properties.get("Runtime"))?;
+        // The runtime is created from its name, as the constants of the construct library depend
+        // on its version. It must belong to a family that supports inline code.
+        let family = if runtime.starts_with("nodejs") {
+            "NODEJS"
+        } else if runtime.starts_with("python") {
+            "PYTHON"
+        } else {
+            return None;
+        };
+        let role = properties.get("Role")?;
+        let role = match context.construct(role, Some("Arn")) {
+            Some(role) => L2Value::Construct(role),
+            None => L2Value::Call {
+                service: "IAM".into(),
+                class: "Role".into(),
+                method: "fromRoleArn".into(),
+                args: vec![
+                    L2Value::Scope,
+                    L2Value::string(&format!("{}ImportedRole", context.logical_id)),
+                    L2Value::Value(role.clone()),
+                ],
+                options: true,
+            },
+        };
+
+        let mut props = IndexMap::default();
+        props.insert(
+            "code".into(),
+            L2Value::Call {
+                service: "Lambda".into(),
+                class: "Code".into(),
+                method: "fromInline".into(),
+                args: vec![L2Value::Value(code)],
+                options: false,
+            },
+        );
+        props.insert(
+            "runtime".into(),
+            L2Value::New {
+                service: "Lambda".into(),
+                class: "Runtime".into(),
+                args: vec![
+                    L2Value::string(runtime),
+                    L2Value::Enum {
+                        service: "Lambda".into(),
+                        name: "RuntimeFamily".into(),
+                        member: family.into(),
+                    },
+                    L2Value::Struct {
+                        service: "Lambda".into(),
+                        name: "LambdaRuntimeProps".into(),
+                        fields: IndexMap::from_iter([(
+                            "supportsInlineCode".to_string(),
+                            L2Value::Value(ResourceIr::Bool(true)),
+                        )]),
+                    },
+                ],
+                options: false,
+            },
+        );
+        props.insert("role".into(), role);
+        properties.shift_remove("Code");
+        properties.shift_remove("Runtime");
+        properties.shift_remove("Role");
+        take(
+            properties,
+            &mut props,
+            &[
+                ("Handler", "handler"),
+                ("FunctionName", "functionName"),
+                ("Description", "description"),
+                ("MemorySize", "memorySize"),
+            ],
+            &[("Timeout", "timeout")],
+        );
+        if let Some(ResourceIr::Object(_, environment)) = properties.get("Environment") {
+            if let (1, Some(variables)) = (environment.len(), environment.get("Variables")) {
+                props.insert("environment".into(), L2Value::Value(variables.clone()));
+                properties.shift_remove("Environment");
+            }
+        }
+        Some(props)
+    }
+
+    fn attribute(&self, attribute: Option<&str>) -> Option<&str> {
+        match attribute {
+            None => Some("functionName"),
+            Some("Arn") => Some("functionArn"),
+            Some(_) => None,
+        }
+    }
+}
+
+// `iam.Role`, for roles assumed by AWS services.
+struct Role;
+
+impl L2Mapping for Role {
+    fn class(&self) -> &str {
+        "Role"
+    }
+
+    fn props(
+        &self,
+        properties: &mut IndexMap<String, ResourceIr, Hasher>,
+        _: &MappingContext,
+    ) -> Option<IndexMap<String, L2Value, Hasher>> {
+        let Some(ResourceIr::Object(_, document)) = properties.get("AssumeRolePolicyDocument")
+        else {
+            return None;
+        };
+        if document
+            .keys()
+            .any(|key| key != "Version" && key != "Statement")
+        {
+            return None;
+        }
+        let statement = match document.get("Statement")? {
+            ResourceIr::Array(_, statements) if statements.len() == 1 => &statements[0],
+            _ => return None,
+        };
+        let ResourceIr::Object(_, statement) = statement else {
+            return None;
+        };
+        let action = match statement.get("Action")? {
+            ResourceIr::Array(_, actions) if actions.len() == 1 => literal(actions.first()),
+            action => literal(Some(action)),
+        };
+        let principal = match statement.get("Principal")? {
+            ResourceIr::Object(_, principal) if principal.len() == 1 => principal.get("Service")?,
+            _ => return None,
+        };
+        let services = match principal {
+            ResourceIr::Array(_, services) => services
+                .iter()
+                .map(|service| literal(Some(service)))
+                .collect::<Option<Vec<_>>>()?,
+            service => vec![literal(Some(service))?],
+        };
+        if statement.len() != 3
+            || literal(statement.get("Effect")) != Some("Allow")
+            || action != Some("sts:AssumeRole")
+            || services.is_empty()
+        {
+            return None;
+        }
+
+        let mut principals = services.into_iter().map(|service| L2Value::New {
+            service: "IAM".into(),
+            class: "ServicePrincipal".into(),
+            args: vec![L2Value::string(service)],
+            options: true,
+        });
+        let assumed_by = match principals.len() {
+            1 => principals.next()?,
+            _ => L2Value::New {
+                service: "IAM".into(),
+                class: "CompositePrincipal".into(),
+                args: principals.collect(),
+                options: false,
+            },
+        };
+
+        let mut props = IndexMap::default();
+        props.insert("assumedBy".into(), assumed_by);
+        properties.shift_remove("AssumeRolePolicyDocument");
+        take(
+            properties,
+            &mut props,
+            &[
+                ("RoleName", "roleName"),
+                ("Path", "path"),
+                ("Description", "description"),
+            ],
+            &[("MaxSessionDuration", "maxSessionDuration")],
+        );
+        Some(props)
+    }
+
+    fn attribute(&self, attribute: Option<&str>) -> Option<&str> {
+        match attribute {
+            None => Some("roleName"),
+            Some("Arn") => Some("roleArn"),
+            Some("RoleId") => Some("roleId"),
+            Some(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,321 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use crate::cdk::Schema;
+use crate::ir::resources::ResourceInstruction;
+use crate::CloudformationParseTree;
+
+use super::*;
+
+fn program(source: &str, mappings: &L2Mappings) -> CloudformationProgramIr {
+    let template: CloudformationParseTree = serde_yaml::from_str(source).unwrap();
+    let mut ir = CloudformationProgramIr::from(template, Schema::builtin()).unwrap();
+    ir.use_l2_constructs(mappings);
+    ir
+}
+
+fn resource<'a>(ir: &'a CloudformationProgramIr, name: &str) -> &'a ResourceInstruction {
+    ir.resources.iter().find(|r| r.name == name).unwrap()
+}
+
+fn reference(name: &str, origin: Origin) -> ResourceIr {
+    ResourceIr::Ref(Reference::new(name, origin))
+}
+
+#[test]
+fn builtin_mappings() {
+    let ir = program(
+        r#"
+        Resources:
+          Bucket:
+            Type: AWS::S3::Bucket
+            Properties:
+              VersioningConfiguration:
+                Status: Enabled
+              Tags:
+                - Key: team
+                  Value: core
+          Role:
+            Type: AWS::IAM::Role
+            Properties:
+              AssumeRolePolicyDocument:
+                Statement:
+                  - Effect: Allow
+                    Principal:
+                      Service: [lambda.amazonaws.com, edgelambda.amazonaws.com]
+                    Action: sts:AssumeRole
+          Function:
+            Type: AWS::Lambda::Function
+            DependsOn: Bucket
+            Properties:
+              Runtime: nodejs20.x
+              Handler: index.handler
+              Role: !GetAtt Role.Arn
+              Timeout: 30
+              Code:
+                ZipFile: exports.handler = async () => {};
+              Environment:
+                Variables:
+                  BUCKET: !Ref Bucket
+                  DOMAIN: !GetAtt Bucket.DomainName
+        Outputs:
+          Function:
+            Value: !GetAtt Function.Arn
+          SnapStart:
+            Value: !GetAtt Function.SnapStartResponse.ApplyOn
+        "#,
+        &L2Mappings::builtin(),
+    );
+
+    let bucket = resource(&ir, "Bucket");
+    assert!(bucket.properties.is_empty());
+    assert_eq!(bucket.deletion_policy, Some(DeletionPolicy::Delete));
+    assert_eq!(
+        bucket.update_replace_policy,
+        Some(UpdateReplacePolicy::Delete)
+    );
+    let construct = bucket.l2_construct.as_ref().unwrap();
+    assert_eq!(construct.class, "Bucket");
+    assert_eq!(
+        construct.props["versioned"],
+        L2Value::Value(ResourceIr::Bool(true))
+    );
+    // What the construct can't express is set on its L1 resource, as CloudFormation expects it.
+    let ResourceIr::Array(_, tags) = &construct.overrides["Tags"] else {
+        panic!("{:?}", construct.overrides);
+    };
+    assert!(matches!(
+        &tags[0],
+        ResourceIr::Object(TypeReference::Primitive(Primitive::Json), _)
+    ));
+
+    let role = resource(&ir, "Role").l2_construct.as_ref().unwrap();
+    let L2Value::New { class, args, .. } = &role.props["assumedBy"] else {
+        panic!("{:?}", role.props);
+    };
+    assert_eq!(class, "CompositePrincipal");
+    assert_eq!(args.len(), 2);
+
+    let function = resource(&ir, "Function");
+    assert_eq!(function.dependencies, vec!["CfnBucket"]);
+    assert_eq!(
+        function.references,
+        ["Bucket", "Role"].into_iter().map(String::from).collect()
+    );
+    let construct = function.l2_construct.as_ref().unwrap();
+    assert_eq!(construct.props["role"], L2Value::Construct("Role".into()));
+    assert_eq!(
+        construct.props["timeout"],
+        L2Value::Seconds(ResourceIr::Number(30))
+    );
+    assert!(construct.overrides.is_empty(), "{:?}", construct.overrides);
+    let L2Value::Value(ResourceIr::Object(_, variables)) = &construct.props["environment"] else {
+        panic!("{:?}", construct.props);
+    };
+    // References use the attributes of the construct, or of its L1 resource.
+    assert_eq!(
+        variables["BUCKET"],
+        reference(
+            "Bucket",
+            Origin::ConstructAttribute {
+                attribute: "bucketName".into()
+            }
+        )
+    );
+    assert_eq!(
+        variables["DOMAIN"],
+        reference(
+            "Bucket",
+            Origin::ConstructAttribute {
+                attribute: "bucketDomainName".into()
+            }
+        )
+    );
+    assert_eq!(
+        ir.outputs[0].value,
+        reference(
+            "Function",
+            Origin::ConstructAttribute {
+                attribute: "functionArn".into()
+            }
+        )
+    );
+    assert_eq!(
+        ir.outputs[1].value,
+        reference(
+            "CfnFunction",
+            Origin::GetAttribute {
+                attribute: "SnapStartResponseApplyOn".into(),
+                conditional: false,
+            }
+        )
+    );
+
+    let services: Vec<_> = ir
+        .imports
+        .iter()
+        .map(|import| import.service.as_deref())
+        .collect();
+    assert_eq!(
+        services,
+        vec![None, Some("IAM"), Some("Lambda"), Some("S3")]
+    );
+}
+
+#[test]
+fn fallbacks() {
+    let ir = program(
+        r#"
+        Parameters:
+          Key:
+            Type: String
+        Conditions:
+          Never: !Equals [a, b]
+        Resources:
+          Conditional:
+            Type: AWS::SQS::Queue
+            Condition: Never
+          Table:
+            Type: AWS::DynamoDB::Table
+            Properties:
+              AttributeDefinitions:
+                - AttributeName: !Ref Key
+                  AttributeType: S
+              KeySchema:
+                - AttributeName: !Ref Key
+                  KeyType: HASH
+          Role:
+            Type: AWS::IAM::Role
+            Properties:
+              AssumeRolePolicyDocument:
+                Statement:
+                  - Effect: Allow
+                    Principal:
+                      AWS: arn:aws:iam::123456789012:root
+                    Action: sts:AssumeRole
+          Function:
+            Type: AWS::Lambda::Function
+            Properties:
+              Runtime: java21
+              Handler: index.handler
+              Role: !GetAtt Role.Arn
+              Code:
+                ZipFile: class Handler {}
+          Topic:
+            Type: AWS::SNS::Topic
+          CfnTopic:
+            Type: AWS::SNS::Topic
+        "#,
+        &L2Mappings::builtin(),
+    );
+    for resource in &ir.resources {
+        let expected = resource.name == "CfnTopic";
+        assert_eq!(
+            resource.l2_construct.is_some(),
+            expected,
+            "{}",
+            resource.name
+        );
+    }
+    assert!(!resource(&ir, "Table").properties.is_empty());
+}
+
+#[test]
+fn custom_mappings() {
+    struct Vpc;
+    impl L2Mapping for Vpc {
+        fn class(&self) -> &str {
+            "Vpc"
+        }
+
+        fn props(
+            &self,
+            properties: &mut IndexMap<String, ResourceIr, Hasher>,
+            _: &MappingContext,
+        ) -> Option<IndexMap<String, L2Value, Hasher>> {
+            let cidr = properties.shift_remove("CidrBlock")?;
+            Some(IndexMap::from_iter([(
+                "ipAddresses".to_string(),
+                L2Value::Call {
+                    service: "EC2".into(),
+                    class: "IpAddresses".into(),
+                    method: "cidr".into(),
+                    args: vec![L2Value::Value(cidr)],
+                    options: false,
+                },
+            )]))
+        }
+
+        fn attribute(&self, attribute: Option<&str>) -> Option<&str> {
+            match attribute {
+                None => Some("vpcId"),
+                _ => None,
+            }
+        }
+    }
+
+    let mut mappings = L2Mappings::default();
+    mappings.register("AWS::EC2::VPC", Vpc);
+    let ir = program(
+        r#"
+        Resources:
+          Vpc:
+            Type: AWS::EC2::VPC
+            Properties:
+              CidrBlock: 10.0.0.0/16
+          Queue:
+            Type: AWS::SQS::Queue
+            Properties:
+              QueueName: !Ref Vpc
+        "#,
+        &mappings,
+    );
+    assert_eq!(
+        resource(&ir, "Vpc").l2_construct.as_ref().unwrap().class,
+        "Vpc"
+    );
+    let queue = resource(&ir, "Queue");
+    assert!(queue.l2_construct.is_none());
+    assert_eq!(
+        queue.properties["QueueName"],
+        reference(
+            "Vpc",
+            Origin::ConstructAttribute {
+                attribute: "vpcId".into()
+            }
+        )
+    );
+}
+
+#[test]
+fn property_comments_follow_the_props() {
+    let template: CloudformationParseTree = r#"
+Resources:
+  Queue:
+    Type: AWS::SQS::Queue
+    Properties:
+      # Named by the caller.
+      QueueName: orders
+      # In bytes.
+      MaximumMessageSize: 1024
+      # Kept as an override.
+      RedriveAllowPolicy:
+        redrivePermission: denyAll
+"#
+    .parse()
+    .unwrap();
+    let mut ir = CloudformationProgramIr::from(template, Schema::builtin()).unwrap();
+    ir.use_l2_constructs(&L2Mappings::builtin());
+
+    let queue = resource(&ir, "Queue");
+    assert_eq!(
+        queue.property_comments["queueName"],
+        vec!["Named by the caller.".to_string()]
+    );
+    assert_eq!(
+        queue.property_comments["RedriveAllowPolicy"],
+        vec!["Kept as an override.".to_string()]
+    );
+    // A prop with another name than its property keeps its comment above the construct.
+    assert!(!queue.property_comments.contains_key("MaximumMessageSize"));
+    assert!(queue.comments.contains(&"In bytes.".to_string()));
+}
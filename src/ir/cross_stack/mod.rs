@@ -0,0 +1,465 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use voca_rs::case::{camel_case, pascal_case};
+
+use crate::ir::constructor::{ConstructorParameter, DefaultValue};
+use crate::ir::constructs::L2Construct;
+use crate::ir::reference::{Origin, PseudoParameter, Reference};
+use crate::ir::resources::ResourceIr;
+use crate::ir::CloudformationProgramIr;
+
+// CrossStackImport is an `Fn::ImportValue` of a stack that was resolved to an output of another
+// stack of the same application (see `link_stacks`). The importing stack receives the value of the
+// output through one of its props, which makes it a native CDK cross-stack reference.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CrossStackImport {
+    // The constructor parameter of the importing stack that receives the value.
+    pub parameter: String,
+    // The exporting stack, and its output holding the value.
+    pub stack: String,
+    pub output: String,
+}
+
+/// An `Fn::ImportValue` that [`link_stacks`] left as is.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnresolvedImport {
+    /// The importing stack.
+    pub stack: String,
+    /// The name of the imported export, with pseudo parameters left as `${AWS::...}` variables.
+    /// It is `None` when the name is only known at deploy time.
+    pub export: Option<String>,
+    pub reason: String,
+}
+
+impl fmt::Display for UnresolvedImport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.export {
+            Some(export) => write!(f, "{} imports {export}: {}", self.stack, self.reason),
+            None => write!(f, "{} imports a value: {}", self.stack, self.reason),
+        }
+    }
+}
+
+/// Resolves the `Fn::ImportValue`s of `stacks` (each a stack name and its program) that import an
+/// export of another of the stacks. Each of them becomes a reference to a new prop of the importing
+/// stack, listed in its `cross_stack_imports`, and `stacks` is reordered so that every stack comes
+/// after the stacks it imports from. The stacks are deployed as `stack_id` (the construct ID of the
+/// stack in the application), which is what `${AWS::StackName}` resolves to in export names.
+///
+/// Imports that can't be resolved are left as is, and returned.
+pub fn link_stacks(
+    stacks: &mut Vec<(String, CloudformationProgramIr)>,
+    stack_id: impl Fn(&str) -> String,
+) -> Vec<UnresolvedImport> {
+    // Export names are unique within an account and region, so duplicates can't all be deployed.
+    let mut exports: HashMap<String, Option<Export>> = HashMap::new();
+    for (index, (name, ir)) in stacks.iter().enumerate() {
+        let id = stack_id(name);
+        for output in &ir.outputs {
+            let Some(export) = output
+                .export
+                .as_ref()
+                .and_then(|e| export_name(e, &id, &ir.constructor.inputs))
+            else {
+                continue;
+            };
+            let entry = Export {
+                stack: index,
+                output: output.name.clone(),
+                conditional: output.condition.is_some(),
+            };
+            exports
+                .entry(export)
+                .and_modify(|existing| *existing = None)
+                .or_insert(Some(entry));
+        }
+    }
+
+    let mut unresolved = Vec::new();
+    // The stacks each stack imports from, by index.
+    let mut producers: Vec<HashSet<usize>> = vec![HashSet::new(); stacks.len()];
+    let names: Vec<String> = stacks.iter().map(|(name, _)| name.clone()).collect();
+    for (index, (name, ir)) in stacks.iter_mut().enumerate() {
+        let id = stack_id(name);
+        let mut linker = Linker {
+            stack: index,
+            stack_id: &id,
+            names: &names,
+            exports: &exports,
+            producers: &mut producers,
+            inputs: &ir.constructor.inputs,
+            new_inputs: Vec::new(),
+            imports: Vec::new(),
+            unresolved: &mut unresolved,
+        };
+        for resource in &mut ir.resources {
+            for value in resource.properties.values_mut() {
+                linker.link(value);
+            }
+            for value in [&mut resource.metadata, &mut resource.update_policy]
+                .into_iter()
+                .flatten()
+            {
+                linker.link(value);
+            }
+            if let Some(nested) = &mut resource.nested_stack {
+                for value in nested.parameters.values_mut() {
+                    linker.link(value);
+                }
+            }
+            if let Some(construct) = &mut resource.l2_construct {
+                for value in construct.values_mut() {
+                    linker.link(value);
+                }
+            }
+        }
+        for output in &mut ir.outputs {
+            linker.link(&mut output.value);
+            if let Some(export) = &mut output.export {
+                linker.link(export);
+            }
+        }
+
+        let Linker {
+            new_inputs,
+            imports,
+            ..
+        } = linker;
+        ir.constructor.inputs.extend(new_inputs);
+        ir.cross_stack_imports.extend(imports);
+
+        // Nested stacks would have to receive the value from their parent, which isn't supported.
+        for nested in ir.nested_stacks() {
+            let inputs = &nested.program.constructor.inputs;
+            for import in nested.program.imports() {
+                let export = import_name(import, &nested.class_name, inputs);
+                let reason = match export.as_ref().map(|name| exports.get(name)) {
+                    Some(Some(Some(_))) => format!(
+                        "cross-stack references can't be passed to the nested stack {}",
+                        nested.class_name
+                    ),
+                    Some(_) => "none of the converted templates exports it".to_string(),
+                    None => "its name is only known at deploy time".to_string(),
+                };
+                unresolved.push(UnresolvedImport {
+                    stack: name.clone(),
+                    export,
+                    reason,
+                });
+            }
+        }
+    }
+
+    // Stable topological sort: each stack is placed after the stacks it imports from, which are
+    // known not to form a cycle.
+    let mut order: Vec<usize> = Vec::with_capacity(stacks.len());
+    while order.len() < stacks.len() {
+        let next = (0..stacks.len())
+            .find(|index| {
+                !order.contains(index) && producers[*index].iter().all(|p| order.contains(p))
+            })
+            .expect("cross-stack imports never form a cycle");
+        order.push(next);
+    }
+    let mut slots: Vec<Option<(String, CloudformationProgramIr)>> =
+        std::mem::take(stacks).into_iter().map(Some).collect();
+    stacks.extend(order.into_iter().filter_map(|index| slots[index].take()));
+
+    unresolved
+}
+
+#[derive(Debug)]
+struct Export {
+    stack: usize,
+    output: String,
+    conditional: bool,
+}
+
+// Linker replaces the imports of one stack.
+struct Linker<'a> {
+    stack: usize,
+    stack_id: &'a str,
+    names: &'a [String],
+    exports: &'a HashMap<String, Option<Export>>,
+    producers: &'a mut Vec<HashSet<usize>>,
+    inputs: &'a [ConstructorParameter],
+    new_inputs: Vec<ConstructorParameter>,
+    imports: Vec<CrossStackImport>,
+    unresolved: &'a mut Vec<UnresolvedImport>,
+}
+
+impl Linker<'_> {
+    fn link(&mut self, value: &mut ResourceIr) {
+        value.replace_imports(&mut |import| self.resolve(import));
+    }
+
+    fn resolve(&mut self, import: &ResourceIr) -> Option<ResourceIr> {
+        let name = import_name(import, self.stack_id, self.inputs);
+        let result = match name.as_ref().map(|name| self.exports.get(name)) {
+            None => Err("its name is only known at deploy time".to_string()),
+            Some(None) => Err("none of the converted templates exports it".to_string()),
+            Some(Some(None)) => Err("several of the converted templates export it".to_string()),
+            Some(Some(Some(export))) if export.stack == self.stack => {
+                Err("a stack can't import its own exports".to_string())
+            }
+            Some(Some(Some(export))) if export.conditional => Err(format!(
+                "the output of {} that exports it is conditional",
+                self.names[export.stack]
+            )),
+            Some(Some(Some(export))) if self.depends_on(export.stack, self.stack) => Err(format!(
+                "{} already depends on {}",
+                self.names[export.stack], self.names[self.stack]
+            )),
+            Some(Some(Some(export))) => Ok(export),
+        };
+        let export = match result {
+            Ok(export) => export,
+            Err(reason) => {
+                self.unresolved.push(UnresolvedImport {
+                    stack: self.names[self.stack].clone(),
+                    export: name,
+                    reason,
+                });
+                return None;
+            }
+        };
+
+        let producer = &self.names[export.stack];
+        let parameter = match self
+            .imports
+            .iter()
+            .find(|import| &import.stack == producer && import.output == export.output)
+        {
+            Some(import) => import.parameter.clone(),
+            None => {
+                let parameter = self.parameter_name(producer, &export.output);
+                self.new_inputs.push(ConstructorParameter {
+                    name: parameter.clone(),
+                    logical_id: pascal_case(&parameter),
+                    description: Some(format!(
+                        "The {} output of {producer}, which exports it as {}",
+                        export.output,
+                        name.as_deref().unwrap_or_default()
+                    )),
+                    ..Default::default()
+                });
+                self.imports.push(CrossStackImport {
+                    parameter: parameter.clone(),
+                    stack: producer.clone(),
+                    output: export.output.clone(),
+                });
+                self.producers[self.stack].insert(export.stack);
+                parameter
+            }
+        };
+        Some(ResourceIr::Ref(Reference::new(
+            &parameter,
+            Origin::Parameter,
+        )))
+    }
+
+    // Whether `stack` imports from `other`, directly or not.
+    fn depends_on(&self, stack: usize, other: usize) -> bool {
+        let mut pending = vec![stack];
+        let mut seen = HashSet::new();
+        while let Some(stack) = pending.pop() {
+            if stack == other {
+                return true;
+            }
+            if seen.insert(stack) {
+                pending.extend(self.producers[stack].iter().copied());
+            }
+        }
+        false
+    }
+
+    // The prop receiving an output is named after it, unless the stack already has such a prop.
+    fn parameter_name(&self, producer: &str, output: &str) -> String {
+        let taken = |name: &str| {
+            self.inputs
+                .iter()
+                .chain(&self.new_inputs)
+                .any(|input| input.name == name)
+        };
+        let mut name = camel_case(output);
+        if taken(&name) {
+            name = camel_case(&format!("{producer} {output}"));
+        }
+        let base = name.clone();
+        let mut suffix = 1;
+        while taken(&name) {
+            suffix += 1;
+            name = format!("{base}{suffix}");
+        }
+        name
+    }
+}
+
+// The names of exports can be made of pseudo parameters, and of parameters with a default value.
+// Pseudo parameters other than the stack name are left as variables: the stacks of an application
+// are deployed to the same account and region, so they match the same variables of other stacks.
+fn default_value(parameters: &[ConstructorParameter], name: &str) -> Option<String> {
+    let name = camel_case(name);
+    let input = parameters.iter().find(|input| input.name == name)?;
+    match &input.default_value {
+        Some(DefaultValue::String(value)) => Some(value.clone()),
+        _ => None,
+    }
+}
+
+// The name of the export an `Fn::ImportValue` imports.
+fn import_name(
+    import: &ResourceIr,
+    stack_id: &str,
+    parameters: &[ConstructorParameter],
+) -> Option<String> {
+    match import {
+        ResourceIr::ImportValue(name) => export_name(name, stack_id, parameters),
+        _ => None,
+    }
+}
+
+fn export_name(
+    value: &ResourceIr,
+    stack_id: &str,
+    parameters: &[ConstructorParameter],
+) -> Option<String> {
+    match value {
+        ResourceIr::String(value) => Some(value.clone()),
+        ResourceIr::Sub(parts) => parts
+            .iter()
+            .map(|part| export_name(part, stack_id, parameters))
+            .collect(),
+        ResourceIr::Join(separator, items) => Some(
+            items
+                .iter()
+                .map(|item| export_name(item, stack_id, parameters))
+                .collect::<Option<Vec<_>>>()?
+                .join(separator),
+        ),
+        ResourceIr::Ref(Reference {
+            origin: Origin::PseudoParameter(pseudo),
+            ..
+        }) => Some(match pseudo {
+            PseudoParameter::StackName => stack_id.to_string(),
+            PseudoParameter::AccountId => "${AWS::AccountId}".into(),
+            PseudoParameter::Partition => "${AWS::Partition}".into(),
+            PseudoParameter::Region => "${AWS::Region}".into(),
+            PseudoParameter::URLSuffix => "${AWS::URLSuffix}".into(),
+            PseudoParameter::StackId | PseudoParameter::NotificationArns => return None,
+        }),
+        ResourceIr::Ref(Reference {
+            origin: Origin::Parameter | Origin::CfnParameter,
+            name,
+        }) => default_value(parameters, name),
+        _ => None,
+    }
+}
+
+impl CloudformationProgramIr {
+    // The `Fn::ImportValue`s of the program, outside of its nested stacks.
+    fn imports(&self) -> Vec<&ResourceIr> {
+        let mut imports = Vec::new();
+        for resource in &self.resources {
+            for value in resource.properties.values() {
+                value.collect_imports(&mut imports);
+            }
+            for value in resource.l2_construct.iter().flat_map(L2Construct::values) {
+                value.collect_imports(&mut imports);
+            }
+        }
+        for output in &self.outputs {
+            output.value.collect_imports(&mut imports);
+        }
+        imports
+    }
+}
+
+impl ResourceIr {
+    // Replaces the `Fn::ImportValue`s within this value for which `replace` returns a value.
+    fn replace_imports(&mut self, replace: &mut impl FnMut(&ResourceIr) -> Option<ResourceIr>) {
+        match self {
+            ResourceIr::Null
+            | ResourceIr::Bool(_)
+            | ResourceIr::Number(_)
+            | ResourceIr::Double(_)
+            | ResourceIr::String(_)
+            | ResourceIr::Ref(_) => {}
+            ResourceIr::ImportValue(_) => {
+                if let Some(value) = replace(self) {
+                    *self = value;
+                }
+            }
+            ResourceIr::Array(_, items) | ResourceIr::Join(_, items) | ResourceIr::Sub(items) => {
+                for item in items {
+                    item.replace_imports(replace);
+                }
+            }
+            ResourceIr::Object(_, entries) => {
+                for value in entries.values_mut() {
+                    value.replace_imports(replace);
+                }
+            }
+            ResourceIr::If(_, x, y) | ResourceIr::Map(_, x, y) => {
+                x.replace_imports(replace);
+                y.replace_imports(replace);
+            }
+            ResourceIr::Split(_, x)
+            | ResourceIr::Base64(x)
+            | ResourceIr::GetAZs(x)
+            | ResourceIr::Select(_, x)
+            | ResourceIr::Length(x)
+            | ResourceIr::ToJsonString(x) => x.replace_imports(replace),
+            ResourceIr::Cidr(x, y, z) => {
+                x.replace_imports(replace);
+                y.replace_imports(replace);
+                z.replace_imports(replace);
+            }
+        }
+    }
+}
+
+impl ResourceIr {
+    fn collect_imports<'a>(&'a self, imports: &mut Vec<&'a ResourceIr>) {
+        match self {
+            ResourceIr::Null
+            | ResourceIr::Bool(_)
+            | ResourceIr::Number(_)
+            | ResourceIr::Double(_)
+            | ResourceIr::String(_)
+            | ResourceIr::Ref(_) => {}
+            ResourceIr::ImportValue(_) => imports.push(self),
+            ResourceIr::Array(_, items) | ResourceIr::Join(_, items) | ResourceIr::Sub(items) => {
+                for item in items {
+                    item.collect_imports(imports);
+                }
+            }
+            ResourceIr::Object(_, entries) => {
+                for value in entries.values() {
+                    value.collect_imports(imports);
+                }
+            }
+            ResourceIr::If(_, x, y) | ResourceIr::Map(_, x, y) => {
+                x.collect_imports(imports);
+                y.collect_imports(imports);
+            }
+            ResourceIr::Split(_, x)
+            | ResourceIr::Base64(x)
+            | ResourceIr::GetAZs(x)
+            | ResourceIr::Select(_, x)
+            | ResourceIr::Length(x)
+            | ResourceIr::ToJsonString(x) => x.collect_imports(imports),
+            ResourceIr::Cidr(x, y, z) => {
+                x.collect_imports(imports);
+                y.collect_imports(imports);
+                z.collect_imports(imports);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,213 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use crate::cdk::Schema;
+use crate::ir::constructor::ConstructorType;
+use crate::CloudformationParseTree;
+
+use super::*;
+
+fn program(source: &str) -> CloudformationProgramIr {
+    let template: CloudformationParseTree = serde_yaml::from_str(source).unwrap();
+    CloudformationProgramIr::from(template, Schema::builtin()).unwrap()
+}
+
+fn link(stacks: &[(&str, &str)]) -> (Vec<(String, CloudformationProgramIr)>, Vec<String>) {
+    let mut stacks = stacks
+        .iter()
+        .map(|(name, source)| (name.to_string(), program(source)))
+        .collect();
+    let unresolved = link_stacks(&mut stacks, |name| format!("{name}-id"));
+    let unresolved = unresolved.iter().map(ToString::to_string).collect();
+    (stacks, unresolved)
+}
+
+const NETWORK: &str = r#"
+    Resources:
+      Vpc:
+        Type: AWS::EC2::VPC
+        Properties:
+          CidrBlock: 10.0.0.0/16
+    Conditions:
+      Never: !Equals [a, b]
+    Outputs:
+      VpcId:
+        Value: !Ref Vpc
+        Export:
+          Name: !Sub "${AWS::StackName}-VpcId"
+      Cidr:
+        Value: !GetAtt Vpc.CidrBlock
+        Export:
+          Name: !Join [":", [!Ref "AWS::Region", cidr]]
+      Hidden:
+        Condition: Never
+        Value: !Ref Vpc
+        Export:
+          Name: hidden
+"#;
+
+#[test]
+fn imports_become_props() {
+    let (stacks, unresolved) = link(&[
+        (
+            "App",
+            r#"
+            Parameters:
+              NetworkStack:
+                Type: String
+                Default: Network-id
+            Resources:
+              Group:
+                Type: AWS::EC2::SecurityGroup
+                Properties:
+                  GroupDescription: !ImportValue
+                    Fn::Sub: "${AWS::Region}:cidr"
+                  VpcId: !ImportValue
+                    Fn::Sub: "${NetworkStack}-VpcId"
+                  Tags:
+                    - Key: vpc
+                      Value: !ImportValue Network-id-VpcId
+            "#,
+        ),
+        ("Network", NETWORK),
+    ]);
+    assert!(unresolved.is_empty(), "{unresolved:?}");
+
+    // The exporting stack comes first
+    let names: Vec<_> = stacks.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["Network", "App"]);
+
+    let app = &stacks[1].1;
+    assert_eq!(
+        app.cross_stack_imports,
+        vec![
+            CrossStackImport {
+                parameter: "cidr".into(),
+                stack: "Network".into(),
+                output: "Cidr".into(),
+            },
+            CrossStackImport {
+                parameter: "vpcId".into(),
+                stack: "Network".into(),
+                output: "VpcId".into(),
+            },
+        ]
+    );
+    let vpc_id = &app.constructor.inputs[2];
+    assert_eq!(vpc_id.name, "vpcId");
+    assert_eq!(vpc_id.constructor_type, ConstructorType::String);
+    assert_eq!(vpc_id.default_value, None);
+    assert!(!vpc_id.is_cfn_parameter());
+
+    let properties = &app.resources[0].properties;
+    let vpc_ref = ResourceIr::Ref(Reference::new("vpcId", Origin::Parameter));
+    assert_eq!(properties["VpcId"], vpc_ref);
+    // The same output is imported through a single prop
+    let ResourceIr::Array(_, tags) = &properties["Tags"] else {
+        panic!("{:?}", properties["Tags"]);
+    };
+    let ResourceIr::Object(_, tag) = &tags[0] else {
+        panic!("{:?}", tags[0]);
+    };
+    assert_eq!(tag["Value"], vpc_ref);
+}
+
+#[test]
+fn unresolved_imports() {
+    let (stacks, unresolved) = link(&[
+        (
+            "App",
+            r#"
+            Parameters:
+              Suffix:
+                Type: String
+            Resources:
+              Queue:
+                Type: AWS::SQS::Queue
+                Properties:
+                  QueueName: !ImportValue elsewhere
+                  KmsMasterKeyId: !ImportValue hidden
+                  RedriveAllowPolicy: !ImportValue
+                    Fn::Sub: "network-${Suffix}"
+            Outputs:
+              Name:
+                Value: !Ref Queue
+                Export:
+                  Name: twice
+            "#,
+        ),
+        (
+            "Other",
+            r#"
+            Resources:
+              Topic:
+                Type: AWS::SNS::Topic
+                Properties:
+                  TopicName: !ImportValue twice
+            Outputs:
+              Name:
+                Value: !Ref Topic
+                Export:
+                  Name: twice
+            "#,
+        ),
+        ("Network", NETWORK),
+    ]);
+    assert_eq!(
+        unresolved,
+        vec![
+            "App imports elsewhere: none of the converted templates exports it",
+            "App imports hidden: the output of Network that exports it is conditional",
+            "App imports a value: its name is only known at deploy time",
+            "Other imports twice: several of the converted templates export it",
+        ]
+    );
+    assert!(stacks
+        .iter()
+        .all(|(_, ir)| ir.cross_stack_imports.is_empty()));
+    assert_eq!(
+        stacks[0].1.resources[0].properties["QueueName"],
+        ResourceIr::ImportValue(Box::new(ResourceIr::String("elsewhere".into())))
+    );
+}
+
+#[test]
+fn cycles() {
+    let (stacks, unresolved) = link(&[
+        (
+            "First",
+            r#"
+            Resources:
+              Queue:
+                Type: AWS::SQS::Queue
+                Properties:
+                  QueueName: !ImportValue second
+            Outputs:
+              Name:
+                Value: !Ref Queue
+                Export:
+                  Name: first
+            "#,
+        ),
+        (
+            "Second",
+            r#"
+            Resources:
+              Queue:
+                Type: AWS::SQS::Queue
+                Properties:
+                  QueueName: !ImportValue first
+            Outputs:
+              Name:
+                Value: !Ref Queue
+                Export:
+                  Name: second
+            "#,
+        ),
+    ]);
+    assert_eq!(
+        unresolved,
+        vec!["Second imports first: First already depends on Second"]
+    );
+    let names: Vec<_> = stacks.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["Second", "First"]);
+}
@@ -0,0 +1,165 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::parser::span::{SourceMap, Span};
+use crate::Error;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+// A Diagnostic is a single problem found while building the IR. The path points at the offending
+// template entry, using the same notation as the CloudFormation console (e.g:
+// `Resources.MyBucket.Properties.Tags[2].Key`).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub path: String,
+    pub message: String,
+    pub span: Option<Span>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.span {
+            Some(span) => write!(f, "{} ({span}): {}", self.path, self.message),
+            None => write!(f, "{}: {}", self.path, self.message),
+        }
+    }
+}
+
+// Diagnostics collects every error and warning encountered while translating a template, so that
+// a single run can report all of them instead of stopping at the first one.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Diagnostics {
+    entries: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub fn error(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.push(Severity::Error, path.into(), message.into());
+    }
+
+    pub fn warning(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.push(Severity::Warning, path.into(), message.into());
+    }
+
+    /// Records `error` as located at `path`. Errors raised deep inside a value carry their own
+    /// path relative to that value, which gets appended to `path`, and its position in the
+    /// template when it is known.
+    pub fn report(&mut self, path: impl Into<String>, mut error: Error) {
+        let mut path = path.into();
+        let mut span = None;
+        let error = loop {
+            match error {
+                Error::LocatedError {
+                    path: relative,
+                    source,
+                } => {
+                    if !relative.starts_with('[') {
+                        path.push('.');
+                    }
+                    path.push_str(&relative);
+                    error = *source;
+                }
+                Error::SpannedError {
+                    span: position,
+                    source,
+                } => {
+                    span = Some(position);
+                    error = *source;
+                }
+                error => break error,
+            }
+        };
+        let error = match error {
+            Error::UnsupportedIntrinsic {
+                intrinsic,
+                location,
+            } if location.is_empty() => Error::UnsupportedIntrinsic {
+                intrinsic,
+                location: path.clone(),
+            },
+            error => error,
+        };
+        self.entries.push(Diagnostic {
+            severity: Severity::Error,
+            path,
+            message: error.to_string(),
+            span,
+        });
+    }
+
+    fn push(&mut self, severity: Severity, path: String, message: String) {
+        self.entries.push(Diagnostic {
+            severity,
+            path,
+            message,
+            span: None,
+        });
+    }
+
+    /// Resolves the position in the original template of every diagnostic that has none yet.
+    pub fn locate(&mut self, source_map: &SourceMap) {
+        for diagnostic in self.entries.iter_mut().filter(|d| d.span.is_none()) {
+            diagnostic.span = source_map.span(&diagnostic.path);
+        }
+    }
+
+    #[inline]
+    pub fn has_errors(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.entries.iter()
+    }
+
+    pub fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &Diagnostic> {
+        self.entries
+            .iter()
+            .filter(move |diagnostic| diagnostic.severity == severity)
+    }
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, diagnostic) in self.entries.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}: {diagnostic}", diagnostic.severity)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
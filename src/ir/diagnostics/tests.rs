@@ -0,0 +1,146 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use crate::ir::diagnostics::{Diagnostic, Diagnostics, Severity};
+use crate::parser::span::{SourceMap, Span};
+use crate::Error;
+
+#[test]
+fn test_report_plain_error() {
+    let mut diagnostics = Diagnostics::default();
+    diagnostics.report(
+        "Resources.MyBucket.Properties.Foo",
+        Error::ResourceInstructionError {
+            message: "Foo is not a valid property".into(),
+        },
+    );
+
+    assert!(diagnostics.has_errors());
+    assert_eq!(
+        diagnostics.iter().collect::<Vec<_>>(),
+        vec![&Diagnostic {
+            severity: Severity::Error,
+            path: "Resources.MyBucket.Properties.Foo".into(),
+            message: "Foo is not a valid property".into(),
+            span: None,
+        }]
+    );
+}
+
+#[test]
+fn test_report_located_error() {
+    let error = Error::ResourceTranslationError {
+        message: "invalid digit found in string".into(),
+    };
+    let mut diagnostics = Diagnostics::default();
+    diagnostics.report(
+        "Resources.MyBucket.Properties.Tags",
+        error.at_key("Key").at_index(2),
+    );
+    diagnostics.report(
+        "Outputs.Out.Value",
+        Error::ResourceTranslationError {
+            message: "nested".into(),
+        }
+        .at_key("Inner"),
+    );
+
+    let paths: Vec<&str> = diagnostics.iter().map(|d| d.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "Resources.MyBucket.Properties.Tags[2].Key",
+            "Outputs.Out.Value.Inner"
+        ]
+    );
+    assert_eq!(
+        diagnostics.iter().next().unwrap().message,
+        "invalid digit found in string"
+    );
+}
+
+#[test]
+fn test_warnings_are_not_errors() {
+    let mut diagnostics = Diagnostics::default();
+    diagnostics.warning("Outputs.Out.Export", "ignored");
+
+    assert!(!diagnostics.has_errors());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics.with_severity(Severity::Error).count(), 0);
+    assert_eq!(diagnostics.with_severity(Severity::Warning).count(), 1);
+}
+
+#[test]
+fn test_locate() {
+    let source_map = SourceMap::new("Resources:\n  MyBucket:\n    Type: AWS::S3::Bucket\n");
+    let mut diagnostics = Diagnostics::default();
+    diagnostics.error(
+        "Resources.MyBucket.Properties.Foo",
+        "Foo is not a valid property",
+    );
+    diagnostics.locate(&source_map);
+
+    let diagnostic = diagnostics.iter().next().unwrap();
+    assert_eq!(diagnostic.span, Some(Span { line: 2, column: 3 }));
+    assert_eq!(
+        diagnostic.to_string(),
+        "Resources.MyBucket.Properties.Foo (2:3): Foo is not a valid property"
+    );
+}
+
+#[test]
+fn test_errors_point_back_to_the_template() {
+    let template: crate::CloudformationParseTree = r#"Parameters:
+  Port:
+    Type: Number
+    Default: eighty
+Resources:
+  Group:
+    Type: AWS::EC2::SecurityGroup
+    Properties:
+      GroupDescription: test
+      SecurityGroupIngress:
+        - IpProtocol: tcp
+          FromPort: eighty
+      Foo: bar
+"#
+    .parse()
+    .unwrap();
+    let Err(Error::InvalidTemplate { diagnostics }) =
+        crate::ir::CloudformationProgramIr::from(template, crate::cdk::Schema::builtin())
+    else {
+        panic!("the template is valid");
+    };
+
+    let spans: Vec<(&str, Option<(usize, usize)>)> = diagnostics
+        .iter()
+        .map(|d| (d.path.as_str(), d.span.map(|s| (s.line, s.column))))
+        .collect();
+    assert_eq!(
+        spans,
+        vec![
+            ("Parameters.Port.Default", Some((2, 3))),
+            (
+                "Resources.Group.Properties.SecurityGroupIngress[0].FromPort",
+                Some((12, 11))
+            ),
+            ("Resources.Group.Properties.Foo", Some((13, 7))),
+        ]
+    );
+}
+
+#[test]
+fn test_innermost_span_is_kept() {
+    let error = Error::ResourceTranslationError {
+        message: "invalid digit found in string".into(),
+    }
+    .at_span(Some(Span { line: 3, column: 5 }))
+    .at_key("Key")
+    .at_span(Some(Span { line: 2, column: 3 }));
+    assert_eq!(error.span(), Some(Span { line: 3, column: 5 }));
+
+    let mut diagnostics = Diagnostics::default();
+    diagnostics.report("Resources.Bucket.Properties.Tags", error);
+    let diagnostic = diagnostics.iter().next().unwrap();
+    assert_eq!(diagnostic.path, "Resources.Bucket.Properties.Tags.Key");
+    assert_eq!(diagnostic.span, Some(Span { line: 3, column: 5 }));
+}
@@ -15,7 +15,12 @@ fn test_invalid_resource_type_name() {
         depends_on: vec![],
         update_policy: Option::None,
         deletion_policy: Option::None,
+        update_replace_policy: Option::None,
+        creation_policy: Option::None,
         properties: IndexMap::new(),
+        span: None,
+        comments: Vec::new(),
+        property_comments: IndexMap::new(),
     };
     let parse_tree = IndexMap::from([("Resource".to_string(), resource_attributes)]);
     let import_instruction = ImportInstruction::from(&parse_tree).unwrap_err();
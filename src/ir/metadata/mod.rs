@@ -0,0 +1,228 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use indexmap::IndexMap;
+use voca_rs::case::camel_case;
+
+use crate::cdk::{Primitive, Schema, TypeReference};
+use crate::ir::constructor::Constructor;
+use crate::ir::resources::{ResourceIr, ResourceTranslator};
+use crate::parser::metadata::{Interface, TemplateMetadata, INTERFACE_KEY};
+use crate::util::Hasher;
+
+use super::diagnostics::Diagnostics;
+use super::ReferenceOrigins;
+
+// MetadataInstruction is the top-level `Metadata` section of the template, which synthesizers
+// emit into `templateOptions.metadata`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MetadataInstruction {
+    pub interface: Option<InterfaceInstruction>,
+    pub entries: IndexMap<String, ResourceIr, Hasher>,
+}
+
+// InterfaceInstruction is the `AWS::CloudFormation::Interface` metadata. Parameters are identified
+// by their constructor name, which `Constructor::logical_id` turns back into the logical ID of their
+// CfnParameter. Only parameters that are still declared as CfnParameters are kept, as the
+// console has nothing to show for the others.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InterfaceInstruction {
+    pub parameter_groups: Vec<ParameterGroupInstruction>,
+    pub parameter_labels: IndexMap<String, String, Hasher>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParameterGroupInstruction {
+    pub label: Option<String>,
+    pub parameters: Vec<String>,
+}
+
+impl MetadataInstruction {
+    pub(super) fn from(
+        parse_tree: Option<TemplateMetadata>,
+        constructor: &Constructor,
+        schema: &Schema,
+        origins: &ReferenceOrigins,
+        diagnostics: &mut Diagnostics,
+    ) -> Option<Self> {
+        let parse_tree = parse_tree?;
+
+        let translator = ResourceTranslator {
+            schema,
+            origins,
+            value_type: Some(TypeReference::Primitive(Primitive::Json)),
+        };
+        let mut entries =
+            IndexMap::with_capacity_and_hasher(parse_tree.entries.len(), Hasher::default());
+        for (name, value) in parse_tree.entries {
+            match translator.translate(value) {
+                Ok(value) => {
+                    entries.insert(name, value);
+                }
+                Err(err) => diagnostics.report(format!("Metadata.{name}"), err),
+            }
+        }
+
+        let interface = parse_tree
+            .interface
+            .map(|interface| InterfaceInstruction::from(interface, constructor, diagnostics))
+            .filter(|interface| !interface.is_empty());
+
+        Some(Self { interface, entries })
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.interface.is_none() && self.entries.is_empty()
+    }
+
+    /// Renders the metadata as a single JSON object, using `logical_id` to turn the constructor
+    /// name of each parameter in the interface into the logical ID of its CfnParameter.
+    pub fn to_ir(&self, logical_id: impl Fn(&str) -> String) -> ResourceIr {
+        let mut object = IndexMap::with_capacity_and_hasher(
+            self.entries.len() + usize::from(self.interface.is_some()),
+            Hasher::default(),
+        );
+        if let Some(interface) = &self.interface {
+            object.insert(INTERFACE_KEY.into(), interface.to_ir(logical_id));
+        }
+        object.extend(
+            self.entries
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone())),
+        );
+        ResourceIr::Object(TypeReference::Primitive(Primitive::Json), object)
+    }
+}
+
+impl InterfaceInstruction {
+    fn from(
+        interface: Interface,
+        constructor: &Constructor,
+        diagnostics: &mut Diagnostics,
+    ) -> Self {
+        let path = format!("Metadata.{INTERFACE_KEY}");
+        let mut resolve = |path: String, name: &str| -> Option<String> {
+            let constructor_name = camel_case(name);
+            match constructor
+                .inputs
+                .iter()
+                .find(|input| input.name == constructor_name)
+            {
+                Some(input) if input.is_cfn_parameter() => Some(constructor_name),
+                Some(_) => None,
+                None => {
+                    diagnostics.warning(
+                        path,
+                        format!("{name} is not a parameter of this template and will be ignored"),
+                    );
+                    None
+                }
+            }
+        };
+
+        let mut parameter_groups = Vec::with_capacity(interface.parameter_groups.len());
+        for (index, group) in interface.parameter_groups.into_iter().enumerate() {
+            let parameters: Vec<String> = group
+                .parameters
+                .iter()
+                .enumerate()
+                .filter_map(|(param_index, name)| {
+                    resolve(
+                        format!("{path}.ParameterGroups[{index}].Parameters[{param_index}]"),
+                        name,
+                    )
+                })
+                .collect();
+            if !parameters.is_empty() {
+                parameter_groups.push(ParameterGroupInstruction {
+                    label: group.label.map(|label| label.default),
+                    parameters,
+                });
+            }
+        }
+
+        let parameter_labels = interface
+            .parameter_labels
+            .into_iter()
+            .filter_map(|(name, label)| {
+                resolve(format!("{path}.ParameterLabels.{name}"), &name)
+                    .map(|name| (name, label.default))
+            })
+            .collect();
+
+        Self {
+            parameter_groups,
+            parameter_labels,
+        }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.parameter_groups.is_empty() && self.parameter_labels.is_empty()
+    }
+
+    /// Removes `name` from every group and label, dropping groups that end up empty.
+    pub(super) fn remove_parameter(&mut self, name: &str) {
+        for group in &mut self.parameter_groups {
+            group.parameters.retain(|parameter| parameter != name);
+        }
+        self.parameter_groups
+            .retain(|group| !group.parameters.is_empty());
+        self.parameter_labels.shift_remove(name);
+    }
+
+    fn to_ir(&self, logical_id: impl Fn(&str) -> String) -> ResourceIr {
+        let json = || TypeReference::Primitive(Primitive::Json);
+        let label = |text: &str| {
+            ResourceIr::Object(
+                json(),
+                IndexMap::from_iter([("default".to_string(), ResourceIr::String(text.into()))]),
+            )
+        };
+
+        let mut object = IndexMap::with_capacity_and_hasher(2, Hasher::default());
+        if !self.parameter_groups.is_empty() {
+            let groups = self
+                .parameter_groups
+                .iter()
+                .map(|group| {
+                    let mut entry = IndexMap::with_capacity_and_hasher(2, Hasher::default());
+                    if let Some(text) = &group.label {
+                        entry.insert("Label".to_string(), label(text));
+                    }
+                    entry.insert(
+                        "Parameters".to_string(),
+                        ResourceIr::Array(
+                            json(),
+                            group
+                                .parameters
+                                .iter()
+                                .map(|name| ResourceIr::String(logical_id(name)))
+                                .collect(),
+                        ),
+                    );
+                    ResourceIr::Object(json(), entry)
+                })
+                .collect();
+            object.insert(
+                "ParameterGroups".to_string(),
+                ResourceIr::Array(json(), groups),
+            );
+        }
+        if !self.parameter_labels.is_empty() {
+            let labels = self
+                .parameter_labels
+                .iter()
+                .map(|(name, text)| (logical_id(name), label(text)))
+                .collect();
+            object.insert(
+                "ParameterLabels".to_string(),
+                ResourceIr::Object(json(), labels),
+            );
+        }
+        ResourceIr::Object(json(), object)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,132 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use crate::ir::diagnostics::Diagnostics;
+use crate::ir::CloudformationProgramIr;
+use crate::CloudformationParseTree;
+
+use super::*;
+
+fn translate(template: &str) -> (CloudformationProgramIr, Diagnostics) {
+    let template: CloudformationParseTree = serde_yaml::from_str(template).unwrap();
+    let mut diagnostics = Diagnostics::default();
+    let ir = CloudformationProgramIr::from_with_diagnostics(
+        template,
+        Schema::builtin(),
+        &mut diagnostics,
+    );
+    (ir, diagnostics)
+}
+
+#[test]
+fn template_format_version_and_metadata() {
+    let (ir, diagnostics) = translate(
+        r#"
+        AWSTemplateFormatVersion: 2010-09-09
+        Metadata:
+          Generator: !Sub "${AWS::StackName}-generator"
+        Resources: {}
+        "#,
+    );
+
+    assert!(diagnostics.is_empty(), "{diagnostics}");
+    assert_eq!(ir.template_format_version.as_deref(), Some("2010-09-09"));
+    let metadata = ir.metadata.unwrap();
+    assert_eq!(metadata.interface, None);
+    assert!(matches!(metadata.entries["Generator"], ResourceIr::Sub(_)));
+}
+
+#[test]
+fn interface_keeps_cfn_parameters_only() {
+    let (ir, diagnostics) = translate(
+        r#"
+        Metadata:
+          AWS::CloudFormation::Interface:
+            ParameterGroups:
+              - Label:
+                  default: Network
+                Parameters: [VpcId, Missing]
+              - Label:
+                  default: Naming
+                Parameters: [BucketName]
+            ParameterLabels:
+              VpcId:
+                default: VPC
+              BucketName:
+                default: Bucket name
+        Parameters:
+          VpcId:
+            Type: AWS::EC2::VPC::Id
+          BucketName:
+            Type: String
+        Resources: {}
+        "#,
+    );
+
+    assert_eq!(
+        ir.metadata.unwrap().interface,
+        Some(InterfaceInstruction {
+            parameter_groups: vec![ParameterGroupInstruction {
+                label: Some("Network".into()),
+                parameters: vec!["vpcId".into()],
+            }],
+            parameter_labels: IndexMap::from_iter([("vpcId".to_string(), "VPC".to_string())]),
+        })
+    );
+
+    let diagnostic = diagnostics.iter().next().unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostic.path,
+        "Metadata.AWS::CloudFormation::Interface.ParameterGroups[0].Parameters[1]"
+    );
+    assert!(!diagnostics.has_errors());
+}
+
+#[test]
+fn interface_drops_ssm_lookups() {
+    let (mut ir, _) = translate(
+        r#"
+        Metadata:
+          AWS::CloudFormation::Interface:
+            ParameterLabels:
+              ImageId:
+                default: AMI
+        Parameters:
+          ImageId:
+            Type: AWS::SSM::Parameter::Value<AWS::EC2::Image::Id>
+        Resources: {}
+        "#,
+    );
+
+    assert!(ir.metadata.as_ref().unwrap().interface.is_some());
+    ir.lookup_ssm_parameters();
+    assert!(ir.metadata.unwrap().is_empty());
+}
+
+#[test]
+fn to_ir_uses_logical_ids() {
+    let metadata = MetadataInstruction {
+        interface: Some(InterfaceInstruction {
+            parameter_groups: vec![ParameterGroupInstruction {
+                label: None,
+                parameters: vec!["vpcId".into()],
+            }],
+            parameter_labels: IndexMap::default(),
+        }),
+        entries: IndexMap::from_iter([("Version".to_string(), ResourceIr::Number(2))]),
+    };
+
+    let json = metadata
+        .to_ir(|name| name.to_uppercase())
+        .to_json()
+        .unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "AWS::CloudFormation::Interface": {
+                "ParameterGroups": [{ "Parameters": ["VPCID"] }],
+            },
+            "Version": 2,
+        })
+    );
+}
@@ -1,38 +1,59 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use indexmap::IndexMap;
 
 use crate::cdk::Schema;
 use crate::ir::conditions::ConditionInstruction;
-use crate::ir::constructor::Constructor;
+use crate::ir::constructor::{Constructor, ConstructorType};
+use crate::ir::cross_stack::CrossStackImport;
+use crate::ir::diagnostics::Diagnostics;
 use crate::ir::importer::ImportInstruction;
 use crate::ir::mappings::MappingInstruction;
+use crate::ir::metadata::MetadataInstruction;
+use crate::ir::nested_stacks::NestedStackInstruction;
 use crate::ir::outputs::OutputInstruction;
 use crate::ir::resources::ResourceInstruction;
-use crate::{CloudformationParseTree, Error};
+use crate::ir::rules::RuleInstruction;
+use crate::{CloudformationParseTree, Error, Hasher};
 
 use self::reference::{Origin, PseudoParameter};
 
 pub mod conditions;
 pub mod constructor;
+pub mod constructs;
+pub mod cross_stack;
+pub mod diagnostics;
 pub mod importer;
 pub mod mappings;
+pub mod metadata;
+pub mod nested_stacks;
 pub mod outputs;
+pub mod prune;
 pub mod reference;
 pub mod resources;
+pub mod rules;
+pub mod simplify;
 pub mod sub;
+pub mod validation;
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct CloudformationProgramIr {
+    pub template_format_version: Option<String>,
     pub description: Option<String>,
+    pub metadata: Option<MetadataInstruction>,
     pub transforms: Vec<String>,
 
     pub imports: Vec<ImportInstruction>,
     pub constructor: Constructor,
     pub conditions: Vec<ConditionInstruction>,
     pub mappings: Vec<MappingInstruction>,
+    pub rules: Vec<RuleInstruction>,
     pub resources: Vec<ResourceInstruction>,
     pub outputs: Vec<OutputInstruction>,
+    // Set by `cross_stack::link_stacks`: the props that receive an output of another stack.
+    pub cross_stack_imports: Vec<CrossStackImport>,
 }
 
 impl CloudformationProgramIr {
@@ -43,24 +64,140 @@ impl CloudformationProgramIr {
         parse_tree: CloudformationParseTree,
         schema: &Schema,
     ) -> Result<CloudformationProgramIr, Error> {
-        let origins = ReferenceOrigins::new(&parse_tree);
+        let mut diagnostics = Diagnostics::default();
+        let ir = Self::from_with_diagnostics(parse_tree, schema, &mut diagnostics);
+        if diagnostics.has_errors() {
+            return Err(Error::InvalidTemplate { diagnostics });
+        }
+        Ok(ir)
+    }
+
+    // from_with_diagnostics translates as much of the parse tree as possible, recording every
+    // semantic problem it runs into in `diagnostics` instead of stopping at the first one. The
+    // returned IR is only meaningful if no errors were recorded.
+    pub fn from_with_diagnostics(
+        parse_tree: CloudformationParseTree,
+        schema: &Schema,
+        diagnostics: &mut Diagnostics,
+    ) -> CloudformationProgramIr {
+        Self::from_with_nested_stacks(parse_tree, schema, HashMap::new(), diagnostics)
+    }
+
+    // from_with_nested_stacks is from_with_diagnostics for a template whose nested stacks were
+    // converted as well: `nested_stacks` holds the program of some `AWS::CloudFormation::Stack`
+    // resources, by logical ID. These resources become instances of a `cdk.NestedStack` subclass.
+    pub fn from_with_nested_stacks(
+        parse_tree: CloudformationParseTree,
+        schema: &Schema,
+        mut nested_stacks: HashMap<String, CloudformationProgramIr>,
+        diagnostics: &mut Diagnostics,
+    ) -> CloudformationProgramIr {
+        nested_stacks.retain(|name, _| {
+            let Some(resource) = parse_tree
+                .resources
+                .get(name)
+                .filter(|resource| resource.resource_type == "AWS::CloudFormation::Stack")
+            else {
+                diagnostics.error(
+                    format!("Resources.{name}"),
+                    format!("{name} is not an AWS::CloudFormation::Stack resource"),
+                );
+                return false;
+            };
+            // A NestedStack is always created, and has no update policy, so these resources are
+            // kept as a CfnStack.
+            for (attribute, present) in [
+                ("Condition", resource.condition.is_some()),
+                ("UpdatePolicy", resource.update_policy.is_some()),
+            ] {
+                if present {
+                    diagnostics.warning(
+                        format!("Resources.{name}.{attribute}"),
+                        format!(
+                            "{attribute} is not supported on nested stacks, so {name} is kept as a CfnStack"
+                        ),
+                    );
+                    return false;
+                }
+            }
+            true
+        });
+
+        let origins = ReferenceOrigins::new(&parse_tree).with_nested_stacks(&nested_stacks);
+        let constructor = Constructor::from(parse_tree.parameters, diagnostics);
+        // Invalid resource type names are reported by ResourceInstruction::from
+        let mut imports = ImportInstruction::from(&parse_tree.resources).unwrap_or_default();
+        let mut resources =
+            ResourceInstruction::from(parse_tree.resources, schema, &origins, diagnostics);
+        NestedStackInstruction::nest(&mut resources, nested_stacks, &mut imports, diagnostics);
 
-        Ok(CloudformationProgramIr {
+        CloudformationProgramIr {
+            template_format_version: parse_tree.aws_template_format_version,
             description: parse_tree.description,
+            metadata: MetadataInstruction::from(
+                parse_tree.metadata,
+                &constructor,
+                schema,
+                &origins,
+                diagnostics,
+            ),
             transforms: parse_tree.transforms,
-            conditions: ConditionInstruction::from(parse_tree.conditions),
-            imports: ImportInstruction::from(&parse_tree.resources)?,
-            constructor: Constructor::from(parse_tree.parameters),
+            conditions: ConditionInstruction::from(parse_tree.conditions, diagnostics),
+            imports,
+            constructor,
             mappings: MappingInstruction::from(parse_tree.mappings),
-            resources: ResourceInstruction::from(parse_tree.resources, schema, &origins)?,
-            outputs: OutputInstruction::from(parse_tree.outputs, schema, &origins)?,
-        })
+            rules: RuleInstruction::from(parse_tree.rules, &origins, diagnostics),
+            resources,
+            outputs: OutputInstruction::from(parse_tree.outputs, schema, &origins, diagnostics),
+            cross_stack_imports: Vec::new(),
+        }
+    }
+
+    // lookup_ssm_parameters makes the synthesizers resolve `AWS::SSM::Parameter::Value<String>`
+    // (and similar single-valued) parameters using `ssm.StringParameter.valueForStringParameter`,
+    // rather than declaring a CfnParameter for them. The parameter then holds the name of the SSM
+    // parameter to read.
+    pub fn lookup_ssm_parameters(&mut self) {
+        let mut found = false;
+        for input in &mut self.constructor.inputs {
+            if input.constructor_type.is_ssm_string() {
+                input.ssm_lookup = true;
+                found = true;
+                // The parameter no longer exists in the synthesized template.
+                if let Some(interface) = self
+                    .metadata
+                    .as_mut()
+                    .and_then(|metadata| metadata.interface.as_mut())
+                {
+                    interface.remove_parameter(&input.name);
+                }
+            }
+        }
+
+        let ssm = ImportInstruction {
+            organization: "AWS".into(),
+            service: Some("SSM".into()),
+        };
+        if let Some(metadata) = &mut self.metadata {
+            if metadata.interface.as_ref().is_some_and(|i| i.is_empty()) {
+                metadata.interface = None;
+            }
+        }
+        if found && !self.imports.contains(&ssm) {
+            self.imports.push(ssm);
+            self.imports
+                .sort_by(|left, right| left.service.cmp(&right.service));
+        }
     }
 }
 
 #[derive(Debug)]
 struct ReferenceOrigins {
     origins: HashMap<String, Origin>,
+    // The outputs of each nested stack, and whether they are conditional
+    nested_stack_outputs: HashMap<String, HashMap<String, bool>>,
+    // The CloudFormation type of each resource, used to check the attributes it is asked for
+    resource_types: HashMap<String, String>,
 }
 
 impl ReferenceOrigins {
@@ -68,10 +205,11 @@ impl ReferenceOrigins {
         let mut origins = HashMap::default();
 
         origins.extend(parse_tree.parameters.iter().map(|(name, param)| {
-            if param
-                .no_echo
-                .as_ref()
-                .is_some_and(|x| x.to_lowercase() == "true")
+            if ConstructorType::from(&param.parameter_type).is_aws_specific()
+                || param
+                    .no_echo
+                    .as_ref()
+                    .is_some_and(|x| x.to_lowercase() == "true")
             {
                 (name.clone(), Origin::CfnParameter)
             } else {
@@ -88,7 +226,71 @@ impl ReferenceOrigins {
             )
         }));
 
-        Self { origins }
+        let resource_types = parse_tree
+            .resources
+            .iter()
+            .map(|(name, res)| (name.clone(), res.resource_type.clone()))
+            .collect();
+
+        Self {
+            origins,
+            nested_stack_outputs: HashMap::default(),
+            resource_types,
+        }
+    }
+
+    fn with_nested_stacks(
+        mut self,
+        nested_stacks: &HashMap<String, CloudformationProgramIr>,
+    ) -> Self {
+        for (name, program) in nested_stacks {
+            // Conditions are not supported on nested stacks, see NestedStackInstruction::nest
+            self.origins
+                .insert(name.clone(), Origin::LogicalId { conditional: false });
+            self.nested_stack_outputs.insert(
+                name.clone(),
+                program
+                    .outputs
+                    .iter()
+                    .map(|output| (output.name.clone(), output.condition.is_some()))
+                    .collect(),
+            );
+        }
+        self
+    }
+
+    // The origin of the `attribute` of a nested stack, which only has its outputs as attributes,
+    // or None if `logical_id` is not a nested stack.
+    fn for_nested_stack_attribute(
+        &self,
+        logical_id: &str,
+        attribute: &str,
+    ) -> Result<Option<Origin>, Error> {
+        let Some(outputs) = self.nested_stack_outputs.get(logical_id) else {
+            return Ok(None);
+        };
+        attribute
+            .strip_prefix("Outputs.")
+            .and_then(|output| {
+                outputs
+                    .get(output)
+                    .map(|conditional| Origin::NestedStackOutput {
+                        output: output.into(),
+                        conditional: *conditional,
+                    })
+            })
+            .map(Some)
+            .ok_or_else(|| Error::ResourceTranslationError {
+                message: format!("{attribute} is not an output of the nested stack {logical_id}"),
+            })
+    }
+
+    fn resource_type(&self, logical_id: &str) -> Option<&str> {
+        self.resource_types.get(logical_id).map(String::as_str)
+    }
+
+    fn is_nested_stack(&self, logical_id: &str) -> bool {
+        self.nested_stack_outputs.contains_key(logical_id)
     }
 
     fn for_ref(&self, ref_name: &str) -> Option<Origin> {
@@ -108,3 +310,24 @@ impl ReferenceOrigins {
             .unwrap_or(false)
     }
 }
+
+/// Extracts one dependency cycle from a graph that could not be fully sorted. Every node that is
+/// not in `sorted` still waits on another unsorted node, so following those edges must eventually
+/// loop back on itself. The returned path starts and ends with the same node.
+fn find_cycle(
+    dependencies: &IndexMap<&str, BTreeSet<&str>, Hasher>,
+    sorted: &HashSet<&str>,
+) -> Vec<String> {
+    let mut path: Vec<&str> = Vec::new();
+    let mut current = dependencies.keys().find(|name| !sorted.contains(*name));
+    while let Some(&name) = current {
+        if let Some(start) = path.iter().position(|&visited| visited == name) {
+            path.drain(..start);
+            path.push(name);
+            break;
+        }
+        path.push(name);
+        current = dependencies[name].iter().find(|dep| !sorted.contains(*dep));
+    }
+    path.into_iter().map(ToString::to_string).collect()
+}
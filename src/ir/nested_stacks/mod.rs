@@ -0,0 +1,237 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+use voca_rs::case::{camel_case, pascal_case};
+
+use crate::cdk::{Primitive, TypeReference};
+use crate::ir::constructor::{ConstructorParameter, ConstructorType};
+use crate::ir::importer::ImportInstruction;
+use crate::ir::resources::{ResourceInstruction, ResourceIr, ResourceType};
+use crate::ir::CloudformationProgramIr;
+use crate::primitives::WrapperF64;
+use crate::{Error, Hasher};
+
+use super::diagnostics::Diagnostics;
+
+// NestedStackInstruction is an `AWS::CloudFormation::Stack` resource whose template was converted
+// as well, so that it is emitted as a subclass of `cdk.NestedStack` instead of a `CfnStack`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NestedStackInstruction {
+    pub class_name: String,
+    pub program: Box<CloudformationProgramIr>,
+    // The value of each parameter of the nested stack, by the name of the constructor parameter
+    // it is passed to. Values are shaped after the parameter's type (e.g: lists are arrays).
+    pub parameters: IndexMap<String, ResourceIr, Hasher>,
+}
+
+impl NestedStackInstruction {
+    // The name of the class generated for the nested stack with the given logical ID.
+    pub fn class_name(logical_id: &str) -> String {
+        let name = pascal_case(logical_id);
+        let name = name.strip_suffix("Stack").unwrap_or(&name);
+        format!("{name}NestedStack")
+    }
+
+    // nest turns the resources named in `nested_stacks` into nested stacks, passing their
+    // `Parameters` to the constructor of the nested stack's class. Their other attributes are set
+    // on the CfnStack resource of the nested stack by the synthesizers. The imports of the nested
+    // stacks are added to `imports`, since all classes are emitted in the same file.
+    pub(super) fn nest(
+        resources: &mut [ResourceInstruction],
+        mut nested_stacks: HashMap<String, CloudformationProgramIr>,
+        imports: &mut Vec<ImportInstruction>,
+        diagnostics: &mut Diagnostics,
+    ) {
+        let mut class_names: Vec<String> = Vec::new();
+        for resource in resources.iter_mut() {
+            let Some(program) = nested_stacks.remove(&resource.name) else {
+                continue;
+            };
+            let path = format!("Resources.{}", resource.name);
+
+            let class_name = Self::class_name(&resource.name);
+            for name in std::iter::once(class_name.clone()).chain(program.nested_class_names()) {
+                if class_names.contains(&name) {
+                    diagnostics.error(
+                        &path,
+                        format!("the class {name} of this nested stack is already declared"),
+                    );
+                }
+                class_names.push(name);
+            }
+
+            let mut parameters = IndexMap::default();
+            for (name, value) in std::mem::take(&mut resource.properties) {
+                let prop_path = format!("{path}.Properties.{name}");
+                match (name.as_str(), value) {
+                    ("TemplateURL", _) => {}
+                    ("Parameters", ResourceIr::Object(_, values)) => {
+                        for (name, value) in values {
+                            let param_name = camel_case(&name);
+                            let Some(param) = program
+                                .constructor
+                                .inputs
+                                .iter()
+                                .find(|param| param.name == param_name)
+                            else {
+                                diagnostics.error(
+                                    format!("{prop_path}.{name}"),
+                                    format!("{name} is not a parameter of the nested stack"),
+                                );
+                                continue;
+                            };
+                            match shape_parameter(value, param) {
+                                Ok(value) => {
+                                    parameters.insert(param_name, value);
+                                }
+                                Err(err) => diagnostics.report(format!("{prop_path}.{name}"), err),
+                            }
+                        }
+                    }
+                    ("Parameters", _) => {
+                        diagnostics.error(
+                            prop_path,
+                            "the parameters of a nested stack must be listed one by one",
+                        );
+                    }
+                    (name, _) => diagnostics.warning(
+                        prop_path,
+                        format!("{name} is not carried over to the nested stack"),
+                    ),
+                }
+            }
+            for param in &program.constructor.inputs {
+                if param.default_value.is_none() && !parameters.contains_key(&param.name) {
+                    diagnostics.error(
+                        format!("{path}.Properties.Parameters"),
+                        format!(
+                            "the nested stack's {} parameter has no default value, so it must be passed",
+                            pascal_case(&param.name)
+                        ),
+                    );
+                }
+            }
+
+            for import in &program.imports {
+                if !imports.contains(import) {
+                    imports.push(import.clone());
+                }
+            }
+
+            resource.nested_stack = Some(Self {
+                class_name,
+                program: Box::new(program),
+                parameters,
+            });
+        }
+
+        // The CfnStack constructs are gone, so their module may no longer be used.
+        let uses_cloudformation = resources.iter().any(|resource| {
+            resource.nested_stack.is_none()
+                && matches!(&resource.resource_type, ResourceType::AWS { service, .. } if service == "CloudFormation")
+        });
+        if !uses_cloudformation {
+            imports.retain(|import| import.service.as_deref() != Some("CloudFormation"));
+        }
+        imports.sort_by(|left, right| left.service.cmp(&right.service));
+    }
+}
+
+impl CloudformationProgramIr {
+    // The classes of all nested stacks within this program, at any depth.
+    fn nested_class_names(&self) -> Vec<String> {
+        self.resources
+            .iter()
+            .filter_map(|resource| resource.nested_stack.as_ref())
+            .flat_map(|nested| {
+                std::iter::once(nested.class_name.clone())
+                    .chain(nested.program.nested_class_names())
+            })
+            .collect()
+    }
+
+    // The programs of all nested stacks within this program, children before their parents, so
+    // that synthesizers can declare their classes before they are used.
+    pub fn nested_stacks(&self) -> Vec<&NestedStackInstruction> {
+        let mut nested_stacks = Vec::new();
+        for nested in self
+            .resources
+            .iter()
+            .filter_map(|resource| resource.nested_stack.as_ref())
+        {
+            nested_stacks.extend(nested.program.nested_stacks());
+            nested_stacks.push(nested);
+        }
+        nested_stacks
+    }
+}
+
+// Nested stack parameters are strings in the template, while the props of the nested stack's
+// class are typed after the parameter (see the synthesizers), so literal values are converted to
+// that type. Lists that are only known at deploy time are split.
+fn shape_parameter(value: ResourceIr, param: &ConstructorParameter) -> Result<ResourceIr, Error> {
+    let invalid = |expected: &str| Error::ResourceTranslationError {
+        message: format!(
+            "expected {expected} for a {} parameter",
+            param.constructor_type
+        ),
+    };
+    let number = |item: &str| {
+        let item = item.trim();
+        match item.parse::<i64>() {
+            Ok(number) => Ok(ResourceIr::Number(number)),
+            Err(_) => item
+                .parse::<f64>()
+                .map(|double| ResourceIr::Double(WrapperF64::new(double)))
+                .map_err(|_| invalid("a literal list of numbers")),
+        }
+    };
+    let string_list = |items| ResourceIr::Array(TypeReference::Primitive(Primitive::String), items);
+
+    match (&param.constructor_type, value) {
+        (ConstructorType::Boolean, ResourceIr::Bool(value)) => Ok(ResourceIr::Bool(value)),
+        (ConstructorType::Boolean, ResourceIr::String(value)) => {
+            match value.to_ascii_lowercase().as_str() {
+                "true" => Ok(ResourceIr::Bool(true)),
+                "false" => Ok(ResourceIr::Bool(false)),
+                _ => Err(invalid("true or false")),
+            }
+        }
+        (ConstructorType::Boolean, _) => Err(invalid("true or false")),
+        (ConstructorType::ListOfNumbers, ResourceIr::String(value)) => Ok(ResourceIr::Array(
+            TypeReference::Primitive(Primitive::Number),
+            value.split(',').map(number).collect::<Result<_, _>>()?,
+        )),
+        (ConstructorType::ListOfNumbers, ResourceIr::Array(_, items))
+            if items
+                .iter()
+                .all(|item| matches!(item, ResourceIr::Number(_) | ResourceIr::Double(_))) =>
+        {
+            Ok(ResourceIr::Array(
+                TypeReference::Primitive(Primitive::Number),
+                items,
+            ))
+        }
+        (ConstructorType::ListOfNumbers, _) => Err(invalid("a literal list of numbers")),
+        (t, ResourceIr::String(value)) if t.is_list() => Ok(string_list(
+            value
+                .split(',')
+                .map(|item| ResourceIr::String(item.trim().into()))
+                .collect(),
+        )),
+        (t, ResourceIr::Join(separator, items)) if t.is_list() && separator == "," => {
+            Ok(string_list(items))
+        }
+        (t, value @ ResourceIr::Array(..)) if t.is_list() => Ok(value),
+        (t, value) if t.is_list() => Ok(ResourceIr::Split(",".into(), Box::new(value))),
+        (_, ResourceIr::Number(value)) => Ok(ResourceIr::String(value.to_string())),
+        (_, ResourceIr::Double(value)) => Ok(ResourceIr::String(f64::from(value).to_string())),
+        (_, ResourceIr::Bool(value)) => Ok(ResourceIr::String(value.to_string())),
+        (_, value) => Ok(value),
+    }
+}
+
+#[cfg(test)]
+mod tests;
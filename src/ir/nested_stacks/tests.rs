@@ -0,0 +1,292 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use crate::cdk::Schema;
+use crate::ir::diagnostics::Severity;
+use crate::ir::reference::{Origin, Reference};
+use crate::parser::resource::DeletionPolicy;
+use crate::CloudformationParseTree;
+
+use super::*;
+
+const CHILD: &str = r#"
+    Parameters:
+      VpcId:
+        Type: String
+      Subnets:
+        Type: CommaDelimitedList
+        Default: a,b
+      Public:
+        Type: String
+        AllowedValues: ["true", "false"]
+        Default: "false"
+    Conditions:
+      IsPublic: !Equals [!Ref Public, "true"]
+    Resources:
+      Group:
+        Type: AWS::EC2::SecurityGroup
+        Properties:
+          GroupDescription: test
+          VpcId: !Ref VpcId
+    Outputs:
+      GroupId:
+        Value: !GetAtt Group.GroupId
+      PublicGroupId:
+        Condition: IsPublic
+        Value: !GetAtt Group.GroupId
+"#;
+
+fn convert(parent: &str) -> (CloudformationProgramIr, Diagnostics) {
+    let child: CloudformationParseTree = serde_yaml::from_str(CHILD).unwrap();
+    let child = CloudformationProgramIr::from(child, Schema::builtin()).unwrap();
+    let parent: CloudformationParseTree = serde_yaml::from_str(parent).unwrap();
+    let mut diagnostics = Diagnostics::default();
+    let ir = CloudformationProgramIr::from_with_nested_stacks(
+        parent,
+        Schema::builtin(),
+        HashMap::from([("Network".to_string(), child)]),
+        &mut diagnostics,
+    );
+    (ir, diagnostics)
+}
+
+#[test]
+fn nested_stack() {
+    let (ir, diagnostics) = convert(
+        r#"
+        Resources:
+          Vpc:
+            Type: AWS::EC2::VPC
+            Properties:
+              CidrBlock: 10.0.0.0/16
+          Network:
+            Type: AWS::CloudFormation::Stack
+            Properties:
+              TemplateURL: ./network.yaml
+              Parameters:
+                VpcId: !Ref Vpc
+                Subnets: !Join [",", [x, y]]
+                Public: true
+          Queue:
+            Type: AWS::SQS::Queue
+            Properties:
+              QueueName: !GetAtt Network.Outputs.GroupId
+        "#,
+    );
+    assert!(diagnostics.is_empty(), "{diagnostics}");
+
+    let network = ir.resources[1].nested_stack.as_ref().unwrap();
+    assert_eq!(network.class_name, "NetworkNestedStack");
+    assert_eq!(network.program.resources[0].name, "Group");
+    assert!(ir.resources[1].properties.is_empty());
+    assert_eq!(
+        network.parameters,
+        IndexMap::<_, _, Hasher>::from_iter([
+            (
+                "vpcId".to_string(),
+                ResourceIr::Ref(Reference::new(
+                    "Vpc",
+                    Origin::LogicalId { conditional: false }
+                ))
+            ),
+            (
+                "subnets".to_string(),
+                ResourceIr::Array(
+                    TypeReference::Primitive(Primitive::String),
+                    vec![
+                        ResourceIr::String("x".into()),
+                        ResourceIr::String("y".into())
+                    ]
+                )
+            ),
+            ("public".to_string(), ResourceIr::Bool(true)),
+        ])
+    );
+
+    // The queue depends on the nested stack through its output
+    assert_eq!(ir.resources[2].name, "Queue");
+    assert_eq!(
+        ir.resources[2].properties["QueueName"],
+        ResourceIr::Ref(Reference::new(
+            "Network",
+            Origin::NestedStackOutput {
+                output: "GroupId".into(),
+                conditional: false
+            }
+        ))
+    );
+
+    // The CfnStack is gone, and the nested stack's imports are needed by its class
+    let services: Vec<_> = ir
+        .imports
+        .iter()
+        .map(|import| import.service.as_deref())
+        .collect();
+    assert_eq!(services, vec![None, Some("EC2"), Some("SQS")]);
+    assert_eq!(ir.nested_stacks().len(), 1);
+}
+
+#[test]
+fn conditional_output() {
+    let (ir, diagnostics) = convert(
+        r#"
+        Resources:
+          Network:
+            Type: AWS::CloudFormation::Stack
+            Properties:
+              TemplateURL: ./network.yaml
+              Parameters:
+                VpcId: vpc-1234
+        Outputs:
+          GroupId:
+            Value: !Sub "${Network.Outputs.PublicGroupId}"
+        "#,
+    );
+    assert!(diagnostics.is_empty(), "{diagnostics}");
+    assert_eq!(
+        ir.outputs[0].value,
+        ResourceIr::Sub(vec![ResourceIr::Ref(Reference::new(
+            "Network",
+            Origin::NestedStackOutput {
+                output: "PublicGroupId".into(),
+                conditional: true
+            }
+        ))])
+    );
+}
+
+#[test]
+fn diagnostics() {
+    let (_, diagnostics) = convert(
+        r#"
+        Resources:
+          Network:
+            Type: AWS::CloudFormation::Stack
+            Properties:
+              TemplateURL: ./network.yaml
+              TimeoutInMinutes: 5
+              Parameters:
+                Public: maybe
+                Unknown: value
+          Queue:
+            Type: AWS::SQS::Queue
+            Properties:
+              QueueName: !GetAtt Network.Outputs.Missing
+              DelaySeconds: !Ref Network
+        "#,
+    );
+
+    let entries: Vec<_> = diagnostics
+        .iter()
+        .map(|d| (d.severity, d.path.as_str()))
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            (Severity::Error, "Resources.Queue.Properties.QueueName"),
+            (Severity::Error, "Resources.Queue.Properties.DelaySeconds"),
+            (
+                Severity::Warning,
+                "Resources.Network.Properties.TimeoutInMinutes"
+            ),
+            (
+                Severity::Error,
+                "Resources.Network.Properties.Parameters.Public"
+            ),
+            (
+                Severity::Error,
+                "Resources.Network.Properties.Parameters.Unknown"
+            ),
+            (Severity::Error, "Resources.Network.Properties.Parameters"),
+        ]
+    );
+}
+
+#[test]
+fn attributes() {
+    let (ir, diagnostics) = convert(
+        r#"
+        Resources:
+          Network:
+            Type: AWS::CloudFormation::Stack
+            DeletionPolicy: Retain
+            Metadata:
+              Owner: team-a
+            Properties:
+              TemplateURL: ./network.yaml
+              Parameters:
+                VpcId: vpc-1
+        "#,
+    );
+    assert!(diagnostics.is_empty(), "{diagnostics}");
+
+    // The attributes are kept, to be set on the CfnStack of the nested stack.
+    let network = &ir.resources[0];
+    assert!(network.nested_stack.is_some());
+    assert_eq!(network.deletion_policy, Some(DeletionPolicy::Retain));
+    assert!(network.metadata.is_some());
+}
+
+#[test]
+fn conditional_stack() {
+    let (ir, diagnostics) = convert(
+        r#"
+        Conditions:
+          IsProd: !Equals [!Ref AWS::AccountId, "123456789012"]
+        Resources:
+          Network:
+            Type: AWS::CloudFormation::Stack
+            Condition: IsProd
+            Properties:
+              TemplateURL: ./network.yaml
+          Queue:
+            Type: AWS::SQS::Queue
+            Properties:
+              QueueName: !GetAtt Network.Outputs.GroupId
+        "#,
+    );
+
+    // A nested stack is always created, so the resource is kept as a CfnStack.
+    let network = &ir.resources[0];
+    assert!(network.nested_stack.is_none());
+    assert_eq!(network.condition.as_deref(), Some("IsProd"));
+    let entries: Vec<_> = diagnostics
+        .iter()
+        .map(|d| (d.severity, d.path.as_str(), d.message.as_str()))
+        .collect();
+    assert_eq!(
+        entries,
+        vec![(
+            Severity::Warning,
+            "Resources.Network.Condition",
+            "Condition is not supported on nested stacks, so Network is kept as a CfnStack"
+        )]
+    );
+}
+
+#[test]
+fn not_a_stack() {
+    let (ir, diagnostics) = convert(
+        r#"
+        Resources:
+          Network:
+            Type: AWS::SQS::Queue
+        "#,
+    );
+    assert!(ir.resources[0].nested_stack.is_none());
+    assert_eq!(
+        diagnostics.iter().next().unwrap().message,
+        "Network is not an AWS::CloudFormation::Stack resource"
+    );
+}
+
+#[test]
+fn class_names() {
+    assert_eq!(
+        NestedStackInstruction::class_name("Network"),
+        "NetworkNestedStack"
+    );
+    assert_eq!(
+        NestedStackInstruction::class_name("DatabaseStack"),
+        "DatabaseNestedStack"
+    );
+}
@@ -7,17 +7,19 @@ use crate::ir::resources::{ResourceIr, ResourceTranslator};
 use crate::parser::output::Output;
 use crate::parser::resource::ResourceValue;
 use crate::util::Hasher;
-use crate::Error;
 
+use super::diagnostics::Diagnostics;
 use super::ReferenceOrigins;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct OutputInstruction {
     pub name: String,
     pub export: Option<ResourceIr>,
     pub value: ResourceIr,
     pub condition: Option<String>,
     pub description: Option<String>,
+    // The comments of the output in a YAML template, emitted along with its description.
+    pub comments: Vec<String>,
 }
 
 impl OutputInstruction {
@@ -25,24 +27,40 @@ impl OutputInstruction {
         parse_tree: IndexMap<String, Output, Hasher>,
         schema: &Schema,
         origins: &ReferenceOrigins,
-    ) -> Result<Vec<Self>, Error> {
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<Self> {
         let mut list = Vec::with_capacity(parse_tree.len());
 
         for (name, output) in parse_tree {
+            let path = format!("Outputs.{name}");
             let resource_translator = ResourceTranslator {
                 schema,
                 origins,
                 value_type: Some(TypeReference::Primitive(Primitive::Json)),
             };
 
-            let value = resource_translator.translate(output.value)?;
+            let value = match resource_translator.translate(output.value) {
+                Ok(value) => value,
+                Err(err) => {
+                    diagnostics.report(format!("{path}.Value"), err);
+                    continue;
+                }
+            };
             let condition = output.condition;
             let description = output.description;
             let mut export: Option<ResourceIr> = None;
-            if let Some(ResourceValue::Object(x)) = output.export {
-                if let Some(x) = x.get_key_value("Name") {
-                    export = Some(resource_translator.translate(x.1.clone())?);
+            match output.export {
+                Some(ResourceValue::Object(x)) if x.contains_key("Name") => {
+                    match resource_translator.translate(x["Name"].clone()) {
+                        Ok(name) => export = Some(name),
+                        Err(err) => diagnostics.report(format!("{path}.Export.Name"), err),
+                    }
                 }
+                Some(_) => diagnostics.warning(
+                    format!("{path}.Export"),
+                    "Export has no Name and will be ignored",
+                ),
+                None => {}
             }
 
             list.push(Self {
@@ -51,10 +69,11 @@ impl OutputInstruction {
                 value,
                 condition,
                 description,
+                comments: output.comments,
             })
         }
 
-        Ok(list)
+        list
     }
 }
 
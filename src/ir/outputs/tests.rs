@@ -11,16 +11,48 @@ pub fn none() {
             IndexMap::default(),
             Schema::builtin(),
             &ReferenceOrigins::new(&CloudformationParseTree {
+                aws_template_format_version: None,
                 description: None,
+                metadata: None,
                 transforms: vec![],
                 conditions: IndexMap::default(),
                 mappings: IndexMap::default(),
                 outputs: IndexMap::default(),
                 parameters: IndexMap::default(),
+                rules: IndexMap::default(),
                 resources: IndexMap::default()
-            })
-        )
-        .unwrap(),
+            }),
+            &mut Diagnostics::default(),
+        ),
         vec![]
     );
 }
+
+#[test]
+pub fn export_without_name_is_a_warning() {
+    let template: CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Resources: {}
+        Outputs:
+          Out:
+            Value: hello
+            Export:
+              Nom: typo
+        "#,
+    )
+    .unwrap();
+
+    let mut diagnostics = Diagnostics::default();
+    let outputs = OutputInstruction::from(
+        template.outputs.clone(),
+        Schema::builtin(),
+        &ReferenceOrigins::new(&template),
+        &mut diagnostics,
+    );
+
+    assert_eq!(outputs.len(), 1);
+    assert_eq!(outputs[0].export, None);
+    assert!(!diagnostics.has_errors());
+    let warning = diagnostics.iter().next().unwrap();
+    assert_eq!(warning.path, "Outputs.Out.Export");
+}
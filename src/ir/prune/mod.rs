@@ -0,0 +1,154 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use std::collections::HashSet;
+use std::mem;
+
+use voca_rs::case::camel_case;
+
+use crate::ir::conditions::ConditionIr;
+use crate::ir::diagnostics::Diagnostics;
+use crate::ir::reference::Origin;
+use crate::ir::resources::ResourceIr;
+use crate::ir::rules::{RuleIr, RuleValueIr};
+use crate::ir::simplify::{visit, visit_condition};
+use crate::ir::CloudformationProgramIr;
+
+impl CloudformationProgramIr {
+    // prune removes the conditions, mappings and parameters that are not reachable from the
+    // resources, outputs, metadata and rules of the template, and reports each of them as a
+    // warning. Nested stacks are pruned too, and their parent stops passing the parameters they
+    // no longer have.
+    pub fn prune(&mut self, diagnostics: &mut Diagnostics) {
+        for resource in &mut self.resources {
+            let Some(nested) = &mut resource.nested_stack else {
+                continue;
+            };
+            let mut nested_diagnostics = Diagnostics::default();
+            nested.program.prune(&mut nested_diagnostics);
+            // The paths of these warnings are in the nested template, not in this one.
+            for diagnostic in nested_diagnostics.iter() {
+                diagnostics.warning(
+                    format!("Resources.{}", resource.name),
+                    format!(
+                        "{} in its template: {}",
+                        diagnostic.path, diagnostic.message
+                    ),
+                );
+            }
+            let inputs = &nested.program.constructor.inputs;
+            nested
+                .parameters
+                .retain(|name, _| inputs.iter().any(|input| input.name == *name));
+        }
+
+        // Conditions may be all that uses a mapping or a parameter, so they go first.
+        for name in self.remove_unused_conditions() {
+            diagnostics.warning(
+                format!("Conditions.{name}"),
+                "unused condition, it was removed",
+            );
+        }
+        for name in self.remove_unused_mappings() {
+            diagnostics.warning(format!("Mappings.{name}"), "unused mapping, it was removed");
+        }
+        // Inputs are named after their prop, from which the logical ID can't be told.
+        for name in self.remove_unused_parameters() {
+            diagnostics.warning(
+                "Parameters",
+                format!("unused parameter {name}, it was removed"),
+            );
+        }
+    }
+
+    // Removes the parameters nothing refers to, and returns their names. Unlike references, inputs
+    // are named after the prop they become.
+    fn remove_unused_parameters(&mut self) -> Vec<String> {
+        let mut used: HashSet<String> = self
+            .cross_stack_imports
+            .iter()
+            .map(|import| import.parameter.clone())
+            .collect();
+        for condition in &self.conditions {
+            visit_condition(&condition.value, &mut |value| {
+                if let ConditionIr::Ref(reference) = value {
+                    used.insert(camel_case(&reference.name));
+                }
+            });
+        }
+        for rule in &self.rules {
+            let assertions = rule.assertions.iter().map(|assertion| &assertion.assert);
+            for rule in rule.condition.iter().chain(assertions) {
+                rule_parameters(rule, &mut used);
+            }
+        }
+        let mut values = Vec::new();
+        self.values_mut(&mut values, true);
+        for value in values {
+            visit(value, &mut |value| {
+                if let ResourceIr::Ref(reference) = value {
+                    if matches!(reference.origin, Origin::Parameter | Origin::CfnParameter) {
+                        used.insert(camel_case(&reference.name));
+                    }
+                }
+            });
+        }
+
+        let (kept, removed): (Vec<_>, Vec<_>) = mem::take(&mut self.constructor.inputs)
+            .into_iter()
+            .partition(|input| used.contains(&input.name));
+        self.constructor.inputs = kept;
+
+        let removed: Vec<String> = removed.into_iter().map(|input| input.name).collect();
+        if let Some(metadata) = &mut self.metadata {
+            if let Some(interface) = &mut metadata.interface {
+                for name in &removed {
+                    interface.remove_parameter(name);
+                }
+                if interface.is_empty() {
+                    metadata.interface = None;
+                }
+            }
+        }
+        removed
+    }
+}
+
+// Adds the parameters `rule` refers to to `used`.
+fn rule_parameters(rule: &RuleIr, used: &mut HashSet<String>) {
+    match rule {
+        RuleIr::And(operands) | RuleIr::Or(operands) => {
+            for operand in operands {
+                rule_parameters(operand, used);
+            }
+        }
+        RuleIr::Not(operand) => rule_parameters(operand, used),
+        RuleIr::Equals(x, y)
+        | RuleIr::Contains(x, y)
+        | RuleIr::EachMemberEquals(x, y)
+        | RuleIr::EachMemberIn(x, y) => {
+            rule_value_parameters(x, used);
+            rule_value_parameters(y, used);
+        }
+    }
+}
+
+fn rule_value_parameters(value: &RuleValueIr, used: &mut HashSet<String>) {
+    match value {
+        RuleValueIr::List(items) => {
+            for item in items {
+                rule_value_parameters(item, used);
+            }
+        }
+        RuleValueIr::Ref(reference) => {
+            used.insert(camel_case(&reference.name));
+        }
+        RuleValueIr::ValueOf(name, _) => {
+            used.insert(camel_case(name));
+        }
+        // These apply to every parameter of a type, which does not keep any of them alive.
+        RuleValueIr::Str(_) | RuleValueIr::RefAll(_) | RuleValueIr::ValueOfAll(..) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,180 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use std::collections::HashMap;
+
+use crate::cdk::Schema;
+use crate::ir::diagnostics::Severity;
+use crate::CloudformationParseTree;
+
+use super::*;
+
+fn program(source: &str) -> CloudformationProgramIr {
+    let template: CloudformationParseTree = serde_yaml::from_str(source).unwrap();
+    CloudformationProgramIr::from(template, Schema::builtin()).unwrap()
+}
+
+#[test]
+fn unreachable_items() {
+    let mut ir = program(
+        r#"
+        Metadata:
+          AWS::CloudFormation::Interface:
+            ParameterGroups:
+              - Label: { default: Settings }
+                Parameters: [Unused]
+        Parameters:
+          Env:
+            Type: String
+          Suffix:
+            Type: String
+          Checked:
+            Type: String
+          Unused:
+            Type: String
+          OnlyUnusedCondition:
+            Type: String
+        Mappings:
+          Names:
+            prod: { Queue: main }
+          Sizes:
+            prod: { Delay: 5 }
+          Orphan:
+            prod: { Value: x }
+        Conditions:
+          IsProd: !Equals [{ Ref: Env }, prod]
+          IsLarge: !Equals [{ Fn::FindInMap: [Sizes, prod, Delay] }, '5']
+          Dead: !Equals [{ Ref: OnlyUnusedCondition }, x]
+          AlsoDead: !Not [{ Condition: Dead }]
+        Rules:
+          CheckValue:
+            Assertions:
+              - Assert: { Fn::Equals: [{ Ref: Checked }, ok] }
+        Resources:
+          Queue:
+            Type: AWS::SQS::Queue
+            Properties:
+              QueueName: !Sub
+                - ${Name}-${Suffix}
+                - Name: { Fn::FindInMap: [Names, prod, Queue] }
+              DelaySeconds: !If [IsLarge, 5, 0]
+        Outputs:
+          Queue:
+            Condition: IsProd
+            Value: !Ref Queue
+        "#,
+    );
+    let mut diagnostics = Diagnostics::default();
+    ir.prune(&mut diagnostics);
+
+    let conditions: Vec<&str> = ir.conditions.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(conditions, vec!["IsLarge", "IsProd"]);
+    let mappings: Vec<&str> = ir.mappings.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(mappings, vec!["Names", "Sizes"]);
+    let parameters: Vec<&str> = ir
+        .constructor
+        .inputs
+        .iter()
+        .map(|input| input.name.as_str())
+        .collect();
+    assert_eq!(parameters, vec!["env", "suffix", "checked"]);
+    // The interface only listed a parameter that no longer exists.
+    assert!(ir.metadata.unwrap().interface.is_none());
+
+    assert!(diagnostics
+        .iter()
+        .all(|diagnostic| diagnostic.severity == Severity::Warning));
+    let warnings: Vec<String> = diagnostics.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        warnings,
+        vec![
+            "Conditions.Dead: unused condition, it was removed",
+            "Conditions.AlsoDead: unused condition, it was removed",
+            "Mappings.Orphan: unused mapping, it was removed",
+            "Parameters: unused parameter unused, it was removed",
+            "Parameters: unused parameter onlyUnusedCondition, it was removed",
+        ]
+    );
+}
+
+#[test]
+fn everything_used() {
+    let mut ir = program(
+        r#"
+        Parameters:
+          Env:
+            Type: String
+        Conditions:
+          IsProd: !Equals [{ Ref: Env }, prod]
+        Resources:
+          Topic:
+            Type: AWS::SNS::Topic
+            Condition: IsProd
+        "#,
+    );
+    let before = ir.clone();
+    let mut diagnostics = Diagnostics::default();
+    ir.prune(&mut diagnostics);
+
+    assert!(diagnostics.is_empty(), "{diagnostics}");
+    assert_eq!(ir, before);
+}
+
+#[test]
+fn nested_stack_parameters() {
+    let child: CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Parameters:
+          Name:
+            Type: String
+          Unused:
+            Type: String
+        Resources:
+          Topic:
+            Type: AWS::SNS::Topic
+            Properties:
+              TopicName: !Ref Name
+        "#,
+    )
+    .unwrap();
+    let child = CloudformationProgramIr::from(child, Schema::builtin()).unwrap();
+    let parent: CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Parameters:
+          Value:
+            Type: String
+        Resources:
+          Child:
+            Type: AWS::CloudFormation::Stack
+            Properties:
+              TemplateURL: child.yaml
+              Parameters:
+                Name: topic
+                Unused: !Ref Value
+        "#,
+    )
+    .unwrap();
+    let mut diagnostics = Diagnostics::default();
+    let mut ir = CloudformationProgramIr::from_with_nested_stacks(
+        parent,
+        Schema::builtin(),
+        HashMap::from([("Child".to_string(), child)]),
+        &mut diagnostics,
+    );
+    assert!(diagnostics.is_empty(), "{diagnostics}");
+    ir.prune(&mut diagnostics);
+
+    let nested = ir.resources[0].nested_stack.as_ref().unwrap();
+    let passed: Vec<&str> = nested.parameters.keys().map(String::as_str).collect();
+    assert_eq!(passed, vec!["name"]);
+    // Only the nested stack used the parameter of the parent.
+    assert!(ir.constructor.inputs.is_empty());
+
+    let warnings: Vec<String> = diagnostics.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        warnings,
+        vec![
+            "Resources.Child: Parameters in its template: unused parameter unused, it was removed",
+            "Parameters: unused parameter value, it was removed",
+        ]
+    );
+}
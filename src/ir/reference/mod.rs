@@ -1,5 +1,11 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
+use crate::ir::conditions::ConditionIr;
+use crate::ir::resources::ResourceIr;
+use crate::ir::rules::{RuleIr, RuleValueIr};
+use crate::ir::simplify::{visit, visit_condition};
+use crate::ir::CloudformationProgramIr;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Reference {
     pub origin: Origin,
@@ -29,7 +35,16 @@ pub enum Origin {
         attribute: String,
         conditional: bool,
     },
+    // An output of a nested stack, i.e: `Fn::GetAtt <logical id>.Outputs.<output>`
+    NestedStackOutput {
+        output: String,
+        conditional: bool,
+    },
     PseudoParameter(PseudoParameter),
+    // An attribute of the L2 construct emitted for a resource, by its camelCase name
+    ConstructAttribute {
+        attribute: String,
+    },
 }
 
 impl From<PseudoParameter> for Origin {
@@ -64,3 +79,76 @@ impl PseudoParameter {
         }
     }
 }
+
+impl CloudformationProgramIr {
+    /// The pseudo parameters the program refers to, in the order they are first used. Those of its
+    /// nested stacks are not included, as they are resolved in their own stack.
+    pub fn pseudo_parameters(&self) -> Vec<PseudoParameter> {
+        let mut used = Vec::new();
+        let mut insert = |reference: &Reference| {
+            if let Origin::PseudoParameter(pseudo) = reference.origin {
+                if !used.contains(&pseudo) {
+                    used.push(pseudo);
+                }
+            }
+        };
+        for condition in &self.conditions {
+            visit_condition(&condition.value, &mut |value| {
+                if let ConditionIr::Ref(reference) = value {
+                    insert(reference);
+                }
+            });
+        }
+        for rule in &self.rules {
+            let assertions = rule.assertions.iter().map(|assertion| &assertion.assert);
+            for rule in rule.condition.iter().chain(assertions) {
+                rule_references(rule, &mut insert);
+            }
+        }
+        // Values are only reachable mutably, which the program is not here.
+        let mut program = self.clone();
+        let mut values = Vec::new();
+        program.values_mut(&mut values, true);
+        for value in values {
+            visit(value, &mut |value| {
+                if let ResourceIr::Ref(reference) = value {
+                    insert(reference);
+                }
+            });
+        }
+        used
+    }
+}
+
+fn rule_references(rule: &RuleIr, visitor: &mut impl FnMut(&Reference)) {
+    match rule {
+        RuleIr::And(operands) | RuleIr::Or(operands) => {
+            for operand in operands {
+                rule_references(operand, visitor);
+            }
+        }
+        RuleIr::Not(operand) => rule_references(operand, visitor),
+        RuleIr::Equals(x, y)
+        | RuleIr::Contains(x, y)
+        | RuleIr::EachMemberEquals(x, y)
+        | RuleIr::EachMemberIn(x, y) => {
+            rule_value_references(x, visitor);
+            rule_value_references(y, visitor);
+        }
+    }
+}
+
+fn rule_value_references(value: &RuleValueIr, visitor: &mut impl FnMut(&Reference)) {
+    match value {
+        RuleValueIr::List(items) => {
+            for item in items {
+                rule_value_references(item, visitor);
+            }
+        }
+        RuleValueIr::Ref(reference) => visitor(reference),
+        RuleValueIr::Str(_)
+        | RuleValueIr::RefAll(_)
+        | RuleValueIr::ValueOf(..)
+        | RuleValueIr::ValueOfAll(..) => {}
+    }
+}
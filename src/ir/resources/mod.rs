@@ -10,16 +10,21 @@ use indexmap::IndexMap;
 use topological_sort::TopologicalSort;
 
 use crate::cdk::*;
+use crate::ir::constructs::L2Construct;
+use crate::ir::nested_stacks::NestedStackInstruction;
 use crate::ir::reference::{Origin, Reference};
 use crate::ir::sub::{sub_parse_tree, SubValue};
+use crate::ir::validation::suggestion;
 use crate::parser::resource::{
-    DeletionPolicy, IntrinsicFunction, ResourceAttributes, ResourceValue,
+    CreationPolicy, DeletionPolicy, IntrinsicFunction, ResourceAttributes, ResourceValue,
+    UpdateReplacePolicy,
 };
 use crate::primitives::WrapperF64;
 use crate::Error;
 use crate::Hasher;
 
-use super::ReferenceOrigins;
+use super::diagnostics::Diagnostics;
+use super::{find_cycle, ReferenceOrigins};
 
 // ResourceIr is the intermediate representation of a nested stack resource.
 // It is slightly more refined than the ResourceValue, in some cases always resolving
@@ -50,6 +55,32 @@ pub enum ResourceIr {
     GetAZs(Box<ResourceIr>),
     Select(usize, Box<ResourceIr>),
     Cidr(Box<ResourceIr>, Box<ResourceIr>, Box<ResourceIr>),
+
+    // Provided by the `AWS::LanguageExtensions` transform
+    Length(Box<ResourceIr>),
+    ToJsonString(Box<ResourceIr>),
+}
+
+impl ResourceIr {
+    // Renders this value as JSON, if it is entirely made of literals (i.e: it does not contain any
+    // intrinsic function or reference, which can only be resolved at deploy time).
+    pub fn to_json(&self) -> Option<serde_json::Value> {
+        match self {
+            Self::Null => Some(serde_json::Value::Null),
+            Self::Bool(bool) => Some((*bool).into()),
+            Self::Number(number) => Some((*number).into()),
+            Self::Double(double) => {
+                serde_json::Number::from_f64((*double).into()).map(serde_json::Value::Number)
+            }
+            Self::String(string) => Some(string.as_str().into()),
+            Self::Array(_, items) => items.iter().map(Self::to_json).collect(),
+            Self::Object(_, entries) => entries
+                .iter()
+                .map(|(key, value)| value.to_json().map(|value| (key.clone(), value)))
+                .collect(),
+            _ => None,
+        }
+    }
 }
 
 // ResourceTranslationInputs is a place to store all the intermediate recursion
@@ -71,7 +102,16 @@ impl<'a, 'b> ResourceTranslator<'a, 'b> {
     }
 
     pub(super) fn translate(&self, resource_value: ResourceValue) -> Result<ResourceIr, Error> {
+        if let Some(TypeReference::Union(members)) = &self.value_type {
+            if let Some(member) = self.union_member(members, resource_value.unspanned()) {
+                return self.with_value_type(member).translate(resource_value);
+            }
+        }
+
         match resource_value {
+            ResourceValue::Spanned(span, value) => self
+                .translate(*value)
+                .map_err(|err| err.at_span(Some(span))),
             ResourceValue::Null => Ok(ResourceIr::Null),
             ResourceValue::Bool(b) => Ok(ResourceIr::Bool(b)),
             ResourceValue::Number(n) => Ok(ResourceIr::Number(n)),
@@ -118,8 +158,12 @@ impl<'a, 'b> ResourceTranslator<'a, 'b> {
                     };
 
                     let mut array_ir = Vec::with_capacity(parse_resource_vec.len());
-                    for parse_resource in parse_resource_vec {
-                        array_ir.push(item_translator.translate(parse_resource)?);
+                    for (index, parse_resource) in parse_resource_vec.into_iter().enumerate() {
+                        array_ir.push(
+                            item_translator
+                                .translate(parse_resource)
+                                .map_err(|err| err.at_index(index))?,
+                        );
                     }
                     array_ir
                 };
@@ -129,9 +173,15 @@ impl<'a, 'b> ResourceTranslator<'a, 'b> {
             ResourceValue::Object(o) => {
                 let mut is_resource_ir_array = false;
                 let property_bag: Box<dyn PropertyBag> = match &self.value_type {
-                    Some(TypeReference::Named(name)) => {
-                        Box::new(self.schema.type_named(name).cloned().unwrap())
-                    }
+                    Some(TypeReference::Named(name)) => match self.schema.type_named(name) {
+                        Some(data_type) => Box::new(data_type.clone()),
+                        // A type the schema does not describe is kept as it was written
+                        None => {
+                            return self
+                                .with_value_type(TypeReference::Primitive(Primitive::Json))
+                                .translate(ResourceValue::Object(o))
+                        }
+                    },
                     Some(TypeReference::Map(item_type)) => Box::new(MapOf(item_type)),
                     Some(TypeReference::List(item_type)) => {
                         is_resource_ir_array = true;
@@ -157,7 +207,8 @@ impl<'a, 'b> ResourceTranslator<'a, 'b> {
                         origins: self.origins,
                         value_type: property_type.map(|pt| pt.value_type),
                     }
-                    .translate(rv)?;
+                    .translate(rv)
+                    .map_err(|err| err.at_key(&s))?;
 
                     new_hash.insert(s, property_ir);
                 }
@@ -199,13 +250,13 @@ impl<'a, 'b> ResourceTranslator<'a, 'b> {
                         let r = vars
                             .into_iter()
                             .map(|x| match x {
-                                SubValue::String(x) => ResourceIr::String(x),
+                                SubValue::String(x) => Ok(ResourceIr::String(x)),
                                 SubValue::Variable(x) => match excess_map.get(&x) {
-                                    None => ResourceIr::Ref(self.translate_ref(&x)),
-                                    Some(x) => x.clone(),
+                                    None => self.translate_ref(&x).map(ResourceIr::Ref),
+                                    Some(x) => Ok(x.clone()),
                                 },
                             })
-                            .collect();
+                            .collect::<Result<_, _>>()?;
                         Ok(ResourceIr::Sub(r))
                     }
                     IntrinsicFunction::FindInMap {
@@ -225,13 +276,10 @@ impl<'a, 'b> ResourceTranslator<'a, 'b> {
                     IntrinsicFunction::GetAtt {
                         logical_name,
                         attribute_name,
-                    } => Ok(ResourceIr::Ref(Reference::new(
-                        &logical_name,
-                        Origin::GetAttribute {
-                            attribute: attribute_name.replace('.', ""),
-                            conditional: self.origins.is_conditional(&logical_name),
-                        },
-                    ))),
+                    } => {
+                        let origin = self.translate_attribute(&logical_name, &attribute_name)?;
+                        Ok(ResourceIr::Ref(Reference::new(&logical_name, origin)))
+                    }
                     IntrinsicFunction::If {
                         condition_name,
                         value_if_true,
@@ -265,7 +313,7 @@ impl<'a, 'b> ResourceTranslator<'a, 'b> {
 
                         Ok(ResourceIr::Split(sep, Box::new(ir)))
                     }
-                    IntrinsicFunction::Ref(x) => Ok(ResourceIr::Ref(self.translate_ref(&x))),
+                    IntrinsicFunction::Ref(x) => Ok(ResourceIr::Ref(self.translate_ref(&x)?)),
                     IntrinsicFunction::Base64(x) => match x {
                         ResourceValue::String(b64) => {
                             match base64::engine::general_purpose::STANDARD.decode(&b64) {
@@ -336,29 +384,126 @@ impl<'a, 'b> ResourceTranslator<'a, 'b> {
                             Box::new(cidr_bits_str),
                         ))
                     }
+                    IntrinsicFunction::Length(list) => {
+                        let ir =
+                            ResourceTranslator::json(self.schema, self.origins).translate(list)?;
+                        Ok(ResourceIr::Length(Box::new(ir)))
+                    }
+                    IntrinsicFunction::ToJsonString(value) => {
+                        let ir =
+                            ResourceTranslator::json(self.schema, self.origins).translate(value)?;
+                        Ok(ResourceIr::ToJsonString(Box::new(ir)))
+                    }
 
-                    unimplemented => unimplemented!("{unimplemented:?}"),
+                    IntrinsicFunction::Transform => Err(Error::UnsupportedIntrinsic {
+                        intrinsic: "Fn::Transform".into(),
+                        // Filled in with the path of the value once reported
+                        location: String::new(),
+                    }),
                 }
             }
         }
     }
 
-    fn translate_ref(&self, x: &str) -> Reference {
-        if let Some(origin) = self.origins.for_ref(x) {
-            Reference::new(x, origin)
+    fn translate_ref(&self, x: &str) -> Result<Reference, Error> {
+        if self.origins.is_nested_stack(x) {
+            Err(Error::ResourceTranslationError {
+                message: format!(
+                    "{x} is a nested stack, which can only be referenced through its outputs"
+                ),
+            })
+        } else if let Some(origin) = self.origins.for_ref(x) {
+            Ok(Reference::new(x, origin))
         } else if let Some((name, attribute)) = x.split_once('.') {
-            Reference::new(
-                name,
-                Origin::GetAttribute {
-                    attribute: attribute.into(),
-                    conditional: self.origins.is_conditional(name),
-                },
-            )
+            // Fn::Sub accepts ${LogicalId.Attribute} as a shorthand for Fn::GetAtt
+            let origin = self.translate_attribute(name, attribute)?;
+            Ok(Reference::new(name, origin))
         } else {
-            Reference::new(x, Origin::LogicalId { conditional: false })
+            Ok(Reference::new(x, Origin::LogicalId { conditional: false }))
         }
     }
 
+    // The origin of the `attribute` of the resource (or nested stack) named `logical_id`. The
+    // attributes of resources known to the schema are checked, so that a typo is reported instead
+    // of producing a reference to an attribute that does not exist.
+    fn translate_attribute(&self, logical_id: &str, attribute: &str) -> Result<Origin, Error> {
+        if let Some(origin) = self
+            .origins
+            .for_nested_stack_attribute(logical_id, attribute)?
+        {
+            return Ok(origin);
+        }
+        if let Some(resource_type) = self.origins.resource_type(logical_id) {
+            // Custom resources and stack outputs have arbitrary attributes
+            let unchecked = resource_type == "AWS::CloudFormation::CustomResource"
+                || (resource_type == "AWS::CloudFormation::Stack"
+                    && attribute.starts_with("Outputs."));
+            let resource = self.schema.resource_type(resource_type);
+            if !unchecked && resource.is_some_and(|res| res.attribute(attribute).is_none()) {
+                return Err(Error::ResourceTranslationError {
+                    message: format!(
+                        "{attribute} is not an attribute of {logical_id} ({resource_type})"
+                    ),
+                });
+            }
+        }
+        Ok(Origin::GetAttribute {
+            attribute: attribute.replace('.', ""),
+            conditional: self.origins.is_conditional(logical_id),
+        })
+    }
+
+    // Picks the member of a union type that best matches the shape of `value`, if any. Objects
+    // match the named type that declares the most of their keys (and none it does not declare),
+    // falling back on maps and JSON. Intrinsic functions are left to resolve their own values.
+    fn union_member(&self, members: &TypeUnion, value: &ResourceValue) -> Option<TypeReference> {
+        let score = |member: &TypeReference| -> Option<usize> {
+            match (member, value) {
+                (TypeReference::Named(name), ResourceValue::Object(obj)) => {
+                    let data_type = self.schema.type_named(name)?;
+                    obj.keys()
+                        .all(|key| data_type.property(key).is_some())
+                        .then_some(obj.len() + 1)
+                }
+                (TypeReference::Map(_), ResourceValue::Object(_))
+                | (TypeReference::Primitive(Primitive::Json), _) => Some(0),
+                (TypeReference::List(_), ResourceValue::Array(_)) => Some(1),
+                (TypeReference::Primitive(primitive), value) => match (primitive, value) {
+                    (Primitive::String, ResourceValue::String(_))
+                    | (Primitive::Boolean, ResourceValue::Bool(_))
+                    | (Primitive::Number, ResourceValue::Number(_) | ResourceValue::Double(_)) => {
+                        Some(2)
+                    }
+                    (Primitive::Timestamp, ResourceValue::String(_))
+                    | (
+                        Primitive::String,
+                        ResourceValue::Bool(_)
+                        | ResourceValue::Number(_)
+                        | ResourceValue::Double(_),
+                    ) => Some(1),
+                    (Primitive::Boolean, ResourceValue::String(text)) => {
+                        text.parse::<bool>().ok().map(|_| 1)
+                    }
+                    (Primitive::Number, ResourceValue::String(text)) => {
+                        text.parse::<f64>().ok().map(|_| 1)
+                    }
+                    _ => None,
+                },
+                _ => None,
+            }
+        };
+
+        let mut best: Option<(usize, &TypeReference)> = None;
+        for member in members.iter() {
+            if let Some(score) = score(member) {
+                if best.is_none_or(|(best_score, _)| score > best_score) {
+                    best = Some((score, member));
+                }
+            }
+        }
+        best.map(|(_, member)| member.clone())
+    }
+
     #[inline]
     fn with_value_type(&self, value_type: TypeReference) -> Self {
         Self {
@@ -389,13 +534,26 @@ pub struct ResourceInstruction {
     pub metadata: Option<ResourceIr>,
     pub update_policy: Option<ResourceIr>,
     pub deletion_policy: Option<DeletionPolicy>,
+    pub update_replace_policy: Option<UpdateReplacePolicy>,
+    pub creation_policy: Option<CreationPolicyIr>,
     pub dependencies: Vec<String>,
     pub resource_type: ResourceType,
     pub properties: IndexMap<String, ResourceIr, Hasher>,
+    // Set when the resource is a nested stack whose template was converted too, in which case
+    // `properties` is empty.
+    pub nested_stack: Option<NestedStackInstruction>,
+    // Set when the resource is emitted as an L2 construct, in which case `properties` is empty.
+    pub l2_construct: Option<L2Construct>,
 
     // `references` identify the logical ID of all other template entities that this resource
     // contains a reference to (i.e: it uses them).
     pub references: BTreeSet<String>,
+
+    // The comments of the resource in a YAML template, and those of its properties by name (or by
+    // the name of the L2 prop they were translated into). They are emitted above the construct and
+    // its props.
+    pub comments: Vec<String>,
+    pub property_comments: IndexMap<String, Vec<String>, Hasher>,
 }
 
 impl ResourceInstruction {
@@ -403,28 +561,54 @@ impl ResourceInstruction {
         parse_tree: IndexMap<String, ResourceAttributes, Hasher>,
         schema: &Schema,
         origins: &ReferenceOrigins,
-    ) -> Result<Vec<Self>, Error> {
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<Self> {
         let mut instructions = Vec::with_capacity(parse_tree.len());
+        let mut complete = true;
 
         for (resource_name, attributes) in parse_tree {
-            let resource_type = ResourceType::parse(&attributes.resource_type)?;
+            let path = format!("Resources.{resource_name}");
+            let span = attributes.span;
+            let resource_type = match ResourceType::parse(&attributes.resource_type) {
+                Ok(resource_type) => resource_type,
+                Err(err) => {
+                    diagnostics.report(format!("{path}.Type"), err.at_span(span));
+                    complete = false;
+                    continue;
+                }
+            };
             let resource_spec = schema.resource_type(&attributes.resource_type);
 
-            let metadata = if let Some(metadata) = attributes.metadata {
-                Some(ResourceTranslator::json(schema, origins).translate(metadata)?)
-            } else {
-                None
-            };
+            let metadata = attributes.metadata.and_then(|metadata| {
+                ResourceTranslator::json(schema, origins)
+                    .translate(metadata)
+                    .map_err(|err| {
+                        diagnostics.report(format!("{path}.Metadata"), err.at_span(span))
+                    })
+                    .ok()
+            });
 
-            let update_policy = if let Some(up) = attributes.update_policy {
-                Some(ResourceTranslator::json(schema, origins).translate(up)?)
-            } else {
-                None
-            };
+            let update_policy = attributes.update_policy.and_then(|up| {
+                ResourceTranslator::json(schema, origins)
+                    .translate(up)
+                    .map_err(|err| {
+                        diagnostics.report(format!("{path}.UpdatePolicy"), err.at_span(span))
+                    })
+                    .ok()
+            });
+
+            let creation_policy = attributes.creation_policy.and_then(|cp| {
+                CreationPolicyIr::from(cp, schema, origins)
+                    .map_err(|err| {
+                        diagnostics.report(format!("{path}.CreationPolicy"), err.at_span(span))
+                    })
+                    .ok()
+            });
 
             let mut properties =
                 IndexMap::with_capacity_and_hasher(attributes.properties.len(), Hasher::default());
             for (prop_name, prop) in attributes.properties {
+                let prop_path = format!("{path}.Properties.{prop_name}");
                 let property_type = resource_spec.and_then(|spec| spec.property(&prop_name));
                 let property_type = property_type.map(|prop| prop.value_type);
                 if property_type.is_none() {
@@ -435,18 +619,32 @@ impl ResourceInstruction {
                         resource_type.type_name(),
                     )
                     .replace('\"', "");
-                    return Err(Error::ResourceInstructionError {
-                        message: format!(
-                            "{prop_name} is not a valid property for resource {resource_name} of type {resource_type}"
-                        ),
-                    });
+                    let mut message = format!(
+                        "{prop_name} is not a valid property for resource {resource_name} of type {resource_type}"
+                    );
+                    let names = resource_spec
+                        .map(|spec| spec.properties())
+                        .unwrap_or_default();
+                    if let Some(suggestion) =
+                        suggestion(&prop_name, names.iter().map(|(name, _)| *name))
+                    {
+                        message.push_str(&format!(", did you mean {suggestion}?"));
+                    }
+                    let err = Error::ResourceInstructionError { message };
+                    diagnostics.report(prop_path, err.at_span(prop.span().or(span)));
+                    continue;
                 }
                 let translator = ResourceTranslator {
                     schema,
                     origins,
                     value_type: property_type,
                 };
-                properties.insert(prop_name, translator.translate(prop)?);
+                match translator.translate(prop) {
+                    Ok(ir) => {
+                        properties.insert(prop_name, ir);
+                    }
+                    Err(err) => diagnostics.report(prop_path, err.at_span(span)),
+                }
             }
 
             let mut instruction = Self {
@@ -455,19 +653,43 @@ impl ResourceInstruction {
                 metadata,
                 update_policy,
                 deletion_policy: attributes.deletion_policy,
+                update_replace_policy: attributes.update_replace_policy,
+                creation_policy,
                 dependencies: attributes.depends_on,
                 resource_type,
                 properties,
+                nested_stack: None,
+                l2_construct: None,
                 references: BTreeSet::default(),
+                comments: attributes.comments,
+                property_comments: attributes.property_comments.into_iter().collect(),
             };
+            if let Some(resource_spec) = resource_spec {
+                instruction.validate(resource_spec, schema, diagnostics);
+            }
             instruction.generate_references();
             instructions.push(instruction);
         }
 
-        Ok(order(instructions))
+        // Dependencies on resources that could not be translated would show up as spurious
+        // unresolved references, so only order a complete set of resources.
+        if !complete {
+            return instructions;
+        }
+        match order(instructions) {
+            Ok(instructions) => instructions,
+            Err(err) => {
+                let path = match &err {
+                    Error::UnresolvedReference { from, .. } => format!("Resources.{from}"),
+                    _ => "Resources".into(),
+                };
+                diagnostics.report(path, err);
+                Vec::new()
+            }
+        }
     }
 
-    fn generate_references(&mut self) {
+    pub(super) fn generate_references(&mut self) {
         self.references.extend(self.dependencies.iter().cloned());
         for (_, property) in &self.properties {
             self.references.extend(find_references(property));
@@ -475,6 +697,76 @@ impl ResourceInstruction {
     }
 }
 
+// CreationPolicyIr is the typed form of a resource's CreationPolicy attribute, so that
+// synthesizers can emit `CfnCreationPolicy` structures instead of untyped JSON.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CreationPolicyIr {
+    pub auto_scaling_creation_policy: Option<AutoScalingCreationPolicyIr>,
+    pub resource_signal: Option<ResourceSignalIr>,
+    pub start_fleet: Option<ResourceIr>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AutoScalingCreationPolicyIr {
+    pub min_successful_instances_percent: Option<ResourceIr>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResourceSignalIr {
+    pub count: Option<ResourceIr>,
+    pub timeout: Option<ResourceIr>,
+}
+
+impl CreationPolicyIr {
+    fn from(
+        policy: CreationPolicy,
+        schema: &Schema,
+        origins: &ReferenceOrigins,
+    ) -> Result<Self, Error> {
+        let translate = |value: Option<ResourceValue>, primitive: Primitive, key: &str| {
+            let translator = ResourceTranslator {
+                schema,
+                origins,
+                value_type: Some(TypeReference::Primitive(primitive)),
+            };
+            value
+                .map(|value| translator.translate(value).map_err(|err| err.at_key(key)))
+                .transpose()
+        };
+
+        let auto_scaling_creation_policy = policy
+            .auto_scaling_creation_policy
+            .map(|asg| {
+                Ok(AutoScalingCreationPolicyIr {
+                    min_successful_instances_percent: translate(
+                        asg.min_successful_instances_percent,
+                        Primitive::Number,
+                        "MinSuccessfulInstancesPercent",
+                    )?,
+                })
+            })
+            .transpose()
+            .map_err(|err: Error| err.at_key("AutoScalingCreationPolicy"))?;
+
+        let resource_signal = policy
+            .resource_signal
+            .map(|signal| {
+                Ok(ResourceSignalIr {
+                    count: translate(signal.count, Primitive::Number, "Count")?,
+                    timeout: translate(signal.timeout, Primitive::String, "Timeout")?,
+                })
+            })
+            .transpose()
+            .map_err(|err: Error| err.at_key("ResourceSignal"))?;
+
+        Ok(Self {
+            auto_scaling_creation_policy,
+            resource_signal,
+            start_fleet: translate(policy.start_fleet, Primitive::Boolean, "StartFleet")?,
+        })
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum ResourceType {
     Alexa { service: String, type_name: String },
@@ -612,35 +904,60 @@ impl fmt::Display for ResourceType {
     }
 }
 
-fn order(resource_instructions: Vec<ResourceInstruction>) -> Vec<ResourceInstruction> {
-    let mut topo = TopologicalSort::new();
-    let mut hash = HashMap::with_capacity(resource_instructions.len());
-    for resource_instruction in resource_instructions {
-        topo.insert(resource_instruction.name.to_string());
-
-        for dep in &resource_instruction.dependencies {
-            topo.add_dependency(dep, resource_instruction.name.to_string());
+fn order(
+    resource_instructions: Vec<ResourceInstruction>,
+) -> Result<Vec<ResourceInstruction>, Error> {
+    let mut dependencies: IndexMap<&str, BTreeSet<&str>, Hasher> =
+        IndexMap::with_capacity_and_hasher(resource_instructions.len(), Hasher::default());
+    for resource_instruction in &resource_instructions {
+        let mut deps: BTreeSet<&str> = resource_instruction
+            .dependencies
+            .iter()
+            .map(String::as_str)
+            .collect();
+        for property in resource_instruction.properties.values() {
+            find_dependencies(property, &mut deps);
         }
-        for (_, property) in &resource_instruction.properties {
-            find_dependencies(&resource_instruction.name, property, &mut topo)
+        dependencies.insert(&resource_instruction.name, deps);
+    }
+
+    let mut topo = TopologicalSort::<&str>::new();
+    for (&name, deps) in &dependencies {
+        topo.insert(name);
+        for &dep in deps {
+            if !dependencies.contains_key(dep) {
+                return Err(Error::UnresolvedReference {
+                    from: name.to_string(),
+                    to: dep.to_string(),
+                });
+            }
+            topo.add_dependency(dep, name);
         }
-        hash.insert(resource_instruction.name.to_string(), resource_instruction);
     }
 
-    let mut sorted_instructions = Vec::with_capacity(hash.len());
+    let mut sorted = Vec::with_capacity(dependencies.len());
     while !topo.is_empty() {
         let mut list = topo.pop_all();
         if list.is_empty() {
-            panic!("Cyclic dependency in your resources ")
+            let sorted = sorted.iter().copied().collect();
+            return Err(Error::CyclicDependency {
+                cycle: find_cycle(&dependencies, &sorted),
+            });
         }
         // Ensures consistent ordering of generated code...
         list.sort();
-        sorted_instructions.extend(list.into_iter().map(|name| match hash.remove(&name) {
-            None => panic!("Attempted to reference or depend on a resource not defined in the CloudFormation template. Resource: {name}"),
-            Some(instruction) => instruction,
-        }));
+        sorted.extend(list);
     }
-    sorted_instructions
+    let sorted: Vec<String> = sorted.into_iter().map(ToString::to_string).collect();
+
+    let mut hash: HashMap<String, ResourceInstruction> = resource_instructions
+        .into_iter()
+        .map(|instruction| (instruction.name.clone(), instruction))
+        .collect();
+    Ok(sorted
+        .into_iter()
+        .filter_map(|name| hash.remove(&name))
+        .collect())
 }
 
 pub(crate) fn find_references(resource: &ResourceIr) -> HashSet<String> {
@@ -679,7 +996,10 @@ pub(crate) fn find_references(resource: &ResourceIr) -> HashSet<String> {
             | Origin::Parameter
             | Origin::Condition
             | Origin::PseudoParameter(_) => { /* No references */ }
-            Origin::GetAttribute { .. } | Origin::LogicalId { .. } => {
+            Origin::GetAttribute { .. }
+            | Origin::NestedStackOutput { .. }
+            | Origin::ConstructAttribute { .. }
+            | Origin::LogicalId { .. } => {
                 set.insert(x.name.clone());
             }
         },
@@ -695,6 +1015,8 @@ pub(crate) fn find_references(resource: &ResourceIr) -> HashSet<String> {
         ResourceIr::Base64(x) => set = find_references(x.deref()),
         ResourceIr::Select(_, x) => set = find_references(x.deref()),
         ResourceIr::GetAZs(x) => set = find_references(x.deref()),
+        ResourceIr::Length(x) => set = find_references(x.deref()),
+        ResourceIr::ToJsonString(x) => set = find_references(x.deref()),
         ResourceIr::Cidr(x, y, z) => {
             set.extend(find_references(x.deref()));
             set.extend(find_references(y.deref()));
@@ -705,11 +1027,7 @@ pub(crate) fn find_references(resource: &ResourceIr) -> HashSet<String> {
     set
 }
 
-fn find_dependencies(
-    resource_name: &str,
-    resource: &ResourceIr,
-    topo: &mut TopologicalSort<String>,
-) {
+fn find_dependencies<'a>(resource: &'a ResourceIr, deps: &mut BTreeSet<&'a str>) {
     match resource {
         ResourceIr::Null
         | ResourceIr::Bool(_)
@@ -720,58 +1038,63 @@ fn find_dependencies(
 
         ResourceIr::Array(_, arr) => {
             for x in arr {
-                find_dependencies(resource_name, x, topo);
+                find_dependencies(x, deps);
             }
         }
         ResourceIr::Object(_, hash) => {
             for x in hash.values() {
-                find_dependencies(resource_name, x, topo);
+                find_dependencies(x, deps);
             }
         }
         ResourceIr::If(_, x, y) => {
-            find_dependencies(resource_name, x.deref(), topo);
-            find_dependencies(resource_name, y.deref(), topo);
+            find_dependencies(x.deref(), deps);
+            find_dependencies(y.deref(), deps);
         }
         ResourceIr::Join(_, arr) => {
             for x in arr {
-                find_dependencies(resource_name, x, topo);
+                find_dependencies(x, deps);
             }
         }
-        ResourceIr::Split(_, ir) => find_dependencies(resource_name, ir, topo),
+        ResourceIr::Split(_, ir) => find_dependencies(ir, deps),
         ResourceIr::Ref(x) => match x.origin {
             Origin::CfnParameter
             | Origin::Parameter
             | Origin::Condition
             | Origin::PseudoParameter(_) => {}
             Origin::LogicalId { .. } => {
-                topo.add_dependency(x.name.to_string(), resource_name.to_string());
+                deps.insert(&x.name);
             }
-            Origin::GetAttribute { .. } => {
-                topo.add_dependency(x.name.to_string(), resource_name.to_string());
+            Origin::GetAttribute { .. }
+            | Origin::NestedStackOutput { .. }
+            | Origin::ConstructAttribute { .. } => {
+                deps.insert(&x.name);
             }
         },
         ResourceIr::Sub(arr) => {
             for x in arr {
-                find_dependencies(resource_name, x, topo);
+                find_dependencies(x, deps);
             }
         }
         ResourceIr::Map(_, y, z) => {
-            find_dependencies(resource_name, y.deref(), topo);
-            find_dependencies(resource_name, z.deref(), topo);
+            find_dependencies(y.deref(), deps);
+            find_dependencies(z.deref(), deps);
         }
         ResourceIr::Base64(x) => {
-            find_dependencies(resource_name, x.deref(), topo);
+            find_dependencies(x.deref(), deps);
         }
         ResourceIr::Select(_, x) => {
-            find_dependencies(resource_name, x.deref(), topo);
+            find_dependencies(x.deref(), deps);
         }
         ResourceIr::GetAZs(x) => {
-            find_dependencies(resource_name, x.deref(), topo);
+            find_dependencies(x.deref(), deps);
+        }
+        ResourceIr::Length(x) | ResourceIr::ToJsonString(x) => {
+            find_dependencies(x.deref(), deps);
         }
         ResourceIr::Cidr(x, y, z) => {
-            find_dependencies(resource_name, x.deref(), topo);
-            find_dependencies(resource_name, y.deref(), topo);
-            find_dependencies(resource_name, z.deref(), topo);
+            find_dependencies(x.deref(), deps);
+            find_dependencies(y.deref(), deps);
+            find_dependencies(z.deref(), deps);
         }
     }
 }
@@ -4,14 +4,18 @@ use std::collections::{BTreeSet, HashMap};
 
 use indexmap::IndexMap;
 
+use crate::ir::diagnostics::Diagnostics;
 use crate::ir::reference::{Origin, Reference};
-use crate::ir::resources::{order, ResourceInstruction, ResourceIr, ResourceType};
+use crate::ir::resources::{
+    order, AutoScalingCreationPolicyIr, CreationPolicyIr, ResourceInstruction, ResourceIr,
+    ResourceSignalIr, ResourceType,
+};
 use crate::ir::ReferenceOrigins;
-use crate::parser::resource::{IntrinsicFunction, ResourceValue};
+use crate::parser::resource::{IntrinsicFunction, ResourceValue, UpdateReplacePolicy};
 use crate::primitives::WrapperF64;
-use crate::Hasher;
+use crate::{CloudformationParseTree, Error, Hasher};
 
-use super::{Primitive, ResourceTranslator, Schema, TypeReference};
+use super::{Primitive, ResourceTranslator, Schema, TypeReference, TypeUnion};
 
 #[test]
 fn test_ir_ordering() {
@@ -20,10 +24,16 @@ fn test_ir_ordering() {
         condition: None,
         metadata: None,
         deletion_policy: None,
+        update_replace_policy: None,
+        creation_policy: None,
         update_policy: None,
         dependencies: Vec::new(),
         resource_type: ResourceType::Custom("Dummy".into()),
+        nested_stack: None,
+        l2_construct: None,
         references: BTreeSet::default(),
+        comments: Vec::new(),
+        property_comments: IndexMap::default(),
         properties: IndexMap::default(),
     };
 
@@ -33,9 +43,15 @@ fn test_ir_ordering() {
         dependencies: Vec::new(),
         metadata: None,
         deletion_policy: None,
+        update_replace_policy: None,
+        creation_policy: None,
         update_policy: None,
         resource_type: ResourceType::Custom("Dummy".into()),
+        nested_stack: None,
+        l2_construct: None,
         references: BTreeSet::default(),
+        comments: Vec::new(),
+        property_comments: IndexMap::default(),
         properties: create_property(
             "something",
             ResourceIr::Ref(Reference::new(
@@ -47,10 +63,84 @@ fn test_ir_ordering() {
 
     let misordered = vec![later.clone(), ir_instruction.clone()];
 
-    let actual = order(misordered);
+    let actual = order(misordered).unwrap();
     assert_eq!(actual, vec![ir_instruction, later]);
 }
 
+#[test]
+fn test_ir_ordering_cycle() {
+    let a = ResourceInstruction {
+        name: "A".to_string(),
+        condition: None,
+        metadata: None,
+        deletion_policy: None,
+        update_replace_policy: None,
+        creation_policy: None,
+        update_policy: None,
+        dependencies: vec!["B".to_string()],
+        resource_type: ResourceType::Custom("Dummy".into()),
+        nested_stack: None,
+        l2_construct: None,
+        references: BTreeSet::default(),
+        comments: Vec::new(),
+        property_comments: IndexMap::default(),
+        properties: IndexMap::default(),
+    };
+
+    let b = ResourceInstruction {
+        name: "B".to_string(),
+        dependencies: Vec::new(),
+        properties: create_property(
+            "something",
+            ResourceIr::Ref(Reference::new(
+                "A",
+                Origin::LogicalId { conditional: false },
+            )),
+        ),
+        ..a.clone()
+    };
+
+    match order(vec![a, b]) {
+        Err(Error::CyclicDependency { cycle }) => assert_eq!(cycle, vec!["A", "B", "A"]),
+        other => panic!("expected a cyclic dependency error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_ir_ordering_undefined_reference() {
+    let a = ResourceInstruction {
+        name: "A".to_string(),
+        condition: None,
+        metadata: None,
+        deletion_policy: None,
+        update_replace_policy: None,
+        creation_policy: None,
+        update_policy: None,
+        dependencies: Vec::new(),
+        resource_type: ResourceType::Custom("Dummy".into()),
+        nested_stack: None,
+        l2_construct: None,
+        references: BTreeSet::default(),
+        comments: Vec::new(),
+        property_comments: IndexMap::default(),
+        properties: create_property(
+            "something",
+            ResourceIr::Ref(Reference::new(
+                "Missing",
+                Origin::LogicalId { conditional: false },
+            )),
+        ),
+    };
+
+    match order(vec![a]) {
+        Err(Error::UnresolvedReference { from, to }) => {
+            assert_eq!(from, "A");
+            assert_eq!(to, "Missing");
+        }
+        other => panic!("expected an unresolved reference error, got {other:?}"),
+    }
+}
+
 #[test]
 fn test_ref_links() {
     let mut ir_instruction = ResourceInstruction {
@@ -58,10 +148,16 @@ fn test_ref_links() {
         condition: None,
         metadata: None,
         deletion_policy: None,
+        update_replace_policy: None,
+        creation_policy: None,
         update_policy: None,
         dependencies: vec!["foo".to_string()],
         resource_type: ResourceType::Custom("Dummy".into()),
+        nested_stack: None,
+        l2_construct: None,
         references: BTreeSet::default(),
+        comments: Vec::new(),
+        property_comments: IndexMap::default(),
         properties: create_property(
             "something",
             ResourceIr::Ref(Reference::new(
@@ -184,6 +280,8 @@ fn unknown_resource_type() {
 fn test_boolean_parse_error() {
     let origins = ReferenceOrigins {
         origins: HashMap::default(),
+        nested_stack_outputs: HashMap::default(),
+        resource_types: HashMap::default(),
     };
     let translator = ResourceTranslator {
         schema: Schema::builtin(),
@@ -202,6 +300,8 @@ fn test_boolean_parse_error() {
 fn test_number_parse_float() {
     let origins = ReferenceOrigins {
         origins: HashMap::default(),
+        nested_stack_outputs: HashMap::default(),
+        resource_types: HashMap::default(),
     };
     let translator = ResourceTranslator {
         schema: Schema::builtin(),
@@ -217,6 +317,8 @@ fn test_number_parse_float() {
 fn test_number_parse_error() {
     let origins = ReferenceOrigins {
         origins: HashMap::default(),
+        nested_stack_outputs: HashMap::default(),
+        resource_types: HashMap::default(),
     };
     let translator = ResourceTranslator {
         schema: Schema::builtin(),
@@ -232,6 +334,8 @@ fn test_number_parse_error() {
 fn test_sub_excess_map_error() {
     let origins = ReferenceOrigins {
         origins: HashMap::default(),
+        nested_stack_outputs: HashMap::default(),
+        resource_types: HashMap::default(),
     };
     let translator = ResourceTranslator {
         schema: Schema::builtin(),
@@ -250,6 +354,8 @@ fn test_sub_excess_map_error() {
 fn test_invalid_base_64() {
     let origins = ReferenceOrigins {
         origins: HashMap::default(),
+        nested_stack_outputs: HashMap::default(),
+        resource_types: HashMap::default(),
     };
     let translator = ResourceTranslator {
         schema: Schema::builtin(),
@@ -270,6 +376,8 @@ fn test_invalid_base_64() {
 fn test_invalid_select_index() {
     let origins = ReferenceOrigins {
         origins: HashMap::default(),
+        nested_stack_outputs: HashMap::default(),
+        resource_types: HashMap::default(),
     };
     let translator = ResourceTranslator {
         schema: Schema::builtin(),
@@ -288,6 +396,8 @@ fn test_invalid_select_index() {
 fn test_invalid_select_index_range_error() {
     let origins = ReferenceOrigins {
         origins: HashMap::default(),
+        nested_stack_outputs: HashMap::default(),
+        resource_types: HashMap::default(),
     };
     let translator = ResourceTranslator {
         schema: Schema::builtin(),
@@ -306,6 +416,8 @@ fn test_invalid_select_index_range_error() {
 fn test_select_index_int_error() {
     let origins = ReferenceOrigins {
         origins: HashMap::default(),
+        nested_stack_outputs: HashMap::default(),
+        resource_types: HashMap::default(),
     };
     let translator = ResourceTranslator {
         schema: Schema::builtin(),
@@ -320,7 +432,402 @@ fn test_select_index_int_error() {
     assert_eq!("Index must be int for Select", result.to_string());
 }
 
+#[test]
+fn test_length_translation() {
+    let origins = ReferenceOrigins {
+        origins: HashMap::default(),
+        nested_stack_outputs: HashMap::default(),
+        resource_types: HashMap::default(),
+    };
+    let translator = ResourceTranslator {
+        schema: Schema::builtin(),
+        origins: &origins,
+        value_type: Some(TypeReference::Primitive(Primitive::Number)),
+    };
+    let resource_value = ResourceValue::IntrinsicFunction(Box::new(IntrinsicFunction::Length(
+        ResourceValue::Array(vec![ResourceValue::String("a".into())]),
+    )));
+    let result = translator.translate(resource_value).unwrap();
+    assert_eq!(
+        ResourceIr::Length(Box::new(ResourceIr::Array(
+            TypeReference::Primitive(Primitive::Json),
+            vec![ResourceIr::String("a".into())]
+        ))),
+        result
+    );
+}
+
+#[test]
+fn test_to_json_string_translation() {
+    let origins = ReferenceOrigins {
+        origins: HashMap::default(),
+        nested_stack_outputs: HashMap::default(),
+        resource_types: HashMap::default(),
+    };
+    let translator = ResourceTranslator {
+        schema: Schema::builtin(),
+        origins: &origins,
+        value_type: Some(TypeReference::Primitive(Primitive::String)),
+    };
+    let resource_value = ResourceValue::IntrinsicFunction(Box::new(
+        IntrinsicFunction::ToJsonString(ResourceValue::Object(IndexMap::from_iter([
+            ("Enabled".to_string(), ResourceValue::Bool(true)),
+            ("Count".to_string(), ResourceValue::Number(3)),
+        ]))),
+    ));
+    let result = translator.translate(resource_value).unwrap();
+    let ResourceIr::ToJsonString(value) = &result else {
+        panic!("unexpected translation: {result:?}");
+    };
+    assert_eq!(
+        r#"{"Count":3,"Enabled":true}"#,
+        value.to_json().unwrap().to_string()
+    );
+}
+
+#[test]
+fn test_to_json_with_reference() {
+    let value = ResourceIr::Array(
+        TypeReference::Primitive(Primitive::Json),
+        vec![ResourceIr::Ref(Reference::new(
+            "A",
+            Origin::LogicalId { conditional: false },
+        ))],
+    );
+    assert_eq!(None, value.to_json());
+}
+
+#[test]
+fn test_creation_and_update_replace_policies() {
+    let template: CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Parameters:
+          Capacity:
+            Type: String
+        Resources:
+          Group:
+            Type: AWS::AutoScaling::AutoScalingGroup
+            UpdateReplacePolicy: Retain
+            CreationPolicy:
+              AutoScalingCreationPolicy:
+                MinSuccessfulInstancesPercent: "50"
+              ResourceSignal:
+                Count: !Ref Capacity
+                Timeout: PT15M
+              StartFleet: "true"
+            Properties:
+              MaxSize: "3"
+              MinSize: "1"
+        "#,
+    )
+    .unwrap();
+
+    let origins = ReferenceOrigins::new(&template);
+    let mut diagnostics = Diagnostics::default();
+    let resources = ResourceInstruction::from(
+        template.resources,
+        Schema::builtin(),
+        &origins,
+        &mut diagnostics,
+    );
+
+    assert!(diagnostics.is_empty(), "{diagnostics}");
+    assert_eq!(
+        resources[0].update_replace_policy,
+        Some(UpdateReplacePolicy::Retain)
+    );
+    assert_eq!(
+        resources[0].creation_policy,
+        Some(CreationPolicyIr {
+            auto_scaling_creation_policy: Some(AutoScalingCreationPolicyIr {
+                min_successful_instances_percent: Some(ResourceIr::Number(50)),
+            }),
+            resource_signal: Some(ResourceSignalIr {
+                count: Some(ResourceIr::Ref(Reference::new(
+                    "Capacity",
+                    Origin::Parameter
+                ))),
+                timeout: Some(ResourceIr::String("PT15M".into())),
+            }),
+            start_fleet: Some(ResourceIr::Bool(true)),
+        })
+    );
+}
+
+#[test]
+fn test_creation_policy_diagnostic() {
+    let template: CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Resources:
+          Group:
+            Type: AWS::AutoScaling::AutoScalingGroup
+            CreationPolicy:
+              ResourceSignal:
+                Count: three
+            Properties:
+              MaxSize: "3"
+              MinSize: "1"
+        "#,
+    )
+    .unwrap();
+
+    let origins = ReferenceOrigins::new(&template);
+    let mut diagnostics = Diagnostics::default();
+    ResourceInstruction::from(
+        template.resources,
+        Schema::builtin(),
+        &origins,
+        &mut diagnostics,
+    );
+
+    let diagnostic = diagnostics.iter().next().unwrap();
+    assert_eq!(
+        diagnostic.path,
+        "Resources.Group.CreationPolicy.ResourceSignal.Count"
+    );
+}
+
+#[test]
+fn test_collects_all_diagnostics() {
+    let template: CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Resources:
+          Group:
+            Type: AWS::EC2::SecurityGroup
+            Properties:
+              GroupDescription: test
+              Foo: bar
+              SecurityGroupIngress:
+                - IpProtocol: tcp
+                  FromPort: 80
+                - IpProtocol: tcp
+                  FromPort: eighty
+          Broken:
+            Type: AWS::S3::Bucket
+            Properties:
+              Bar: baz
+        "#,
+    )
+    .unwrap();
+
+    let origins = ReferenceOrigins::new(&template);
+    let mut diagnostics = Diagnostics::default();
+    ResourceInstruction::from(
+        template.resources,
+        Schema::builtin(),
+        &origins,
+        &mut diagnostics,
+    );
+
+    let paths: Vec<&str> = diagnostics.iter().map(|d| d.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "Resources.Group.Properties.Foo",
+            "Resources.Group.Properties.SecurityGroupIngress[1].FromPort",
+            "Resources.Broken.Properties.Bar",
+        ]
+    );
+    assert!(diagnostics.has_errors());
+}
+
+#[test]
+fn test_unresolved_reference_diagnostic() {
+    let template: CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Resources:
+          Queue:
+            Type: AWS::SQS::Queue
+            DependsOn: Missing
+        "#,
+    )
+    .unwrap();
+
+    let origins = ReferenceOrigins::new(&template);
+    let mut diagnostics = Diagnostics::default();
+    ResourceInstruction::from(
+        template.resources,
+        Schema::builtin(),
+        &origins,
+        &mut diagnostics,
+    );
+
+    let diagnostic = diagnostics.iter().next().unwrap();
+    assert_eq!(diagnostic.path, "Resources.Queue");
+    assert_eq!(
+        diagnostic.message,
+        "Queue references Missing, which is not defined in the template"
+    );
+}
+
+#[test]
+fn test_sub_attribute_references() {
+    let template: CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Resources:
+          Bucket:
+            Type: AWS::S3::Bucket
+          Queue:
+            Type: AWS::SQS::Queue
+            Properties:
+              QueueName: !Sub ${Bucket.Arn}-${!Bucket.Arn}
+          Topic:
+            Type: AWS::SNS::Topic
+            Properties:
+              TopicName: !Sub ${Bucket.Nope}
+              DisplayName: !GetAtt Queue.Url
+        "#,
+    )
+    .unwrap();
+
+    let origins = ReferenceOrigins::new(&template);
+    let mut diagnostics = Diagnostics::default();
+    let resources = ResourceInstruction::from(
+        template.resources,
+        Schema::builtin(),
+        &origins,
+        &mut diagnostics,
+    );
+
+    let queue = resources.iter().find(|r| r.name == "Queue").unwrap();
+    assert_eq!(
+        queue.properties["QueueName"],
+        ResourceIr::Sub(vec![
+            ResourceIr::Ref(Reference::new(
+                "Bucket",
+                Origin::GetAttribute {
+                    attribute: "Arn".into(),
+                    conditional: false,
+                },
+            )),
+            ResourceIr::String("-${Bucket.Arn}".into()),
+        ])
+    );
+
+    let messages: Vec<(&str, &str)> = diagnostics
+        .iter()
+        .map(|d| (d.path.as_str(), d.message.as_str()))
+        .collect();
+    assert_eq!(
+        messages,
+        vec![
+            (
+                "Resources.Topic.Properties.TopicName",
+                "Nope is not an attribute of Bucket (AWS::S3::Bucket)"
+            ),
+            (
+                "Resources.Topic.Properties.DisplayName",
+                "Url is not an attribute of Queue (AWS::SQS::Queue)"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_union_member_resolution() {
+    let origins = ReferenceOrigins {
+        origins: HashMap::default(),
+        nested_stack_outputs: HashMap::default(),
+        resource_types: HashMap::default(),
+    };
+    let named = |name: &'static str| TypeReference::Named(name.into());
+    let policy = named("AWS::Serverless::Function.IAMPolicyDocument");
+    let template = named("AWS::Serverless::Function.SAMPolicyTemplate");
+    let translator = ResourceTranslator {
+        schema: Schema::builtin(),
+        origins: &origins,
+        value_type: Some(TypeReference::Union(TypeUnion::Vec(vec![
+            TypeReference::Primitive(Primitive::String),
+            policy.clone(),
+            TypeReference::List(
+                TypeReference::Union(TypeUnion::Vec(vec![
+                    TypeReference::Primitive(Primitive::String),
+                    policy.clone(),
+                    template.clone(),
+                ]))
+                .into(),
+            ),
+        ]))),
+    };
+    let policies: ResourceValue = serde_yaml::from_str(
+        r#"
+        - AWSLambdaExecute
+        - Statement: []
+        - EC2DescribePolicy: {}
+        "#,
+    )
+    .unwrap();
+
+    let ResourceIr::Array(_, items) = translator.translate(policies).unwrap() else {
+        panic!("policies should be a list");
+    };
+    assert_eq!(items[0], ResourceIr::String("AWSLambdaExecute".into()));
+    assert!(matches!(&items[1], ResourceIr::Object(structure, _) if *structure == policy));
+    assert!(matches!(&items[2], ResourceIr::Object(structure, _) if *structure == template));
+
+    let document: ResourceValue = serde_yaml::from_str("{ Version: '2012-10-17' }").unwrap();
+    assert!(matches!(
+        translator.translate(document).unwrap(),
+        ResourceIr::Object(structure, _) if structure == policy
+    ));
+
+    // Nothing matches the value: it is rejected as before
+    let unknown: ResourceValue = serde_yaml::from_str("{ Unknown: true }").unwrap();
+    assert!(translator.translate(unknown).is_err());
+}
+
+#[test]
+fn test_unknown_named_type_is_json() {
+    let origins = ReferenceOrigins {
+        origins: HashMap::default(),
+        nested_stack_outputs: HashMap::default(),
+        resource_types: HashMap::default(),
+    };
+    let translator = ResourceTranslator {
+        schema: Schema::builtin(),
+        origins: &origins,
+        value_type: Some(TypeReference::Named("AWS::Nope::Nope.Nope".into())),
+    };
+    let value: ResourceValue = serde_yaml::from_str("{ Key: value }").unwrap();
+    assert_eq!(
+        translator.translate(value).unwrap(),
+        ResourceIr::Object(
+            TypeReference::Primitive(Primitive::Json),
+            create_property("Key", ResourceIr::String("value".into()))
+        )
+    );
+}
+
 #[inline]
 fn create_property(name: &str, resource: ResourceIr) -> IndexMap<String, ResourceIr, Hasher> {
     IndexMap::from_iter([(name.into(), resource)])
 }
+
+#[test]
+fn test_unsupported_transform_reports_its_path() {
+    let origins = ReferenceOrigins {
+        origins: HashMap::default(),
+        nested_stack_outputs: HashMap::default(),
+        resource_types: HashMap::default(),
+    };
+    let translator = ResourceTranslator {
+        schema: Schema::builtin(),
+        origins: &origins,
+        value_type: Some(TypeReference::Primitive(Primitive::Json)),
+    };
+    let resource_value =
+        ResourceValue::Array(vec![ResourceValue::Object(IndexMap::from_iter([(
+            "Value".to_string(),
+            ResourceValue::IntrinsicFunction(Box::new(IntrinsicFunction::Transform)),
+        )]))]);
+    let error = translator.translate(resource_value).unwrap_err();
+
+    let mut diagnostics = Diagnostics::default();
+    diagnostics.report("Resources.Bucket.Properties.Tags", error);
+    let diagnostic = diagnostics.iter().next().unwrap();
+    assert_eq!(diagnostic.path, "Resources.Bucket.Properties.Tags[0].Value");
+    assert_eq!(
+        diagnostic.message,
+        "Fn::Transform is not supported in Resources.Bucket.Properties.Tags[0].Value"
+    );
+}
@@ -0,0 +1,169 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use indexmap::IndexMap;
+
+use super::diagnostics::Diagnostics;
+use super::reference::{Origin, Reference};
+use super::ReferenceOrigins;
+
+use crate::parser::rules::{Rule, RuleFunction, RuleValue};
+use crate::util::Hasher;
+use crate::Error;
+
+// RuleInstructions are emitted as CfnRule constructs, so that the migrated stack keeps validating
+// its parameters the same way the original template did.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleInstruction {
+    pub name: String,
+    pub condition: Option<RuleIr>,
+    pub assertions: Vec<AssertionInstruction>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssertionInstruction {
+    pub assert: RuleIr,
+    pub description: Option<String>,
+}
+
+impl RuleInstruction {
+    pub(super) fn from(
+        parse_tree: IndexMap<String, Rule, Hasher>,
+        origins: &ReferenceOrigins,
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<Self> {
+        parse_tree
+            .into_iter()
+            .filter_map(|(name, rule)| {
+                let mut valid = true;
+                let mut report = |path: String, err: Error| {
+                    diagnostics.report(path, err);
+                    valid = false;
+                };
+
+                let condition = match rule.rule_condition.map(|c| c.into_ir(&name, origins)) {
+                    Some(Ok(condition)) => Some(condition),
+                    Some(Err(err)) => {
+                        report(format!("Rules.{name}.RuleCondition"), err);
+                        None
+                    }
+                    None => None,
+                };
+
+                let mut assertions = Vec::with_capacity(rule.assertions.len());
+                for (index, assertion) in rule.assertions.into_iter().enumerate() {
+                    match assertion.assert.into_ir(&name, origins) {
+                        Ok(assert) => assertions.push(AssertionInstruction {
+                            assert,
+                            description: assertion.assert_description,
+                        }),
+                        Err(err) => report(format!("Rules.{name}.Assertions[{index}].Assert"), err),
+                    }
+                }
+
+                valid.then_some(RuleInstruction {
+                    name,
+                    condition,
+                    assertions,
+                })
+            })
+            .collect()
+    }
+}
+
+// RuleIr is the boolean expression of a rule condition or assertion. Unlike template conditions,
+// these cannot be evaluated by the CDK app: they are checked by CloudFormation when the stack is
+// created or updated, so every synthesizer emits them using the `Fn.condition*` functions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleIr {
+    And(Vec<RuleIr>),
+    Or(Vec<RuleIr>),
+    Not(Box<RuleIr>),
+    Equals(RuleValueIr, RuleValueIr),
+    Contains(RuleValueIr, RuleValueIr),
+    EachMemberEquals(RuleValueIr, RuleValueIr),
+    EachMemberIn(RuleValueIr, RuleValueIr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleValueIr {
+    Str(String),
+    List(Vec<RuleValueIr>),
+    Ref(Reference),
+    RefAll(String),
+    ValueOf(String, String),
+    ValueOfAll(String, String),
+}
+
+impl RuleFunction {
+    fn into_ir(self, rule: &str, origins: &ReferenceOrigins) -> Result<RuleIr, Error> {
+        let list = |functions: Vec<RuleFunction>| {
+            functions
+                .into_iter()
+                .map(|function| function.into_ir(rule, origins))
+                .collect::<Result<_, _>>()
+        };
+        Ok(match self {
+            Self::And(x) => RuleIr::And(list(x)?),
+            Self::Or(x) => RuleIr::Or(list(x)?),
+            Self::Not(x) => RuleIr::Not(Box::new(x.into_ir(rule, origins)?)),
+            Self::Equals(a, b) => {
+                RuleIr::Equals(a.into_ir(rule, origins)?, b.into_ir(rule, origins)?)
+            }
+            Self::Contains(a, b) => {
+                RuleIr::Contains(a.into_ir(rule, origins)?, b.into_ir(rule, origins)?)
+            }
+            Self::EachMemberEquals(a, b) => {
+                RuleIr::EachMemberEquals(a.into_ir(rule, origins)?, b.into_ir(rule, origins)?)
+            }
+            Self::EachMemberIn(a, b) => {
+                RuleIr::EachMemberIn(a.into_ir(rule, origins)?, b.into_ir(rule, origins)?)
+            }
+        })
+    }
+}
+
+impl RuleValue {
+    fn into_ir(self, rule: &str, origins: &ReferenceOrigins) -> Result<RuleValueIr, Error> {
+        Ok(match self {
+            Self::String(x) => RuleValueIr::Str(x),
+            Self::List(x) => RuleValueIr::List(
+                x.into_iter()
+                    .map(|val| val.into_ir(rule, origins))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Self::Ref(name) => {
+                // Rules can only look at parameters (and pseudo parameters), as they are
+                // evaluated before any resource is created.
+                let origin = rule_parameter(rule, &name, origins)?;
+                RuleValueIr::Ref(Reference { origin, name })
+            }
+            Self::RefAll(parameter_type) => RuleValueIr::RefAll(parameter_type),
+            Self::ValueOf(name, attribute) => {
+                rule_parameter(rule, &name, origins)?;
+                RuleValueIr::ValueOf(name, attribute)
+            }
+            Self::ValueOfAll(parameter_type, attribute) => {
+                RuleValueIr::ValueOfAll(parameter_type, attribute)
+            }
+        })
+    }
+}
+
+fn rule_parameter(rule: &str, name: &str, origins: &ReferenceOrigins) -> Result<Origin, Error> {
+    match origins.for_ref(name) {
+        Some(origin @ (Origin::Parameter | Origin::CfnParameter | Origin::PseudoParameter(_))) => {
+            Ok(origin)
+        }
+        Some(_) => Err(Error::UnsupportedIntrinsic {
+            intrinsic: format!("Ref to resource {name}"),
+            location: "Rules".into(),
+        }),
+        None => Err(Error::UnresolvedReference {
+            from: rule.into(),
+            to: name.into(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,115 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use crate::ir::reference::PseudoParameter;
+use crate::CloudformationParseTree;
+
+use super::*;
+
+fn translate(template: &str) -> (Vec<RuleInstruction>, Diagnostics) {
+    let template: CloudformationParseTree = serde_yaml::from_str(template).unwrap();
+    let origins = ReferenceOrigins::new(&template);
+    let mut diagnostics = Diagnostics::default();
+    let rules = RuleInstruction::from(template.rules, &origins, &mut diagnostics);
+    (rules, diagnostics)
+}
+
+#[test]
+fn rule_with_condition_and_assertions() {
+    let (rules, diagnostics) = translate(
+        r#"
+        Parameters:
+          Environment:
+            Type: String
+          Subnets:
+            Type: List<AWS::EC2::Subnet::Id>
+        Rules:
+          ProdSubnets:
+            RuleCondition: !Equals [!Ref Environment, prod]
+            Assertions:
+              - Assert: !EachMemberIn [!ValueOf [Subnets, VpcId], !RefAll AWS::EC2::VPC::Id]
+                AssertDescription: Subnets must be in an existing VPC
+              - Assert: !Not [!Equals [!Ref AWS::Region, us-east-1]]
+        Resources: {}
+        "#,
+    );
+
+    assert!(diagnostics.is_empty(), "{diagnostics}");
+    assert_eq!(
+        rules,
+        vec![RuleInstruction {
+            name: "ProdSubnets".into(),
+            condition: Some(RuleIr::Equals(
+                RuleValueIr::Ref(Reference::new("Environment", Origin::Parameter)),
+                RuleValueIr::Str("prod".into()),
+            )),
+            assertions: vec![
+                AssertionInstruction {
+                    assert: RuleIr::EachMemberIn(
+                        RuleValueIr::ValueOf("Subnets".into(), "VpcId".into()),
+                        RuleValueIr::RefAll("AWS::EC2::VPC::Id".into()),
+                    ),
+                    description: Some("Subnets must be in an existing VPC".into()),
+                },
+                AssertionInstruction {
+                    assert: RuleIr::Not(Box::new(RuleIr::Equals(
+                        RuleValueIr::Ref(Reference::new(
+                            "AWS::Region",
+                            Origin::PseudoParameter(PseudoParameter::Region),
+                        )),
+                        RuleValueIr::Str("us-east-1".into()),
+                    ))),
+                    description: None,
+                },
+            ],
+        }]
+    );
+}
+
+#[test]
+fn undefined_parameter_is_reported() {
+    let (rules, diagnostics) = translate(
+        r#"
+        Rules:
+          Broken:
+            Assertions:
+              - Assert: !Contains [[a, b], !Ref Missing]
+          Valid:
+            Assertions:
+              - Assert: !Equals [a, a]
+        Resources: {}
+        "#,
+    );
+
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0].name, "Valid");
+
+    let diagnostic = diagnostics.iter().next().unwrap();
+    assert_eq!(diagnostic.path, "Rules.Broken.Assertions[0].Assert");
+    assert_eq!(
+        diagnostic.message,
+        "Broken references Missing, which is not defined in the template"
+    );
+}
+
+#[test]
+fn resource_reference_is_reported() {
+    let (rules, diagnostics) = translate(
+        r#"
+        Rules:
+          Broken:
+            RuleCondition: !Equals [!Ref Queue, a]
+            Assertions: []
+        Resources:
+          Queue:
+            Type: AWS::SQS::Queue
+        "#,
+    );
+
+    assert!(rules.is_empty());
+    let diagnostic = diagnostics.iter().next().unwrap();
+    assert_eq!(diagnostic.path, "Rules.Broken.RuleCondition");
+    assert_eq!(
+        diagnostic.message,
+        "Ref to resource Queue is not supported in Rules"
+    );
+}
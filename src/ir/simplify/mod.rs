@@ -0,0 +1,662 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+use crate::cdk::{Primitive, PropertyBag, Schema, TypeReference};
+use crate::ir::conditions::{ConditionInstruction, ConditionIr};
+use crate::ir::importer::ImportInstruction;
+use crate::ir::mappings::MappingInstruction;
+use crate::ir::reference::Origin;
+use crate::ir::resources::{find_references, ResourceIr, ResourceType};
+use crate::ir::CloudformationProgramIr;
+use crate::parser::lookup_table::MappingInnerValue;
+
+impl CloudformationProgramIr {
+    // simplify evaluates what the template computes from literals alone, so that the synthesized
+    // code does not: `Fn::If` on conditions that only depend on literals, and `Fn::Join`,
+    // `Fn::Select`, `Fn::Split`, `Fn::Sub`, `Fn::Length` and `Fn::FindInMap` over literal values.
+    // The resources and outputs whose condition is always false are removed, as are the conditions
+    // and mappings that are no longer used. `schema` gives the type of the properties that values
+    // of mappings are folded into.
+    pub fn simplify(&mut self, schema: &Schema) {
+        for resource in &mut self.resources {
+            if let Some(nested) = &mut resource.nested_stack {
+                nested.program.simplify(schema);
+            }
+        }
+
+        // The mappings are only read, but the values they are folded into belong to `self` too.
+        let mappings = self.mappings.clone();
+        let folder = Folder {
+            conditions: known_conditions(&self.conditions, &mappings),
+            mappings: &mappings,
+            schema,
+        };
+        // Properties set to `AWS::NoValue` are left out, like CloudFormation does.
+        for resource in &mut self.resources {
+            let spec = schema.resource_type(&resource.resource_type.to_string());
+            resource.properties = mem::take(&mut resource.properties)
+                .into_iter()
+                .filter_map(|(name, value)| {
+                    let value_type = spec
+                        .and_then(|spec| spec.property(&name))
+                        .map(|property| property.value_type)
+                        .unwrap_or_default();
+                    folder
+                        .fold_present(value, &value_type)
+                        .map(|value| (name, value))
+                })
+                .collect();
+        }
+        let mut values = Vec::new();
+        self.values_mut(&mut values, false);
+        for value in values {
+            *value = folder.fold(mem::replace(value, ResourceIr::Null), &UNKNOWN);
+        }
+        let conditions = folder.conditions;
+
+        self.outputs.retain_mut(|output| {
+            match output
+                .condition
+                .as_ref()
+                .and_then(|name| conditions.get(name))
+            {
+                Some(false) => false,
+                Some(true) => {
+                    output.condition = None;
+                    true
+                }
+                None => true,
+            }
+        });
+
+        // Resources that always exist are no longer conditional, and resources that never do are
+        // removed, unless something still refers to them.
+        let mut unconditional = HashSet::new();
+        for resource in &mut self.resources {
+            if let Some(true) = resource
+                .condition
+                .as_ref()
+                .and_then(|name| conditions.get(name))
+            {
+                resource.condition = None;
+                unconditional.insert(resource.name.clone());
+            }
+            if resource.l2_construct.is_none() {
+                resource.references.clear();
+                resource.generate_references();
+            }
+        }
+        let mut referenced: HashSet<String> = self
+            .resources
+            .iter()
+            .flat_map(|resource| resource.references.iter().cloned())
+            .collect();
+        for output in &self.outputs {
+            referenced.extend(find_references(&output.value));
+        }
+        let mut removed = Vec::new();
+        self.resources.retain(|resource| {
+            let never = resource
+                .condition
+                .as_ref()
+                .and_then(|name| conditions.get(name))
+                == Some(&false);
+            if never && !referenced.contains(&resource.name) {
+                removed.push(resource.resource_type.clone());
+            }
+            !never || referenced.contains(&resource.name)
+        });
+        self.remove_unused_imports(&removed);
+
+        let mut values = Vec::new();
+        self.values_mut(&mut values, true);
+        for value in values {
+            value.visit_references(&mut |reference| {
+                if !unconditional.contains(&reference.name) {
+                    return;
+                }
+                if let Origin::LogicalId { conditional }
+                | Origin::GetAttribute { conditional, .. } = &mut reference.origin
+                {
+                    *conditional = false;
+                }
+            });
+        }
+
+        self.remove_unused_conditions();
+        self.remove_unused_mappings();
+    }
+
+    // Every value of the program which may use intrinsic functions, in no particular order. The
+    // properties of resources are only included if `properties` is set.
+    pub(super) fn values_mut<'a>(
+        &'a mut self,
+        values: &mut Vec<&'a mut ResourceIr>,
+        properties: bool,
+    ) {
+        for resource in &mut self.resources {
+            if properties {
+                values.extend(resource.properties.values_mut());
+            }
+            values.extend(resource.metadata.as_mut());
+            values.extend(resource.update_policy.as_mut());
+            if let Some(policy) = &mut resource.creation_policy {
+                values.extend(policy.start_fleet.as_mut());
+                if let Some(auto_scaling) = &mut policy.auto_scaling_creation_policy {
+                    values.extend(auto_scaling.min_successful_instances_percent.as_mut());
+                }
+                if let Some(signal) = &mut policy.resource_signal {
+                    values.extend(signal.count.as_mut());
+                    values.extend(signal.timeout.as_mut());
+                }
+            }
+            if let Some(nested) = &mut resource.nested_stack {
+                values.extend(nested.parameters.values_mut());
+            }
+            if let Some(construct) = &mut resource.l2_construct {
+                values.extend(construct.values_mut());
+            }
+        }
+        for output in &mut self.outputs {
+            values.push(&mut output.value);
+            values.extend(output.export.as_mut());
+        }
+        if let Some(metadata) = &mut self.metadata {
+            values.extend(metadata.entries.values_mut());
+        }
+    }
+
+    fn remove_unused_imports(&mut self, removed: &[ResourceType]) {
+        let used: Vec<ImportInstruction> = self
+            .resources
+            .iter()
+            .filter_map(|resource| import_of(&resource.resource_type))
+            .collect();
+        for import in removed.iter().filter_map(import_of) {
+            if !used.contains(&import) {
+                self.imports.retain(|existing| *existing != import);
+            }
+        }
+    }
+
+    // Removes the conditions nothing uses, and returns their names.
+    pub(super) fn remove_unused_conditions(&mut self) -> Vec<String> {
+        let mut used: HashSet<String> = HashSet::new();
+        used.extend(self.resources.iter().filter_map(|r| r.condition.clone()));
+        used.extend(self.outputs.iter().filter_map(|o| o.condition.clone()));
+        let mut values = Vec::new();
+        self.values_mut(&mut values, true);
+        for value in values {
+            visit(value, &mut |value| {
+                if let ResourceIr::If(name, ..) = value {
+                    used.insert(name.clone());
+                }
+            });
+        }
+
+        // Conditions come after the conditions they use, so walking them backwards finds every
+        // condition used by another one that is kept.
+        let mut kept = Vec::with_capacity(self.conditions.len());
+        let mut removed = Vec::new();
+        for condition in self.conditions.drain(..).rev() {
+            if !used.contains(&condition.name) {
+                removed.push(condition.name);
+            } else {
+                visit_condition(&condition.value, &mut |value| match value {
+                    ConditionIr::Condition(name) => {
+                        used.insert(name.clone());
+                    }
+                    ConditionIr::Ref(reference) if reference.origin == Origin::Condition => {
+                        used.insert(reference.name.clone());
+                    }
+                    _ => {}
+                });
+                kept.push(condition);
+            }
+        }
+        kept.reverse();
+        self.conditions = kept;
+        removed.reverse();
+        removed
+    }
+
+    // Removes the mappings nothing uses, and returns their names.
+    pub(super) fn remove_unused_mappings(&mut self) -> Vec<String> {
+        let mut used: HashSet<String> = HashSet::new();
+        for condition in &self.conditions {
+            visit_condition(&condition.value, &mut |value| {
+                if let ConditionIr::Map(name, ..) = value {
+                    used.insert(name.clone());
+                }
+            });
+        }
+        let mut values = Vec::new();
+        self.values_mut(&mut values, true);
+        for value in values {
+            visit(value, &mut |value| {
+                if let ResourceIr::Map(name, ..) = value {
+                    used.insert(name.clone());
+                }
+            });
+        }
+        let mut removed = Vec::new();
+        self.mappings.retain(|mapping| {
+            let keep = used.contains(&mapping.name);
+            if !keep {
+                removed.push(mapping.name.clone());
+            }
+            keep
+        });
+        removed
+    }
+}
+
+// The value of a condition expression, when it only depends on literals.
+#[derive(Debug, PartialEq)]
+enum Known {
+    Bool(bool),
+    String(String),
+    List(Vec<String>),
+}
+
+impl Known {
+    fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn text(&self) -> Option<String> {
+        match self {
+            Self::Bool(value) => Some(value.to_string()),
+            Self::String(value) => Some(value.clone()),
+            Self::List(_) => None,
+        }
+    }
+}
+
+// Evaluates the conditions that only depend on literals (and on other such conditions).
+// `conditions` are in dependency order, as `ConditionInstruction::from` sorts them.
+fn known_conditions(
+    conditions: &[ConditionInstruction],
+    mappings: &[MappingInstruction],
+) -> HashMap<String, bool> {
+    let mut known = HashMap::new();
+    for condition in conditions {
+        if let Some(value) = evaluate(&condition.value, &known, mappings).and_then(|v| v.as_bool())
+        {
+            known.insert(condition.name.clone(), value);
+        }
+    }
+    known
+}
+
+fn evaluate(
+    condition: &ConditionIr,
+    known: &HashMap<String, bool>,
+    mappings: &[MappingInstruction],
+) -> Option<Known> {
+    let eval = |condition: &ConditionIr| evaluate(condition, known, mappings);
+    match condition {
+        // A single operand that is known decides And and Or, even if the others are not.
+        ConditionIr::And(operands) | ConditionIr::Or(operands) => {
+            let decisive = matches!(condition, ConditionIr::Or(_));
+            let mut all_known = true;
+            for operand in operands {
+                match eval(operand).and_then(|value| value.as_bool()) {
+                    Some(value) if value == decisive => return Some(Known::Bool(decisive)),
+                    Some(_) => {}
+                    None => all_known = false,
+                }
+            }
+            all_known.then_some(Known::Bool(!decisive))
+        }
+        ConditionIr::Not(operand) => Some(Known::Bool(!eval(operand)?.as_bool()?)),
+        ConditionIr::Equals(left, right) => match (eval(left)?, eval(right)?) {
+            (Known::List(left), Known::List(right)) => Some(Known::Bool(left == right)),
+            (left, right) => Some(Known::Bool(left.text()? == right.text()?)),
+        },
+        ConditionIr::Condition(name) => known.get(name).copied().map(Known::Bool),
+        ConditionIr::Ref(reference) if reference.origin == Origin::Condition => {
+            known.get(&reference.name).copied().map(Known::Bool)
+        }
+        ConditionIr::Ref(_) => None,
+        ConditionIr::Map(name, top_level_key, second_level_key) => {
+            let value = lookup(
+                mappings,
+                name,
+                &eval(top_level_key)?.text()?,
+                &eval(second_level_key)?.text()?,
+            )?;
+            Some(match value {
+                MappingInnerValue::List(items) => Known::List(items.clone()),
+                MappingInnerValue::String(text) => Known::String(text.clone()),
+                MappingInnerValue::Number(number) => Known::String(number.to_string()),
+                MappingInnerValue::Float(float) => Known::String(float.to_string()),
+                MappingInnerValue::Bool(value) => Known::String(value.to_string()),
+            })
+        }
+        ConditionIr::Split(separator, text) => Some(Known::List(
+            eval(text)?
+                .text()?
+                .split(separator.as_str())
+                .map(String::from)
+                .collect(),
+        )),
+        ConditionIr::Select(index, list) => match eval(list)? {
+            Known::List(mut items) if *index < items.len() => {
+                Some(Known::String(items.swap_remove(*index)))
+            }
+            _ => None,
+        },
+        ConditionIr::Str(text) => Some(Known::String(text.clone())),
+    }
+}
+
+fn lookup<'a>(
+    mappings: &'a [MappingInstruction],
+    name: &str,
+    top_level_key: &str,
+    second_level_key: &str,
+) -> Option<&'a MappingInnerValue> {
+    mappings
+        .iter()
+        .find(|mapping| mapping.name == name)?
+        .map
+        .get(top_level_key)?
+        .get(second_level_key)
+}
+
+// The type of values whose type is not known, such as those of outputs or of the parts of a
+// `Fn::Join`. CloudFormation gives them the values of mappings as strings.
+const UNKNOWN: TypeReference = TypeReference::Primitive(Primitive::Unknown);
+const STRING: TypeReference = TypeReference::Primitive(Primitive::String);
+
+struct Folder<'a> {
+    conditions: HashMap<String, bool>,
+    mappings: &'a [MappingInstruction],
+    schema: &'a Schema,
+}
+
+impl Folder<'_> {
+    // Folds `value`, which is used where a value of `value_type` is expected.
+    fn fold(&self, value: ResourceIr, value_type: &TypeReference) -> ResourceIr {
+        let fold = |value: Box<ResourceIr>| Box::new(self.fold(*value, &UNKNOWN));
+        match value {
+            ResourceIr::If(condition, when_true, when_false) => {
+                match self.conditions.get(&condition) {
+                    Some(true) => self.fold(*when_true, value_type),
+                    Some(false) => self.fold(*when_false, value_type),
+                    None => ResourceIr::If(
+                        condition,
+                        Box::new(self.fold(*when_true, value_type)),
+                        Box::new(self.fold(*when_false, value_type)),
+                    ),
+                }
+            }
+            ResourceIr::Array(item_type, items) => {
+                let items = items
+                    .into_iter()
+                    .filter_map(|item| self.fold_present(item, &item_type))
+                    .collect();
+                ResourceIr::Array(item_type, items)
+            }
+            ResourceIr::Object(structure, entries) => {
+                let entries = entries
+                    .into_iter()
+                    .filter_map(|(key, value)| {
+                        let value_type = self.entry_type(&structure, &key);
+                        self.fold_present(value, &value_type)
+                            .map(|value| (key, value))
+                    })
+                    .collect();
+                ResourceIr::Object(structure, entries)
+            }
+            ResourceIr::Join(separator, parts) => {
+                let mut parts: Vec<ResourceIr> = parts
+                    .into_iter()
+                    .filter_map(|part| self.fold_present(part, &UNKNOWN))
+                    .collect();
+                if let [ResourceIr::Array(_, items)] = parts.as_mut_slice() {
+                    if items
+                        .iter()
+                        .all(|item| matches!(item, ResourceIr::String(_)))
+                    {
+                        parts = mem::take(items);
+                    }
+                }
+                let parts = merge_strings(parts, &separator);
+                match parts.as_slice() {
+                    [] => ResourceIr::String(String::new()),
+                    [ResourceIr::String(_)] => parts.into_iter().next().unwrap(),
+                    _ => ResourceIr::Join(separator, parts),
+                }
+            }
+            ResourceIr::Sub(parts) => {
+                let parts = parts
+                    .into_iter()
+                    .map(|part| match self.fold(part, &UNKNOWN) {
+                        ResourceIr::Number(number) => ResourceIr::String(number.to_string()),
+                        ResourceIr::Bool(value) => ResourceIr::String(value.to_string()),
+                        part => part,
+                    })
+                    .collect();
+                let parts = merge_strings(parts, "");
+                match parts.as_slice() {
+                    [] => ResourceIr::String(String::new()),
+                    [ResourceIr::String(_)] => parts.into_iter().next().unwrap(),
+                    _ => ResourceIr::Sub(parts),
+                }
+            }
+            ResourceIr::Select(index, list) => match self.fold(*list, &UNKNOWN) {
+                ResourceIr::Array(_, mut items) if index < items.len() => items.swap_remove(index),
+                list => ResourceIr::Select(index, Box::new(list)),
+            },
+            ResourceIr::Split(separator, text) => match self.fold(*text, &STRING) {
+                ResourceIr::String(text) => ResourceIr::Array(
+                    TypeReference::Primitive(Primitive::String),
+                    text.split(separator.as_str())
+                        .map(|item| ResourceIr::String(item.into()))
+                        .collect(),
+                ),
+                text => ResourceIr::Split(separator, Box::new(text)),
+            },
+            ResourceIr::Map(name, top_level_key, second_level_key) => {
+                let top_level_key = self.fold(*top_level_key, &STRING);
+                let second_level_key = self.fold(*second_level_key, &STRING);
+                if let (ResourceIr::String(top), ResourceIr::String(second)) =
+                    (&top_level_key, &second_level_key)
+                {
+                    if let Some(value) = lookup(self.mappings, &name, top, second)
+                        .and_then(|value| mapping_value(value, value_type))
+                    {
+                        return value;
+                    }
+                }
+                ResourceIr::Map(name, Box::new(top_level_key), Box::new(second_level_key))
+            }
+            ResourceIr::Length(list) => match self.fold(*list, &UNKNOWN) {
+                ResourceIr::Array(_, items) => ResourceIr::Number(items.len() as i64),
+                list => ResourceIr::Length(Box::new(list)),
+            },
+            ResourceIr::Base64(value) => ResourceIr::Base64(fold(value)),
+            ResourceIr::ImportValue(value) => ResourceIr::ImportValue(fold(value)),
+            ResourceIr::GetAZs(value) => ResourceIr::GetAZs(fold(value)),
+            ResourceIr::ToJsonString(value) => ResourceIr::ToJsonString(fold(value)),
+            ResourceIr::Cidr(range, count, mask) => {
+                ResourceIr::Cidr(fold(range), fold(count), fold(mask))
+            }
+            value @ (ResourceIr::Null
+            | ResourceIr::Bool(_)
+            | ResourceIr::Number(_)
+            | ResourceIr::Double(_)
+            | ResourceIr::String(_)
+            | ResourceIr::Ref(_)) => value,
+        }
+    }
+
+    // Folds an item of a list or an entry of an object, which is left out when it is a `Fn::If`
+    // resolving to `AWS::NoValue`.
+    fn fold_present(&self, value: ResourceIr, value_type: &TypeReference) -> Option<ResourceIr> {
+        let is_if = matches!(value, ResourceIr::If(..));
+        match self.fold(value, value_type) {
+            ResourceIr::Null if is_if => None,
+            value => Some(value),
+        }
+    }
+
+    // The type of the entry `key` of an object of type `structure`.
+    fn entry_type(&self, structure: &TypeReference, key: &str) -> TypeReference {
+        let name = match structure {
+            TypeReference::Map(item_type) => return (**item_type).clone(),
+            TypeReference::Named(name) => name,
+            TypeReference::List(item_type) => match &**item_type {
+                TypeReference::Named(name) => name,
+                _ => return UNKNOWN,
+            },
+            _ => return UNKNOWN,
+        };
+        self.schema
+            .type_named(name)
+            .and_then(|data_type| data_type.property(key))
+            .map(|property| property.value_type)
+            .unwrap_or_default()
+    }
+}
+
+// Joins the consecutive literal strings of `parts` with `separator`.
+fn merge_strings(parts: Vec<ResourceIr>, separator: &str) -> Vec<ResourceIr> {
+    let mut merged: Vec<ResourceIr> = Vec::with_capacity(parts.len());
+    for part in parts {
+        match (merged.last_mut(), part) {
+            (Some(ResourceIr::String(text)), ResourceIr::String(more)) => {
+                text.push_str(separator);
+                text.push_str(&more);
+            }
+            (_, part) => merged.push(part),
+        }
+    }
+    merged
+}
+
+// The literal a value of a mapping is folded into where a value of `value_type` is expected, if it
+// is one. Numbers and booleans are strings where a string is expected, or the type is not known.
+fn mapping_value(value: &MappingInnerValue, value_type: &TypeReference) -> Option<ResourceIr> {
+    let text = match value {
+        MappingInnerValue::List(items) => {
+            return matches!(
+                value_type,
+                TypeReference::List(_)
+                    | TypeReference::Primitive(Primitive::Json | Primitive::Unknown)
+            )
+            .then(|| {
+                ResourceIr::Array(
+                    STRING,
+                    items.iter().cloned().map(ResourceIr::String).collect(),
+                )
+            });
+        }
+        MappingInnerValue::String(text) => text.clone(),
+        MappingInnerValue::Number(number) => number.to_string(),
+        MappingInnerValue::Float(float) => float.to_string(),
+        MappingInnerValue::Bool(value) => value.to_string(),
+    };
+    let TypeReference::Primitive(primitive) = value_type else {
+        return None;
+    };
+    match (primitive, value) {
+        (Primitive::String | Primitive::Timestamp | Primitive::Unknown, _) => {
+            Some(ResourceIr::String(text))
+        }
+        (Primitive::Number | Primitive::Json, MappingInnerValue::Number(number)) => {
+            Some(ResourceIr::Number(*number))
+        }
+        (Primitive::Number | Primitive::Json, MappingInnerValue::Float(float)) => {
+            Some(ResourceIr::Double(*float))
+        }
+        (Primitive::Boolean | Primitive::Json, MappingInnerValue::Bool(value)) => {
+            Some(ResourceIr::Bool(*value))
+        }
+        (Primitive::Json, MappingInnerValue::String(_)) => Some(ResourceIr::String(text)),
+        _ => None,
+    }
+}
+
+// The import a resource of this type needs, as `ImportInstruction::from` determines it.
+fn import_of(resource_type: &ResourceType) -> Option<ImportInstruction> {
+    let (organization, service) = match resource_type {
+        ResourceType::AWS { service, .. } if service.eq_ignore_ascii_case("serverless") => {
+            ("AWS", "SAM")
+        }
+        ResourceType::AWS { service, .. } => ("AWS", service.as_str()),
+        ResourceType::Alexa { service, .. } => ("Alexa", service.as_str()),
+        ResourceType::Custom(_) => return None,
+    };
+    Some(ImportInstruction {
+        organization: organization.into(),
+        service: Some(service.into()),
+    })
+}
+
+pub(super) fn visit(value: &ResourceIr, visitor: &mut impl FnMut(&ResourceIr)) {
+    visitor(value);
+    match value {
+        ResourceIr::Null
+        | ResourceIr::Bool(_)
+        | ResourceIr::Number(_)
+        | ResourceIr::Double(_)
+        | ResourceIr::String(_)
+        | ResourceIr::Ref(_) => {}
+        ResourceIr::Array(_, items) | ResourceIr::Join(_, items) | ResourceIr::Sub(items) => {
+            for item in items {
+                visit(item, visitor);
+            }
+        }
+        ResourceIr::Object(_, entries) => {
+            for value in entries.values() {
+                visit(value, visitor);
+            }
+        }
+        ResourceIr::If(_, x, y) | ResourceIr::Map(_, x, y) => {
+            visit(x, visitor);
+            visit(y, visitor);
+        }
+        ResourceIr::Split(_, x)
+        | ResourceIr::Base64(x)
+        | ResourceIr::GetAZs(x)
+        | ResourceIr::Select(_, x)
+        | ResourceIr::ImportValue(x)
+        | ResourceIr::Length(x)
+        | ResourceIr::ToJsonString(x) => visit(x, visitor),
+        ResourceIr::Cidr(x, y, z) => {
+            visit(x, visitor);
+            visit(y, visitor);
+            visit(z, visitor);
+        }
+    }
+}
+
+pub(super) fn visit_condition(value: &ConditionIr, visitor: &mut impl FnMut(&ConditionIr)) {
+    visitor(value);
+    match value {
+        ConditionIr::And(operands) | ConditionIr::Or(operands) => {
+            for operand in operands {
+                visit_condition(operand, visitor);
+            }
+        }
+        ConditionIr::Equals(x, y) | ConditionIr::Map(_, x, y) => {
+            visit_condition(x, visitor);
+            visit_condition(y, visitor);
+        }
+        ConditionIr::Not(x) | ConditionIr::Split(_, x) | ConditionIr::Select(_, x) => {
+            visit_condition(x, visitor)
+        }
+        ConditionIr::Condition(_) | ConditionIr::Str(_) | ConditionIr::Ref(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,222 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use crate::cdk::Schema;
+use crate::ir::reference::Reference;
+use crate::ir::resources::ResourceInstruction;
+use crate::CloudformationParseTree;
+
+use super::*;
+
+fn program(source: &str) -> CloudformationProgramIr {
+    let template: CloudformationParseTree = serde_yaml::from_str(source).unwrap();
+    let mut ir = CloudformationProgramIr::from(template, Schema::builtin()).unwrap();
+    ir.simplify(Schema::builtin());
+    ir
+}
+
+fn resource<'a>(ir: &'a CloudformationProgramIr, name: &str) -> &'a ResourceInstruction {
+    ir.resources.iter().find(|r| r.name == name).unwrap()
+}
+
+fn string(text: &str) -> ResourceIr {
+    ResourceIr::String(text.into())
+}
+
+#[test]
+fn literal_intrinsics() {
+    let ir = program(
+        r#"
+        Parameters:
+          Env:
+            Type: String
+        Mappings:
+          Regions:
+            us-east-1:
+              Ami: ami-123
+              Zones: [a, b]
+        Resources:
+          Queue:
+            Type: AWS::SQS::Queue
+            Properties:
+              QueueName: !Join ['', [my, -, queue]]
+              DelaySeconds: !Select [1, [5, 10, 15]]
+              KmsMasterKeyId: !Join ['-', [a, b, !Ref Env, c, d]]
+              RedriveAllowPolicy: !Sub
+                - ${Name}-${Count}-${Env}
+                - Name: { Fn::Select: [0, { Fn::Split: [',', 'x,y'] }] }
+                  Count: 3
+              Tags:
+                - Key: ami
+                  Value: !FindInMap [Regions, us-east-1, Ami]
+                - Key: zones
+                  Value: !Join [',', !FindInMap [Regions, us-east-1, Zones]]
+                - Key: unknown
+                  Value: !FindInMap [Regions, !Ref AWS::Region, Ami]
+        "#,
+    );
+
+    let queue = resource(&ir, "Queue");
+    assert_eq!(queue.properties["QueueName"], string("my-queue"));
+    assert_eq!(queue.properties["DelaySeconds"], ResourceIr::Number(10));
+    assert_eq!(
+        queue.properties["KmsMasterKeyId"],
+        ResourceIr::Join(
+            "-".into(),
+            vec![
+                string("a-b"),
+                ResourceIr::Ref(Reference::new("Env", Origin::Parameter)),
+                string("c-d"),
+            ]
+        )
+    );
+    assert_eq!(
+        queue.properties["RedriveAllowPolicy"],
+        ResourceIr::Sub(vec![
+            string("x-3-"),
+            ResourceIr::Ref(Reference::new("Env", Origin::Parameter)),
+        ])
+    );
+    let ResourceIr::Array(_, tags) = &queue.properties["Tags"] else {
+        panic!("{:?}", queue.properties["Tags"]);
+    };
+    let values: Vec<&ResourceIr> = tags
+        .iter()
+        .map(|tag| match tag {
+            ResourceIr::Object(_, entries) => &entries["Value"],
+            other => panic!("{other:?}"),
+        })
+        .collect();
+    assert_eq!(values[0], &string("ami-123"));
+    assert_eq!(values[1], &string("a,b"));
+    assert!(matches!(values[2], ResourceIr::Map(..)));
+
+    // The mapping is still used by the lookup that could not be resolved.
+    assert_eq!(ir.mappings.len(), 1);
+}
+
+#[test]
+fn mapping_values_take_the_property_type() {
+    let ir = program(
+        r#"
+        Mappings:
+          Cfg:
+            prod:
+              Size: 20
+              Encrypted: true
+              Name: queue
+              Zones: [a, b]
+        Resources:
+          Queue:
+            Type: AWS::SQS::Queue
+            Properties:
+              QueueName: !FindInMap [Cfg, prod, Size]
+              DelaySeconds: !FindInMap [Cfg, prod, Size]
+              FifoQueue: !FindInMap [Cfg, prod, Encrypted]
+              MaximumMessageSize: !FindInMap [Cfg, prod, Name]
+              KmsMasterKeyId: !FindInMap [Cfg, prod, Zones]
+        Outputs:
+          Size:
+            Value: !FindInMap [Cfg, prod, Size]
+        "#,
+    );
+
+    let queue = resource(&ir, "Queue");
+    assert_eq!(queue.properties["QueueName"], string("20"));
+    assert_eq!(queue.properties["DelaySeconds"], ResourceIr::Number(20));
+    assert_eq!(queue.properties["FifoQueue"], ResourceIr::Bool(true));
+    // Values that are not of the type of the property are left to CloudFormation.
+    assert!(matches!(
+        queue.properties["MaximumMessageSize"],
+        ResourceIr::Map(..)
+    ));
+    assert!(matches!(
+        queue.properties["KmsMasterKeyId"],
+        ResourceIr::Map(..)
+    ));
+    assert_eq!(ir.outputs[0].value, string("20"));
+}
+
+#[test]
+fn known_conditions() {
+    let ir = program(
+        r#"
+        Parameters:
+          Env:
+            Type: String
+        Mappings:
+          Settings:
+            Queue:
+              Enabled: 'true'
+        Conditions:
+          Enabled: !Equals [{ Fn::FindInMap: [Settings, Queue, Enabled] }, 'true']
+          Disabled: !Not [{ Condition: Enabled }]
+          IsProd: !Equals [{ Ref: Env }, prod]
+          ProdAndDisabled: !And [{ Condition: IsProd }, { Condition: Disabled }]
+          Unused: !Equals [a, a]
+        Resources:
+          Queue:
+            Type: AWS::SQS::Queue
+            Condition: Enabled
+            Properties:
+              DelaySeconds: !If [Disabled, 5, !Ref AWS::NoValue]
+              QueueName: !If [IsProd, prod, !Ref AWS::NoValue]
+          Topic:
+            Type: AWS::SNS::Topic
+            Condition: Disabled
+          Never:
+            Type: AWS::S3::Bucket
+            Condition: ProdAndDisabled
+          Bucket:
+            Type: AWS::S3::Bucket
+            Properties:
+              BucketName: !GetAtt Queue.QueueName
+        Outputs:
+          Topic:
+            Condition: Disabled
+            Value: !Ref Topic
+          Queue:
+            Condition: Enabled
+            Value: !Ref Queue
+        "#,
+    );
+
+    let names: Vec<&str> = ir.resources.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, vec!["Queue", "Bucket"]);
+
+    let queue = resource(&ir, "Queue");
+    assert_eq!(queue.condition, None);
+    assert!(
+        !queue.properties.contains_key("DelaySeconds"),
+        "{:?}",
+        queue.properties
+    );
+    assert!(matches!(queue.properties["QueueName"], ResourceIr::If(..)));
+    assert_eq!(
+        resource(&ir, "Bucket").properties["BucketName"],
+        ResourceIr::Ref(Reference::new(
+            "Queue",
+            Origin::GetAttribute {
+                attribute: "QueueName".into(),
+                conditional: false,
+            }
+        ))
+    );
+
+    let outputs: Vec<(&str, Option<&str>)> = ir
+        .outputs
+        .iter()
+        .map(|o| (o.name.as_str(), o.condition.as_deref()))
+        .collect();
+    assert_eq!(outputs, vec![("Queue", None)]);
+
+    let conditions: Vec<&str> = ir.conditions.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(conditions, vec!["IsProd"]);
+    assert!(ir.mappings.is_empty());
+
+    let services: Vec<_> = ir
+        .imports
+        .iter()
+        .map(|import| import.service.as_deref())
+        .collect();
+    assert_eq!(services, vec![None, Some("S3"), Some("SQS")]);
+}
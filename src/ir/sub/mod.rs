@@ -20,9 +20,15 @@ pub fn sub_parse_tree(str: &str) -> Result<Vec<SubValue>, crate::Error> {
 
     match full_resolver.parse(str) {
         Ok((remaining, built_subs)) => {
-            let mut subs = built_subs;
-            if !remaining.is_empty() {
-                subs.push(SubValue::String(remaining.to_string()))
+            let mut subs: Vec<SubValue> = Vec::with_capacity(built_subs.len() + 1);
+            let remaining = (!remaining.is_empty()).then(|| SubValue::String(remaining.into()));
+            for sub in built_subs.into_iter().chain(remaining) {
+                // Escaped literals are split from the text around them while parsing; they are
+                // joined back so each run of literal text is a single string.
+                match (subs.last_mut(), sub) {
+                    (Some(SubValue::String(text)), SubValue::String(more)) => text.push_str(&more),
+                    (_, sub) => subs.push(sub),
+                }
             }
             Ok(subs)
         }
@@ -34,10 +40,10 @@ pub fn sub_parse_tree(str: &str) -> Result<Vec<SubValue>, crate::Error> {
 }
 
 /// inner_resolver will do one of the following:
-/// * take until you see a ${ which is the start of the variable bits.
+/// * take something like ${! ... }, which is the escaped form of the literal text ${ ... }
 /// * take something like ${ ... }
-///   TODO -- there are some Sub strings that will escape the $, that are not captured yet.
-///   Will need to rewrite the parse tree to handle character escapes.
+/// * take until you see a ${ which is the start of the variable bits.
+/// * take the remaining text, which has no variables in it.
 fn inner_resolver(str: &str) -> IResult<&str, SubValue> {
     // Due to the caller being many1, we will need to create out own EOF error to
     // stop the call pattern.
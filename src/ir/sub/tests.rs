@@ -93,11 +93,34 @@ fn test_quotes() -> Result<(), Error> {
 #[test]
 fn test_literal() -> Result<(), Error> {
     let v = sub_parse_tree("echo ${!lol}")?;
+    assert_eq!(v, vec![SubValue::String(String::from("echo ${lol}"))]);
+
+    Ok(())
+}
+
+#[test]
+fn test_literals_around_variables() -> Result<(), Error> {
+    let v = sub_parse_tree("$${!a}-${!}${b}${!c.d}$")?;
+    assert_eq!(
+        v,
+        vec![
+            SubValue::String(String::from("$${a}-${}")),
+            SubValue::Variable(String::from("b")),
+            SubValue::String(String::from("${c.d}$")),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_attribute_variable() -> Result<(), Error> {
+    let v = sub_parse_tree("${MyBucket.Arn}/*")?;
     assert_eq!(
         v,
         vec![
-            SubValue::String(String::from("echo ")),
-            SubValue::String(String::from("${lol}"))
+            SubValue::Variable(String::from("MyBucket.Arn")),
+            SubValue::String(String::from("/*")),
         ]
     );
 
@@ -0,0 +1,247 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use indexmap::IndexMap;
+
+use crate::cdk::{Primitive, PropertyBag, Schema, TypeReference};
+use crate::ir::diagnostics::Diagnostics;
+use crate::ir::resources::{ResourceInstruction, ResourceIr};
+use crate::Hasher;
+
+impl ResourceInstruction {
+    // validate checks the properties of the resource against the schema of `resource_type`, the
+    // way the typed languages would when compiling the synthesized code: it reports the required
+    // properties that are missing and the values whose shape does not match the declared type,
+    // and does so recursively through the structures they hold. Values that are only known at
+    // deploy time (references, most intrinsic functions) are assumed to be right.
+    pub(super) fn validate(
+        &self,
+        resource_type: &dyn PropertyBag,
+        schema: &Schema,
+        diagnostics: &mut Diagnostics,
+    ) {
+        let path = format!("Resources.{}", self.name);
+        let mut validator = Validator {
+            schema,
+            diagnostics,
+        };
+        validator.missing(&path, resource_type, &self.properties);
+        for (name, value) in &self.properties {
+            if let Some(property) = resource_type.property(name) {
+                let path = format!("{path}.Properties.{name}");
+                validator.value(&path, &property.value_type, value);
+            }
+        }
+    }
+}
+
+struct Validator<'a> {
+    schema: &'a Schema,
+    diagnostics: &'a mut Diagnostics,
+}
+
+impl Validator<'_> {
+    fn value(&mut self, path: &str, expected: &TypeReference, value: &ResourceIr) {
+        match (expected, value) {
+            // AWS::NoValue, and the branches of Fn::If, can stand for anything.
+            (_, ResourceIr::Null) => {}
+            (_, ResourceIr::If(_, when_true, when_false)) => {
+                self.value(path, expected, when_true);
+                self.value(path, expected, when_false);
+            }
+            (TypeReference::Union(members), value) => {
+                let fits = members.iter().any(|member| {
+                    let mut diagnostics = Diagnostics::default();
+                    Validator {
+                        schema: self.schema,
+                        diagnostics: &mut diagnostics,
+                    }
+                    .value(path, member, value);
+                    diagnostics.is_empty()
+                });
+                if !fits {
+                    self.mismatch(path, expected, value);
+                }
+            }
+            (TypeReference::List(item_type), ResourceIr::Array(_, items)) => {
+                for (index, item) in items.iter().enumerate() {
+                    self.value(&format!("{path}[{index}]"), item_type, item);
+                }
+            }
+            (TypeReference::Map(item_type), ResourceIr::Object(_, entries)) => {
+                for (key, value) in entries {
+                    self.value(&format!("{path}.{key}"), item_type, value);
+                }
+            }
+            (TypeReference::Named(name), ResourceIr::Object(_, entries)) => {
+                // Types the schema does not describe are translated as JSON, and can't be checked.
+                let Some(data_type) = self.schema.type_named(name) else {
+                    return;
+                };
+                self.missing(path, data_type, entries);
+                for (key, value) in entries {
+                    let path = format!("{path}.{key}");
+                    match data_type.property(key) {
+                        Some(property) => self.value(&path, &property.value_type, value),
+                        None => {
+                            let names = data_type.properties();
+                            let mut message = format!("{key} is not a property of {name}");
+                            if let Some(suggestion) =
+                                suggestion(key, names.iter().map(|(name, _)| *name))
+                            {
+                                message.push_str(&format!(", did you mean {suggestion}?"));
+                            }
+                            self.diagnostics.error(path, message);
+                        }
+                    }
+                }
+            }
+            (expected, value) => {
+                if !fits(expected, value) {
+                    self.mismatch(path, expected, value);
+                }
+            }
+        }
+    }
+
+    // Reports the required properties of `bag` that are not in `entries`.
+    fn missing<V>(
+        &mut self,
+        path: &str,
+        bag: &dyn PropertyBag,
+        entries: &IndexMap<String, V, Hasher>,
+    ) {
+        let mut missing: Vec<&str> = bag
+            .properties()
+            .into_iter()
+            .filter(|(name, property)| property.required && !entries.contains_key(*name))
+            .map(|(name, _)| name)
+            .collect();
+        missing.sort_unstable();
+        for name in missing {
+            self.diagnostics
+                .error(path, format!("the required property {name} is missing"));
+        }
+    }
+
+    fn mismatch(&mut self, path: &str, expected: &TypeReference, value: &ResourceIr) {
+        self.diagnostics.error(
+            path,
+            format!(
+                "expected {}, found {}",
+                describe_type(expected),
+                describe_value(value)
+            ),
+        );
+    }
+}
+
+// Whether `value` can be given where `expected` is declared, for the combinations `Validator::value`
+// does not look into. Scalars are interchangeable, as the synthesizers convert them.
+fn fits(expected: &TypeReference, value: &ResourceIr) -> bool {
+    #[derive(PartialEq)]
+    enum Shape {
+        Scalar,
+        List,
+        Object,
+    }
+    let shape = match value {
+        ResourceIr::Bool(_)
+        | ResourceIr::Number(_)
+        | ResourceIr::Double(_)
+        | ResourceIr::String(_)
+        | ResourceIr::Join(..)
+        | ResourceIr::Sub(_)
+        | ResourceIr::Base64(_)
+        | ResourceIr::Length(_)
+        | ResourceIr::ToJsonString(_) => Shape::Scalar,
+        ResourceIr::Array(..)
+        | ResourceIr::Split(..)
+        | ResourceIr::GetAZs(_)
+        | ResourceIr::Cidr(..) => Shape::List,
+        ResourceIr::Object(..) => Shape::Object,
+        // The others are only known once deployed.
+        _ => return true,
+    };
+    match expected {
+        TypeReference::Primitive(Primitive::Json | Primitive::Unknown) => true,
+        TypeReference::Primitive(_) => shape == Shape::Scalar,
+        TypeReference::List(_) => shape == Shape::List,
+        TypeReference::Map(_) | TypeReference::Named(_) => shape == Shape::Object,
+        TypeReference::Union(_) => true,
+    }
+}
+
+fn describe_type(value_type: &TypeReference) -> String {
+    match value_type {
+        TypeReference::List(_) => "a list".into(),
+        TypeReference::Map(_) => "a map".into(),
+        TypeReference::Named(name) => name.to_string(),
+        TypeReference::Primitive(Primitive::Boolean) => "a boolean".into(),
+        TypeReference::Primitive(Primitive::Number) => "a number".into(),
+        TypeReference::Primitive(Primitive::Timestamp) => "a timestamp".into(),
+        TypeReference::Primitive(_) => "a string".into(),
+        TypeReference::Union(members) => members
+            .iter()
+            .map(describe_type)
+            .collect::<Vec<_>>()
+            .join(" or "),
+    }
+}
+
+fn describe_value(value: &ResourceIr) -> String {
+    match value {
+        ResourceIr::Null => "AWS::NoValue",
+        ResourceIr::Bool(_) => "a boolean",
+        ResourceIr::Number(_) | ResourceIr::Double(_) => "a number",
+        ResourceIr::String(_) => "a string",
+        ResourceIr::Array(..) => "a list",
+        ResourceIr::Object(..) => "an object",
+        ResourceIr::If(..) => "Fn::If",
+        ResourceIr::Join(..) => "Fn::Join",
+        ResourceIr::Split(..) => "Fn::Split",
+        ResourceIr::Ref(_) => "a reference",
+        ResourceIr::Sub(_) => "Fn::Sub",
+        ResourceIr::Map(..) => "Fn::FindInMap",
+        ResourceIr::Base64(_) => "Fn::Base64",
+        ResourceIr::ImportValue(_) => "Fn::ImportValue",
+        ResourceIr::GetAZs(_) => "Fn::GetAZs",
+        ResourceIr::Select(..) => "Fn::Select",
+        ResourceIr::Cidr(..) => "Fn::Cidr",
+        ResourceIr::Length(_) => "Fn::Length",
+        ResourceIr::ToJsonString(_) => "Fn::ToJsonString",
+    }
+    .into()
+}
+
+// The name among `candidates` that `name` is most likely a misspelling of, if any is close enough.
+pub(super) fn suggestion<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let lowercase = name.to_lowercase();
+    let threshold = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|candidate| (distance(&lowercase, &candidate.to_lowercase()), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+// The Levenshtein distance between `a` and `b`.
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,104 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use crate::cdk::Schema;
+use crate::ir::CloudformationProgramIr;
+use crate::CloudformationParseTree;
+
+use super::*;
+
+fn diagnostics(source: &str) -> Vec<String> {
+    let template: CloudformationParseTree = serde_yaml::from_str(source).unwrap();
+    let mut diagnostics = Diagnostics::default();
+    CloudformationProgramIr::from_with_diagnostics(template, Schema::builtin(), &mut diagnostics);
+    diagnostics.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn valid_properties() {
+    let diagnostics = diagnostics(
+        r#"
+        Parameters:
+          Enabled:
+            Type: String
+        Conditions:
+          IsEnabled: !Equals [{ Ref: Enabled }, 'yes']
+        Resources:
+          Bucket:
+            Type: AWS::S3::Bucket
+            Properties:
+              BucketName: !Join ['-', [my, bucket]]
+              VersioningConfiguration:
+                Status: !If [IsEnabled, Enabled, !Ref AWS::NoValue]
+              Tags:
+                - Key: team
+                  Value: 42
+          Queue:
+            Type: AWS::SQS::Queue
+            Properties:
+              DelaySeconds: '5'
+        "#,
+    );
+    assert!(diagnostics.is_empty(), "{diagnostics:#?}");
+}
+
+#[test]
+fn invalid_properties() {
+    let diagnostics = diagnostics(
+        r#"
+        Resources:
+          Bucket:
+            Type: AWS::S3::Bucket
+            Properties:
+              BucketNmae: my-bucket
+              VersioningConfiguration:
+                Stauts: Enabled
+              Tags: team
+          Rule:
+            Type: AWS::Events::Rule
+            Properties:
+              Targets:
+                - Id: target
+                  Arn: [not, a, string]
+          Table:
+            Type: AWS::DynamoDB::Table
+            Properties:
+              KeySchema: !Join [',', [a, b]]
+          Alarm:
+            Type: AWS::CloudWatch::Alarm
+            Properties:
+              ComparisonOperator: GreaterThanThreshold
+              EvaluationPeriods: 1
+              MetricName: Errors
+              Namespace: App
+              Period: 60
+              Statistic: Sum
+              Threshold: 1
+              Dimensions:
+                - Name
+        "#,
+    );
+    assert_eq!(
+        diagnostics,
+        vec![
+            "Resources.Bucket.Properties.BucketNmae: BucketNmae is not a valid property for resource Bucket of type AWS::S3::Bucket, did you mean BucketName?",
+            "Resources.Bucket.Properties.VersioningConfiguration: the required property Status is missing",
+            "Resources.Bucket.Properties.VersioningConfiguration.Stauts: Stauts is not a property of AWS::S3::Bucket.VersioningConfiguration, did you mean Status?",
+            "Resources.Bucket.Properties.Tags: expected a list, found a string",
+            "Resources.Rule.Properties.Targets[0].Arn: expected a string, found a list",
+            "Resources.Table.Properties.KeySchema: expected a list, found Fn::Join",
+            "Resources.Alarm.Properties.Dimensions[0]: expected AWS::CloudWatch::Alarm.Dimension, found a string",
+        ]
+    );
+}
+
+#[test]
+fn suggestions() {
+    let names = ["BucketName", "Tags", "VersioningConfiguration"];
+    assert_eq!(suggestion("bucketname", names), Some("BucketName"));
+    assert_eq!(suggestion("Tag", names), Some("Tags"));
+    assert_eq!(
+        suggestion("VersionConfiguration", names),
+        Some("VersioningConfiguration")
+    );
+    assert_eq!(suggestion("Name", names), None);
+}
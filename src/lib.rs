@@ -12,10 +12,14 @@
 use indexmap::IndexMap;
 use parser::condition::ConditionFunction;
 use parser::lookup_table::MappingTable;
+use parser::metadata::TemplateMetadata;
 use parser::output::Output;
 use parser::parameters::Parameter;
-use parser::resource::ResourceAttributes;
+use parser::resource::{map_with_for_each, ResourceAttributes, ResourceValue};
+use parser::rules::Rule;
+use parser::span::{path, PathSegment, SourceMap};
 use serde::{Deserialize, Deserializer};
+use std::str::FromStr;
 
 pub mod cdk;
 pub mod code;
@@ -24,6 +28,7 @@ pub mod ir;
 pub mod parser;
 pub mod primitives;
 pub mod synthesizer;
+pub mod verify;
 
 mod util;
 
@@ -36,7 +41,10 @@ pub use util::Hasher;
 #[derive(Debug, serde::Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct CloudformationParseTree {
+    #[serde(rename = "AWSTemplateFormatVersion")]
+    pub aws_template_format_version: Option<String>,
     pub description: Option<String>,
+    pub metadata: Option<TemplateMetadata>,
 
     #[serde(
         default,
@@ -49,14 +57,133 @@ pub struct CloudformationParseTree {
     pub conditions: IndexMap<String, ConditionFunction, Hasher>,
     #[serde(default)]
     pub mappings: IndexMap<String, MappingTable, Hasher>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "map_with_for_each")]
     pub outputs: IndexMap<String, Output, Hasher>,
     #[serde(default)]
     pub parameters: IndexMap<String, Parameter, Hasher>,
+    #[serde(default)]
+    pub rules: IndexMap<String, Rule, Hasher>,
 
+    #[serde(deserialize_with = "map_with_for_each")]
     pub resources: IndexMap<String, ResourceAttributes, Hasher>,
 }
 
+impl CloudformationParseTree {
+    /// Parses the YAML or JSON template `source`, whose positions are in `source_map`. Along with
+    /// the template, it records the position of every resource, parameter and property value (so
+    /// that the errors found in them point back to the template), and the comments of the
+    /// resources, their properties, the parameters and the outputs.
+    pub fn from_source(source: &str, source_map: &SourceMap) -> Result<Self, serde_yaml::Error> {
+        let mut template: Self = serde_yaml::from_str(source)?;
+        template.locate(source_map);
+        Ok(template)
+    }
+
+    fn locate(&mut self, source_map: &SourceMap) {
+        for (name, resource) in self.resources.iter_mut() {
+            let resource_path = path(["Resources", name]);
+            resource.span = source_map.closest_span(&resource_path);
+            let properties_path = child(&resource_path, "Properties");
+            for (property, value) in resource.properties.iter_mut() {
+                *value = locate_value(
+                    std::mem::replace(value, ResourceValue::Null),
+                    &mut child(&properties_path, property),
+                    source_map,
+                );
+            }
+            resource.comments = source_map.comments(&resource_path).to_vec();
+            resource.property_comments = resource
+                .properties
+                .keys()
+                .filter_map(|property| {
+                    let comments = source_map.comments(&child(&properties_path, property));
+                    (!comments.is_empty()).then(|| (property.clone(), comments.to_vec()))
+                })
+                .collect();
+        }
+        for (name, parameter) in self.parameters.iter_mut() {
+            let parameter_path = path(["Parameters", name]);
+            parameter.span = source_map.closest_span(&parameter_path);
+            parameter.comments = source_map.comments(&parameter_path).to_vec();
+        }
+        for (name, output) in self.outputs.iter_mut() {
+            output.comments = source_map.comments(&path(["Outputs", name])).to_vec();
+        }
+    }
+
+    /// Lists the `AWS::CloudFormation::Stack` resources whose `TemplateURL` is a literal string,
+    /// along with that URL.
+    pub fn nested_stack_urls(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.resources.iter().filter_map(|(name, resource)| {
+            if resource.resource_type != "AWS::CloudFormation::Stack" {
+                return None;
+            }
+            match resource
+                .properties
+                .get("TemplateURL")
+                .map(ResourceValue::unspanned)
+            {
+                Some(ResourceValue::String(url)) => Some((name.as_str(), url.as_str())),
+                _ => None,
+            }
+        })
+    }
+}
+
+impl FromStr for CloudformationParseTree {
+    type Err = serde_yaml::Error;
+
+    /// Parses a YAML or JSON template, see [`CloudformationParseTree::from_source`].
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Self::from_source(source, &SourceMap::new(source))
+    }
+}
+
+// Wraps `value`, and the values it holds, with their position in the template. Values that come
+// from a `Fn::ForEach` loop are not in the template as such, and are left as they are.
+fn locate_value(
+    value: ResourceValue,
+    path: &mut Vec<PathSegment>,
+    source_map: &SourceMap,
+) -> ResourceValue {
+    let value = match value {
+        ResourceValue::Array(items) => ResourceValue::Array(
+            items
+                .into_iter()
+                .enumerate()
+                .map(|(index, item)| {
+                    path.push(PathSegment::Index(index));
+                    let item = locate_value(item, path, source_map);
+                    path.pop();
+                    item
+                })
+                .collect(),
+        ),
+        ResourceValue::Object(entries) => ResourceValue::Object(
+            entries
+                .into_iter()
+                .map(|(key, entry)| {
+                    path.push(PathSegment::Key(key.clone()));
+                    let entry = locate_value(entry, path, source_map);
+                    path.pop();
+                    (key, entry)
+                })
+                .collect(),
+        ),
+        value => value,
+    };
+    match source_map.exact_span(path) {
+        Some(span) => ResourceValue::Spanned(span, Box::new(value)),
+        None => value,
+    }
+}
+
+fn child(path: &[PathSegment], key: &str) -> Vec<PathSegment> {
+    let mut child = path.to_vec();
+    child.push(PathSegment::Key(key.into()));
+    child
+}
+
 fn string_or_seq_string<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
 where
     D: Deserializer<'de>,
@@ -77,6 +204,7 @@ where
 #[cfg(target_family = "wasm")]
 pub mod wasm {
     use cdk::Schema;
+    use synthesizer::{ConstructOptions, SynthesizerOptions};
     use wasm_bindgen::prelude::*;
 
     use super::*;
@@ -99,20 +227,72 @@ pub mod wasm {
         .into_boxed_slice()
     }
 
+    /// The language-specific options of `transmute`. The fields left unset keep their default
+    /// value.
+    #[wasm_bindgen(getter_with_clone)]
+    #[derive(Default)]
+    pub struct TransmuteOptions {
+        /// The package of the Java stack class (`com.myorg` by default).
+        #[wasm_bindgen(js_name = javaPackage)]
+        pub java_package: Option<String>,
+        /// The path of the Go module (the name of the project by default).
+        #[wasm_bindgen(js_name = goModule)]
+        pub go_module: Option<String>,
+        /// The package of the Go stack (`main` by default).
+        #[wasm_bindgen(js_name = goPackage)]
+        pub go_package: Option<String>,
+        /// The namespace of the C# stack class (the stack's name by default).
+        #[wasm_bindgen(js_name = csharpNamespace)]
+        pub csharp_namespace: Option<String>,
+        /// The module of the Python stack modules (e.g. `infra.stacks`).
+        #[wasm_bindgen(js_name = pythonModule)]
+        pub python_module: Option<String>,
+        /// Emits a construct to add to an existing stack instead of a stack.
+        pub construct: bool,
+        /// Also declares the outputs of a construct as `CfnOutput`s.
+        #[wasm_bindgen(js_name = cfnOutputs)]
+        pub cfn_outputs: bool,
+    }
+
+    #[wasm_bindgen]
+    impl TransmuteOptions {
+        #[wasm_bindgen(constructor)]
+        pub fn new() -> Self {
+            Self::default()
+        }
+    }
+
     /// Transforms the provided template into a CDK application in the specified
     /// language.
     #[wasm_bindgen]
-    pub fn transmute(template: &str, language: &str, stack_name: &str) -> Result<String, JsError> {
-        let cfn_tree: CloudformationParseTree = serde_yaml::from_str(template)?;
+    pub fn transmute(
+        template: &str,
+        language: &str,
+        stack_name: &str,
+        options: Option<TransmuteOptions>,
+    ) -> Result<String, JsError> {
+        let cfn_tree: CloudformationParseTree = template.parse()?;
         let ir = crate::ir::CloudformationProgramIr::from(cfn_tree, Schema::builtin())?;
         let mut output = Vec::new();
 
-        let lang = match language {
-            "go" => "golang",
-            other => other,
-        };
+        let mut synthesizer_options = SynthesizerOptions::new(stack_name);
+        let options = options.unwrap_or_default();
+        if let Some(package) = options.java_package {
+            synthesizer_options.java.package = package;
+        }
+        synthesizer_options.golang.module = options.go_module;
+        if let Some(package) = options.go_package {
+            synthesizer_options.golang.package = package;
+        }
+        synthesizer_options.csharp.namespace = options.csharp_namespace;
+        synthesizer_options.python.module = options.python_module;
+        if options.construct {
+            synthesizer_options.construct = Some(ConstructOptions {
+                cfn_outputs: options.cfn_outputs,
+            });
+        }
 
-        ir.synthesize(lang, &mut output, stack_name)?;
+        ir.synthesize_with(language, &mut output, &synthesizer_options)?;
 
         String::from_utf8(output).map_err(Into::into)
     }
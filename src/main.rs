@@ -1,12 +1,27 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 use cdk_from_cfn::cdk::Schema;
+use cdk_from_cfn::ir::constructs::L2Mappings;
+use cdk_from_cfn::ir::cross_stack::link_stacks;
+use cdk_from_cfn::ir::diagnostics::{Diagnostic, Diagnostics, Severity};
 use cdk_from_cfn::ir::CloudformationProgramIr;
+use cdk_from_cfn::parser::span::{SourceMap, Span};
+use cdk_from_cfn::synthesizer::{
+    project_files, stack_class_name, stack_id, ConstructOptions, ConversionReport, ProjectFile,
+    ProjectStack, SynthesizerOptions,
+};
+use cdk_from_cfn::verify::TemplateDiff;
 use cdk_from_cfn::CloudformationParseTree;
 use cdk_from_cfn::Error;
 use clap::{Arg, ArgAction, Command};
 use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::{fs, io};
+use voca_rs::case::pascal_case;
+
+/// The extensions of the files converted when `--batch` is given a directory.
+const TEMPLATE_EXTENSIONS: [&str; 4] = ["yaml", "yml", "json", "template"];
 
 // Ensure at least one target language is enabled...
 #[cfg(not(any(
@@ -68,21 +83,205 @@ fn main() -> Result<(), Error> {
                 .short('s')
                 .action(ArgAction::Set),
         )
+        .arg(
+            Arg::new("project")
+                .help("Writes a complete CDK application (entry point and manifests) around the stack into this directory, instead of writing the stack alone to OUTPUT")
+                .long("project")
+                .value_name("DIR")
+                .conflicts_with("OUTPUT")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("batch")
+                .help("Converts every .yaml, .yml, .json and .template file under this directory (or matching this glob pattern) into one CDK application written to the --project directory, deriving stack names from the file names")
+                .long("batch")
+                .value_name("DIR|GLOB")
+                .requires("project")
+                .conflicts_with_all(["INPUT", "stack-name"])
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("nested-stack")
+                .help("Converts the template at PATH as the nested stack LOGICAL_ID (use Parent.Child for the nested stacks of a nested stack). Nested stacks whose TemplateURL is a relative path to an existing file are converted as well")
+                .long("nested-stack")
+                .value_name("LOGICAL_ID=PATH")
+                .conflicts_with("batch")
+                .value_parser(parse_nested_stack)
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new("lookup-ssm-parameters")
+                .help("Reads AWS::SSM::Parameter::Value<String> parameters using ssm.StringParameter.valueForStringParameter instead of a CfnParameter")
+                .long("lookup-ssm-parameters")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("simplify")
+                .help("Evaluates the intrinsic functions whose result only depends on literals (e.g. Fn::Join over literal strings, or Fn::If on a condition that is always true), and removes the conditions and mappings left unused")
+                .long("simplify")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("prune")
+                .help("Removes the parameters, conditions and mappings that no resource, output, rule or metadata uses, printing a warning for each of them")
+                .long("prune")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("l2")
+                .help("Emits L2 constructs (e.g. s3.Bucket) for the resource types that have one, instead of their Cfn* class")
+                .long("l2")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("construct")
+                .help("Emits a construct to add to an existing stack instead of a stack: parameters become props, outputs public fields, and pseudo parameters (e.g. AWS::Region) refer to the enclosing stack")
+                .long("construct")
+                .conflicts_with("project")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("cfn-outputs")
+                .help("Also declares the outputs of a --construct as CfnOutputs of the enclosing stack")
+                .long("cfn-outputs")
+                .requires("construct")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("report")
+                .help("Writes a JSON report of the conversion to FILE: the construct class of each resource, the properties left as raw JSON, what each parameter became, the template entries dropped, and the errors and warnings")
+                .long("report")
+                .value_name("FILE")
+                .conflicts_with("batch")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("verify")
+                .help("Instead of converting INPUT, compares it with the template cdk synth produced from its conversion (e.g. cdk.out/MyStack.template.json) and prints their differences, ignoring what the CDK adds, key order and equivalent forms of intrinsic functions. Exits with a failure if they differ")
+                .long("verify")
+                .value_name("SYNTHESIZED")
+                .conflicts_with_all(["batch", "project", "report"])
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("java-package")
+                .help("Sets the package of the Java stack classes")
+                .long("java-package")
+                .value_name("PACKAGE")
+                .default_value("com.myorg"),
+        )
+        .arg(
+            Arg::new("go-module")
+                .help("Sets the module path declared in the go.mod of a Go project (the project's name by default)")
+                .long("go-module")
+                .value_name("PATH"),
+        )
+        .arg(
+            Arg::new("go-package")
+                .help("Sets the package of the Go stack files")
+                .long("go-package")
+                .value_name("PACKAGE")
+                .default_value("main"),
+        )
+        .arg(
+            Arg::new("csharp-namespace")
+                .help("Sets the namespace of the C# stack classes (the stack's name by default)")
+                .long("csharp-namespace")
+                .value_name("NAMESPACE"),
+        )
+        .arg(
+            Arg::new("python-module")
+                .help("Creates the Python stack modules of a project in MODULE (e.g. infra.stacks) instead of next to app.py")
+                .long("python-module")
+                .value_name("MODULE"),
+        )
         .get_matches();
 
-    let cfn_tree: CloudformationParseTree = {
-        let reader: Box<dyn std::io::Read> =
-            match matches.get_one::<String>("INPUT").map(String::as_str) {
-                None | Some("-") => Box::new(io::stdin()),
-                Some(file) => Box::new(fs::File::open(file)?),
-            };
+    let language = matches
+        .get_one::<String>("language")
+        .map(String::as_str)
+        .unwrap_or(targets[0]);
+    let options = ConvertOptions {
+        lookup_ssm_parameters: matches.get_flag("lookup-ssm-parameters"),
+        simplify: matches.get_flag("simplify"),
+        prune: matches.get_flag("prune"),
+        l2_constructs: matches.get_flag("l2"),
+    };
+
+    let mut synthesizer_options = SynthesizerOptions::new(
+        matches
+            .get_one::<String>("stack-name")
+            .map(String::as_str)
+            .unwrap_or("NoctStack"),
+    );
+    let flag = |name: &str| matches.get_one::<String>(name).cloned();
+    synthesizer_options.java.package = flag("java-package").unwrap_or_default();
+    synthesizer_options.golang.module = flag("go-module");
+    synthesizer_options.golang.package = flag("go-package").unwrap_or_default();
+    synthesizer_options.csharp.namespace = flag("csharp-namespace");
+    synthesizer_options.python.module = flag("python-module");
+    if matches.get_flag("construct") {
+        synthesizer_options.construct = Some(ConstructOptions {
+            cfn_outputs: matches.get_flag("cfn-outputs"),
+        });
+    }
+
+    if let Some(batch) = matches.get_one::<String>("batch") {
+        // `--batch` requires `--project`.
+        let project_dir = Path::new(matches.get_one::<String>("project").unwrap());
+        return convert_batch(batch, language, project_dir, options, &synthesizer_options);
+    }
 
-        serde_yaml::from_reader(reader)?
+    let (source, dir, ancestors) = match matches.get_one::<String>("INPUT").map(String::as_str) {
+        None | Some("-") => (io::read_to_string(io::stdin())?, PathBuf::new(), vec![]),
+        Some(file) => (
+            fs::read_to_string(file)?,
+            template_dir(Path::new(file)),
+            vec![fs::canonicalize(file)?],
+        ),
     };
+    if let Some(synthesized) = matches.get_one::<String>("verify") {
+        let differences = TemplateDiff::verify(&source, &fs::read_to_string(synthesized)?)?;
+        if differences.is_empty() {
+            eprintln!("the templates are equivalent");
+            return Ok(());
+        }
+        println!("{}", TemplateDiff::format(&differences));
+        std::process::exit(1);
+    }
 
-    let schema = Cow::Borrowed(Schema::builtin());
+    let origin = TemplateOrigin {
+        dir,
+        nested_stack_paths: matches
+            .get_many::<(String, PathBuf)>("nested-stack")
+            .unwrap_or_default()
+            .cloned()
+            .collect(),
+        ancestors,
+    };
+    let report_path = matches.get_one::<String>("report").map(Path::new);
+    let mut report = ConversionReport::default();
+    let result = convert(&source, &origin, options, report_path.map(|_| &mut report));
+    // The report is written even when the conversion fails, as it lists the errors.
+    if let Some(path) = report_path {
+        if let Ok(ir) = &result {
+            report.describe(ir, Schema::builtin(), &synthesizer_options);
+        }
+        let json = serde_json::to_string_pretty(&report).map_err(io::Error::from)?;
+        fs::write(path, json)?;
+        eprintln!("wrote {}", path.display());
+    }
+    let Ok(ir) = result else {
+        std::process::exit(1);
+    };
 
-    let ir = CloudformationProgramIr::from(cfn_tree, &schema)?;
+    if let Some(project_dir) = matches.get_one::<String>("project") {
+        let project_dir = Path::new(project_dir);
+        for file in ir.synthesize_project(language, &synthesizer_options)? {
+            write_project_file(project_dir, file)?;
+        }
+        return Ok(());
+    }
 
     let mut output: Box<dyn io::Write> = match matches
         .get_one::<String>("OUTPUT")
@@ -93,17 +292,404 @@ fn main() -> Result<(), Error> {
         output_file => Box::new(fs::File::create(output_file)?),
     };
 
-    let language = matches
-        .get_one::<String>("language")
-        .map(String::as_str)
-        .unwrap_or(targets[0]);
+    ir.synthesize_with(language, &mut output, &synthesizer_options)?;
 
-    let stack_name = matches
-        .get_one::<String>("stack-name")
-        .map(String::as_str)
-        .unwrap_or("NoctStack");
+    Ok(())
+}
+
+/// How templates are converted, besides their translation to the IR.
+#[derive(Clone, Copy)]
+struct ConvertOptions {
+    /// Reads SSM parameter types with `ssm.StringParameter` instead of a `CfnParameter`.
+    lookup_ssm_parameters: bool,
+    /// Evaluates what only depends on literals before synthesizing.
+    simplify: bool,
+    /// Removes the parameters, conditions and mappings nothing uses.
+    prune: bool,
+    /// Emits the resources that have an L2 construct as such.
+    l2_constructs: bool,
+}
+
+/// Where a template was read from, which determines where the templates of its nested stacks are.
+struct TemplateOrigin {
+    /// The directory relative `TemplateURL`s are resolved against.
+    dir: PathBuf,
+    /// The templates given with `--nested-stack`, by logical ID relative to this template.
+    nested_stack_paths: Vec<(String, PathBuf)>,
+    /// The templates this one is nested in, outermost first.
+    ancestors: Vec<PathBuf>,
+}
+
+/// Parses a template and converts it to the IR, printing any error or warning along the way. On
+/// failure, returns a short description of what went wrong. When given a `report`, it is started
+/// from the template and collects the errors and warnings.
+fn convert(
+    source: &str,
+    origin: &TemplateOrigin,
+    options: ConvertOptions,
+    mut report: Option<&mut ConversionReport>,
+) -> Result<CloudformationProgramIr, String> {
+    let source_map = SourceMap::new(source);
+
+    let cfn_tree = match CloudformationParseTree::from_source(source, &source_map) {
+        Ok(cfn_tree) => cfn_tree,
+        Err(err) => {
+            eprintln!("error: {err}");
+            let span = err.location().map(|location| Span {
+                line: location.line(),
+                column: location.column(),
+            });
+            if let Some(snippet) = span.and_then(|span| source_map.snippet(span)) {
+                eprintln!("{snippet}");
+            }
+            if let Some(report) = report {
+                report.diagnostics.push(Diagnostic {
+                    severity: Severity::Error,
+                    path: String::new(),
+                    message: err.to_string(),
+                    span,
+                });
+            }
+            return Err("invalid template".into());
+        }
+    };
+    if let Some(report) = report.as_deref_mut() {
+        *report = ConversionReport::new(&cfn_tree, &source_map);
+    }
+
+    let schema = Cow::Borrowed(Schema::builtin());
+
+    let nested_stacks = convert_nested_stacks(&cfn_tree, origin, options)?;
+
+    let mut diagnostics = Diagnostics::default();
+    let mut ir = CloudformationProgramIr::from_with_nested_stacks(
+        cfn_tree,
+        &schema,
+        nested_stacks,
+        &mut diagnostics,
+    );
+    diagnostics.locate(&source_map);
+    print_diagnostics(&diagnostics, &source_map);
+    if let Some(report) = report.as_deref_mut() {
+        report.record(&diagnostics);
+    }
+    if diagnostics.has_errors() {
+        let errors = diagnostics.with_severity(Severity::Error).count();
+        return Err(format!("{errors} error(s)"));
+    }
+    if options.simplify {
+        ir.simplify(&schema);
+    }
+    if options.prune {
+        let mut pruned = Diagnostics::default();
+        ir.prune(&mut pruned);
+        pruned.locate(&source_map);
+        print_diagnostics(&pruned, &source_map);
+        if let Some(report) = report {
+            report.record(&pruned);
+        }
+    }
+    if options.lookup_ssm_parameters {
+        ir.lookup_ssm_parameters();
+    }
+    if options.l2_constructs {
+        ir.use_l2_constructs(&L2Mappings::builtin());
+    }
+    Ok(ir)
+}
 
-    ir.synthesize(language, &mut output, stack_name)?;
+/// Converts the templates of the nested stacks of `cfn_tree` that are available locally: the ones
+/// given with `--nested-stack`, and the ones whose `TemplateURL` is a relative path to a file.
+fn convert_nested_stacks(
+    cfn_tree: &CloudformationParseTree,
+    origin: &TemplateOrigin,
+    options: ConvertOptions,
+) -> Result<HashMap<String, CloudformationProgramIr>, String> {
+    let mut paths: Vec<(String, PathBuf)> = origin
+        .nested_stack_paths
+        .iter()
+        .filter(|(logical_id, _)| !logical_id.contains('.'))
+        .cloned()
+        .collect();
+    for (logical_id, url) in cfn_tree.nested_stack_urls() {
+        if url.contains("://") || paths.iter().any(|(known, _)| known == logical_id) {
+            continue;
+        }
+        let path = origin.dir.join(url);
+        if path.is_file() {
+            paths.push((logical_id.to_string(), path));
+        } else {
+            eprintln!(
+                "warning: {} not found, {logical_id} is kept as a CfnStack",
+                path.display()
+            );
+        }
+    }
 
+    let mut nested_stacks = HashMap::with_capacity(paths.len());
+    for (logical_id, path) in paths {
+        // Errors of the nested template itself are printed as it is converted.
+        let fail = |reason: String| {
+            eprintln!("error: {reason}");
+            format!("nested stack {logical_id}: {reason}")
+        };
+        let canonical =
+            fs::canonicalize(&path).map_err(|err| fail(format!("{}: {err}", path.display())))?;
+        if origin.ancestors.contains(&canonical) {
+            return Err(fail(format!("{} includes itself", path.display())));
+        }
+        let prefix = format!("{logical_id}.");
+        let child = TemplateOrigin {
+            dir: template_dir(&path),
+            nested_stack_paths: origin
+                .nested_stack_paths
+                .iter()
+                .filter_map(|(id, path)| {
+                    Some((id.strip_prefix(&prefix)?.to_string(), path.clone()))
+                })
+                .collect(),
+            ancestors: origin
+                .ancestors
+                .iter()
+                .cloned()
+                .chain([canonical])
+                .collect(),
+        };
+
+        eprintln!(
+            "converting nested stack {logical_id} from {}",
+            path.display()
+        );
+        let source =
+            fs::read_to_string(&path).map_err(|err| fail(format!("{}: {err}", path.display())))?;
+        // The parent prunes its nested stacks along with itself.
+        let nested_options = ConvertOptions {
+            prune: false,
+            ..options
+        };
+        let program = convert(&source, &child, nested_options, None)
+            .map_err(|reason| format!("nested stack {logical_id}: {reason}"))?;
+        nested_stacks.insert(logical_id, program);
+    }
+    Ok(nested_stacks)
+}
+
+/// The directory relative paths in the template at `path` are resolved against.
+fn template_dir(path: &Path) -> PathBuf {
+    path.parent().map(Path::to_path_buf).unwrap_or_default()
+}
+
+/// Parses the value of `--nested-stack`.
+fn parse_nested_stack(value: &str) -> Result<(String, PathBuf), String> {
+    match value.split_once('=') {
+        Some((logical_id, path)) if !logical_id.is_empty() && !path.is_empty() => {
+            Ok((logical_id.to_string(), PathBuf::from(path)))
+        }
+        _ => Err("expected LOGICAL_ID=PATH".into()),
+    }
+}
+
+/// Converts every template matched by `pattern` into a stack of the same CDK application. A
+/// template that fails to convert is reported in the summary and left out of the application,
+/// without stopping the conversion of the others.
+fn convert_batch(
+    pattern: &str,
+    language: &str,
+    project_dir: &Path,
+    options: ConvertOptions,
+    synthesizer_options: &SynthesizerOptions,
+) -> Result<(), Error> {
+    let inputs = batch_inputs(pattern)?;
+    if inputs.is_empty() {
+        eprintln!("error: no template found in {pattern}");
+        std::process::exit(1);
+    }
+
+    let mut programs = Vec::with_capacity(inputs.len());
+    let mut taken = HashSet::new();
+    let mut summary = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        let file_name = input.file_name().unwrap_or_default().to_string_lossy();
+        let base_name = stack_class_name(&file_name);
+        // Templates with the same name in different directories still need distinct classes.
+        let mut stack_name = base_name.clone();
+        let mut suffix = 1;
+        while !taken.insert(stack_name.clone()) {
+            suffix += 1;
+            stack_name = format!("{base_name}{suffix}");
+        }
+
+        eprintln!("converting {}", input.display());
+        let result = fs::read_to_string(&input)
+            .map_err(|err| err.to_string())
+            .and_then(|source| {
+                let origin = TemplateOrigin {
+                    dir: template_dir(&input),
+                    nested_stack_paths: Vec::new(),
+                    ancestors: fs::canonicalize(&input).into_iter().collect(),
+                };
+                convert(&source, &origin, options, None)
+            });
+        summary.push((
+            input,
+            stack_name.clone(),
+            result.map(|ir| programs.push((stack_name, ir))),
+        ));
+    }
+
+    // Imports of values exported by another of the templates become cross-stack references, which
+    // needs the exporting stacks to be instantiated first.
+    let unresolved = link_stacks(&mut programs, |stack_name| stack_id(language, stack_name));
+
+    let mut stacks: Vec<ProjectStack> = Vec::with_capacity(programs.len());
+    for (stack_name, ir) in programs {
+        let stack = ProjectStack::new(&stack_name, &ir);
+        let missing = ir
+            .cross_stack_imports
+            .iter()
+            .find(|import| !stacks.iter().any(|stack| stack.name == import.stack));
+        let result = match missing {
+            Some(import) => Err(format!("imports from {}, which failed", import.stack)),
+            None => ir
+                .synthesize_project_stack(
+                    language,
+                    &SynthesizerOptions {
+                        stack_name: stack_name.clone(),
+                        ..synthesizer_options.clone()
+                    },
+                )
+                .and_then(|file| write_project_file(project_dir, file))
+                .map_err(|err| err.to_string()),
+        };
+        match result {
+            Ok(()) => stacks.push(stack),
+            Err(reason) => {
+                if let Some((.., result)) =
+                    summary.iter_mut().find(|(_, name, _)| name == &stack_name)
+                {
+                    *result = Err(reason);
+                }
+            }
+        }
+    }
+
+    if !stacks.is_empty() {
+        let project_name = project_dir
+            .file_name()
+            .map(|name| pascal_case(&name.to_string_lossy()))
+            .filter(|name| name.starts_with(|c: char| c.is_ascii_alphabetic()))
+            .unwrap_or_else(|| "CdkApp".into());
+        for file in project_files(language, &project_name, &stacks, synthesizer_options)? {
+            write_project_file(project_dir, file)?;
+        }
+    }
+
+    let file_width = summary
+        .iter()
+        .map(|(input, ..)| input.display().to_string().len())
+        .chain(["TEMPLATE".len()])
+        .max()
+        .unwrap_or_default();
+    let stack_width = summary
+        .iter()
+        .map(|(_, stack_name, _)| stack_name.len())
+        .chain(["STACK".len()])
+        .max()
+        .unwrap_or_default();
+    println!(
+        "{:file_width$}  {:stack_width$}  RESULT",
+        "TEMPLATE", "STACK"
+    );
+    let mut failures = 0;
+    for (input, stack_name, result) in &summary {
+        let result = match result {
+            Ok(()) => "ok".to_string(),
+            Err(reason) => {
+                failures += 1;
+                format!("failed: {reason}")
+            }
+        };
+        println!(
+            "{:file_width$}  {stack_name:stack_width$}  {result}",
+            input.display().to_string()
+        );
+    }
+    println!(
+        "{} of {} template(s) converted",
+        summary.len() - failures,
+        summary.len()
+    );
+    if !unresolved.is_empty() {
+        println!("{} import(s) left as Fn::ImportValue:", unresolved.len());
+        for import in &unresolved {
+            println!("  {import}");
+        }
+    }
+
+    if failures > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Lists the templates to convert with `--batch`, in a stable order: the template files under
+/// `pattern` if it is a directory, and the files matching it otherwise.
+fn batch_inputs(pattern: &str) -> Result<Vec<PathBuf>, Error> {
+    let mut inputs = Vec::new();
+    if Path::new(pattern).is_dir() {
+        let mut dirs = vec![PathBuf::from(pattern)];
+        while let Some(dir) = dirs.pop() {
+            for entry in fs::read_dir(dir)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else if path
+                    .extension()
+                    .is_some_and(|ext| TEMPLATE_EXTENSIONS.iter().any(|known| ext == *known))
+                {
+                    inputs.push(path);
+                }
+            }
+        }
+    } else {
+        let paths = glob::glob(pattern).map_err(|err| Error::IOError {
+            err: io::Error::new(io::ErrorKind::InvalidInput, err),
+        })?;
+        for path in paths {
+            let path = path.map_err(glob::GlobError::into_error)?;
+            if path.is_file() {
+                inputs.push(path);
+            }
+        }
+    }
+    inputs.sort();
+    Ok(inputs)
+}
+
+fn write_project_file(project_dir: &Path, file: ProjectFile) -> Result<(), Error> {
+    let path = project_dir.join(&file.path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, file.contents)?;
+    eprintln!("wrote {}", path.display());
     Ok(())
 }
+
+fn print_diagnostics(diagnostics: &Diagnostics, source_map: &SourceMap) {
+    for severity in [Severity::Error, Severity::Warning] {
+        let entries: Vec<_> = diagnostics.with_severity(severity).collect();
+        if entries.is_empty() {
+            continue;
+        }
+        eprintln!("{} {severity}(s):", entries.len());
+        for diagnostic in entries {
+            eprintln!("  {diagnostic}");
+            if let Some(snippet) = diagnostic.span.and_then(|span| source_map.snippet(span)) {
+                for line in snippet.lines() {
+                    eprintln!("    {line}");
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,10 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 use super::resource::ResourceValue;
+use crate::Hasher;
+use indexmap::IndexMap;
 use serde::de::{Error, VariantAccess};
+use serde_yaml::value::{TaggedValue, Value};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum IntrinsicFunction {
@@ -50,8 +53,8 @@ pub enum IntrinsicFunction {
     Transform,
 
     // Provided by the `AWS::LanguageExtensions` transform
-    Length,
-    ToJsonString,
+    Length(ResourceValue),
+    ToJsonString(ResourceValue),
 }
 
 static INTRINSIC_FUNCTION_TAGS: &[&str] = &[
@@ -67,6 +70,8 @@ static INTRINSIC_FUNCTION_TAGS: &[&str] = &[
     "Split",
     "Sub",
     "Ref",
+    "Length",
+    "ToJsonString",
 ];
 
 impl IntrinsicFunction {
@@ -126,6 +131,8 @@ impl IntrinsicFunction {
                 Self::Sub { string, replaces }
             }
             "Ref" => Self::Ref(data.newtype_variant()?),
+            "Length" => Self::Length(data.newtype_variant()?),
+            "ToJsonString" => Self::ToJsonString(data.newtype_variant()?),
             unknown => return Err(A::Error::unknown_variant(unknown, INTRINSIC_FUNCTION_TAGS)),
         })
     }
@@ -187,6 +194,8 @@ impl IntrinsicFunction {
                 Some(Self::Sub { string, replaces })
             }
             "!Ref" | "Ref" => Some(Self::Ref(data.next_value()?)),
+            "!Length" | "Fn::Length" => Some(Self::Length(data.next_value()?)),
+            "!ToJsonString" | "Fn::ToJsonString" => Some(Self::ToJsonString(data.next_value()?)),
             _ => None,
         })
     }
@@ -231,3 +240,186 @@ impl SubPayload {
         }
     }
 }
+
+// The key prefix identifying an `Fn::ForEach` loop, which is followed by the loop's name.
+const FOR_EACH_PREFIX: &str = "Fn::ForEach::";
+
+// An `Fn::ForEach` loop, provided by the `AWS::LanguageExtensions` transform. Loops are unrolled
+// while the template is parsed (as the transform would do), so the collection must be a literal
+// list.
+#[derive(Debug)]
+struct ForEach {
+    loop_name: String,
+    identifier: String,
+    collection: ResourceValue,
+    fragment: serde_yaml::Mapping,
+}
+
+impl ForEach {
+    fn from_value<E: serde::de::Error>(loop_name: &str, value: Value) -> Result<Self, E> {
+        let (identifier, collection, fragment) = serde::Deserialize::deserialize(value)
+            .map_err(|cause| E::custom(format!("invalid {FOR_EACH_PREFIX}{loop_name}: {cause}")))?;
+        Ok(Self {
+            loop_name: loop_name.into(),
+            identifier,
+            collection,
+            fragment,
+        })
+    }
+
+    // Produces the entries of the fragment once for each item of the collection, with the loop
+    // identifier replaced by that item.
+    fn expand<E: serde::de::Error>(&self) -> Result<Vec<(String, Value)>, E> {
+        let loop_name = &self.loop_name;
+        let items = match &self.collection {
+            ResourceValue::Array(items) => items
+                .iter()
+                .map(|item| match item {
+                    ResourceValue::String(item) => Ok(item.clone()),
+                    ResourceValue::Number(item) => Ok(item.to_string()),
+                    ResourceValue::Double(item) => Ok(item.to_string()),
+                    ResourceValue::Bool(item) => Ok(item.to_string()),
+                    other => Err(E::custom(format!(
+                        "{FOR_EACH_PREFIX}{loop_name} collection items must be literals, found {other:?}"
+                    ))),
+                })
+                .collect::<Result<Vec<_>, E>>()?,
+            other => {
+                return Err(E::custom(format!(
+                    "{FOR_EACH_PREFIX}{loop_name} requires a literal list as its collection, found {other:?} \
+                     (loops are unrolled during the conversion, so they cannot iterate over a parameter)"
+                )))
+            }
+        };
+
+        let mut entries = Vec::with_capacity(items.len() * self.fragment.len());
+        for item in &items {
+            for (key, value) in &self.fragment {
+                let key = key.as_str().ok_or_else(|| {
+                    E::custom(format!(
+                        "{FOR_EACH_PREFIX}{} output keys must be strings, found {key:?}",
+                        self.loop_name
+                    ))
+                })?;
+                entries.push((self.replace_in_key(key, item), self.replace(value, item)));
+            }
+        }
+        Ok(entries)
+    }
+
+    // Replaces `${Identifier}` with the item, and `&{Identifier}` with the item stripped of any
+    // non-alphanumeric characters.
+    fn replace_in_key(&self, key: &str, item: &str) -> String {
+        let alphanumeric: String = item.chars().filter(|c| c.is_alphanumeric()).collect();
+        key.replace(&format!("${{{}}}", self.identifier), item)
+            .replace(&format!("&{{{}}}", self.identifier), &alphanumeric)
+    }
+
+    // Replaces references to the identifier (`Ref` and `Fn::Sub` variables) with the item.
+    fn replace(&self, value: &Value, item: &str) -> Value {
+        match value {
+            Value::Mapping(mapping) => {
+                if mapping.len() == 1
+                    && mapping.get("Ref").and_then(Value::as_str) == Some(&self.identifier)
+                {
+                    return Value::String(item.into());
+                }
+                mapping
+                    .iter()
+                    .map(|(key, value)| {
+                        let value = match key.as_str() {
+                            Some("Fn::Sub") => self.replace_in_sub(value, item),
+                            _ => self.replace(value, item),
+                        };
+                        let key = match key {
+                            Value::String(key) => Value::String(self.replace_in_key(key, item)),
+                            key => key.clone(),
+                        };
+                        (key, value)
+                    })
+                    .collect::<serde_yaml::Mapping>()
+                    .into()
+            }
+            Value::Sequence(sequence) => sequence
+                .iter()
+                .map(|value| self.replace(value, item))
+                .collect::<Vec<_>>()
+                .into(),
+            Value::Tagged(tagged) => {
+                if tagged.tag == "Ref" && tagged.value.as_str() == Some(&self.identifier) {
+                    return Value::String(item.into());
+                }
+                let value = if tagged.tag == "Sub" {
+                    self.replace_in_sub(&tagged.value, item)
+                } else {
+                    self.replace(&tagged.value, item)
+                };
+                Value::Tagged(Box::new(TaggedValue {
+                    tag: tagged.tag.clone(),
+                    value,
+                }))
+            }
+            other => other.clone(),
+        }
+    }
+
+    fn replace_in_sub(&self, value: &Value, item: &str) -> Value {
+        let variable = format!("${{{}}}", self.identifier);
+        match value {
+            Value::String(string) => Value::String(string.replace(&variable, item)),
+            Value::Sequence(sequence) => sequence
+                .iter()
+                .enumerate()
+                .map(|(index, value)| match (index, value) {
+                    (0, Value::String(string)) => Value::String(string.replace(&variable, item)),
+                    (_, value) => self.replace(value, item),
+                })
+                .collect::<Vec<_>>()
+                .into(),
+            other => self.replace(other, item),
+        }
+    }
+}
+
+// Deserializes a map of template entries (e.g: the `Resources` section), unrolling any
+// `Fn::ForEach` loop found among its keys.
+pub(crate) fn map_with_for_each<'de, D, T>(
+    deserializer: D,
+) -> Result<IndexMap<String, T, Hasher>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    let entries: IndexMap<String, Value, Hasher> = serde::Deserialize::deserialize(deserializer)?;
+    let mut map = IndexMap::with_capacity_and_hasher(entries.len(), Hasher::default());
+    insert_with_for_each(&mut map, entries)?;
+    Ok(map)
+}
+
+// Inserts the provided entries in the map, unrolling any `Fn::ForEach` loop found among them.
+pub(crate) fn insert_with_for_each<T, E>(
+    map: &mut IndexMap<String, T, Hasher>,
+    entries: impl IntoIterator<Item = (String, Value)>,
+) -> Result<(), E>
+where
+    T: serde::de::DeserializeOwned,
+    E: serde::de::Error,
+{
+    for (key, value) in entries {
+        if let Some(loop_name) = key.strip_prefix(FOR_EACH_PREFIX) {
+            let for_each = ForEach::from_value::<E>(loop_name, value)?;
+            insert_with_for_each(map, for_each.expand::<E>()?)?;
+            continue;
+        }
+        let value = T::deserialize(value).map_err(E::custom)?;
+        if map.contains_key(&key) {
+            return Err(E::custom(format!("duplicate object key {key:?}")));
+        }
+        map.insert(key, value);
+    }
+    Ok(())
+}
+
+pub(crate) fn is_for_each(key: &str) -> bool {
+    key.starts_with(FOR_EACH_PREFIX)
+}
@@ -0,0 +1,79 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use indexmap::IndexMap;
+
+use crate::parser::resource::ResourceValue;
+use crate::Hasher;
+
+pub const INTERFACE_KEY: &str = "AWS::CloudFormation::Interface";
+
+// TemplateMetadata is the top-level `Metadata` section of a template. Its contents are mostly
+// free-form, except for the `AWS::CloudFormation::Interface` key, which drives how parameters
+// are grouped and labelled in the CloudFormation console.
+// See https://docs.aws.amazon.com/AWSCloudFormation/latest/UserGuide/metadata-section-structure.html
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TemplateMetadata {
+    pub interface: Option<Interface>,
+    pub entries: IndexMap<String, ResourceValue, Hasher>,
+}
+
+// See https://docs.aws.amazon.com/AWSCloudFormation/latest/UserGuide/aws-cloudformation-interface.html
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Interface {
+    #[serde(default)]
+    pub parameter_groups: Vec<ParameterGroup>,
+    #[serde(default)]
+    pub parameter_labels: IndexMap<String, Label, Hasher>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ParameterGroup {
+    pub label: Option<Label>,
+    #[serde(default)]
+    pub parameters: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+pub struct Label {
+    pub default: String,
+}
+
+// This can't be derived with `#[serde(flatten)]`, as flattened maps do not support YAML
+// short-form tags (e.g: `!Ref Foo`) in their values.
+impl<'de> serde::Deserialize<'de> for TemplateMetadata {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<TemplateMetadata, D::Error> {
+        struct TemplateMetadataVisitor;
+        impl<'de> serde::de::Visitor<'de> for TemplateMetadataVisitor {
+            type Value = TemplateMetadata;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a map of template metadata")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut data: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut metadata = TemplateMetadata::default();
+                while let Some(key) = data.next_key::<String>()? {
+                    if key == INTERFACE_KEY {
+                        metadata.interface = Some(data.next_value()?);
+                    } else {
+                        let value = data.next_value()?;
+                        metadata.entries.insert(key, value);
+                    }
+                }
+                Ok(metadata)
+            }
+        }
+
+        deserializer.deserialize_map(TemplateMetadataVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,68 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use crate::parser::resource::IntrinsicFunction;
+
+use super::*;
+
+#[test]
+fn interface_and_entries() {
+    let metadata: TemplateMetadata = serde_yaml::from_str(
+        r#"
+        AWS::CloudFormation::Interface:
+          ParameterGroups:
+            - Label:
+                default: Network Configuration
+              Parameters: [VpcId, SubnetIds]
+            - Parameters: [KeyName]
+          ParameterLabels:
+            VpcId:
+              default: Which VPC should this be deployed to?
+        Owner: !Ref AWS::AccountId
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        metadata.interface,
+        Some(Interface {
+            parameter_groups: vec![
+                ParameterGroup {
+                    label: Some(Label {
+                        default: "Network Configuration".into()
+                    }),
+                    parameters: vec!["VpcId".into(), "SubnetIds".into()],
+                },
+                ParameterGroup {
+                    label: None,
+                    parameters: vec!["KeyName".into()],
+                },
+            ],
+            parameter_labels: IndexMap::from_iter([(
+                "VpcId".to_string(),
+                Label {
+                    default: "Which VPC should this be deployed to?".into()
+                }
+            )]),
+        })
+    );
+    assert_eq!(
+        metadata.entries.into_iter().collect::<Vec<_>>(),
+        vec![(
+            "Owner".to_string(),
+            IntrinsicFunction::Ref("AWS::AccountId".into()).into()
+        )]
+    );
+}
+
+#[test]
+fn without_interface() {
+    let metadata: TemplateMetadata =
+        serde_yaml::from_str(r#"{ "Comment": "Hello", "Version": 2 }"#).unwrap();
+
+    assert_eq!(metadata.interface, None);
+    assert_eq!(
+        metadata.entries.keys().collect::<Vec<_>>(),
+        vec!["Comment", "Version"]
+    );
+    assert_eq!(metadata.entries["Version"], ResourceValue::Number(2));
+}
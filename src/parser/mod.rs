@@ -3,9 +3,12 @@
 pub mod condition;
 mod intrinsics;
 pub mod lookup_table;
+pub mod metadata;
 pub mod output;
 pub mod parameters;
 pub mod resource;
+pub mod rules;
+pub mod span;
 
 #[cfg(test)]
 mod tests;
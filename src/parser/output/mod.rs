@@ -9,4 +9,8 @@ pub struct Output {
     pub export: Option<ResourceValue>,
     pub condition: Option<String>,
     pub description: Option<String>,
+
+    // Comments of the output in a YAML template, see CloudformationParseTree::from_source
+    #[serde(skip)]
+    pub comments: Vec<String>,
 }
@@ -2,15 +2,101 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 use std::fmt;
 
+use serde::de::Error;
+
+use super::span::Span;
+
 #[derive(Debug, PartialEq, serde::Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct Parameter {
     pub allowed_values: Option<Vec<String>>,
-    pub default: Option<String>,
+    pub default: Option<ParameterDefault>,
     pub description: Option<String>,
     #[serde(rename = "Type")]
     pub parameter_type: ParameterType,
     pub no_echo: Option<String>,
+    pub allowed_pattern: Option<String>,
+    pub constraint_description: Option<String>,
+    #[serde(default, deserialize_with = "length")]
+    pub min_length: Option<u64>,
+    #[serde(default, deserialize_with = "length")]
+    pub max_length: Option<u64>,
+    #[serde(default, deserialize_with = "value")]
+    pub min_value: Option<f64>,
+    #[serde(default, deserialize_with = "value")]
+    pub max_value: Option<f64>,
+
+    // Position of the parameter in the template, see CloudformationParseTree::from_source
+    #[serde(skip)]
+    pub span: Option<Span>,
+
+    // Comments of the parameter in a YAML template, see CloudformationParseTree::from_source
+    #[serde(skip)]
+    pub comments: Vec<String>,
+}
+
+// The Default of a parameter, as written in the template. Scalars keep their textual form: they
+// are only interpreted according to the parameter's type when translated to IR.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParameterDefault {
+    Scalar(String),
+    List(Vec<String>),
+}
+
+impl<'de> serde::Deserialize<'de> for ParameterDefault {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ParameterDefault, D::Error> {
+        struct ParameterDefaultVisitor;
+        impl<'de> serde::de::Visitor<'de> for ParameterDefaultVisitor {
+            type Value = ParameterDefault;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string, number, boolean, or a list of those")
+            }
+
+            fn visit_bool<E: Error>(self, val: bool) -> Result<Self::Value, E> {
+                Ok(ParameterDefault::Scalar(val.to_string()))
+            }
+
+            fn visit_f64<E: Error>(self, val: f64) -> Result<Self::Value, E> {
+                Ok(ParameterDefault::Scalar(val.to_string()))
+            }
+
+            fn visit_i64<E: Error>(self, val: i64) -> Result<Self::Value, E> {
+                Ok(ParameterDefault::Scalar(val.to_string()))
+            }
+
+            fn visit_u64<E: Error>(self, val: u64) -> Result<Self::Value, E> {
+                Ok(ParameterDefault::Scalar(val.to_string()))
+            }
+
+            fn visit_str<E: Error>(self, val: &str) -> Result<Self::Value, E> {
+                Ok(ParameterDefault::Scalar(val.into()))
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut list = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(item) = seq.next_element()? {
+                    match item {
+                        ParameterDefault::Scalar(item) => list.push(item),
+                        ParameterDefault::List(_) => {
+                            return Err(A::Error::invalid_type(
+                                serde::de::Unexpected::Seq,
+                                &"a string, number or boolean",
+                            ))
+                        }
+                    }
+                }
+                Ok(ParameterDefault::List(list))
+            }
+        }
+
+        deserializer.deserialize_any(ParameterDefaultVisitor)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, serde_enum_str::Deserialize_enum_str)]
@@ -38,5 +124,50 @@ impl fmt::Display for ParameterType {
     }
 }
 
+// Length and value constraints are numbers, but they are frequently written as strings in JSON
+// templates (e.g: `"MinLength": "1"`), which CloudFormation accepts as well.
+struct ConstraintNumber;
+
+impl<'de> serde::de::Visitor<'de> for ConstraintNumber {
+    type Value = f64;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a number, or a string containing a number")
+    }
+
+    fn visit_f64<E: Error>(self, val: f64) -> Result<Self::Value, E> {
+        Ok(val)
+    }
+
+    fn visit_i64<E: Error>(self, val: i64) -> Result<Self::Value, E> {
+        Ok(val as f64)
+    }
+
+    fn visit_u64<E: Error>(self, val: u64) -> Result<Self::Value, E> {
+        Ok(val as f64)
+    }
+
+    fn visit_str<E: Error>(self, val: &str) -> Result<Self::Value, E> {
+        val.trim()
+            .parse()
+            .map_err(|_| E::invalid_value(serde::de::Unexpected::Str(val), &self))
+    }
+}
+
+fn value<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+    deserializer.deserialize_any(ConstraintNumber).map(Some)
+}
+
+fn length<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<u64>, D::Error> {
+    let val = deserializer.deserialize_any(ConstraintNumber)?;
+    if val < 0.0 || val.fract() != 0.0 {
+        return Err(D::Error::invalid_value(
+            serde::de::Unexpected::Float(val),
+            &"a non-negative integer",
+        ));
+    }
+    Ok(Some(val as u64))
+}
+
 #[cfg(test)]
 mod tests;
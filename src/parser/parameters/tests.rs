@@ -16,3 +16,78 @@ fn test_parameter_type_display() {
         "CustomType"
     );
 }
+
+#[test]
+fn test_parameter_constraints() {
+    let parameter: Parameter = serde_json::from_str(
+        r#"{
+            "Type": "String",
+            "MinLength": "1",
+            "MaxLength": 64,
+            "AllowedPattern": "[a-zA-Z][a-zA-Z0-9]*",
+            "ConstraintDescription": "Must begin with a letter"
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(parameter.min_length, Some(1));
+    assert_eq!(parameter.max_length, Some(64));
+    assert_eq!(
+        parameter.allowed_pattern.as_deref(),
+        Some("[a-zA-Z][a-zA-Z0-9]*")
+    );
+    assert_eq!(
+        parameter.constraint_description.as_deref(),
+        Some("Must begin with a letter")
+    );
+    assert_eq!(parameter.min_value, None);
+
+    let parameter: Parameter = serde_yaml::from_str(
+        r#"
+        Type: Number
+        MinValue: 0.5
+        MaxValue: '65535'
+        "#,
+    )
+    .unwrap();
+    assert_eq!(parameter.min_value, Some(0.5));
+    assert_eq!(parameter.max_value, Some(65535.0));
+    assert_eq!(parameter.min_length, None);
+}
+
+#[test]
+fn test_parameter_invalid_length() {
+    let err = serde_yaml::from_str::<Parameter>("{ Type: String, MinLength: -1 }").unwrap_err();
+    assert!(
+        err.to_string().contains("expected a non-negative integer"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_parameter_default() {
+    let parameter: Parameter = serde_yaml::from_str("{ Type: Number, Default: 5 }").unwrap();
+    assert_eq!(
+        parameter.default,
+        Some(ParameterDefault::Scalar("5".into()))
+    );
+
+    let parameter: Parameter = serde_yaml::from_str("{ Type: Boolean, Default: false }").unwrap();
+    assert_eq!(
+        parameter.default,
+        Some(ParameterDefault::Scalar("false".into()))
+    );
+
+    let parameter: Parameter =
+        serde_yaml::from_str("{ Type: CommaDelimitedList, Default: [a, 2] }").unwrap();
+    assert_eq!(
+        parameter.default,
+        Some(ParameterDefault::List(vec!["a".into(), "2".into()]))
+    );
+
+    let err = serde_yaml::from_str::<Parameter>("{ Type: String, Default: [[a]] }").unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("expected a string, number or boolean"),
+        "{err}"
+    );
+}
@@ -10,7 +10,10 @@ use std::convert::TryInto;
 use std::fmt;
 use std::marker::PhantomData;
 
+pub(crate) use super::intrinsics::map_with_for_each;
 pub use super::intrinsics::IntrinsicFunction;
+use super::intrinsics::{insert_with_for_each, is_for_each};
+use super::span::Span;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum ResourceValue {
@@ -23,6 +26,27 @@ pub enum ResourceValue {
     Object(IndexMap<String, ResourceValue, Hasher>),
 
     IntrinsicFunction(Box<IntrinsicFunction>),
+
+    // A value along with its position in the template, see CloudformationParseTree::from_source
+    Spanned(Span, Box<ResourceValue>),
+}
+
+impl ResourceValue {
+    /// The position of this value in the template, if it is known.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Self::Spanned(span, _) => Some(*span),
+            _ => None,
+        }
+    }
+
+    /// This value, without the position it may be wrapped with.
+    pub fn unspanned(&self) -> &Self {
+        match self {
+            Self::Spanned(_, value) => value.unspanned(),
+            value => value,
+        }
+    }
 }
 
 impl From<IntrinsicFunction> for ResourceValue {
@@ -93,6 +117,11 @@ impl<'de> serde::de::Deserialize<'de> for ResourceValue {
                         }
                         return Ok(intrinsic.into());
                     }
+                    if is_for_each(&key) {
+                        let value: serde_yaml::Value = data.next_value()?;
+                        insert_with_for_each::<_, A::Error>(&mut map, [(key, value)])?;
+                        continue;
+                    }
                     match map.entry(key) {
                         Entry::Vacant(entry) => {
                             entry.insert(data.next_value()?);
@@ -170,8 +199,23 @@ pub struct ResourceAttributes {
 
     pub deletion_policy: Option<DeletionPolicy>,
 
-    #[serde(default)]
+    pub update_replace_policy: Option<UpdateReplacePolicy>,
+
+    pub creation_policy: Option<CreationPolicy>,
+
+    #[serde(default, deserialize_with = "map_with_for_each")]
     pub properties: IndexMap<String, ResourceValue>,
+
+    // Position of the resource in the template, see CloudformationParseTree::from_source
+    #[serde(skip)]
+    pub span: Option<Span>,
+
+    // Comments of the resource and of its properties in a YAML template (by property name), see
+    // CloudformationParseTree::from_source
+    #[serde(skip)]
+    pub comments: Vec<String>,
+    #[serde(skip)]
+    pub property_comments: IndexMap<String, Vec<String>>,
 }
 
 fn string_or_seq_string<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
@@ -224,5 +268,47 @@ impl fmt::Display for DeletionPolicy {
     }
 }
 
+// UpdateReplacePolicy is rendered with CfnDeletionPolicy, but RetainExceptOnCreate is only valid as
+// a DeletionPolicy.
+#[derive(Clone, Copy, Debug, PartialEq, serde_enum_str::Deserialize_enum_str)]
+pub enum UpdateReplacePolicy {
+    Delete,
+    Retain,
+    Snapshot,
+}
+
+impl fmt::Display for UpdateReplacePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Delete => write!(f, "DELETE"),
+            Self::Retain => write!(f, "RETAIN"),
+            Self::Snapshot => write!(f, "SNAPSHOT"),
+        }
+    }
+}
+
+// CreationPolicy holds the settings CloudFormation waits on before a resource is considered
+// created. Leaf values may use intrinsic functions (e.g: `Count: !Ref DesiredCapacity`).
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct CreationPolicy {
+    pub auto_scaling_creation_policy: Option<AutoScalingCreationPolicy>,
+    pub resource_signal: Option<ResourceSignal>,
+    pub start_fleet: Option<ResourceValue>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct AutoScalingCreationPolicy {
+    pub min_successful_instances_percent: Option<ResourceValue>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct ResourceSignal {
+    pub count: Option<ResourceValue>,
+    pub timeout: Option<ResourceValue>,
+}
+
 #[cfg(test)]
 mod tests;
@@ -365,6 +365,150 @@ fn intrinsic_ref() {
     );
 }
 
+#[test]
+fn intrinsic_length() {
+    const LOGICAL_NAME: &str = "LogicalName";
+
+    assert_eq!(
+        ResourceValue::from_value(json!({ "Fn::Length": ["a", "b"] })).unwrap(),
+        IntrinsicFunction::Length(ResourceValue::Array(vec![
+            ResourceValue::String("a".into()),
+            ResourceValue::String("b".into())
+        ]))
+        .into(),
+    );
+    assert_eq!(
+        ResourceValue::from_value(
+            serde_yaml::from_str(&format!("!Length {{ Ref: {LOGICAL_NAME} }}")).unwrap()
+        )
+        .unwrap(),
+        IntrinsicFunction::Length(IntrinsicFunction::Ref(LOGICAL_NAME.into()).into()).into(),
+    );
+}
+
+#[test]
+fn intrinsic_to_json_string() {
+    assert_eq!(
+        ResourceValue::from_value(json!({ "Fn::ToJsonString": { "Key": "Value" } })).unwrap(),
+        IntrinsicFunction::ToJsonString(ResourceValue::Object(IndexMap::from_iter([(
+            "Key".to_string(),
+            ResourceValue::String("Value".into())
+        )])))
+        .into(),
+    );
+    assert_eq!(
+        ResourceValue::from_value(serde_yaml::from_str("!ToJsonString [1, 2]").unwrap()).unwrap(),
+        IntrinsicFunction::ToJsonString(ResourceValue::Array(vec![
+            ResourceValue::Number(1),
+            ResourceValue::Number(2)
+        ]))
+        .into(),
+    );
+}
+
+#[test]
+fn for_each_in_object() {
+    let value: Value = serde_yaml::from_str(
+        r#"
+        Static: value
+        Fn::ForEach::Topics:
+          - Name
+          - [Success, Failure-Queue]
+          - "&{Name}Topic":
+              Arn: !Ref Name
+              Label: !Sub "${Name}-label-${!Name}"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        ResourceValue::from_value(value).unwrap(),
+        ResourceValue::Object(IndexMap::from_iter([
+            ("Static".to_string(), ResourceValue::String("value".into())),
+            (
+                "SuccessTopic".to_string(),
+                ResourceValue::Object(IndexMap::from_iter([
+                    ("Arn".to_string(), ResourceValue::String("Success".into())),
+                    (
+                        "Label".to_string(),
+                        IntrinsicFunction::Sub {
+                            string: "Success-label-${!Name}".into(),
+                            replaces: None
+                        }
+                        .into()
+                    ),
+                ]))
+            ),
+            (
+                "FailureQueueTopic".to_string(),
+                ResourceValue::Object(IndexMap::from_iter([
+                    (
+                        "Arn".to_string(),
+                        ResourceValue::String("Failure-Queue".into())
+                    ),
+                    (
+                        "Label".to_string(),
+                        IntrinsicFunction::Sub {
+                            string: "Failure-Queue-label-${!Name}".into(),
+                            replaces: None
+                        }
+                        .into()
+                    ),
+                ]))
+            ),
+        ]))
+    );
+}
+
+#[test]
+fn for_each_requires_literal_collection() {
+    let value: Value = serde_yaml::from_str(
+        r#"
+        Fn::ForEach::Topics:
+          - Name
+          - !Ref TopicNames
+          - "${Name}": !Ref Name
+        "#,
+    )
+    .unwrap();
+
+    let error = ResourceValue::from_value(value).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("Fn::ForEach::Topics requires a literal list as its collection"));
+    assert!(error
+        .to_string()
+        .contains("cannot iterate over a parameter"));
+}
+
+#[test]
+fn for_each_in_properties() {
+    let resource: ResourceAttributes = serde_yaml::from_str(
+        r#"
+        Type: AWS::SNS::Topic
+        Properties:
+          Fn::ForEach::Names:
+            - Prop
+            - [TopicName, DisplayName]
+            - "${Prop}": !Sub "my-${Prop}"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        resource.properties.keys().collect::<Vec<_>>(),
+        vec!["TopicName", "DisplayName"]
+    );
+    assert_eq!(
+        resource.properties["DisplayName"],
+        IntrinsicFunction::Sub {
+            string: "my-DisplayName".into(),
+            replaces: None
+        }
+        .into()
+    );
+}
+
 impl ResourceValue {
     #[inline(always)]
     fn from_value(value: Value) -> Result<Self, serde_yaml::Error> {
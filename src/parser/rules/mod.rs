@@ -0,0 +1,316 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use serde::de::{Error, VariantAccess};
+
+// A Rule validates parameter values (or combinations thereof) before a stack is created or
+// updated. See https://docs.aws.amazon.com/AWSCloudFormation/latest/UserGuide/rules-section-structure.html
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Rule {
+    pub rule_condition: Option<RuleFunction>,
+    #[serde(default)]
+    pub assertions: Vec<RuleAssertion>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct RuleAssertion {
+    pub assert: RuleFunction,
+    pub assert_description: Option<String>,
+}
+
+// RuleFunctions are the boolean expressions allowed in a rule condition or assertion.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RuleFunction {
+    And(Vec<RuleFunction>),
+    Or(Vec<RuleFunction>),
+    Not(Box<RuleFunction>),
+    Equals(RuleValue, RuleValue),
+    Contains(RuleValue, RuleValue),
+    EachMemberEquals(RuleValue, RuleValue),
+    EachMemberIn(RuleValue, RuleValue),
+}
+
+const RULE_FUNCTIONS: &[&str] = &[
+    "Fn::And",
+    "Fn::Or",
+    "Fn::Not",
+    "Fn::Equals",
+    "Fn::Contains",
+    "Fn::EachMemberEquals",
+    "Fn::EachMemberIn",
+];
+
+impl RuleFunction {
+    fn from_variant_access<'de, A: VariantAccess<'de>>(
+        variant: &str,
+        data: A,
+    ) -> Result<Self, A::Error> {
+        match variant {
+            "And" => Ok(Self::And(data.newtype_variant()?)),
+            "Or" => Ok(Self::Or(data.newtype_variant()?)),
+            "Not" => Ok(Self::Not(Box::new(
+                data.newtype_variant::<Singleton>()?.unwrap(),
+            ))),
+            "Equals" => {
+                let (left, right) = data.newtype_variant()?;
+                Ok(Self::Equals(left, right))
+            }
+            "Contains" => {
+                let (list, value) = data.newtype_variant()?;
+                Ok(Self::Contains(list, value))
+            }
+            "EachMemberEquals" => {
+                let (list, value) = data.newtype_variant()?;
+                Ok(Self::EachMemberEquals(list, value))
+            }
+            "EachMemberIn" => {
+                let (to_check, to_match) = data.newtype_variant()?;
+                Ok(Self::EachMemberIn(to_check, to_match))
+            }
+            unknown => Err(A::Error::unknown_variant(unknown, RULE_FUNCTIONS)),
+        }
+    }
+
+    fn from_map_access<'de, A: serde::de::MapAccess<'de>>(
+        variant: &str,
+        data: &mut A,
+    ) -> Result<Self, A::Error> {
+        match variant {
+            "Fn::And" => Ok(Self::And(data.next_value()?)),
+            "Fn::Or" => Ok(Self::Or(data.next_value()?)),
+            "Fn::Not" => Ok(Self::Not(Box::new(
+                data.next_value::<Singleton>()?.unwrap(),
+            ))),
+            "Fn::Equals" => {
+                let (left, right) = data.next_value()?;
+                Ok(Self::Equals(left, right))
+            }
+            "Fn::Contains" => {
+                let (list, value) = data.next_value()?;
+                Ok(Self::Contains(list, value))
+            }
+            "Fn::EachMemberEquals" => {
+                let (list, value) = data.next_value()?;
+                Ok(Self::EachMemberEquals(list, value))
+            }
+            "Fn::EachMemberIn" => {
+                let (to_check, to_match) = data.next_value()?;
+                Ok(Self::EachMemberIn(to_check, to_match))
+            }
+            unknown => Err(A::Error::unknown_variant(unknown, RULE_FUNCTIONS)),
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for RuleFunction {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<RuleFunction, D::Error> {
+        struct RuleFunctionVisitor;
+        impl<'de> serde::de::Visitor<'de> for RuleFunctionVisitor {
+            type Value = RuleFunction;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a CloudFormation rule function")
+            }
+
+            fn visit_enum<A: serde::de::EnumAccess<'de>>(
+                self,
+                data: A,
+            ) -> Result<Self::Value, A::Error> {
+                let (variant, data) = data.variant::<String>()?;
+                Self::Value::from_variant_access(&variant, data)
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut data: A,
+            ) -> Result<Self::Value, A::Error> {
+                let variant: String = match data.next_key()? {
+                    Some(key) => key,
+                    None => return Err(A::Error::invalid_length(0, &Self)),
+                };
+                let value = Self::Value::from_map_access(&variant, &mut data)?;
+                if data.next_key::<String>()?.is_some() {
+                    return Err(A::Error::invalid_length(2, &Self));
+                }
+                Ok(value)
+            }
+        }
+
+        deserializer.deserialize_any(RuleFunctionVisitor)
+    }
+}
+
+// RuleValues are the operands of rule functions: literals, parameter references, and the
+// rule-specific functions that look up parameter values.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RuleValue {
+    String(String),
+    List(Vec<RuleValue>),
+    Ref(String),
+    RefAll(String),
+    ValueOf(String, String),
+    ValueOfAll(String, String),
+}
+
+const RULE_VALUE_FUNCTIONS: &[&str] = &["Ref", "Fn::RefAll", "Fn::ValueOf", "Fn::ValueOfAll"];
+
+impl<'de> serde::Deserialize<'de> for RuleValue {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<RuleValue, D::Error> {
+        struct RuleValueVisitor;
+        impl<'de> serde::de::Visitor<'de> for RuleValueVisitor {
+            type Value = RuleValue;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a CloudFormation rule value")
+            }
+
+            fn visit_bool<E: Error>(self, val: bool) -> Result<Self::Value, E> {
+                Ok(RuleValue::String(val.to_string()))
+            }
+
+            fn visit_f64<E: Error>(self, val: f64) -> Result<Self::Value, E> {
+                Ok(RuleValue::String(val.to_string()))
+            }
+
+            fn visit_i64<E: Error>(self, val: i64) -> Result<Self::Value, E> {
+                Ok(RuleValue::String(val.to_string()))
+            }
+
+            fn visit_u64<E: Error>(self, val: u64) -> Result<Self::Value, E> {
+                Ok(RuleValue::String(val.to_string()))
+            }
+
+            fn visit_str<E: Error>(self, val: &str) -> Result<Self::Value, E> {
+                Ok(RuleValue::String(val.into()))
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut list = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(item) = seq.next_element()? {
+                    list.push(item);
+                }
+                Ok(RuleValue::List(list))
+            }
+
+            fn visit_enum<A: serde::de::EnumAccess<'de>>(
+                self,
+                data: A,
+            ) -> Result<Self::Value, A::Error> {
+                let (variant, data) = data.variant::<String>()?;
+                match variant.as_str() {
+                    "Ref" => Ok(RuleValue::Ref(data.newtype_variant()?)),
+                    "RefAll" => Ok(RuleValue::RefAll(data.newtype_variant()?)),
+                    "ValueOf" => {
+                        let (parameter, attribute) = data.newtype_variant()?;
+                        Ok(RuleValue::ValueOf(parameter, attribute))
+                    }
+                    "ValueOfAll" => {
+                        let (parameter_type, attribute) = data.newtype_variant()?;
+                        Ok(RuleValue::ValueOfAll(parameter_type, attribute))
+                    }
+                    unknown => Err(A::Error::unknown_variant(unknown, RULE_VALUE_FUNCTIONS)),
+                }
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut data: A,
+            ) -> Result<Self::Value, A::Error> {
+                let key: String = match data.next_key()? {
+                    Some(key) => key,
+                    None => return Err(A::Error::invalid_length(0, &Self)),
+                };
+                let value = match key.as_str() {
+                    "Ref" => RuleValue::Ref(data.next_value()?),
+                    "Fn::RefAll" => RuleValue::RefAll(data.next_value()?),
+                    "Fn::ValueOf" => {
+                        let (parameter, attribute) = data.next_value()?;
+                        RuleValue::ValueOf(parameter, attribute)
+                    }
+                    "Fn::ValueOfAll" => {
+                        let (parameter_type, attribute) = data.next_value()?;
+                        RuleValue::ValueOfAll(parameter_type, attribute)
+                    }
+                    unknown => {
+                        return Err(A::Error::unknown_variant(unknown, RULE_VALUE_FUNCTIONS))
+                    }
+                };
+                if data.next_key::<String>()?.is_some() {
+                    return Err(A::Error::invalid_length(2, &Self));
+                }
+                Ok(value)
+            }
+        }
+
+        deserializer.deserialize_any(RuleValueVisitor)
+    }
+}
+
+// Fn::Not accepts its operand either on its own or wrapped in a single-item list. This can't be
+// an untagged enum, as those do not support YAML short-form tags (e.g: `!Not [!Equals [a, b]]`).
+struct Singleton(RuleFunction);
+
+impl Singleton {
+    fn unwrap(self) -> RuleFunction {
+        self.0
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Singleton {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Singleton, D::Error> {
+        struct SingletonVisitor;
+        impl<'de> serde::de::Visitor<'de> for SingletonVisitor {
+            type Value = Singleton;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a CloudFormation rule function, or a list of one")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let value = match seq.next_element()? {
+                    Some(value) => value,
+                    None => return Err(A::Error::invalid_length(0, &Self)),
+                };
+                if seq.next_element::<RuleFunction>()?.is_some() {
+                    return Err(A::Error::invalid_length(2, &Self));
+                }
+                Ok(Singleton(value))
+            }
+
+            fn visit_enum<A: serde::de::EnumAccess<'de>>(
+                self,
+                data: A,
+            ) -> Result<Self::Value, A::Error> {
+                let (variant, data) = data.variant::<String>()?;
+                RuleFunction::from_variant_access(&variant, data).map(Singleton)
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut data: A,
+            ) -> Result<Self::Value, A::Error> {
+                let variant: String = match data.next_key()? {
+                    Some(key) => key,
+                    None => return Err(A::Error::invalid_length(0, &Self)),
+                };
+                let value = RuleFunction::from_map_access(&variant, &mut data)?;
+                if data.next_key::<String>()?.is_some() {
+                    return Err(A::Error::invalid_length(2, &Self));
+                }
+                Ok(Singleton(value))
+            }
+        }
+
+        deserializer.deserialize_any(SingletonVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests;
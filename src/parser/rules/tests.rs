@@ -0,0 +1,148 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use super::*;
+
+#[test]
+fn function_contains() {
+    let expected = RuleFunction::Contains(
+        RuleValue::List(vec![
+            RuleValue::String("a1.large".into()),
+            RuleValue::String("a1.xlarge".into()),
+        ]),
+        RuleValue::Ref("InstanceType".into()),
+    );
+
+    assert_eq!(
+        expected,
+        serde_yaml::from_str("!Contains [[a1.large, a1.xlarge], !Ref InstanceType]").unwrap(),
+    );
+    assert_eq!(
+        expected,
+        serde_yaml::from_str("Fn::Contains: [[a1.large, a1.xlarge], { Ref: InstanceType }]")
+            .unwrap(),
+    );
+}
+
+#[test]
+fn function_each_member_equals() {
+    let expected = RuleFunction::EachMemberEquals(
+        RuleValue::ValueOfAll("AWS::EC2::Subnet::Id".into(), "VpcId".into()),
+        RuleValue::Ref("VpcId".into()),
+    );
+
+    assert_eq!(
+        expected,
+        serde_yaml::from_str(
+            "!EachMemberEquals [!ValueOfAll [AWS::EC2::Subnet::Id, VpcId], !Ref VpcId]"
+        )
+        .unwrap(),
+    );
+    assert_eq!(
+        expected,
+        serde_yaml::from_str(
+            "Fn::EachMemberEquals: [{ Fn::ValueOfAll: [AWS::EC2::Subnet::Id, VpcId] }, { Ref: VpcId }]"
+        )
+        .unwrap(),
+    );
+}
+
+#[test]
+fn function_each_member_in() {
+    let expected = RuleFunction::EachMemberIn(
+        RuleValue::ValueOf("Subnets".into(), "VpcId".into()),
+        RuleValue::RefAll("AWS::EC2::VPC::Id".into()),
+    );
+
+    assert_eq!(
+        expected,
+        serde_yaml::from_str(
+            "!EachMemberIn [!ValueOf [Subnets, VpcId], !RefAll AWS::EC2::VPC::Id]"
+        )
+        .unwrap(),
+    );
+    assert_eq!(
+        expected,
+        serde_yaml::from_str(
+            "Fn::EachMemberIn: [{ Fn::ValueOf: [Subnets, VpcId] }, { Fn::RefAll: AWS::EC2::VPC::Id }]"
+        )
+        .unwrap(),
+    );
+}
+
+#[test]
+fn function_not() {
+    let expected = RuleFunction::Not(Box::new(RuleFunction::Equals(
+        RuleValue::Ref("Environment".into()),
+        RuleValue::String("prod".into()),
+    )));
+
+    assert_eq!(
+        expected,
+        serde_yaml::from_str("!Not [!Equals [!Ref Environment, prod]]").unwrap(),
+    );
+    assert_eq!(
+        expected,
+        serde_yaml::from_str("Fn::Not: { Fn::Equals: [{ Ref: Environment }, prod] }").unwrap(),
+    );
+}
+
+#[test]
+fn function_and_or() {
+    let equals = RuleFunction::Equals(RuleValue::String("1".into()), RuleValue::String("1".into()));
+    let expected = RuleFunction::And(vec![
+        RuleFunction::Or(vec![equals.clone(), equals.clone()]),
+        equals,
+    ]);
+
+    assert_eq!(
+        expected,
+        serde_yaml::from_str("!And [!Or [!Equals [1, 1], !Equals [1, 1]], !Equals [1, 1]]")
+            .unwrap(),
+    );
+}
+
+#[test]
+fn unknown_function() {
+    let err = serde_yaml::from_str::<RuleFunction>("!If [Cond, a, b]").unwrap_err();
+    assert!(err.to_string().contains("unknown variant `If`"), "{err}");
+}
+
+#[test]
+fn rule() {
+    let rule: Rule = serde_yaml::from_str(
+        r#"
+        RuleCondition: !Equals [!Ref Environment, prod]
+        Assertions:
+          - Assert: !Contains [[m5.large], !Ref InstanceType]
+            AssertDescription: Production requires m5.large
+          - Assert: !Not [!Equals [!Ref InstanceType, '']]
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        rule,
+        Rule {
+            rule_condition: Some(RuleFunction::Equals(
+                RuleValue::Ref("Environment".into()),
+                RuleValue::String("prod".into()),
+            )),
+            assertions: vec![
+                RuleAssertion {
+                    assert: RuleFunction::Contains(
+                        RuleValue::List(vec![RuleValue::String("m5.large".into())]),
+                        RuleValue::Ref("InstanceType".into()),
+                    ),
+                    assert_description: Some("Production requires m5.large".into()),
+                },
+                RuleAssertion {
+                    assert: RuleFunction::Not(Box::new(RuleFunction::Equals(
+                        RuleValue::Ref("InstanceType".into()),
+                        RuleValue::String("".into()),
+                    ))),
+                    assert_description: None,
+                },
+            ],
+        }
+    );
+}
@@ -0,0 +1,348 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use std::collections::HashMap;
+use std::fmt;
+
+use yaml_rust2::parser::{Event, Parser};
+
+/// A position in the original template. Both `line` and `column` are 1-based.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// One step of the path to an entry of the template: the key of a mapping entry, or the index of a
+/// sequence item. Keys are kept whole, so that they may hold `.` or `[`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+impl From<&str> for PathSegment {
+    fn from(key: &str) -> Self {
+        Self::Key(key.into())
+    }
+}
+
+impl From<&String> for PathSegment {
+    fn from(key: &String) -> Self {
+        Self::Key(key.clone())
+    }
+}
+
+impl From<usize> for PathSegment {
+    fn from(index: usize) -> Self {
+        Self::Index(index)
+    }
+}
+
+/// Builds the path made of `segments` (e.g: `path(["Resources", name, "Properties"])`).
+pub fn path<S: Into<PathSegment>>(segments: impl IntoIterator<Item = S>) -> Vec<PathSegment> {
+    segments.into_iter().map(Into::into).collect()
+}
+
+/// Maps the entries of a template (e.g: `Resources.MyBucket.Properties.Tags[2].Key`) to their
+/// position in the original YAML or JSON source.
+///
+/// `serde_yaml` does not expose positions while deserializing, so the source is read a second time
+/// by an event parser, to locate every mapping key and sequence item. Values that cannot be
+/// located exactly resolve to their closest located ancestor.
+///
+/// The comments `serde_yaml` discards are kept too: those on the lines right above an entry, and
+/// the one at the end of its line.
+#[derive(Clone, Debug, Default)]
+pub struct SourceMap {
+    lines: Vec<String>,
+    spans: HashMap<Vec<PathSegment>, Span>,
+    comments: HashMap<Vec<PathSegment>, Vec<String>>,
+}
+
+impl SourceMap {
+    pub fn new(source: &str) -> Self {
+        let spans = index(source);
+        let lines: Vec<String> = source.lines().map(ToString::to_string).collect();
+        let comments = spans
+            .iter()
+            .filter_map(|(path, span)| {
+                let comments = comments_at(&lines, *span);
+                (!comments.is_empty()).then(|| (path.clone(), comments))
+            })
+            .collect();
+        Self {
+            lines,
+            spans,
+            comments,
+        }
+    }
+
+    /// Returns the text of the comments attached to `path` (without their `#`), if any. Unlike
+    /// spans, comments are never inherited from an ancestor.
+    pub fn comments(&self, path: &[PathSegment]) -> &[String] {
+        self.comments.get(path).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the position of `path`, if it could be located.
+    pub fn exact_span(&self, path: &[PathSegment]) -> Option<Span> {
+        self.spans.get(path).copied()
+    }
+
+    /// Returns the position of `path`, or of its closest ancestor that could be located.
+    pub fn closest_span(&self, path: &[PathSegment]) -> Option<Span> {
+        (0..=path.len())
+            .rev()
+            .find_map(|len| self.exact_span(&path[..len]))
+    }
+
+    /// Returns the position of the entry at `path`, written as in diagnostics (e.g:
+    /// `Resources.MyBucket.Properties.Tags[2].Key`), or of its closest ancestor that could be
+    /// located. As keys may hold `.` or `[` themselves, the path is matched against the keys of the
+    /// template rather than split on them.
+    pub fn span(&self, path: &str) -> Option<Span> {
+        let mut best = Vec::new();
+        self.resolve(&mut Vec::new(), path, &mut best);
+        self.exact_span(&best)
+    }
+
+    // Walks the located entries along `rest`, keeping the deepest one reached in `best`. Returns
+    // whether the whole path was located.
+    fn resolve(
+        &self,
+        prefix: &mut Vec<PathSegment>,
+        rest: &str,
+        best: &mut Vec<PathSegment>,
+    ) -> bool {
+        if rest.is_empty() {
+            *best = prefix.clone();
+            return true;
+        }
+        if prefix.len() > best.len() {
+            *best = prefix.clone();
+        }
+
+        let mut candidates = Vec::new();
+        if let Some((index, remaining)) =
+            rest.strip_prefix('[').and_then(|rest| rest.split_once(']'))
+        {
+            if let Ok(index) = index.parse() {
+                candidates.push((PathSegment::Index(index), remaining));
+            }
+        } else {
+            let rest = match prefix.is_empty() {
+                true => rest,
+                false => match rest.strip_prefix('.') {
+                    Some(rest) => rest,
+                    None => return false,
+                },
+            };
+            // The longest keys first, so that `A.B` is preferred over `A` followed by `B`.
+            for (end, _) in rest
+                .char_indices()
+                .filter(|(_, c)| *c == '.' || *c == '[')
+                .chain([(rest.len(), ' ')])
+                .collect::<Vec<_>>()
+                .into_iter()
+                .rev()
+            {
+                candidates.push((PathSegment::Key(rest[..end].into()), &rest[end..]));
+            }
+        }
+
+        for (segment, remaining) in candidates {
+            prefix.push(segment);
+            let found =
+                self.spans.contains_key(prefix.as_slice()) && self.resolve(prefix, remaining, best);
+            prefix.pop();
+            if found {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// The keys at the root of the template (e.g: `Resources`), in the order they appear.
+    pub fn sections(&self) -> Vec<&str> {
+        let mut sections: Vec<(&str, Span)> = self
+            .spans
+            .iter()
+            .filter_map(|(path, span)| match path.as_slice() {
+                [PathSegment::Key(key)] => Some((key.as_str(), *span)),
+                _ => None,
+            })
+            .collect();
+        sections.sort_unstable_by_key(|(_, span)| *span);
+        sections.into_iter().map(|(path, _)| path).collect()
+    }
+
+    /// Renders the line pointed at by `span`, with a caret under the offending column.
+    pub fn snippet(&self, span: Span) -> Option<String> {
+        let line = self.lines.get(span.line.checked_sub(1)?)?;
+        let gutter = span.line.to_string().len();
+        let padding: String = line
+            .chars()
+            .take(span.column.saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        Some(format!(
+            "{:gutter$}--> line {}, column {}\n{:gutter$} |\n{} | {line}\n{:gutter$} | {padding}^",
+            "", span.line, span.column, "", span.line, ""
+        ))
+    }
+}
+
+// The comments attached to the entry at `span`: the comment lines right above it, when it starts
+// its line, followed by the comment that ends its line.
+fn comments_at(lines: &[String], span: Span) -> Vec<String> {
+    let Some(line) = lines.get(span.line - 1) else {
+        return Vec::new();
+    };
+    let indent = span.column - 1;
+    let mut comments = Vec::new();
+    if line.chars().take(indent).all(char::is_whitespace) {
+        for above in lines[..span.line - 1].iter().rev() {
+            let text = above.trim_start();
+            // More indented lines belong to a block scalar above (e.g: a script's own comments).
+            match text.strip_prefix('#') {
+                Some(comment) if above.len() - text.len() <= indent => {
+                    comments.push(comment_text(comment));
+                }
+                _ => break,
+            }
+        }
+        comments.reverse();
+    }
+    let rest: String = line.chars().skip(indent).collect();
+    if let Some(comment) = trailing_comment(&rest) {
+        comments.push(comment_text(comment));
+    }
+    comments
+}
+
+// Finds the comment that ends `line`, skipping `#` characters within quoted scalars.
+fn trailing_comment(line: &str) -> Option<&str> {
+    let mut quote = None;
+    let mut escaped = false;
+    let mut previous = ' ';
+    for (index, c) in line.char_indices() {
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(open) if c == open => quote = None,
+            Some(_) => {}
+            // Quotes only open a scalar at its start, not within plain text (e.g: `it's`).
+            None if (c == '"' || c == '\'')
+                && (previous.is_whitespace() || "[{,".contains(previous)) =>
+            {
+                quote = Some(c)
+            }
+            None if c == '#' && previous.is_whitespace() => return Some(&line[index + 1..]),
+            None => {}
+        }
+        previous = c;
+    }
+    None
+}
+
+fn comment_text(comment: &str) -> String {
+    comment
+        .strip_prefix(' ')
+        .unwrap_or(comment)
+        .trim_end()
+        .to_string()
+}
+
+// Records the position of every mapping key and sequence item of the first document of `source`,
+// as reported by the YAML parser (which reads JSON templates too). A source the parser rejects is
+// indexed up to the error.
+fn index(source: &str) -> HashMap<Vec<PathSegment>, Span> {
+    let mut spans = HashMap::new();
+    // The path of each open collection, if it is not within a complex mapping key.
+    let mut stack: Vec<Collection> = Vec::new();
+    let mut parser = Parser::new_from_str(source);
+    while let Ok((event, marker)) = parser.next_token() {
+        let span = Span {
+            line: marker.line(),
+            column: marker.col() + 1,
+        };
+        let key = match &event {
+            Event::Scalar(value, ..) => Some(value.as_str()),
+            Event::MappingEnd | Event::SequenceEnd => {
+                stack.pop();
+                continue;
+            }
+            Event::DocumentEnd | Event::StreamEnd => break,
+            _ => None,
+        };
+
+        let path = match stack.last_mut() {
+            None => Some(Vec::new()),
+            Some(Collection::Sequence { path, next }) => {
+                let item = path
+                    .as_ref()
+                    .map(|path| child(path, PathSegment::Index(*next)));
+                *next += 1;
+                if let Some(item) = &item {
+                    spans.entry(item.clone()).or_insert(span);
+                }
+                item
+            }
+            Some(Collection::Mapping { path, value }) => match value.take() {
+                // The key of the entry: its value is found at the path of the key.
+                None => {
+                    let entry = path
+                        .as_deref()
+                        .zip(key)
+                        .map(|(path, key)| child(path, PathSegment::Key(key.into())));
+                    if let Some(entry) = &entry {
+                        spans.entry(entry.clone()).or_insert(span);
+                    }
+                    // The parser starts a block mapping at its first `:`, rather than at its first
+                    // key (e.g: `- Key: value`).
+                    if let Some(start) = path.as_ref().and_then(|path| spans.get_mut(path)) {
+                        if span < *start {
+                            *start = span;
+                        }
+                    }
+                    *value = Some(entry);
+                    None
+                }
+                Some(entry) => entry,
+            },
+        };
+
+        match event {
+            Event::MappingStart(..) => stack.push(Collection::Mapping { path, value: None }),
+            Event::SequenceStart(..) => stack.push(Collection::Sequence { path, next: 0 }),
+            _ => {}
+        }
+    }
+    spans
+}
+
+fn child(path: &[PathSegment], segment: PathSegment) -> Vec<PathSegment> {
+    let mut child = path.to_vec();
+    child.push(segment);
+    child
+}
+
+enum Collection {
+    // `value` holds the path of the next value once its key was read.
+    Mapping {
+        path: Option<Vec<PathSegment>>,
+        value: Option<Option<Vec<PathSegment>>>,
+    },
+    Sequence {
+        path: Option<Vec<PathSegment>>,
+        next: usize,
+    },
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,207 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use crate::parser::span::{path, PathSegment, SourceMap, Span};
+
+const YAML: &str = r#"AWSTemplateFormatVersion: "2010-09-09"
+# A comment
+Resources:
+  MyBucket:
+    Type: AWS::S3::Bucket
+    Properties:
+      BucketName: !Sub "${AWS::StackName}-bucket"
+      Tags:
+        - Key: first
+          Value: one
+        - Key: second
+          Value: |
+            multi
+            line
+        -
+          Key: third
+          Value: [a, {Ref: Other}]
+  'Quoted':
+    Type: AWS::SQS::Queue
+Outputs:
+  Name:
+    Value:
+      Fn::GetAtt:
+      - MyBucket
+      - Arn
+"#;
+
+#[test]
+fn test_yaml_spans() {
+    let map = SourceMap::new(YAML);
+    let span = |path: &str| map.span(path).map(|s| (s.line, s.column));
+
+    assert_eq!(span("Resources"), Some((3, 1)));
+    assert_eq!(span("Resources.MyBucket"), Some((4, 3)));
+    assert_eq!(
+        span("Resources.MyBucket.Properties.BucketName"),
+        Some((7, 7))
+    );
+    assert_eq!(span("Resources.MyBucket.Properties.Tags[0]"), Some((9, 11)));
+    assert_eq!(
+        span("Resources.MyBucket.Properties.Tags[1].Value"),
+        Some((12, 11))
+    );
+    assert_eq!(
+        span("Resources.MyBucket.Properties.Tags[2].Key"),
+        Some((16, 11))
+    );
+    assert_eq!(
+        span("Resources.MyBucket.Properties.Tags[2].Value[1].Ref"),
+        Some((17, 23))
+    );
+    assert_eq!(span("Resources.Quoted.Type"), Some((19, 5)));
+    assert_eq!(span("Outputs.Name.Value.Fn::GetAtt[1]"), Some((25, 9)));
+}
+
+#[test]
+fn test_sections() {
+    let map = SourceMap::new(YAML);
+    assert_eq!(
+        map.sections(),
+        vec!["AWSTemplateFormatVersion", "Resources", "Outputs"]
+    );
+}
+
+#[test]
+fn test_unknown_paths_resolve_to_ancestor() {
+    let map = SourceMap::new(YAML);
+    assert_eq!(
+        map.span("Resources.MyBucket.Properties.Tags[0].Missing[3]"),
+        Some(Span {
+            line: 9,
+            column: 11
+        })
+    );
+    assert_eq!(map.span("Nothing"), None);
+}
+
+#[test]
+fn test_json_spans() {
+    let map = SourceMap::new(
+        "{\n  \"Resources\": {\n    \"Topic\": {\n      \"Type\": \"AWS::SNS::Topic\",\n      \"Properties\": {\"Subscription\": [{\"Endpoint\": \"a\"}]}\n    }\n  }\n}\n",
+    );
+    let span = |path: &str| map.span(path).map(|s| (s.line, s.column));
+
+    assert_eq!(span("Resources.Topic"), Some((3, 5)));
+    assert_eq!(span("Resources.Topic.Type"), Some((4, 7)));
+    assert_eq!(
+        span("Resources.Topic.Properties.Subscription[0].Endpoint"),
+        Some((5, 40))
+    );
+}
+
+#[test]
+fn test_snippet() {
+    let map = SourceMap::new(YAML);
+    let span = map
+        .span("Resources.MyBucket.Properties.Tags[1].Key")
+        .unwrap();
+    assert_eq!(
+        map.snippet(span).unwrap(),
+        "  --> line 11, column 11\n   |\n11 |         - Key: second\n   |           ^"
+    );
+}
+
+#[test]
+fn test_comments() {
+    let map = SourceMap::new(
+        r#"Resources:
+  # The bucket of the site.
+  #
+  # Kept on deletion.
+  Bucket:
+    Type: AWS::S3::Bucket # the type
+    Properties:
+      BucketName: "site#bucket" # it's named
+  Function:
+    Type: AWS::Lambda::Function
+    Properties:
+      Code:
+        ZipFile: |
+          # not a comment of Handler
+      Handler: index.handler
+"#,
+    );
+
+    assert_eq!(
+        map.comments(&path(["Resources", "Bucket"])),
+        ["The bucket of the site.", "", "Kept on deletion."]
+    );
+    assert_eq!(
+        map.comments(&path(["Resources", "Bucket", "Type"])),
+        ["the type"]
+    );
+    assert_eq!(
+        map.comments(&path(["Resources", "Bucket", "Properties", "BucketName"])),
+        ["it's named"]
+    );
+    assert!(map.comments(&path(["Resources"])).is_empty());
+    assert!(map
+        .comments(&path(["Resources", "Function", "Properties", "Handler"]))
+        .is_empty());
+    assert!(map
+        .comments(&path(["Resources", "Bucket", "Properties"]))
+        .is_empty());
+}
+
+#[test]
+fn test_keys_with_separators() {
+    let map = SourceMap::new(
+        r#"Resources:
+  Function:
+    Type: AWS::Lambda::Function
+    Properties:
+      Environment:
+        Variables:
+          # The site.
+          site.url: example.com
+          site: other
+          "list[0]": value
+          list:
+            - first
+"#,
+    );
+    let span = |path: &str| map.span(path).map(|s| (s.line, s.column));
+    let variables = path([
+        "Resources",
+        "Function",
+        "Properties",
+        "Environment",
+        "Variables",
+    ]);
+    let variable = |key: &str| {
+        let mut path = variables.clone();
+        path.push(key.into());
+        path
+    };
+
+    assert_eq!(
+        map.exact_span(&variable("site.url"))
+            .map(|s| (s.line, s.column)),
+        Some((8, 11))
+    );
+    assert_eq!(map.comments(&variable("site.url")), ["The site."]);
+    assert!(map.comments(&variable("site")).is_empty());
+    assert_eq!(
+        map.exact_span(&variable("list[0]"))
+            .map(|s| (s.line, s.column)),
+        Some((10, 11))
+    );
+    let mut item = variable("list");
+    item.push(PathSegment::Index(0));
+    assert_eq!(
+        map.exact_span(&item).map(|s| (s.line, s.column)),
+        Some((12, 15))
+    );
+
+    // Diagnostics write paths as text, which are matched against the keys of the template.
+    let prefix = "Resources.Function.Properties.Environment.Variables";
+    assert_eq!(span(&format!("{prefix}.site.url")), Some((8, 11)));
+    assert_eq!(span(&format!("{prefix}.site")), Some((9, 11)));
+    assert_eq!(span(&format!("{prefix}.list[0]")), Some((10, 11)));
+    assert_eq!(span(&format!("{prefix}.site.url.Missing")), Some((8, 11)));
+}
@@ -1,7 +1,10 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use crate::parser::resource::{DeletionPolicy, IntrinsicFunction};
+use crate::parser::resource::{
+    AutoScalingCreationPolicy, CreationPolicy, DeletionPolicy, IntrinsicFunction, ResourceSignal,
+    UpdateReplacePolicy,
+};
 use crate::parser::resource::{ResourceAttributes, ResourceValue};
 use crate::{assert_resource_equal, json, json_internal_vec, map};
 use indexmap::IndexMap;
@@ -28,6 +31,8 @@ fn test_parse_tree_basics() {
         metadata: Option::None,
         update_policy: Option::None,
         deletion_policy: Option::None,
+        update_replace_policy: Option::None,
+        creation_policy: Option::None,
         depends_on: vec![],
         resource_type: "AWS::IAM::Role".into(),
         properties: map! {
@@ -37,6 +42,9 @@ fn test_parse_tree_basics() {
             "NotExistent" => ResourceValue::Null,
             "Array" => ResourceValue::Array(vec![ResourceValue::String("hi".into()), ResourceValue::String("there".into())])
         },
+        span: None,
+        comments: Vec::new(),
+        property_comments: IndexMap::new(),
     };
     assert_resource_equal!("LogicalResource" => resource_template, resource);
 }
@@ -62,6 +70,8 @@ fn test_basic_parse_tree_with_condition() {
         metadata: Option::None,
         update_policy: Option::None,
         deletion_policy: Option::None,
+        update_replace_policy: Option::None,
+        creation_policy: Option::None,
         depends_on: vec![],
         resource_type: "AWS::IAM::Role".into(),
         properties: map! {
@@ -71,6 +81,9 @@ fn test_basic_parse_tree_with_condition() {
             "NotExistent" => ResourceValue::Null,
             "Array" => ResourceValue::Array(vec![ResourceValue::String("hi".into()), ResourceValue::String("there".into())])
         },
+        span: None,
+        comments: Vec::new(),
+        property_comments: IndexMap::new(),
     };
     assert_resource_equal!("LogicalResource" => resource_template, resource);
 }
@@ -100,6 +113,8 @@ fn test_basic_parse_tree_with_metadata() {
         })),
         update_policy: Option::None,
         deletion_policy: Option::None,
+        update_replace_policy: Option::None,
+        creation_policy: Option::None,
         depends_on: vec![],
         resource_type: "AWS::IAM::Role".into(),
         properties: map! {
@@ -109,6 +124,9 @@ fn test_basic_parse_tree_with_metadata() {
             "NotExistent" => ResourceValue::Null,
             "Array" => ResourceValue::Array(vec![ResourceValue::String("hi".into()), ResourceValue::String("there".into())])
         },
+        span: None,
+        comments: Vec::new(),
+        property_comments: IndexMap::new(),
     };
     assert_resource_equal!("LogicalResource" => resource_template, resource);
 }
@@ -134,6 +152,8 @@ fn test_parse_tree_basics_with_deletion_policy() {
         metadata: Option::None,
         update_policy: Option::None,
         deletion_policy: Option::Some(DeletionPolicy::Retain),
+        update_replace_policy: Option::None,
+        creation_policy: Option::None,
         depends_on: vec![],
         resource_type: "AWS::IAM::Role".into(),
         properties: map! {
@@ -143,6 +163,68 @@ fn test_parse_tree_basics_with_deletion_policy() {
             "NotExistent" => ResourceValue::Null,
             "Array" => ResourceValue::Array(vec![ResourceValue::String("hi".into()), ResourceValue::String("there".into())])
         },
+        span: None,
+        comments: Vec::new(),
+        property_comments: IndexMap::new(),
+    };
+
+    assert_resource_equal!("LogicalResource" => resource_template, resource);
+}
+
+#[test]
+fn test_parse_tree_rejects_retain_except_on_create_update_replace_policy() {
+    let deletion: Result<ResourceAttributes, _> =
+        serde_yaml::from_str("Type: AWS::S3::Bucket\nDeletionPolicy: RetainExceptOnCreate\n");
+    assert_eq!(
+        deletion.unwrap().deletion_policy,
+        Some(DeletionPolicy::RetainExceptOnCreate)
+    );
+
+    let update_replace: Result<ResourceAttributes, _> =
+        serde_yaml::from_str("Type: AWS::S3::Bucket\nUpdateReplacePolicy: RetainExceptOnCreate\n");
+    assert!(update_replace.is_err());
+}
+
+#[test]
+fn test_parse_tree_basics_with_creation_and_update_replace_policies() {
+    let resource_template = json!({
+        "LogicalResource": {
+            "Type": "AWS::AutoScaling::AutoScalingGroup",
+            "UpdateReplacePolicy": "Snapshot",
+            "CreationPolicy": {
+                "AutoScalingCreationPolicy": {
+                    "MinSuccessfulInstancesPercent": 50
+                },
+                "ResourceSignal": {
+                    "Count": {"Ref": "Capacity"},
+                    "Timeout": "PT15M"
+                }
+            }
+        }
+    });
+
+    let resource: ResourceAttributes = ResourceAttributes {
+        condition: Option::None,
+        metadata: Option::None,
+        update_policy: Option::None,
+        deletion_policy: Option::None,
+        update_replace_policy: Option::Some(UpdateReplacePolicy::Snapshot),
+        creation_policy: Option::Some(CreationPolicy {
+            auto_scaling_creation_policy: Some(AutoScalingCreationPolicy {
+                min_successful_instances_percent: Some(ResourceValue::Number(50)),
+            }),
+            resource_signal: Some(ResourceSignal {
+                count: Some(IntrinsicFunction::Ref("Capacity".into()).into()),
+                timeout: Some(ResourceValue::String("PT15M".into())),
+            }),
+            start_fleet: None,
+        }),
+        depends_on: vec![],
+        resource_type: "AWS::AutoScaling::AutoScalingGroup".into(),
+        properties: IndexMap::new(),
+        span: None,
+        comments: Vec::new(),
+        property_comments: IndexMap::new(),
     };
 
     assert_resource_equal!("LogicalResource" => resource_template, resource);
@@ -166,11 +248,16 @@ fn test_parse_tree_sub_str() {
         metadata: Option::None,
         update_policy: Option::None,
         deletion_policy: Option::None,
+        update_replace_policy: Option::None,
+        creation_policy: Option::None,
         depends_on: vec![],
         resource_type: "AWS::IAM::Role".into(),
         properties: map! {
             "RoleName" => IntrinsicFunction::Sub{ string:"bobs-role-${AWS::Region}".into(), replaces: None }.into()
         },
+        span: None,
+        comments: Vec::new(),
+        property_comments: IndexMap::new(),
     };
     assert_resource_equal!("LogicalResource" => resource_template, resource);
 }
@@ -193,11 +280,16 @@ fn test_parse_tree_yaml_codes() {
         metadata: Option::None,
         update_policy: Option::None,
         deletion_policy: Option::None,
+        update_replace_policy: Option::None,
+        creation_policy: Option::None,
         depends_on: vec![],
         resource_type: "AWS::IAM::Role".into(),
         properties: map! {
             "RoleName" => IntrinsicFunction::Sub{ string: "bobs-role-${AWS::Region}".into(), replaces: None }.into()
         },
+        span: None,
+        comments: Vec::new(),
+        property_comments: IndexMap::new(),
     };
     assert_resource_equal!("LogicalResource" => resource_template, resource);
 }
@@ -220,11 +312,78 @@ fn test_parse_get_attr_shorthand() {
         metadata: Option::None,
         update_policy: Option::None,
         deletion_policy: Option::None,
+        update_replace_policy: Option::None,
+        creation_policy: Option::None,
         depends_on: vec![],
         resource_type: "AWS::IAM::Role".into(),
         properties: map! {
             "RoleName" => IntrinsicFunction::GetAtt{ logical_name: "Foo".into(), attribute_name: "Bar".into() }.into()
         },
+        span: None,
+        comments: Vec::new(),
+        property_comments: IndexMap::new(),
     };
     assert_resource_equal!("LogicalResource" => resource_template, resource);
 }
+
+#[test]
+fn test_parse_resources_for_each() {
+    let template: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Transform: AWS::LanguageExtensions
+        Resources:
+          Fn::ForEach::Topics:
+            - TopicName
+            - [Success, Failure]
+            - "Topic${TopicName}":
+                Type: AWS::SNS::Topic
+                Properties:
+                  TopicName: !Ref TopicName
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        template.resources.keys().collect::<Vec<_>>(),
+        vec!["TopicSuccess", "TopicFailure"]
+    );
+    assert_eq!(
+        template.resources["TopicFailure"].properties["TopicName"],
+        ResourceValue::String("Failure".into())
+    );
+}
+
+#[test]
+fn test_parse_from_source_records_spans() {
+    let template: crate::CloudformationParseTree = r#"Parameters:
+  Env:
+    Type: String
+Resources:
+  Group:
+    Type: AWS::EC2::SecurityGroup
+    Properties:
+      GroupDescription: test
+      SecurityGroupIngress:
+        - IpProtocol: tcp
+          FromPort: 80
+"#
+    .parse()
+    .unwrap();
+
+    let span = |line, column| crate::parser::span::Span { line, column };
+    let group = &template.resources["Group"];
+    assert_eq!(group.span, Some(span(5, 3)));
+    assert_eq!(template.parameters["Env"].span, Some(span(2, 3)));
+    assert_eq!(
+        group.properties["GroupDescription"],
+        ResourceValue::Spanned(span(8, 7), Box::new(ResourceValue::String("test".into())))
+    );
+    let ResourceValue::Array(rules) = group.properties["SecurityGroupIngress"].unspanned() else {
+        panic!("SecurityGroupIngress is not an array");
+    };
+    let ResourceValue::Object(rule) = rules[0].unspanned() else {
+        panic!("the rule is not an object");
+    };
+    assert_eq!(rules[0].span(), Some(span(10, 11)));
+    assert_eq!(rule["FromPort"].span(), Some(span(11, 11)));
+}
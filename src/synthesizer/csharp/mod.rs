@@ -3,12 +3,16 @@
 use crate::cdk::{ItemType, Primitive, Schema, TypeReference};
 use crate::code::{CodeBuffer, IndentOptions};
 use crate::ir::conditions::ConditionIr;
-use crate::ir::constructor::ConstructorParameter;
+use crate::ir::constructor::{
+    ConstructorParameter, ConstructorType, DefaultValue, ParameterConstraint, PropValidation,
+};
+use crate::ir::constructs::{L2Construct, L2Value};
 use crate::ir::importer::ImportInstruction;
 use crate::ir::mappings::OutputType;
 use crate::ir::outputs::OutputInstruction;
 use crate::ir::reference::{Origin, PseudoParameter, Reference};
-use crate::ir::resources::ResourceIr;
+use crate::ir::resources::{CreationPolicyIr, ResourceInstruction, ResourceIr};
+use crate::ir::rules::{RuleIr, RuleValueIr};
 use crate::ir::CloudformationProgramIr;
 use crate::parser::lookup_table::MappingInnerValue;
 use crate::Error;
@@ -16,68 +20,72 @@ use std::borrow::Cow;
 use std::io;
 use voca_rs::case::{camel_case, pascal_case};
 
-use super::Synthesizer;
+use super::{ConstructOptions, Synthesizer, SynthesizerOptions};
 
 const INDENT: Cow<'static, str> = Cow::Borrowed("    ");
 
 pub struct CSharp<'a> {
     schema: &'a Schema,
+    namespace: Option<String>,
+    construct: Option<ConstructOptions>,
 }
 
 impl<'a> CSharp<'a> {
     pub fn new(schema: &'a Schema) -> Self {
-        Self { schema }
+        Self {
+            schema,
+            namespace: None,
+            construct: None,
+        }
     }
-}
 
-impl Default for CSharp<'_> {
-    fn default() -> Self {
-        Self::new(Schema::builtin())
+    /// Declares the stack in `namespace`, instead of a namespace named after the stack.
+    pub fn in_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
     }
-}
 
-impl Synthesizer for CSharp<'_> {
-    fn synthesize(
+    /// Declares a construct instead of a stack (nested stacks are still declared as such).
+    pub fn as_construct(mut self, options: ConstructOptions) -> Self {
+        self.construct = Some(options);
+        self
+    }
+
+    // Emits the class of a stack and the class of its props. Nested stacks extend `NestedStack`
+    // rather than `Stack`, and constructs extend `Construct`, leaving the stack-wide settings of
+    // the template to the stack they are added to.
+    fn emit_stack(
         &self,
-        ir: CloudformationProgramIr,
-        into: &mut dyn io::Write,
+        namespace: &CodeBuffer,
+        ir: &CloudformationProgramIr,
         stack_name: &str,
+        nested: bool,
+        construct: Option<ConstructOptions>,
     ) -> Result<(), Error> {
-        // Initialize the code buffer in which all of the code will be generated
-        let code = CodeBuffer::default();
-
-        // Imports
-        for import in &ir.imports {
-            code.line(import.to_csharp()?)
-        }
-        code.line("using Constructs;");
-        code.line("using System.Collections.Generic;");
-        code.newline();
-
-        // Namespace definition
-        let namespace = code.indent_with_options(IndentOptions {
-            indent: INDENT,
-            leading: Some(format!("namespace {stack_name}\n{{").into()),
-            trailing: Some("}".into()),
-            trailing_newline: true,
-        });
+        let base_class = match (nested, construct) {
+            (_, Some(_)) => "Construct",
+            (true, None) => "NestedStack",
+            (false, None) => "Stack",
+        };
 
         // Props
+        let extends = match construct {
+            Some(_) => String::new(),
+            None => format!(" : {base_class}Props"),
+        };
         let stack_props_class = namespace.indent_with_options(IndentOptions {
             indent: INDENT,
-            leading: Some(format!("public class {stack_name}Props : StackProps\n{{").into()),
+            leading: Some(format!("public class {stack_name}Props{extends}\n{{").into()),
             trailing: Some("}".into()),
             trailing_newline: true,
         });
 
         for param in &ir.constructor.inputs {
-            if let Some(description) = &param.description {
-                stack_props_class.line("/// <summary>");
-                for description_line in description.split('\n') {
-                    stack_props_class.line(format!("/// {description_line}"));
-                }
-                stack_props_class.line("/// </summary>");
-            }
+            emit_summary(
+                &stack_props_class,
+                param.description.as_deref(),
+                &param.comments,
+            );
             stack_props_class.line(param.to_csharp_auto_property());
             stack_props_class.newline();
         }
@@ -85,40 +93,36 @@ impl Synthesizer for CSharp<'_> {
         namespace.newline();
 
         // Description - comment before the stack class
-        if let Some(descr) = ir.description {
-            namespace.line("/// <summary>");
-            for description_line in descr.split('\n') {
-                namespace.line(format!("/// {description_line}"));
-            }
-            namespace.line("/// </summary>");
-        }
+        emit_summary(namespace, ir.description.as_deref(), &[]);
 
         // Stack class definition
         let stack_class = namespace.indent_with_options(IndentOptions {
             indent: INDENT,
-            leading: Some(format!("public class {stack_name} : Stack\n{{").into()),
+            leading: Some(format!("public class {stack_name} : {base_class}\n{{").into()),
             trailing: Some("}".into()),
             trailing_newline: true,
         });
 
         // Properties for each output
         for output in &ir.outputs {
-            if let Some(description) = &output.description {
-                stack_class.line("/// <summary>");
-                for description_line in description.split('\n') {
-                    stack_class.line(format!("/// {description_line}"));
-                }
-                stack_class.line("/// </summary>");
-            }
+            emit_summary(
+                &stack_class,
+                output.description.as_deref(),
+                &output.comments,
+            );
             stack_class.line(format!("public object {} {{ get; }} ", output.name));
             stack_class.newline();
         }
 
         // Constructor
+        let base_args = match construct {
+            Some(_) => "scope, id",
+            None => "scope, id, props",
+        };
         let ctor = stack_class.indent_with_options(IndentOptions {
             indent: INDENT,
             leading: Some(format!(
-                "public {stack_name}(Construct scope, string id, {stack_name}Props props = null) : base(scope, id, props)\n{{"
+                "public {stack_name}(Construct scope, string id, {stack_name}Props props = null) : base({base_args})\n{{"
             ).into()),
             trailing: Some("}".into()),
             trailing_newline: true,
@@ -129,44 +133,46 @@ impl Synthesizer for CSharp<'_> {
             .constructor
             .inputs
             .iter()
-            .filter(|p| p.constructor_type.contains("AWS::") || p.default_value.is_some())
+            .filter(|p| p.is_cfn_parameter() || p.default_value.is_some())
             .collect::<Vec<&ConstructorParameter>>();
         if !have_default_or_special_type_params.is_empty() {
             ctor.line("// Applying default props");
             ctor.line(format!("props ??= new {stack_name}Props();"));
             for param in have_default_or_special_type_params {
                 let name = pascal_case(&param.name);
-                // example: AWS::EC2::Image::Id, List<AWS::EC2::VPC::Id>, AWS::SSM::Parameter::Value<List<String>>
-                if param.constructor_type.contains("AWS::")
-                    || param.no_echo.as_ref().is_some_and(|x| x == "true")
-                {
-                    let value_as = match &param.constructor_type {
-                        t if t.contains("List") => "ValueAsList",
-                        _ => "ValueAsString",
+                if param.ssm_lookup {
+                    let name_value = match &param.default_value {
+                        Some(v) => format!("props.{name} ?? \"{}\"", v.to_string().escape_debug()),
+                        None => format!("props.{name}"),
+                    };
+                    ctor.line(format!(
+                        "props.{name} = StringParameter.ValueForStringParameter(this, {name_value});"
+                    ));
+                } else if param.is_cfn_parameter() {
+                    let value_as = match param.constructor_type.is_list() {
+                        true => "ValueAsList",
+                        false => "ValueAsString",
                     };
                     let cfn_param = ctor.indent_with_options(IndentOptions {
                         indent: INDENT,
                         leading: Some(
                             format!(
-                                "props.{name} = new CfnParameter(this, \"{name}\", new CfnParameterProps\n{{")
+                                "props.{name} = new CfnParameter(this, \"{}\", new CfnParameterProps\n{{", param.logical_id)
                             .into(),
                         ),
                         trailing: Some(format!("}}).{value_as};").into()),
                         trailing_newline: true,
                     });
                     cfn_param.line(format!("Type = \"{}\",", param.constructor_type));
-                    let list_optional_prefix = match &param.constructor_type {
-                        t if t.contains("List") => "string.Join(\",\", ",
-                        _ => "",
-                    };
-                    let list_optional_suffix = match &param.constructor_type {
-                        t if t.contains("List") => ")",
-                        _ => "",
-                    };
+                    let (list_optional_prefix, list_optional_suffix) =
+                        match param.constructor_type.is_list() {
+                            true => ("string.Join(\",\", ", ")"),
+                            false => ("", ""),
+                        };
                     if let Some(v) = &param.default_value {
                         cfn_param.line(format!(
                             "Default = {list_optional_prefix}props.{name}{list_optional_suffix} ?? \"{}\",",
-                            v.escape_debug()
+                            v.to_string().escape_debug()
                         ));
                     } else {
                         cfn_param.line(format!(
@@ -176,29 +182,14 @@ impl Synthesizer for CSharp<'_> {
                     if let Some(v) = &param.description {
                         cfn_param.line(format!("Description = \"{v}\","));
                     };
+                    param.emit_csharp_constraints(&cfn_param);
                     if let Some(v) = &param.no_echo {
                         cfn_param.line(format!("NoEcho = {v},"));
                     }
                 } else {
                     let value = match &param.default_value {
                         None => "".to_owned(),
-                        Some(value) => {
-                            let value = match param.constructor_type.as_str() {
-                                "String" => format!("\"{}\"", value.escape_debug()),
-                                "List<Number>" => format!("[{value}]"),
-                                "CommaDelimitedList" => format!(
-                                    "[{}]",
-                                    value
-                                        .split(',')
-                                        .map(|v| format!("\"{}\"", v.escape_debug()))
-                                        .collect::<Vec<String>>()
-                                        .join(",")
-                                ),
-                                "Boolean" => value.clone(),
-                                _ => value.clone(),
-                            };
-                            value
-                        }
+                        Some(value) => value.to_csharp(),
                     };
 
                     ctor.line(format!("props.{name} ??= {value};"));
@@ -207,11 +198,44 @@ impl Synthesizer for CSharp<'_> {
             ctor.newline();
         }
 
+        let validations = ir.constructor.validations();
+        if !validations.is_empty() {
+            ctor.line("// Validating props");
+            for validation in &validations {
+                validation.emit_csharp(&ctor);
+            }
+            ctor.newline();
+        }
+
+        // Template options, which are those of the stack a construct is added to
+        let metadata = ir.metadata.as_ref().filter(|metadata| !metadata.is_empty());
+        if construct.is_none() && (ir.template_format_version.is_some() || metadata.is_some()) {
+            ctor.line("// Template options");
+            if let Some(version) = &ir.template_format_version {
+                ctor.line(format!(
+                    "TemplateOptions.TemplateFormatVersion = \"{}\";",
+                    version.escape_debug()
+                ));
+            }
+            if let Some(metadata) = metadata {
+                ctor.text("TemplateOptions.Metadata = ");
+                metadata
+                    .to_ir(|name| ir.constructor.logical_id(name))
+                    .emit_csharp(&ctor, self.schema)?;
+                ctor.text(";\n");
+            }
+            ctor.newline();
+        }
+
         // Transforms
         if !ir.transforms.is_empty() {
             ctor.line("// Transforms");
+            let stack = match construct {
+                Some(_) => "Stack.Of(this).",
+                None => "",
+            };
             for transform in &ir.transforms {
-                ctor.line(format!("AddTransform(\"{transform}\");"));
+                ctor.line(format!("{stack}AddTransform(\"{transform}\");"));
             }
         }
 
@@ -283,10 +307,87 @@ impl Synthesizer for CSharp<'_> {
             ctor.newline();
         }
 
+        // Rules
+        if !ir.rules.is_empty() {
+            ctor.newline();
+            ctor.line("// Rules");
+        }
+        for rule in &ir.rules {
+            let rule_constructor = ctor.indent_with_options(IndentOptions {
+                indent: INDENT,
+                leading: Some(
+                    format!("new CfnRule(this, \"{}\", new CfnRuleProps\n{{", rule.name).into(),
+                ),
+                trailing: Some("});".into()),
+                trailing_newline: true,
+            });
+            if let Some(condition) = &rule.condition {
+                rule_constructor.text("RuleCondition = ");
+                condition.emit_csharp(&rule_constructor);
+                rule_constructor.text(",");
+                rule_constructor.newline();
+            }
+            if rule.assertions.is_empty() {
+                continue;
+            }
+            let assertions = rule_constructor.indent_with_options(IndentOptions {
+                indent: INDENT,
+                leading: Some("Assertions = new []\n{".into()),
+                trailing: Some("},".into()),
+                trailing_newline: true,
+            });
+            for assertion in &rule.assertions {
+                let assertion_block = assertions.indent_with_options(IndentOptions {
+                    indent: INDENT,
+                    leading: Some("new CfnRuleAssertion\n{".into()),
+                    trailing: Some("},".into()),
+                    trailing_newline: true,
+                });
+                assertion_block.text("Assert = ");
+                assertion.assert.emit_csharp(&assertion_block);
+                assertion_block.text(",");
+                assertion_block.newline();
+                // The CDK requires a description, even though CloudFormation does not.
+                assertion_block.line(format!(
+                    "AssertDescription = \"{}\",",
+                    assertion
+                        .description
+                        .as_deref()
+                        .unwrap_or_default()
+                        .escape_debug()
+                ));
+            }
+        }
+
         // Resources
         ctor.newline();
         ctor.line("// Resources");
         for resource in &ir.resources {
+            ctor.comments("//", &resource.comments);
+            if let Some(nested) = &resource.nested_stack {
+                let nested_constructor = ctor.indent_with_options(IndentOptions {
+                    indent: INDENT,
+                    leading: Some(format!("var {var_name} = new {class}(this, \"{construct_id}\", new {class}Props\n{{",
+                        var_name = camel_case(&resource.name),
+                        class = nested.class_name,
+                        construct_id = resource.name,
+                    ).into()),
+                    trailing: Some("});".into()),
+                    trailing_newline: true,
+                });
+                for (name, value) in &nested.parameters {
+                    nested_constructor.text(format!("{name} = ", name = pascal_case(name)));
+                    value.emit_csharp(&nested_constructor, self.schema)?;
+                    nested_constructor.text(",");
+                    nested_constructor.newline();
+                }
+                self.emit_nested_stack_attributes(&ctor, resource)?;
+                continue;
+            }
+            if let Some(construct) = &resource.l2_construct {
+                self.emit_l2_construct(&ctor, resource, construct)?;
+                continue;
+            }
             let class = resource.resource_type.type_name();
             let resource_constructor = ctor.indent_with_options(IndentOptions {
                 indent: INDENT,
@@ -298,11 +399,33 @@ impl Synthesizer for CSharp<'_> {
                 trailing_newline: true,
             });
             for (name, value) in &resource.properties {
+                if let Some(comments) = resource.property_comments.get(name) {
+                    resource_constructor.comments("//", comments);
+                }
                 resource_constructor.text(format!("{name} = ", name = pascal_case(name)));
                 value.emit_csharp(&resource_constructor, self.schema)?;
                 resource_constructor.text(",");
                 resource_constructor.newline();
             }
+
+            let var_name = camel_case(&resource.name);
+            if let Some(update_replace_policy) = &resource.update_replace_policy {
+                ctor.line(format!(
+                    "{var_name}.CfnOptions.UpdateReplacePolicy = CfnDeletionPolicy.{update_replace_policy};"
+                ));
+            }
+            if let Some(creation_policy) = &resource.creation_policy {
+                let policy = ctor.indent_with_options(IndentOptions {
+                    indent: INDENT,
+                    leading: Some(
+                        format!("{var_name}.CfnOptions.CreationPolicy = new CfnCreationPolicy\n{{")
+                            .into(),
+                    ),
+                    trailing: Some("};".into()),
+                    trailing_newline: true,
+                });
+                creation_policy.emit_csharp(&policy, self.schema)?;
+            }
         }
 
         // Set values for the outputs
@@ -311,9 +434,327 @@ impl Synthesizer for CSharp<'_> {
             ctor.line("// Outputs");
 
             for op in &ir.outputs {
-                op.emit_csharp(&ctor, self.schema)?;
+                match construct {
+                    Some(construct) if !construct.cfn_outputs => {
+                        op.emit_csharp_property(&ctor, self.schema)?
+                    }
+                    _ => op.emit_csharp(&ctor, self.schema)?,
+                }
+            }
+        }
+
+        // Pseudo parameters, which a construct gets from the stack it is in
+        if construct.is_some() {
+            for pseudo in ir.pseudo_parameters() {
+                let (value_type, name) = match pseudo {
+                    PseudoParameter::NotificationArns => ("string[]", "NotificationArns"),
+                    PseudoParameter::AccountId => ("string", "Account"),
+                    PseudoParameter::Partition => ("string", "Partition"),
+                    PseudoParameter::Region => ("string", "Region"),
+                    PseudoParameter::StackId => ("string", "StackId"),
+                    PseudoParameter::StackName => ("string", "StackName"),
+                    PseudoParameter::URLSuffix => ("string", "UrlSuffix"),
+                };
+                stack_class.newline();
+                stack_class.line(format!(
+                    "private {value_type} {name} => Stack.Of(this).{name};"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    // The attributes of a nested stack apply to the CfnStack deploying it.
+    fn emit_nested_stack_attributes(
+        &self,
+        ctor: &CodeBuffer,
+        resource: &ResourceInstruction,
+    ) -> Result<(), Error> {
+        let cfn_stack = format!("{}.NestedStackResource", camel_case(&resource.name));
+        if let Some(ResourceIr::Object(_, entries)) = &resource.metadata {
+            for (name, value) in entries {
+                ctor.text(format!(
+                    "{cfn_stack}.AddMetadata(\"{}\", ",
+                    name.escape_debug()
+                ));
+                value.emit_csharp(ctor, self.schema)?;
+                ctor.text(");\n");
+            }
+        }
+        if let Some(deletion_policy) = &resource.deletion_policy {
+            ctor.line(format!(
+                "{cfn_stack}.CfnOptions.DeletionPolicy = CfnDeletionPolicy.{deletion_policy};"
+            ));
+        }
+        if let Some(update_replace_policy) = &resource.update_replace_policy {
+            ctor.line(format!(
+                "{cfn_stack}.CfnOptions.UpdateReplacePolicy = CfnDeletionPolicy.{update_replace_policy};"
+            ));
+        }
+        if let Some(creation_policy) = &resource.creation_policy {
+            let policy = ctor.indent_with_options(IndentOptions {
+                indent: INDENT,
+                leading: Some(
+                    format!("{cfn_stack}.CfnOptions.CreationPolicy = new CfnCreationPolicy\n{{")
+                        .into(),
+                ),
+                trailing: Some("};".into()),
+                trailing_newline: true,
+            });
+            creation_policy.emit_csharp(&policy, self.schema)?;
+        }
+        Ok(())
+    }
+
+    // The classes of the construct library are fully qualified, as some of their names are used
+    // by several modules (e.g: `Function`).
+    fn emit_l2_construct(
+        &self,
+        ctor: &CodeBuffer,
+        resource: &ResourceInstruction,
+        construct: &L2Construct,
+    ) -> Result<(), Error> {
+        let class = l2_class(resource.resource_type.service(), &construct.class);
+        let var_name = camel_case(&resource.name);
+        let constructor = ctor.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some(
+                format!(
+                    "var {var_name} = new {class}(this, \"{}\", new {class}Props\n{{",
+                    resource.name
+                )
+                .into(),
+            ),
+            trailing: Some("});".into()),
+            trailing_newline: true,
+        });
+        for (name, value) in &construct.props {
+            if let Some(comments) = resource.property_comments.get(name) {
+                constructor.comments("//", comments);
+            }
+            constructor.text(format!("{} = ", pascal_case(name)));
+            value.emit_csharp(&constructor, self.schema)?;
+            constructor.text(",");
+            constructor.newline();
+        }
+
+        // The L1 resource keeps the logical ID of the template, and what the construct can't express
+        let cfn_var_name = camel_case(&L2Construct::escape_hatch(&resource.name));
+        ctor.line(format!(
+            "var {cfn_var_name} = (Cfn{}){var_name}.Node.DefaultChild;",
+            resource.resource_type.type_name()
+        ));
+        ctor.line(format!(
+            "{cfn_var_name}.OverrideLogicalId(\"{}\");",
+            resource.name
+        ));
+        for (name, value) in &construct.overrides {
+            if let Some(comments) = resource.property_comments.get(name) {
+                ctor.comments("//", comments);
+            }
+            ctor.text(format!("{cfn_var_name}.AddPropertyOverride(\"{name}\", "));
+            value.emit_csharp(ctor, self.schema)?;
+            ctor.text(");\n");
+        }
+        // Some constructs retain their resource by default, unlike the template.
+        if let Some(deletion_policy) = &resource.deletion_policy {
+            ctor.line(format!(
+                "{cfn_var_name}.CfnOptions.DeletionPolicy = CfnDeletionPolicy.{deletion_policy};"
+            ));
+        }
+        if let Some(update_replace_policy) = &resource.update_replace_policy {
+            ctor.line(format!(
+                "{cfn_var_name}.CfnOptions.UpdateReplacePolicy = CfnDeletionPolicy.{update_replace_policy};"
+            ));
+        }
+        if let Some(creation_policy) = &resource.creation_policy {
+            let policy = ctor.indent_with_options(IndentOptions {
+                indent: INDENT,
+                leading: Some(
+                    format!("{cfn_var_name}.CfnOptions.CreationPolicy = new CfnCreationPolicy\n{{")
+                        .into(),
+                ),
+                trailing: Some("};".into()),
+                trailing_newline: true,
+            });
+            creation_policy.emit_csharp(&policy, self.schema)?;
+        }
+        Ok(())
+    }
+}
+
+// Emits the documentation of a member: its description, followed by the comments of its entry in
+// the template.
+fn emit_summary(output: &CodeBuffer, description: Option<&str>, comments: &[String]) {
+    if description.is_none() && comments.is_empty() {
+        return;
+    }
+    output.line("/// <summary>");
+    for description_line in description.into_iter().flat_map(|d| d.split('\n')) {
+        output.line(format!("/// {description_line}"));
+    }
+    output.comments("///", comments);
+    output.line("/// </summary>");
+}
+
+// Quotes a string as a C# literal. Control and non-ASCII characters are written as `\uNNNN`, where
+// `escape_debug` would write `\u{..}`, which C# does not understand.
+fn csharp_string(value: &str) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '"' => literal.push_str("\\\""),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c if c.is_ascii() && !c.is_control() => literal.push(c),
+            c => {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    literal.push_str(&format!("\\u{unit:04x}"));
+                }
             }
         }
+    }
+    literal.push('"');
+    literal
+}
+
+// The fully qualified name of a class of the module of `service`.
+fn l2_class(service: &str, class: &str) -> String {
+    format!("Amazon.CDK.AWS.{service}.{class}")
+}
+
+impl L2Value {
+    fn emit_csharp(&self, output: &CodeBuffer, schema: &Schema) -> Result<(), Error> {
+        let emit_args = |args: &[L2Value]| -> Result<(), Error> {
+            output.text("(");
+            for (index, arg) in args.iter().enumerate() {
+                if index > 0 {
+                    output.text(", ");
+                }
+                arg.emit_csharp(output, schema)?;
+            }
+            output.text(")");
+            Ok(())
+        };
+        match self {
+            L2Value::Value(value) => value.emit_csharp(output, schema)?,
+            L2Value::Seconds(value) => {
+                output.text("Duration.Seconds(");
+                value.emit_csharp(output, schema)?;
+                output.text(")");
+            }
+            L2Value::Enum {
+                service,
+                name,
+                member,
+            } => output.text(format!("{}.{member}", l2_class(service, name))),
+            L2Value::Struct {
+                service,
+                name,
+                fields,
+            } => {
+                let object_block = output.indent_with_options(IndentOptions {
+                    indent: INDENT,
+                    leading: Some(format!("new {}\n{{", l2_class(service, name)).into()),
+                    trailing: Some("}".into()),
+                    trailing_newline: false,
+                });
+                for (name, value) in fields {
+                    object_block.text(format!("{} = ", pascal_case(name)));
+                    value.emit_csharp(&object_block, schema)?;
+                    object_block.text(",");
+                    object_block.newline();
+                }
+            }
+            L2Value::New {
+                service,
+                class,
+                args,
+                ..
+            } => {
+                output.text(format!("new {}", l2_class(service, class)));
+                emit_args(args)?;
+            }
+            L2Value::Call {
+                service,
+                class,
+                method,
+                args,
+                ..
+            } => {
+                output.text(format!(
+                    "{}.{}",
+                    l2_class(service, class),
+                    pascal_case(method)
+                ));
+                emit_args(args)?;
+            }
+            L2Value::Scope => output.text("this"),
+            L2Value::Construct(name) => output.text(camel_case(name)),
+        }
+        Ok(())
+    }
+}
+
+impl Default for CSharp<'_> {
+    fn default() -> Self {
+        Self::new(Schema::builtin())
+    }
+}
+
+impl From<&SynthesizerOptions> for CSharp<'_> {
+    fn from(options: &SynthesizerOptions) -> Self {
+        let mut csharp = Self::new(Schema::builtin());
+        if let Some(namespace) = &options.csharp.namespace {
+            csharp = csharp.in_namespace(namespace.clone());
+        }
+        if let Some(construct) = options.construct {
+            csharp = csharp.as_construct(construct);
+        }
+        csharp
+    }
+}
+
+impl Synthesizer for CSharp<'_> {
+    fn synthesize(
+        &self,
+        ir: CloudformationProgramIr,
+        into: &mut dyn io::Write,
+        stack_name: &str,
+    ) -> Result<(), Error> {
+        // Initialize the code buffer in which all of the code will be generated
+        let code = CodeBuffer::default();
+
+        // Imports
+        for import in &ir.imports {
+            code.line(import.to_csharp()?)
+        }
+        code.line("using Constructs;");
+        code.line("using System.Collections.Generic;");
+        code.newline();
+
+        // Namespace definition
+        let namespace = code.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some(
+                format!(
+                    "namespace {}\n{{",
+                    self.namespace.as_deref().unwrap_or(stack_name)
+                )
+                .into(),
+            ),
+            trailing: Some("}".into()),
+            trailing_newline: true,
+        });
+
+        for nested in ir.nested_stacks() {
+            self.emit_stack(&namespace, &nested.program, &nested.class_name, true, None)?;
+            namespace.newline();
+        }
+        self.emit_stack(&namespace, &ir, stack_name, false, self.construct)?;
 
         Ok(code.write(into)?)
     }
@@ -348,8 +789,8 @@ impl ImportInstruction {
 impl ConstructorParameter {
     fn to_csharp_auto_property(&self) -> String {
         let prop_type = match &self.constructor_type {
-            t if t.contains("List") => "string[]",
-            t if t == "Boolean" => "bool?",
+            t if t.is_list() => "string[]",
+            ConstructorType::Boolean => "bool?",
             _ => "string",
         };
 
@@ -358,6 +799,100 @@ impl ConstructorParameter {
             pascal_case(&self.name)
         )
     }
+
+    fn emit_csharp_constraints(&self, output: &CodeBuffer) {
+        if let Some(values) = &self.allowed_values {
+            let values = values
+                .iter()
+                .map(|v| format!("\"{}\"", v.escape_debug()))
+                .collect::<Vec<_>>();
+            output.line(format!(
+                "AllowedValues = new [] {{ {} }},",
+                values.join(", ")
+            ));
+        }
+        if let Some(v) = &self.allowed_pattern {
+            output.line(format!("AllowedPattern = \"{}\",", v.escape_debug()));
+        }
+        if let Some(v) = &self.constraint_description {
+            output.line(format!("ConstraintDescription = \"{}\",", v.escape_debug()));
+        }
+        if let Some(v) = self.min_length {
+            output.line(format!("MinLength = {v},"));
+        }
+        if let Some(v) = self.max_length {
+            output.line(format!("MaxLength = {v},"));
+        }
+        if let Some(v) = self.min_value {
+            output.line(format!("MinValue = {v},"));
+        }
+        if let Some(v) = self.max_value {
+            output.line(format!("MaxValue = {v},"));
+        }
+    }
+}
+
+impl PropValidation<'_> {
+    // Tokens can only be checked at deploy time, so they are skipped.
+    fn emit_csharp(&self, output: &CodeBuffer) {
+        let value = format!("props.{}", pascal_case(&self.param.name));
+        let block = output.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some(
+                format!("if ({value} != null && !Token.IsUnresolved({value}))\n{{").into(),
+            ),
+            trailing: Some("}".into()),
+            trailing_newline: true,
+        });
+        for check in &self.checks {
+            let violated = match &check.constraint {
+                ParameterConstraint::AllowedValues(values) => {
+                    let values = values
+                        .iter()
+                        .map(|v| format!("\"{}\"", v.escape_debug()))
+                        .collect::<Vec<_>>();
+                    format!(
+                        "!new List<string> {{ {} }}.Contains({value})",
+                        values.join(", ")
+                    )
+                }
+                ParameterConstraint::AllowedPattern(pattern) => format!(
+                    "!System.Text.RegularExpressions.Regex.IsMatch({value}, \"^(?:{})$\")",
+                    pattern.escape_debug()
+                ),
+                ParameterConstraint::MinLength(min) => format!("{value}.Length < {min}"),
+                ParameterConstraint::MaxLength(max) => format!("{value}.Length > {max}"),
+                ParameterConstraint::MinValue(min) => format!("double.Parse({value}) < {min}"),
+                ParameterConstraint::MaxValue(max) => format!("double.Parse({value}) > {max}"),
+            };
+            block
+                .indent_with_options(IndentOptions {
+                    indent: INDENT,
+                    leading: Some(format!("if ({violated})\n{{").into()),
+                    trailing: Some("}".into()),
+                    trailing_newline: true,
+                })
+                .line(format!(
+                    "throw new System.ArgumentException(\"{}\");",
+                    check.message.escape_debug()
+                ));
+        }
+    }
+}
+
+impl DefaultValue {
+    // Number props are typed as strings, and list props as string arrays.
+    fn to_csharp(&self) -> String {
+        match self {
+            DefaultValue::String(value) => format!("\"{}\"", value.escape_debug()),
+            DefaultValue::Number(value) => format!("\"{value}\""),
+            DefaultValue::Bool(value) => value.to_string(),
+            DefaultValue::List(items) => {
+                let items = items.iter().map(Self::to_csharp).collect::<Vec<_>>();
+                format!("new [] {{ {} }}", items.join(", "))
+            }
+        }
+    }
 }
 
 trait CsharpEmitter {
@@ -427,6 +962,129 @@ impl ConditionIr {
     }
 }
 
+impl CsharpEmitter for CreationPolicyIr {
+    fn emit_csharp(&self, output: &CodeBuffer, schema: &Schema) -> Result<(), Error> {
+        if let Some(asg) = &self.auto_scaling_creation_policy {
+            let asg_block = output.indent_with_options(IndentOptions {
+                indent: INDENT,
+                leading: Some(
+                    "AutoScalingCreationPolicy = new CfnResourceAutoScalingCreationPolicy\n{"
+                        .into(),
+                ),
+                trailing: Some("},".into()),
+                trailing_newline: true,
+            });
+            if let Some(percent) = &asg.min_successful_instances_percent {
+                asg_block.text("MinSuccessfulInstancesPercent = ");
+                percent.emit_csharp(&asg_block, schema)?;
+                asg_block.text(",");
+                asg_block.newline();
+            }
+        }
+        if let Some(signal) = &self.resource_signal {
+            let signal_block = output.indent_with_options(IndentOptions {
+                indent: INDENT,
+                leading: Some("ResourceSignal = new CfnResourceSignal\n{".into()),
+                trailing: Some("},".into()),
+                trailing_newline: true,
+            });
+            if let Some(count) = &signal.count {
+                signal_block.text("Count = ");
+                count.emit_csharp(&signal_block, schema)?;
+                signal_block.text(",");
+                signal_block.newline();
+            }
+            if let Some(timeout) = &signal.timeout {
+                signal_block.text("Timeout = ");
+                timeout.emit_csharp(&signal_block, schema)?;
+                signal_block.text(",");
+                signal_block.newline();
+            }
+        }
+        if let Some(start_fleet) = &self.start_fleet {
+            output.text("StartFleet = ");
+            start_fleet.emit_csharp(output, schema)?;
+            output.text(",");
+            output.newline();
+        }
+        Ok(())
+    }
+}
+
+impl RuleIr {
+    fn emit_csharp(&self, output: &CodeBuffer) {
+        let (function, left, left_as_list, right, right_as_list) = match self {
+            RuleIr::And(list) | RuleIr::Or(list) => {
+                let function = match self {
+                    RuleIr::And(_) => "Fn.ConditionAnd",
+                    _ => "Fn.ConditionOr",
+                };
+                output.text(format!("{function}("));
+                for (index, condition) in list.iter().enumerate() {
+                    if index > 0 {
+                        output.text(", ");
+                    }
+                    condition.emit_csharp(output);
+                }
+                output.text(")");
+                return;
+            }
+            RuleIr::Not(condition) => {
+                output.text("Fn.ConditionNot(");
+                condition.emit_csharp(output);
+                output.text(")");
+                return;
+            }
+            RuleIr::Equals(left, right) => ("Fn.ConditionEquals", left, false, right, false),
+            RuleIr::Contains(list, value) => ("Fn.ConditionContains", list, true, value, false),
+            RuleIr::EachMemberEquals(list, value) => {
+                ("Fn.ConditionEachMemberEquals", list, true, value, false)
+            }
+            RuleIr::EachMemberIn(to_check, to_match) => {
+                ("Fn.ConditionEachMemberIn", to_check, true, to_match, true)
+            }
+        };
+        output.text(format!("{function}("));
+        left.emit_csharp(output, left_as_list);
+        output.text(", ");
+        right.emit_csharp(output, right_as_list);
+        output.text(")");
+    }
+}
+
+impl RuleValueIr {
+    /// `as_list` is set where the CDK expects a `string[]`, as `Fn.ValueOf` is typed as a string
+    /// even when the attribute holds a list.
+    fn emit_csharp(&self, output: &CodeBuffer, as_list: bool) {
+        match self {
+            RuleValueIr::Str(str) => output.text(format!("\"{}\"", str.escape_debug())),
+            RuleValueIr::List(list) => {
+                output.text("new [] { ");
+                for (index, value) in list.iter().enumerate() {
+                    if index > 0 {
+                        output.text(", ");
+                    }
+                    value.emit_csharp(output, false);
+                }
+                output.text(" }");
+            }
+            RuleValueIr::Ref(reference) => reference.emit_csharp(output),
+            RuleValueIr::RefAll(parameter_type) => {
+                output.text(format!("Fn.RefAll(\"{parameter_type}\")"))
+            }
+            RuleValueIr::ValueOf(name, attribute) if as_list => output.text(format!(
+                "Token.AsList(Fn.ValueOf(\"{name}\", \"{attribute}\"))"
+            )),
+            RuleValueIr::ValueOf(name, attribute) => {
+                output.text(format!("Fn.ValueOf(\"{name}\", \"{attribute}\")"))
+            }
+            RuleValueIr::ValueOfAll(parameter_type, attribute) => output.text(format!(
+                "Fn.ValueOfAll(\"{parameter_type}\", \"{attribute}\")"
+            )),
+        }
+    }
+}
+
 impl Reference {
     fn emit_csharp(&self, output: &CodeBuffer) {
         match &self.origin {
@@ -442,6 +1100,15 @@ impl Reference {
             Origin::LogicalId { conditional: _ } => {
                 output.text(format!("{}.Ref", camel_case(&self.name.replace('.', ""))))
             }
+            Origin::NestedStackOutput {
+                output: name,
+                conditional: _,
+            } => output.text(format!("{}.{name} as string", camel_case(&self.name))),
+            Origin::ConstructAttribute { attribute } => output.text(format!(
+                "{}.{}",
+                camel_case(&self.name),
+                pascal_case(attribute)
+            )),
             Origin::CfnParameter | Origin::Parameter => {
                 output.text(format!("props.{}", pascal_case(&self.name)))
             }
@@ -488,10 +1155,26 @@ impl ResourceIr {
                 };
                 Ok(())
             }
-            ResourceIr::Array(_structure, array) => {
+            ResourceIr::Array(structure, array) => {
+                // An implicitly typed array needs a best type for its items, which unions and
+                // untyped items of different kinds don't have.
+                let is_mixed = match structure {
+                    TypeReference::Union(_) => true,
+                    TypeReference::Primitive(Primitive::Json) => array.windows(2).any(|pair| {
+                        std::mem::discriminant(&pair[0]) != std::mem::discriminant(&pair[1])
+                    }),
+                    _ => false,
+                };
                 let array_block = output.indent_with_options(IndentOptions {
                     indent: INDENT,
-                    leading: Some("new []\n{".into()),
+                    leading: Some(
+                        if is_mixed {
+                            "new object[]\n{"
+                        } else {
+                            "new []\n{"
+                        }
+                        .into(),
+                    ),
                     trailing: Some("}".into()),
                     trailing_newline: false,
                 });
@@ -522,7 +1205,13 @@ impl ResourceIr {
                             Ok(())
                         }
                         name => {
-                            let name = &schema.type_named(name).unwrap().name.csharp;
+                            let name = &schema
+                                .type_named(name)
+                                .ok_or_else(|| Error::TypeReferenceError {
+                                    message: format!("{name} is not a known property type"),
+                                })?
+                                .name
+                                .csharp;
                             let object_block = output.indent_with_options(IndentOptions {
                                 indent: INDENT,
                                 leading: Some(format!("new {}\n{{", name.name).into()),
@@ -623,7 +1312,9 @@ impl ResourceIr {
                 output.text("$\"");
                 for part in parts {
                     match part {
-                        ResourceIr::String(lit) => output.text(lit.clone()),
+                        ResourceIr::String(lit) => {
+                            output.text(lit.replace('{', "{{").replace('}', "}}"))
+                        }
                         other => {
                             output.text("{");
                             other.emit_csharp(output, schema)?;
@@ -695,6 +1386,31 @@ impl ResourceIr {
                 output.text(")");
                 Ok(())
             }
+            ResourceIr::Length(list) => match list.as_ref() {
+                // The items of a literal array are known, whatever their types.
+                ResourceIr::Array(_, items) => {
+                    output.text(items.len().to_string());
+                    Ok(())
+                }
+                other => {
+                    output.text("Fn.Len(");
+                    other.emit_csharp(output, schema)?;
+                    output.text(")");
+                    Ok(())
+                }
+            },
+            ResourceIr::ToJsonString(value) => match value.to_json() {
+                Some(json) => {
+                    output.text(csharp_string(&json.to_string()));
+                    Ok(())
+                }
+                None => {
+                    output.text("Fn.ToJsonString(");
+                    value.emit_csharp(output, schema)?;
+                    output.text(")");
+                    Ok(())
+                }
+            },
         }
     }
 }
@@ -702,18 +1418,7 @@ impl ResourceIr {
 impl CsharpEmitter for OutputInstruction {
     fn emit_csharp(&self, output: &CodeBuffer, schema: &Schema) -> Result<(), Error> {
         let var_name = &self.name;
-
-        if let Some(cond) = &self.condition {
-            output.line(format!("{var_name} = {}", camel_case(cond)));
-            output.text(format!("{INDENT}? "));
-            let indented = output.indent(INDENT);
-            self.value.emit_csharp(&indented, schema)?;
-            output.line(format!("\n{INDENT}: null;"));
-        } else {
-            output.text(format!("{var_name} = "));
-            self.value.emit_csharp(output, schema)?;
-            output.line(";");
-        }
+        self.emit_csharp_property(output, schema)?;
 
         // Create CfnOutputs if the output is an export
         if let Some(export) = &self.export {
@@ -735,6 +1440,24 @@ impl CsharpEmitter for OutputInstruction {
 }
 
 impl OutputInstruction {
+    // Sets the property the output is exposed as.
+    fn emit_csharp_property(&self, output: &CodeBuffer, schema: &Schema) -> Result<(), Error> {
+        let var_name = &self.name;
+
+        if let Some(cond) = &self.condition {
+            output.line(format!("{var_name} = {}", camel_case(cond)));
+            output.text(format!("{INDENT}? "));
+            let indented = output.indent(INDENT);
+            self.value.emit_csharp(&indented, schema)?;
+            output.line(format!("\n{INDENT}: null;"));
+        } else {
+            output.text(format!("{var_name} = "));
+            self.value.emit_csharp(output, schema)?;
+            output.line(";");
+        }
+        Ok(())
+    }
+
     fn emit_cfn_output(
         &self,
         output: &CodeBuffer,
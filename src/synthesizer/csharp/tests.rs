@@ -14,7 +14,7 @@ use crate::{
     primitives::WrapperF64,
 };
 
-use super::CsharpEmitter;
+use super::{csharp_string, CsharpEmitter};
 
 #[test]
 fn test_fn_split() {
@@ -189,6 +189,7 @@ fn test_output_instruction() {
         value: ResourceIr::Number(2),
         condition: Option::None,
         description: Option::None,
+        comments: Vec::new(),
     };
     let result = output_instruction.emit_csharp(&output, &schema);
     assert_eq!((), result.unwrap());
@@ -460,3 +461,162 @@ fn test_resource_ir_get_azs_error() {
         result.to_string(),
     );
 }
+
+#[test]
+fn test_rule_contains() {
+    use crate::ir::reference::{Origin, Reference};
+    use crate::ir::rules::{RuleIr, RuleValueIr};
+
+    let output = CodeBuffer::default();
+    RuleIr::Or(vec![
+        RuleIr::Contains(
+            RuleValueIr::List(vec![
+                RuleValueIr::Str("a".into()),
+                RuleValueIr::Str("b".into()),
+            ]),
+            RuleValueIr::Ref(Reference::new("Name", Origin::Parameter)),
+        ),
+        RuleIr::EachMemberIn(
+            RuleValueIr::ValueOf("Subnets".into(), "VpcId".into()),
+            RuleValueIr::RefAll("AWS::EC2::VPC::Id".into()),
+        ),
+    ])
+    .emit_csharp(&output);
+
+    let mut result = Vec::new();
+    output.write(&mut result).unwrap();
+    assert_eq!(
+        "Fn.ConditionOr(Fn.ConditionContains(new [] { \"a\", \"b\" }, props.Name), Fn.ConditionEachMemberIn(Token.AsList(Fn.ValueOf(\"Subnets\", \"VpcId\")), Fn.RefAll(\"AWS::EC2::VPC::Id\")))",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_resource_ir_length() {
+    let output = CodeBuffer::default();
+    let schema = Cow::Borrowed(Schema::builtin());
+    for list in [
+        ResourceIr::Array(TypeReference::Primitive(Primitive::String), vec![]),
+        ResourceIr::Array(
+            TypeReference::Primitive(Primitive::Json),
+            vec![
+                ResourceIr::String("a".into()),
+                ResourceIr::Number(1),
+                ResourceIr::Bool(true),
+            ],
+        ),
+    ] {
+        ResourceIr::Length(Box::new(list))
+            .emit_csharp(&output, &schema)
+            .unwrap();
+        output.text(",");
+    }
+
+    let mut result = Vec::new();
+    output.write(&mut result).unwrap();
+    assert_eq!("0,3,", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_union_list_is_an_object_array() {
+    let template: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Transform: AWS::Serverless-2016-10-31
+        Resources:
+          Function:
+            Type: AWS::Serverless::Function
+            Properties:
+              Runtime: nodejs20.x
+              Handler: index.handler
+              CodeUri: s3://my-bucket/code.zip
+              Policies:
+                - AWSLambdaExecute
+                - Version: '2012-10-17'
+                  Statement: []
+        "#,
+    )
+    .unwrap();
+    let ir = crate::ir::CloudformationProgramIr::from(template, Schema::builtin()).unwrap();
+    let mut output = Vec::new();
+    ir.synthesize("csharp", &mut output, "Stack").unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.contains("Policies = new object[]\n"), "{output}");
+}
+
+#[test]
+fn test_resource_ir_unknown_type_error() {
+    let output = CodeBuffer::default();
+    let schema = Cow::Borrowed(Schema::builtin());
+    let resource_ir = ResourceIr::Object(
+        TypeReference::Named("AWS::Nope::Thing.Property".into()),
+        IndexMap::new(),
+    );
+    let result = resource_ir.emit_csharp(&output, &schema).unwrap_err();
+    assert_eq!(
+        "AWS::Nope::Thing.Property is not a known property type",
+        result.to_string(),
+    );
+}
+
+#[test]
+fn test_mixed_json_list_is_an_object_array() {
+    let output = CodeBuffer::default();
+    let schema = Cow::Borrowed(Schema::builtin());
+    for list in [
+        ResourceIr::Array(
+            TypeReference::Primitive(Primitive::Json),
+            vec![ResourceIr::String("a".into()), ResourceIr::Number(1)],
+        ),
+        ResourceIr::Array(
+            TypeReference::Primitive(Primitive::String),
+            vec![
+                ResourceIr::String("a".into()),
+                ResourceIr::ImportValue(Box::new(ResourceIr::String("b".into()))),
+            ],
+        ),
+    ] {
+        list.emit_csharp(&output, &schema).unwrap();
+        output.newline();
+    }
+
+    let mut result = Vec::new();
+    output.write(&mut result).unwrap();
+    let result = String::from_utf8(result).unwrap();
+    assert!(result.starts_with("new object[]\n"), "{result}");
+    assert!(result.contains("\nnew []\n"), "{result}");
+}
+
+#[test]
+fn test_csharp_string() {
+    assert_eq!(csharp_string("plain"), "\"plain\"");
+    assert_eq!(
+        csharp_string("it's \"quoted\"\n\\d+"),
+        r#""it's \"quoted\"\n\\d+""#
+    );
+    assert_eq!(
+        csharp_string("a\u{7}b\u{85}é😀"),
+        "\"a\\u0007b\\u0085\\u00e9\\ud83d\\ude00\""
+    );
+}
+
+#[test]
+fn test_resource_ir_to_json_string_literal() {
+    let output = CodeBuffer::default();
+    let schema = Cow::Borrowed(Schema::builtin());
+    let resource_ir = ResourceIr::ToJsonString(Box::new(ResourceIr::Object(
+        TypeReference::Primitive(Primitive::Json),
+        IndexMap::from_iter([(
+            "Name".to_string(),
+            ResourceIr::String("café\u{2028}".into()),
+        )]),
+    )));
+    resource_ir.emit_csharp(&output, &schema).unwrap();
+
+    let mut result = Vec::new();
+    output.write(&mut result).unwrap();
+    assert_eq!(
+        "\"{\\\"Name\\\":\\\"caf\\u00e9\\u2028\\\"}\"",
+        String::from_utf8(result).unwrap()
+    );
+}
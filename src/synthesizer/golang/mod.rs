@@ -5,31 +5,68 @@
 use crate::cdk::{ItemType, Primitive, Schema, TypeReference};
 use crate::code::{CodeBuffer, IndentOptions};
 use crate::ir::conditions::ConditionIr;
-use crate::ir::constructor::ConstructorParameter;
+use crate::ir::constructor::{
+    ConstructorParameter, ConstructorType, DefaultValue, ParameterConstraint, PropValidation,
+};
+use crate::ir::constructs::{L2Construct, L2Value};
 use crate::ir::importer::ImportInstruction;
 use crate::ir::mappings::OutputType;
+use crate::ir::nested_stacks::NestedStackInstruction;
 use crate::ir::reference::{Origin, PseudoParameter, Reference};
-use crate::ir::resources::{find_references, ResourceInstruction, ResourceIr};
+use crate::ir::resources::{find_references, CreationPolicyIr, ResourceInstruction, ResourceIr};
+use crate::ir::rules::{RuleIr, RuleValueIr};
 use crate::ir::CloudformationProgramIr;
 use crate::parser::lookup_table::MappingInnerValue;
 use crate::Error;
 use std::borrow::Cow;
+use std::collections::BTreeSet;
 use std::io;
 use std::rc::Rc;
 use voca_rs::case::{camel_case, pascal_case, snake_case};
 
-use super::Synthesizer;
+use super::{ConstructOptions, Synthesizer, SynthesizerOptions};
 
 const INDENT: Cow<'static, str> = Cow::Borrowed("\t");
 const TERNARY: &str = "ifCondition";
 
 pub struct Golang<'a> {
     schema: &'a Schema,
+    package: String,
+    entry_point: bool,
+    construct: Option<ConstructOptions>,
 }
 
 impl<'a> Golang<'a> {
     pub fn new(schema: &'a Schema) -> Self {
-        Self { schema }
+        Self {
+            schema,
+            package: "main".into(),
+            entry_point: true,
+            construct: None,
+        }
+    }
+
+    /// Declares the stack in `package` instead of `main`. Only the `main` package can be run, so
+    /// the `main` and `env` functions are then left out.
+    pub fn in_package(mut self, package: impl Into<String>) -> Self {
+        self.package = package.into();
+        self
+    }
+
+    /// Emits an `add<StackName>(app)` function instead of the `main` and `env` functions, so that
+    /// several stacks can be part of the same Go package. The package must then provide `main`,
+    /// `env` and the `ifCondition` helper itself.
+    pub fn without_entry_point(mut self) -> Self {
+        self.entry_point = false;
+        self
+    }
+
+    /// Declares a construct instead of a stack (nested stacks are still declared as such). An app
+    /// can't hold a construct directly, so neither the entry point nor the add function are
+    /// emitted.
+    pub fn as_construct(mut self, options: ConstructOptions) -> Self {
+        self.construct = Some(options);
+        self
     }
 }
 
@@ -39,6 +76,16 @@ impl Default for Golang<'_> {
     }
 }
 
+impl From<&SynthesizerOptions> for Golang<'_> {
+    fn from(options: &SynthesizerOptions) -> Self {
+        let golang = Self::new(Schema::builtin()).in_package(options.golang.package.clone());
+        match options.construct {
+            Some(construct) => golang.as_construct(construct),
+            None => golang,
+        }
+    }
+}
+
 impl Synthesizer for Golang<'_> {
     fn synthesize(
         &self,
@@ -48,7 +95,7 @@ impl Synthesizer for Golang<'_> {
     ) -> Result<(), Error> {
         let code = CodeBuffer::default();
 
-        code.line("package main");
+        code.line(format!("package {}", self.package));
         code.newline();
 
         let imports = code.indent_with_options(IndentOptions {
@@ -68,342 +115,727 @@ impl Synthesizer for Golang<'_> {
 
         code.newline();
 
-        let props = code.indent_with_options(IndentOptions {
-            indent: INDENT,
-            leading: Some(format!("type {stack_name}Props struct {{").into()),
-            trailing: Some("}".into()),
-            trailing_newline: true,
-        });
-        props.line("cdk.StackProps"); // Extends cdk.StackProps
-        for param in &ir.constructor.inputs {
-            if let Some(description) = &param.description {
-                props.indent("/// ".into()).line(description.to_owned());
-            }
-            props.line(param.to_golang_field());
+        // The stack is declared before the ternary helper, and nested stacks after it.
+        let stack = code.section(false);
+        let context = &mut {
+            let fmt = stdlib_imports.section(false);
+            let regexp = stdlib_imports.section(false);
+            let strconv = stdlib_imports.section(false);
+            let time = stdlib_imports.section(false);
+            let blank = stdlib_imports.section(false);
+            let ternary = code.section(false);
+            GoContext::new(self.schema, fmt, regexp, strconv, time, blank, ternary)
+        };
+        // Stacks sharing a package can't each declare the ternary helper.
+        context.has_ternary = !self.entry_point;
+
+        emit_stack(context, &stack, &ir, stack_name, false, self.construct)?;
+        for nested in ir.nested_stacks() {
+            code.newline();
+            emit_stack(
+                context,
+                &code,
+                &nested.program,
+                &nested.class_name,
+                true,
+                None,
+            )?;
+        }
+        if self.construct.is_some() || (self.entry_point && self.package != "main") {
+            return Ok(code.write(into)?);
         }
         code.newline();
 
-        if let Some(description) = &ir.description {
-            code.indent("/// ".into()).line(description.to_owned());
+        if !self.entry_point {
+            // Values imported from other stacks are passed to the add function.
+            let arguments: String = ir
+                .cross_stack_imports
+                .iter()
+                .map(|import| {
+                    format!(
+                        ", {} *string",
+                        golang_identifier(&import.parameter, IdentifierKind::Unexported)
+                    )
+                })
+                .collect();
+            let add_block = code.indent_with_options(IndentOptions {
+                indent: INDENT,
+                leading: Some(
+                    format!("func add{stack_name}(app cdk.App{arguments}) *{stack_name} {{").into(),
+                ),
+                trailing: Some("}".into()),
+                trailing_newline: true,
+            });
+            add_block.text("return ");
+            emit_instantiation(&add_block, &ir, stack_name);
+            return Ok(code.write(into)?);
         }
-        let class = code.indent_with_options(IndentOptions {
+
+        let main_block = code.indent_with_options(IndentOptions {
             indent: INDENT,
-            leading: Some(format!("type {stack_name} struct {{").into()),
+            leading: Some("func main() {".into()),
             trailing: Some("}".into()),
             trailing_newline: true,
         });
-        class.line("cdk.Stack");
-        for output in &ir.outputs {
-            if let Some(description) = &output.description {
-                class.indent("/// ".into()).line(description.to_owned());
-            }
-            class.line(format!(
-                "{name} interface{{}} // TODO: fix to appropriate type",
-                name = golang_identifier(&output.name, IdentifierKind::Exported)
-            ));
-        }
+
+        main_block.line("defer jsii.Close()");
+        main_block.newline();
+        main_block.line("app := cdk.NewApp(nil)");
+        main_block.newline();
+        emit_instantiation(&main_block, &ir, stack_name);
+        main_block.newline();
+        main_block.line("app.Synth(nil)");
         code.newline();
 
-        let ctor = code.indent_with_options(IndentOptions {
+        emit_env(&code);
+
+        Ok(code.write(into)?)
+    }
+}
+
+// emit_stack declares the props, the struct and the constructor of a stack. Nested stacks embed
+// `cdk.NestedStack` rather than `cdk.Stack`, and constructs embed `constructs.Construct`, leaving
+// the stack-wide settings of the template to the stack they are added to.
+fn emit_stack(
+    context: &mut GoContext,
+    code: &CodeBuffer,
+    ir: &CloudformationProgramIr,
+    stack_name: &str,
+    nested: bool,
+    construct: Option<ConstructOptions>,
+) -> Result<(), Error> {
+    let base_class = if nested { "NestedStack" } else { "Stack" };
+    context.used_parameters.clear();
+    context.construct = construct.is_some();
+
+    let props = code.indent_with_options(IndentOptions {
+        indent: INDENT,
+        leading: Some(format!("type {stack_name}Props struct {{").into()),
+        trailing: Some("}".into()),
+        trailing_newline: true,
+    });
+    if construct.is_none() {
+        props.line(format!("cdk.{base_class}Props")); // Extends cdk.StackProps
+    }
+    for param in &ir.constructor.inputs {
+        if let Some(description) = &param.description {
+            props.indent("/// ".into()).line(description.to_owned());
+        }
+        props.comments("///", &param.comments);
+        props.line(param.to_golang_field());
+    }
+    code.newline();
+
+    if let Some(description) = &ir.description {
+        code.indent("/// ".into()).line(description.to_owned());
+    }
+    let class = code.indent_with_options(IndentOptions {
+        indent: INDENT,
+        leading: Some(format!("type {stack_name} struct {{").into()),
+        trailing: Some("}".into()),
+        trailing_newline: true,
+    });
+    class.line(match construct {
+        Some(_) => "constructs.Construct".into(),
+        None => format!("cdk.{base_class}"),
+    });
+    for output in &ir.outputs {
+        if let Some(description) = &output.description {
+            class.indent("/// ".into()).line(description.to_owned());
+        }
+        class.comments("///", &output.comments);
+        class.line(format!(
+            "{name} interface{{}} // TODO: fix to appropriate type",
+            name = golang_identifier(&output.name, IdentifierKind::Exported)
+        ));
+    }
+    code.newline();
+
+    let ctor = code.indent_with_options(IndentOptions {
+        indent: INDENT,
+        leading: Some(
+            format!(
+                "func New{stack_name}(scope constructs.Construct, id string, props *{stack_name}Props) *{stack_name} {{"
+            )
+            .into(),
+        ),
+        trailing: Some("}".into()),
+        trailing_newline: true,
+    });
+
+    for mapping in &ir.mappings {
+        let leaf_type = match mapping.output_type() {
+            OutputType::Complex => "interface{}",
+            OutputType::Consistent(inner) => match inner {
+                MappingInnerValue::Bool(_) => "*bool",
+                MappingInnerValue::Float(_) | MappingInnerValue::Number(_) => "*float64",
+                MappingInnerValue::String(_) => "*string",
+                MappingInnerValue::List(_) => "[]*string",
+            },
+        };
+
+        let used = ir.uses_map_table(&mapping.name);
+        if !used {
+            // Go is merciless about dead stores... so we comment out unused maps...
+            ctor.line("/*");
+        }
+        let map = ctor.indent_with_options(IndentOptions {
             indent: INDENT,
             leading: Some(
                 format!(
-                    "func New{stack_name}(scope constructs.Construct, id string, props *{stack_name}Props) *{stack_name} {{"
+                    "{} := map[*string]map[*string]{leaf_type}{{",
+                    golang_identifier(&mapping.name, IdentifierKind::Unexported)
                 )
                 .into(),
             ),
             trailing: Some("}".into()),
             trailing_newline: true,
         });
-
-        let context = &mut {
-            let fmt = stdlib_imports.section(false);
-            let time = stdlib_imports.section(false);
-            let blank = stdlib_imports.section(false);
-            let ternary = code.section(false);
-            GoContext::new(self.schema, fmt, time, blank, ternary)
-        };
-
-        for mapping in &ir.mappings {
-            let leaf_type = match mapping.output_type() {
-                OutputType::Complex => "interface{}",
-                OutputType::Consistent(inner) => match inner {
-                    MappingInnerValue::Bool(_) => "*bool",
-                    MappingInnerValue::Float(_) | MappingInnerValue::Number(_) => "*float64",
-                    MappingInnerValue::String(_) => "*string",
-                    MappingInnerValue::List(_) => "[]*string",
-                },
-            };
-
-            let used = ir.uses_map_table(&mapping.name);
-            if !used {
-                // Go is merciless about dead stores... so we comment out unused maps...
-                ctor.line("/*");
-            }
-            let map = ctor.indent_with_options(IndentOptions {
+        for (key, inner) in &mapping.map {
+            let inner_map = map.indent_with_options(IndentOptions {
                 indent: INDENT,
-                leading: Some(
-                    format!(
-                        "{} := map[*string]map[*string]{leaf_type}{{",
-                        golang_identifier(&mapping.name, IdentifierKind::Unexported)
-                    )
-                    .into(),
-                ),
-                trailing: Some("}".into()),
+                leading: Some(format!("jsii.String({key:?}): map[*string]{leaf_type}{{").into()),
+                trailing: Some("},".into()),
                 trailing_newline: true,
             });
-            for (key, inner) in &mapping.map {
-                let inner_map = map.indent_with_options(IndentOptions {
-                    indent: INDENT,
-                    leading: Some(
-                        format!("jsii.String({key:?}): map[*string]{leaf_type}{{").into(),
-                    ),
-                    trailing: Some("},".into()),
-                    trailing_newline: true,
-                });
-                for (key, value) in inner {
-                    inner_map.text(format!("jsii.String({key:?}): "));
-                    match value {
-                        MappingInnerValue::Bool(bool) => {
-                            inner_map.text(format!("jsii.Bool({bool})"))
-                        }
-                        MappingInnerValue::Number(num) => {
-                            inner_map.text(format!("jsii.Number({num})"))
-                        }
-                        MappingInnerValue::Float(num) => {
-                            inner_map.text(format!("jsii.Number({num})"))
-                        }
-                        MappingInnerValue::String(str) => {
-                            inner_map.text(format!("jsii.String({str:?})"))
-                        }
-                        MappingInnerValue::List(items) => {
-                            let list = inner_map.indent_with_options(IndentOptions {
-                                indent: INDENT,
-                                leading: Some("[]*string{".into()),
-                                trailing: Some("}".into()),
-                                trailing_newline: false,
-                            });
-                            for item in items {
-                                list.line(format!("jsii.String({item:?}),"));
-                            }
+            for (key, value) in inner {
+                inner_map.text(format!("jsii.String({key:?}): "));
+                match value {
+                    MappingInnerValue::Bool(bool) => inner_map.text(format!("jsii.Bool({bool})")),
+                    MappingInnerValue::Number(num) => inner_map.text(format!("jsii.Number({num})")),
+                    MappingInnerValue::Float(num) => inner_map.text(format!("jsii.Number({num})")),
+                    MappingInnerValue::String(str) => {
+                        inner_map.text(format!("jsii.String({str:?})"))
+                    }
+                    MappingInnerValue::List(items) => {
+                        let list = inner_map.indent_with_options(IndentOptions {
+                            indent: INDENT,
+                            leading: Some("[]*string{".into()),
+                            trailing: Some("}".into()),
+                            trailing_newline: false,
+                        });
+                        for item in items {
+                            list.line(format!("jsii.String({item:?}),"));
                         }
                     }
-                    inner_map.line(",");
                 }
+                inner_map.line(",");
             }
-            if !used {
-                ctor.line("*/");
-            }
-            ctor.newline();
         }
-        ctor.line("var sprops cdk.StackProps");
+        if !used {
+            ctor.line("*/");
+        }
+        ctor.newline();
+    }
+    if construct.is_some() {
+        ctor.line("construct := constructs.NewConstruct(scope, &id)");
+    } else {
+        ctor.line(format!("var sprops cdk.{base_class}Props"));
         let props_not_nil_block = ctor.indent_with_options(IndentOptions {
             indent: INDENT,
             leading: Some("if props != nil {".into()),
             trailing: Some("}".into()),
             trailing_newline: true,
         });
-        props_not_nil_block.line("sprops = props.StackProps");
-        ctor.line("stack := cdk.NewStack(scope, &id, &sprops)");
-        ctor.newline();
-
-        if !ir.transforms.is_empty() {
-            for transform in &ir.transforms {
-                ctor.line(format!("stack.AddTransform(jsii.String(\"{transform}\"))"));
-            }
-            ctor.newline();
+        props_not_nil_block.line(format!("sprops = props.{base_class}Props"));
+        ctor.line(format!("stack := cdk.New{base_class}(scope, &id, &sprops)"));
+    }
+    ctor.newline();
+
+    emit_parameter_validation(context, &ctor, &ir.constructor.validations());
+    // Declared once the rest of the stack is rendered, as Go rejects unused variables.
+    let parameters = ctor.section(false);
+
+    // The template options are those of the stack, which a construct does not own.
+    let metadata = ir.metadata.as_ref().filter(|metadata| !metadata.is_empty());
+    if construct.is_none() && (ir.template_format_version.is_some() || metadata.is_some()) {
+        if let Some(version) = &ir.template_format_version {
+            ctor.line(format!(
+                "stack.TemplateOptions().SetTemplateFormatVersion(jsii.String({version:?}))"
+            ));
+        }
+        if let Some(metadata) = metadata {
+            ctor.text("stack.TemplateOptions().SetMetadata(&");
+            metadata
+                .to_ir(|name| ir.constructor.logical_id(name))
+                .emit_golang(context, &ctor, Some(")"))?;
         }
+        ctor.newline();
+    }
 
-        for condition in &ir.conditions {
-            ctor.text(format!(
-                "{name} := ",
-                name = golang_identifier(&condition.name, IdentifierKind::Unexported)
+    if !ir.transforms.is_empty() {
+        for transform in &ir.transforms {
+            ctor.line(format!(
+                "{}.AddTransform(jsii.String(\"{transform}\"))",
+                context.stack()
             ));
-            condition.value.emit_golang(context, &ctor, None)?;
-            ctor.newline();
-            ctor.newline();
         }
+        ctor.newline();
+    }
 
-        for resource in &ir.resources {
-            let ns =
-                golang_identifier(resource.resource_type.service(), IdentifierKind::ModuleName);
-            let class = resource.resource_type.type_name();
+    for condition in &ir.conditions {
+        ctor.text(format!(
+            "{name} := ",
+            name = golang_identifier(&condition.name, IdentifierKind::Unexported)
+        ));
+        condition.value.emit_golang(context, &ctor, None)?;
+        ctor.newline();
+        ctor.newline();
+    }
 
-            let prefix = if ir.resources.iter().any(|other| {
-                other.name != resource.name && other.references.contains(&resource.name)
-            }) || ir
-                .outputs
-                .iter()
-                .any(|output| find_references(&output.value).contains(&resource.name))
-            {
+    for rule in &ir.rules {
+        let props = ctor.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some(
                 format!(
-                    "{varname} := ",
-                    varname = golang_identifier(&resource.name, IdentifierKind::Unexported)
+                    "cdk.NewCfnRule({}, jsii.String({:?}), &cdk.CfnRuleProps{{",
+                    context.scope(),
+                    rule.name
                 )
-            } else {
-                "".into()
-            };
-            let params = ctor.indent_with_options(IndentOptions {
-                indent: INDENT,
-                leading: Some(format!("{prefix}{ns}.NewCfn{class}(").into()),
-                trailing: Some(")".into()),
-                trailing_newline: true,
-            });
-            params.line("stack,");
-            params.line(format!("jsii.String({:?}),", resource.name));
-            let props = params.indent_with_options(IndentOptions {
+                .into(),
+            ),
+            trailing: Some("})".into()),
+            trailing_newline: true,
+        });
+        if let Some(condition) = &rule.condition {
+            props.text("RuleCondition: ");
+            condition.emit_golang(context, &props, Some(",\n"))?;
+        }
+        if !rule.assertions.is_empty() {
+            let assertions = props.indent_with_options(IndentOptions {
                 indent: INDENT,
-                leading: Some(format!("&{ns}.Cfn{class}Props{{").into()),
+                leading: Some("Assertions: &[]*cdk.CfnRuleAssertion{".into()),
                 trailing: Some("},".into()),
                 trailing_newline: true,
             });
-            for (name, value) in &resource.properties {
-                props.text(format!(
-                    "{}: ",
-                    golang_identifier(name, IdentifierKind::Exported)
-                ));
-                value.emit_golang(context, &props, None)?;
-                props.line(",");
-            }
-            ctor.newline();
-        }
-
-        for output in &ir.outputs {
-            if let Some(export) = &output.export {
-                let props = ctor.indent_with_options(IndentOptions {
+            for assertion in &rule.assertions {
+                let fields = assertions.indent_with_options(IndentOptions {
                     indent: INDENT,
-                    leading: Some(
-                        format!(
-                            "cdk.NewCfnOutput(stack, jsii.String(\"CfnOutput{}\"), &cdk.CfnOutputProps{{",
-                            output.name
-                        )
-                        .into(),
-                    ),
-                    trailing: Some("})".into()),
+                    leading: Some("{".into()),
+                    trailing: Some("},".into()),
                     trailing_newline: true,
                 });
-                props.line(format!("Key: jsii.String({name:?}),", name = output.name));
-                if let Some(description) = &output.description {
-                    props.line(format!("Description: jsii.String({description:?}),"));
-                }
-                props.text("ExportName: ");
-                export.emit_golang(context, &props, Some(","))?;
-                props.text("Value: ");
-                output.value.emit_golang(context, &props, Some(","))?;
-                ctor.newline();
+                fields.text("Assert: ");
+                assertion
+                    .assert
+                    .emit_golang(context, &fields, Some(",\n"))?;
+                // The CDK requires a description, even though CloudFormation does not.
+                fields.line(format!(
+                    "AssertDescription: jsii.String({:?}),",
+                    assertion.description.as_deref().unwrap_or_default()
+                ));
             }
         }
+        ctor.newline();
+    }
 
-        let fields = ctor.indent_with_options(IndentOptions {
+    for resource in &ir.resources {
+        let ns = golang_identifier(resource.resource_type.service(), IdentifierKind::ModuleName);
+        let class = resource.resource_type.type_name();
+
+        let has_options = resource.deletion_policy.is_some()
+            || resource.update_policy.is_some()
+            || resource.update_replace_policy.is_some()
+            || resource.creation_policy.is_some()
+            || (resource.nested_stack.is_some() && resource.metadata.is_some());
+        let prefix = if has_options
+            || ir.resources.iter().any(|other| {
+                other.name != resource.name && other.references.contains(&resource.name)
+            })
+            || ir
+                .outputs
+                .iter()
+                .any(|output| find_references(&output.value).contains(&resource.name))
+        {
+            format!(
+                "{varname} := ",
+                varname = golang_identifier(&resource.name, IdentifierKind::Unexported)
+            )
+        } else {
+            "".into()
+        };
+        ctor.comments("//", &resource.comments);
+        if let Some(nested) = &resource.nested_stack {
+            emit_nested_stack(context, &ctor, &prefix, resource, nested)?;
+            ctor.newline();
+            continue;
+        }
+        if let Some(construct) = &resource.l2_construct {
+            emit_l2_construct(context, &ctor, resource, construct)?;
+            ctor.newline();
+            continue;
+        }
+        let params = ctor.indent_with_options(IndentOptions {
             indent: INDENT,
-            leading: Some(format!("return &{stack_name}{{").into()),
-            trailing: Some("}".into()),
+            leading: Some(format!("{prefix}{ns}.NewCfn{class}(").into()),
+            trailing: Some(")".into()),
+            trailing_newline: true,
+        });
+        params.line(format!("{},", context.scope()));
+        params.line(format!("jsii.String({:?}),", resource.name));
+        let props = params.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some(format!("&{ns}.Cfn{class}Props{{").into()),
+            trailing: Some("},".into()),
             trailing_newline: true,
         });
-        fields.line("Stack: stack,");
-        for output in &ir.outputs {
-            fields.text(format!(
-                "{name}: ",
-                name = golang_identifier(&output.name, IdentifierKind::Exported)
+        for (name, value) in &resource.properties {
+            if let Some(comments) = resource.property_comments.get(name) {
+                props.comments("//", comments);
+            }
+            props.text(format!(
+                "{}: ",
+                golang_identifier(name, IdentifierKind::Exported)
             ));
-            output.value.emit_golang(context, &fields, None)?;
-            fields.line(",");
+            value.emit_golang(context, &props, None)?;
+            props.line(",");
         }
-        code.newline();
+        let varname = golang_identifier(&resource.name, IdentifierKind::Unexported);
+        if let Some(update_policy) = &resource.update_policy {
+            // The update policy is set as an override, as its value is not typed.
+            ctor.text(format!(
+                "{varname}.AddOverride(jsii.String(\"UpdatePolicy\"), "
+            ));
+            update_policy.emit_golang(context, &ctor, None)?;
+            ctor.line(")");
+        }
+        if let Some(deletion_policy) = &resource.deletion_policy {
+            ctor.line(format!(
+                "{varname}.CfnOptions().SetDeletionPolicy(cdk.CfnDeletionPolicy_{deletion_policy})"
+            ));
+        }
+        if let Some(update_replace_policy) = &resource.update_replace_policy {
+            ctor.line(format!(
+                "{varname}.CfnOptions().SetUpdateReplacePolicy(cdk.CfnDeletionPolicy_{update_replace_policy})"
+            ));
+        }
+        if let Some(creation_policy) = &resource.creation_policy {
+            let policy = ctor.indent_with_options(IndentOptions {
+                indent: INDENT,
+                leading: Some(
+                    format!("{varname}.CfnOptions().SetCreationPolicy(&cdk.CfnCreationPolicy{{")
+                        .into(),
+                ),
+                trailing: Some("})".into()),
+                trailing_newline: true,
+            });
+            creation_policy.emit_golang(context, &policy, None)?;
+        }
+        ctor.newline();
+    }
 
-        let main_block = code.indent_with_options(IndentOptions {
+    let cfn_outputs = construct.is_none_or(|construct| construct.cfn_outputs);
+    for output in ir.outputs.iter().filter(|_| cfn_outputs) {
+        if let Some(export) = &output.export {
+            let props = ctor.indent_with_options(IndentOptions {
+                indent: INDENT,
+                leading: Some(
+                    format!(
+                        "cdk.NewCfnOutput({}, jsii.String(\"CfnOutput{}\"), &cdk.CfnOutputProps{{",
+                        context.scope(),
+                        output.name
+                    )
+                    .into(),
+                ),
+                trailing: Some("})".into()),
+                trailing_newline: true,
+            });
+            props.line(format!("Key: jsii.String({name:?}),", name = output.name));
+            if let Some(description) = &output.description {
+                props.line(format!("Description: jsii.String({description:?}),"));
+            }
+            props.text("ExportName: ");
+            export.emit_golang(context, &props, Some(","))?;
+            props.text("Value: ");
+            output.value.emit_golang(context, &props, Some(","))?;
+            ctor.newline();
+        }
+    }
+
+    let fields = ctor.indent_with_options(IndentOptions {
+        indent: INDENT,
+        leading: Some(format!("return &{stack_name}{{").into()),
+        trailing: Some("}".into()),
+        trailing_newline: true,
+    });
+    fields.line(match construct {
+        Some(_) => "Construct: construct,".into(),
+        None => format!("{base_class}: stack,"),
+    });
+    for output in &ir.outputs {
+        fields.text(format!(
+            "{name}: ",
+            name = golang_identifier(&output.name, IdentifierKind::Exported)
+        ));
+        output.value.emit_golang(context, &fields, None)?;
+        fields.line(",");
+    }
+    emit_parameters(context, &parameters, &ir.constructor.inputs);
+    Ok(())
+}
+
+// emit_l2_construct instantiates the L2 construct of a resource. Its L1 resource is always used, so
+// both are assigned to variables.
+fn emit_l2_construct(
+    context: &mut GoContext,
+    output: &CodeBuffer,
+    resource: &ResourceInstruction,
+    construct: &L2Construct,
+) -> Result<(), Error> {
+    let ns = golang_identifier(resource.resource_type.service(), IdentifierKind::ModuleName);
+    let class = &construct.class;
+    let varname = golang_identifier(&resource.name, IdentifierKind::Unexported);
+    let params = output.indent_with_options(IndentOptions {
+        indent: INDENT,
+        leading: Some(format!("{varname} := {ns}.New{class}(").into()),
+        trailing: Some(")".into()),
+        trailing_newline: true,
+    });
+    params.line(format!("{},", context.scope()));
+    params.line(format!("jsii.String({:?}),", resource.name));
+    let props = params.indent_with_options(IndentOptions {
+        indent: INDENT,
+        leading: Some(format!("&{ns}.{class}Props{{").into()),
+        trailing: Some("},".into()),
+        trailing_newline: true,
+    });
+    for (name, value) in &construct.props {
+        if let Some(comments) = resource.property_comments.get(name) {
+            props.comments("//", comments);
+        }
+        props.text(format!(
+            "{}: ",
+            golang_identifier(name, IdentifierKind::Exported)
+        ));
+        value.emit_golang(context, &props, None)?;
+        props.line(",");
+    }
+
+    // The L1 resource keeps the logical ID of the template, and what the construct can't express
+    let cfn_varname = golang_identifier(
+        &L2Construct::escape_hatch(&resource.name),
+        IdentifierKind::Unexported,
+    );
+    output.line(format!(
+        "{cfn_varname} := {varname}.Node().DefaultChild().({ns}.Cfn{})",
+        resource.resource_type.type_name()
+    ));
+    output.line(format!(
+        "{cfn_varname}.OverrideLogicalId(jsii.String({:?}))",
+        resource.name
+    ));
+    for (name, value) in &construct.overrides {
+        if let Some(comments) = resource.property_comments.get(name) {
+            output.comments("//", comments);
+        }
+        output.text(format!(
+            "{cfn_varname}.AddPropertyOverride(jsii.String({name:?}), "
+        ));
+        value.emit_golang(context, output, None)?;
+        output.line(")");
+    }
+    // Some constructs retain their resource by default, unlike the template.
+    if let Some(deletion_policy) = &resource.deletion_policy {
+        output.line(format!(
+            "{cfn_varname}.CfnOptions().SetDeletionPolicy(cdk.CfnDeletionPolicy_{deletion_policy})"
+        ));
+    }
+    if let Some(update_replace_policy) = &resource.update_replace_policy {
+        output.line(format!(
+            "{cfn_varname}.CfnOptions().SetUpdateReplacePolicy(cdk.CfnDeletionPolicy_{update_replace_policy})"
+        ));
+    }
+    if let Some(creation_policy) = &resource.creation_policy {
+        let policy = output.indent_with_options(IndentOptions {
             indent: INDENT,
-            leading: Some("func main() {".into()),
-            trailing: Some("}".into()),
+            leading: Some(
+                format!("{cfn_varname}.CfnOptions().SetCreationPolicy(&cdk.CfnCreationPolicy{{")
+                    .into(),
+            ),
+            trailing: Some("})".into()),
             trailing_newline: true,
         });
+        creation_policy.emit_golang(context, &policy, None)?;
+    }
+    Ok(())
+}
 
-        main_block.line("defer jsii.Close()");
-        main_block.newline();
-        main_block.line("app := cdk.NewApp(nil)");
-        main_block.newline();
-        let split_stack_name: Vec<&str> = stack_name.split("Stack").collect();
-        main_block.line(format!(
-            "New{stack_name}(app, \"{}\", {stack_name}Props{{",
-            split_stack_name[0]
+// emit_nested_stack instantiates the class of a nested stack. CfnParameter props are strings, so
+// lists are passed comma-delimited.
+fn emit_nested_stack(
+    context: &mut GoContext,
+    output: &CodeBuffer,
+    prefix: &str,
+    resource: &ResourceInstruction,
+    nested: &NestedStackInstruction,
+) -> Result<(), Error> {
+    let class = &nested.class_name;
+    let params = output.indent_with_options(IndentOptions {
+        indent: INDENT,
+        leading: Some(format!("{prefix}New{class}(").into()),
+        trailing: Some(")".into()),
+        trailing_newline: true,
+    });
+    params.line(format!("{},", context.scope()));
+    params.line(format!("{:?},", resource.name));
+    let props = params.indent_with_options(IndentOptions {
+        indent: INDENT,
+        leading: Some(format!("&{class}Props{{").into()),
+        trailing: Some("},".into()),
+        trailing_newline: true,
+    });
+    for param in &nested.program.constructor.inputs {
+        let Some(value) = nested.parameters.get(&param.name) else {
+            continue;
+        };
+        props.text(format!(
+            "{}: ",
+            golang_identifier(&param.name, IdentifierKind::Exported)
         ));
-        main_block.indent(INDENT).line("cdk.StackProps{");
-        main_block.indent(INDENT).indent(INDENT).line("Env: env(),");
-        main_block.indent(INDENT).line("},");
-        for param in &ir.constructor.inputs {
-            if param.default_value.is_some() {
-                main_block.indent(INDENT).line(format!(
-                    "{}: \"{}\",",
-                    golang_identifier(&param.name, IdentifierKind::Exported),
-                    param.default_value.clone().unwrap()
-                ));
+        match value {
+            ResourceIr::Split(_, joined) if param.is_cfn_parameter() => {
+                joined.emit_golang(context, &props, None)?
+            }
+            value if param.is_cfn_parameter() && param.constructor_type.is_list() => {
+                props.text("cdk.Fn_Join(jsii.String(\",\"), ");
+                value.emit_golang(context, &props, None)?;
+                props.text(")");
             }
+            value => value.emit_golang(context, &props, None)?,
         }
-        main_block.line("})");
-        main_block.newline();
-        main_block.line("app.Synth(nil)");
-        code.newline();
-
-        code.line(
-            "// env determines the AWS environment (account+region) in which our stack is to",
-        );
-        code.line("// be deployed. For more information see: https://docs.aws.amazon.com/cdk/latest/guide/environments.html");
+        props.line(",");
+    }
 
-        let env_block = code.indent_with_options(IndentOptions {
+    // The attributes of the nested stack apply to the CfnStack deploying it.
+    let cfn_stack = format!(
+        "{}.NestedStackResource()",
+        golang_identifier(&resource.name, IdentifierKind::Unexported)
+    );
+    if let Some(ResourceIr::Object(_, entries)) = &resource.metadata {
+        for (name, value) in entries {
+            output.text(format!("{cfn_stack}.AddMetadata(jsii.String({name:?}), "));
+            value.emit_golang(context, output, None)?;
+            output.line(")");
+        }
+    }
+    if let Some(deletion_policy) = &resource.deletion_policy {
+        output.line(format!(
+            "{cfn_stack}.CfnOptions().SetDeletionPolicy(cdk.CfnDeletionPolicy_{deletion_policy})"
+        ));
+    }
+    if let Some(update_replace_policy) = &resource.update_replace_policy {
+        output.line(format!(
+            "{cfn_stack}.CfnOptions().SetUpdateReplacePolicy(cdk.CfnDeletionPolicy_{update_replace_policy})"
+        ));
+    }
+    if let Some(creation_policy) = &resource.creation_policy {
+        let policy = output.indent_with_options(IndentOptions {
             indent: INDENT,
-            leading: Some("func env() *cdk.Environment {".into()),
-            trailing: Some("}".into()),
+            leading: Some(
+                format!("{cfn_stack}.CfnOptions().SetCreationPolicy(&cdk.CfnCreationPolicy{{")
+                    .into(),
+            ),
+            trailing: Some("})".into()),
             trailing_newline: true,
         });
+        creation_policy.emit_golang(context, &policy, None)?;
+    }
+    Ok(())
+}
 
-        env_block.line("// If unspecified, this stack will be \"environment-agnostic\".");
-        env_block
-            .line("// Account/Region-dependent features and context lookups will not work, but a");
-        env_block.line("// single synthesized template can be deployed anywhere.");
-        env_block
-            .line("//---------------------------------------------------------------------------");
-        env_block.line("return nil");
-        env_block.newline();
-        env_block
-            .line("// Uncomment if you know exactly what account and region you want to deploy");
-        env_block.line("// the stack to. This is the recommendation for production stacks.");
-        env_block
-            .line("//---------------------------------------------------------------------------");
-        env_block.line("// return &cdk.Environment{");
-        env_block.line("//  Account: jsii.String(\"123456789012\"),");
-        env_block.line("//  Region:  jsii.String(\"us-east-1\"),");
-        env_block.line("// }");
-        env_block.newline();
-        env_block
-            .line("// Uncomment to specialize this stack for the AWS Account and Region that are");
-        env_block.line("// implied by the current CLI configuration. This is recommended for dev");
-        env_block.line("// stacks.");
-        env_block
-            .line("//---------------------------------------------------------------------------");
-        env_block.line("// return &cdk.Environment{");
-        env_block.line("//  Account: jsii.String(os.Getenv(\"CDK_DEFAULT_ACCOUNT\")),");
-        env_block.line("//  Region:  jsii.String(os.Getenv(\"CDK_DEFAULT_REGION\")),");
-        env_block.line("// }");
-
-        Ok(code.write(into)?)
+// emit_instantiation adds the stack to `app`, passing the default value of every parameter.
+fn emit_instantiation(output: &CodeBuffer, ir: &CloudformationProgramIr, stack_name: &str) {
+    let split_stack_name: Vec<&str> = stack_name.split("Stack").collect();
+    output.line(format!(
+        "New{stack_name}(app, \"{}\", &{stack_name}Props{{",
+        split_stack_name[0]
+    ));
+    output.indent(INDENT).line("StackProps: cdk.StackProps{");
+    output.indent(INDENT).indent(INDENT).line("Env: env(),");
+    output.indent(INDENT).line("},");
+    for param in &ir.constructor.inputs {
+        if let Some(value) = &param.default_value {
+            let value = match param.is_cfn_parameter() {
+                true => format!("jsii.String({:?})", value.to_string()),
+                false => value.to_golang(),
+            };
+            output.indent(INDENT).line(format!(
+                "{}: {value},",
+                golang_identifier(&param.name, IdentifierKind::Exported),
+            ));
+        }
+    }
+    for import in &ir.cross_stack_imports {
+        output.indent(INDENT).line(format!(
+            "{}: {},",
+            golang_identifier(&import.parameter, IdentifierKind::Exported),
+            golang_identifier(&import.parameter, IdentifierKind::Unexported),
+        ));
     }
+    output.line("})");
+}
+
+// emit_env declares the `env` function, which determines the environment of the stacks.
+fn emit_env(code: &CodeBuffer) {
+    code.line("// env determines the AWS environment (account+region) in which our stack is to");
+    code.line("// be deployed. For more information see: https://docs.aws.amazon.com/cdk/latest/guide/environments.html");
+
+    let env_block = code.indent_with_options(IndentOptions {
+        indent: INDENT,
+        leading: Some("func env() *cdk.Environment {".into()),
+        trailing: Some("}".into()),
+        trailing_newline: true,
+    });
+
+    env_block.line("// If unspecified, this stack will be \"environment-agnostic\".");
+    env_block.line("// Account/Region-dependent features and context lookups will not work, but a");
+    env_block.line("// single synthesized template can be deployed anywhere.");
+    env_block.line("//---------------------------------------------------------------------------");
+    env_block.line("return nil");
+    env_block.newline();
+    env_block.line("// Uncomment if you know exactly what account and region you want to deploy");
+    env_block.line("// the stack to. This is the recommendation for production stacks.");
+    env_block.line("//---------------------------------------------------------------------------");
+    env_block.line("// return &cdk.Environment{");
+    env_block.line("//  Account: jsii.String(\"123456789012\"),");
+    env_block.line("//  Region:  jsii.String(\"us-east-1\"),");
+    env_block.line("// }");
+    env_block.newline();
+    env_block.line("// Uncomment to specialize this stack for the AWS Account and Region that are");
+    env_block.line("// implied by the current CLI configuration. This is recommended for dev");
+    env_block.line("// stacks.");
+    env_block.line("//---------------------------------------------------------------------------");
+    env_block.line("// return &cdk.Environment{");
+    env_block.line("//  Account: jsii.String(os.Getenv(\"CDK_DEFAULT_ACCOUNT\")),");
+    env_block.line("//  Region:  jsii.String(os.Getenv(\"CDK_DEFAULT_REGION\")),");
+    env_block.line("// }");
 }
 
 struct GoContext<'a> {
     schema: &'a Schema,
     fmt: Rc<CodeBuffer>,
+    regexp: Rc<CodeBuffer>,
+    strconv: Rc<CodeBuffer>,
     time: Rc<CodeBuffer>,
     blank: Rc<CodeBuffer>,
     ternary: Rc<CodeBuffer>,
     has_fmt: bool,
+    has_regexp: bool,
+    has_strconv: bool,
     has_time: bool,
     has_blank: bool,
     has_ternary: bool,
+    used_parameters: BTreeSet<String>,
+    // Whether the stack being emitted is a construct, in which case its resources are added to the
+    // `construct` variable rather than to the `stack` one.
+    construct: bool,
 }
 impl<'a> GoContext<'a> {
     const fn new(
         schema: &'a Schema,
         fmt: Rc<CodeBuffer>,
+        regexp: Rc<CodeBuffer>,
+        strconv: Rc<CodeBuffer>,
         time: Rc<CodeBuffer>,
         blank: Rc<CodeBuffer>,
         ternary: Rc<CodeBuffer>,
@@ -411,13 +843,35 @@ impl<'a> GoContext<'a> {
         Self {
             schema,
             fmt,
+            regexp,
+            strconv,
             time,
             blank,
             ternary,
             has_fmt: false,
+            has_regexp: false,
+            has_strconv: false,
             has_time: false,
             has_blank: false,
             has_ternary: false,
+            used_parameters: BTreeSet::new(),
+            construct: false,
+        }
+    }
+
+    // The variable holding the scope of the resources.
+    const fn scope(&self) -> &'static str {
+        match self.construct {
+            true => "construct",
+            false => "stack",
+        }
+    }
+
+    // The expression of the stack the resources are in.
+    const fn stack(&self) -> &'static str {
+        match self.construct {
+            true => "cdk.Stack_Of(construct)",
+            false => "stack",
         }
     }
 
@@ -431,6 +885,26 @@ impl<'a> GoContext<'a> {
         self.insert_blank();
     }
 
+    fn import_regexp(&mut self) {
+        if self.has_regexp {
+            return;
+        }
+        self.regexp.line("\"regexp\"");
+        self.has_regexp = true;
+
+        self.insert_blank();
+    }
+
+    fn import_strconv(&mut self) {
+        if self.has_strconv {
+            return;
+        }
+        self.strconv.line("\"strconv\"");
+        self.has_strconv = true;
+
+        self.insert_blank();
+    }
+
     fn import_time(&mut self) {
         if self.has_time {
             return;
@@ -483,6 +957,173 @@ impl<'a> GoContext<'a> {
     }
 }
 
+fn emit_parameter_validation(
+    context: &mut GoContext,
+    output: &CodeBuffer,
+    validations: &[PropValidation],
+) {
+    if validations.is_empty() {
+        return;
+    }
+
+    let props = output.indent_with_options(IndentOptions {
+        indent: INDENT,
+        leading: Some("if props != nil {".into()),
+        trailing: Some("}".into()),
+        trailing_newline: true,
+    });
+    for validation in validations {
+        let param = validation.param;
+        let name = golang_identifier(&param.name, IdentifierKind::Exported);
+        let block = props.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some(
+                format!(
+                    "if value := props.{name}; value != nil && !*cdk.Token_IsUnresolved(value) {{"
+                )
+                .into(),
+            ),
+            trailing: Some("}".into()),
+            trailing_newline: true,
+        });
+        for check in &validation.checks {
+            let message = &check.message;
+            let violated = match &check.constraint {
+                ParameterConstraint::AllowedValues(values) => {
+                    let switch = block.indent_with_options(IndentOptions {
+                        indent: "".into(),
+                        leading: Some("switch *value {".into()),
+                        trailing: Some("}".into()),
+                        trailing_newline: true,
+                    });
+                    let values = values.iter().map(|v| format!("{v:?}")).collect::<Vec<_>>();
+                    switch.line(format!("case {}:", values.join(", ")));
+                    switch.line("default:");
+                    switch.indent(INDENT).line(format!("panic({message:?})"));
+                    continue;
+                }
+                ParameterConstraint::AllowedPattern(pattern) => {
+                    context.import_regexp();
+                    let pattern = format!("^(?:{pattern})$");
+                    let pattern = if pattern.contains('`') {
+                        format!("{pattern:?}")
+                    } else {
+                        format!("`{pattern}`")
+                    };
+                    format!("!regexp.MustCompile({pattern}).MatchString(*value)")
+                }
+                ParameterConstraint::MinLength(min) => format!("len([]rune(*value)) < {min}"),
+                ParameterConstraint::MaxLength(max) => format!("len([]rune(*value)) > {max}"),
+                ParameterConstraint::MinValue(min) => {
+                    context.import_strconv();
+                    format!("number, err := strconv.ParseFloat(*value, 64); err != nil || number < {min}")
+                }
+                ParameterConstraint::MaxValue(max) => {
+                    context.import_strconv();
+                    format!("number, err := strconv.ParseFloat(*value, 64); err != nil || number > {max}")
+                }
+            };
+            block
+                .indent_with_options(IndentOptions {
+                    indent: INDENT,
+                    leading: Some(format!("if {violated} {{").into()),
+                    trailing: Some("}".into()),
+                    trailing_newline: true,
+                })
+                .line(format!("panic({message:?})"));
+        }
+    }
+    output.newline();
+}
+
+// Parameters that CloudFormation validates or resolves are declared as CfnParameters (or looked up
+// from SSM), using the prop value as their default. Only the ones that are referenced are kept in a
+// variable.
+fn emit_parameters(context: &GoContext, output: &CodeBuffer, inputs: &[ConstructorParameter]) {
+    for param in inputs.iter().filter(|p| p.is_cfn_parameter()) {
+        let var_name = golang_identifier(&param.name, IdentifierKind::Unexported);
+        let prop_name = golang_identifier(&param.name, IdentifierKind::Exported);
+        let prefix = match context.used_parameters.contains(&var_name) {
+            true => format!("{var_name} := "),
+            false => "".into(),
+        };
+        if param.ssm_lookup {
+            output.line(format!(
+                "{prefix}ssm.StringParameter_ValueForStringParameter({}, props.{prop_name}, nil)",
+                context.scope()
+            ));
+            output.newline();
+            continue;
+        }
+
+        let value_as = match param.constructor_type.is_list() {
+            true => "ValueAsList",
+            false => "ValueAsString",
+        };
+        let props = output.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some(
+                format!(
+                    "{prefix}cdk.NewCfnParameter({}, jsii.String({:?}), &cdk.CfnParameterProps{{",
+                    context.scope(),
+                    param.logical_id
+                )
+                .into(),
+            ),
+            trailing: Some(match prefix.is_empty() {
+                true => "})".into(),
+                false => format!("}}).{value_as}()").into(),
+            }),
+            trailing_newline: true,
+        });
+        props.line(format!(
+            "Type: jsii.String({:?}),",
+            param.constructor_type.to_string()
+        ));
+        props.line(format!("Default: props.{prop_name},"));
+        if let Some(description) = &param.description {
+            props.line(format!("Description: jsii.String({description:?}),"));
+        }
+        if let Some(values) = &param.allowed_values {
+            let values = values
+                .iter()
+                .map(|v| format!("jsii.String({v:?})"))
+                .collect::<Vec<_>>();
+            props.line(format!(
+                "AllowedValues: &[]*string{{{}}},",
+                values.join(", ")
+            ));
+        }
+        if let Some(pattern) = &param.allowed_pattern {
+            props.line(format!("AllowedPattern: jsii.String({pattern:?}),"));
+        }
+        if let Some(description) = &param.constraint_description {
+            props.line(format!(
+                "ConstraintDescription: jsii.String({description:?}),"
+            ));
+        }
+        if let Some(v) = param.min_length {
+            props.line(format!("MinLength: jsii.Number({v}),"));
+        }
+        if let Some(v) = param.max_length {
+            props.line(format!("MaxLength: jsii.Number({v}),"));
+        }
+        if let Some(v) = param.min_value {
+            props.line(format!("MinValue: jsii.Number({v}),"));
+        }
+        if let Some(v) = param.max_value {
+            props.line(format!("MaxValue: jsii.Number({v}),"));
+        }
+        if let Some(no_echo) = &param.no_echo {
+            props.line(format!(
+                "NoEcho: jsii.Bool({}),",
+                no_echo.eq_ignore_ascii_case("true")
+            ));
+        }
+        output.newline();
+    }
+}
+
 trait Inspectable {
     /// Whether the rendered code for this entity uses the named mapping table.
     fn uses_map_table(&self, name: &str) -> bool;
@@ -494,6 +1135,12 @@ impl Inspectable for CloudformationProgramIr {
             .iter()
             .any(|cond| cond.value.uses_map_table(name))
             || self.resources.iter().any(|res| res.uses_map_table(name))
+            || self.metadata.as_ref().is_some_and(|metadata| {
+                metadata
+                    .entries
+                    .values()
+                    .any(|val| val.uses_map_table(name))
+            })
             || self
                 .outputs
                 .iter()
@@ -530,6 +1177,27 @@ impl Inspectable for ResourceInstruction {
                 .as_ref()
                 .map(|val| val.uses_map_table(name))
                 .unwrap_or(false)
+            || self
+                .creation_policy
+                .as_ref()
+                .map(|val| val.uses_map_table(name))
+                .unwrap_or(false)
+    }
+}
+
+impl Inspectable for CreationPolicyIr {
+    fn uses_map_table(&self, name: &str) -> bool {
+        let asg = self
+            .auto_scaling_creation_policy
+            .iter()
+            .flat_map(|asg| &asg.min_successful_instances_percent);
+        let signal = self
+            .resource_signal
+            .iter()
+            .flat_map(|signal| signal.count.iter().chain(&signal.timeout));
+        asg.chain(signal)
+            .chain(&self.start_fleet)
+            .any(|val| val.uses_map_table(name))
     }
 }
 
@@ -555,6 +1223,7 @@ impl Inspectable for ResourceIr {
             Self::Select(_, list) => list.uses_map_table(name),
             Self::Split(_, text) => text.uses_map_table(name),
             Self::Base64(value) => value.uses_map_table(name),
+            Self::Length(value) | Self::ToJsonString(value) => value.uses_map_table(name),
             Self::Null
             | Self::Bool(_)
             | Self::String(_)
@@ -609,16 +1278,45 @@ impl ConstructorParameter {
         format!(
             "{name} {type}",
             name = golang_identifier(&self.name, IdentifierKind::Exported),
-            r#type = match self.constructor_type.as_ref() {
-                "String" => "*string".into(),
+            r#type = match &self.constructor_type {
+                // CfnParameter values are passed the way CloudFormation expects them, so lists
+                // are comma-delimited.
+                _ if self.is_cfn_parameter() => "*string".into(),
+                // Number props are strings, as in the other languages.
+                ConstructorType::String | ConstructorType::Number => "*string".into(),
                 other => format!("interface{{/* {other} */}}"),
             }
         )
     }
 }
 
+impl DefaultValue {
+    fn to_golang(&self) -> String {
+        match self {
+            DefaultValue::String(value) => format!("jsii.String({value:?})"),
+            // Number props are strings, but List<Number> props are number slices
+            DefaultValue::Number(value) => format!("jsii.String(\"{value}\")"),
+            DefaultValue::Bool(value) => format!("jsii.Bool({value})"),
+            DefaultValue::List(items) => {
+                let item_type = match items.first() {
+                    Some(DefaultValue::Number(_)) => "*float64",
+                    _ => "*string",
+                };
+                let items = items
+                    .iter()
+                    .map(|item| match item {
+                        DefaultValue::Number(value) => format!("jsii.Number({value})"),
+                        other => other.to_golang(),
+                    })
+                    .collect::<Vec<_>>();
+                format!("&[]{item_type}{{{}}}", items.join(", "))
+            }
+        }
+    }
+}
+
 trait AsGolang {
-    fn as_golang(&self, schema: &Schema) -> Cow<'static, str>;
+    fn as_golang(&self, schema: &Schema) -> Result<Cow<'static, str>, Error>;
 }
 
 trait GolangEmitter {
@@ -696,6 +1394,166 @@ impl GolangEmitter for ConditionIr {
     }
 }
 
+impl GolangEmitter for CreationPolicyIr {
+    fn emit_golang(
+        &self,
+        context: &mut GoContext,
+        output: &CodeBuffer,
+        trailer: Option<&str>,
+    ) -> Result<(), Error> {
+        if let Some(asg) = &self.auto_scaling_creation_policy {
+            let asg_props = output.indent_with_options(IndentOptions {
+                indent: INDENT,
+                leading: Some(
+                    "AutoScalingCreationPolicy: &cdk.CfnResourceAutoScalingCreationPolicy{".into(),
+                ),
+                trailing: Some("},".into()),
+                trailing_newline: true,
+            });
+            if let Some(percent) = &asg.min_successful_instances_percent {
+                asg_props.text("MinSuccessfulInstancesPercent: ");
+                percent.emit_golang(context, &asg_props, None)?;
+                asg_props.line(",");
+            }
+        }
+        if let Some(signal) = &self.resource_signal {
+            let signal_props = output.indent_with_options(IndentOptions {
+                indent: INDENT,
+                leading: Some("ResourceSignal: &cdk.CfnResourceSignal{".into()),
+                trailing: Some("},".into()),
+                trailing_newline: true,
+            });
+            if let Some(count) = &signal.count {
+                signal_props.text("Count: ");
+                count.emit_golang(context, &signal_props, None)?;
+                signal_props.line(",");
+            }
+            if let Some(timeout) = &signal.timeout {
+                signal_props.text("Timeout: ");
+                timeout.emit_golang(context, &signal_props, None)?;
+                signal_props.line(",");
+            }
+        }
+        if let Some(start_fleet) = &self.start_fleet {
+            output.text("StartFleet: ");
+            start_fleet.emit_golang(context, output, None)?;
+            output.line(",");
+        }
+        if let Some(trailer) = trailer {
+            output.text(trailer.to_owned());
+        }
+        Ok(())
+    }
+}
+
+impl GolangEmitter for RuleIr {
+    fn emit_golang(
+        &self,
+        context: &mut GoContext,
+        output: &CodeBuffer,
+        trailer: Option<&str>,
+    ) -> Result<(), Error> {
+        match self {
+            Self::And(list) | Self::Or(list) => {
+                output.text(match self {
+                    Self::And(_) => "cdk.Fn_ConditionAnd(",
+                    _ => "cdk.Fn_ConditionOr(",
+                });
+                for (idx, cond) in list.iter().enumerate() {
+                    if idx > 0 {
+                        output.text(", ");
+                    }
+                    cond.emit_golang(context, output, None)?;
+                }
+                output.text(")");
+            }
+            Self::Not(cond) => {
+                output.text("cdk.Fn_ConditionNot(");
+                cond.emit_golang(context, output, None)?;
+                output.text(")");
+            }
+            Self::Equals(lhs, rhs) => {
+                output.text("cdk.Fn_ConditionEquals(");
+                lhs.emit_golang(context, output, false)?;
+                output.text(", ");
+                rhs.emit_golang(context, output, false)?;
+                output.text(")");
+            }
+            Self::Contains(list, value) => {
+                output.text("cdk.Fn_ConditionContains(");
+                list.emit_golang(context, output, true)?;
+                output.text(", ");
+                value.emit_golang(context, output, false)?;
+                output.text(")");
+            }
+            Self::EachMemberEquals(list, value) => {
+                output.text("cdk.Fn_ConditionEachMemberEquals(");
+                list.emit_golang(context, output, true)?;
+                output.text(", ");
+                value.emit_golang(context, output, false)?;
+                output.text(")");
+            }
+            Self::EachMemberIn(to_check, to_match) => {
+                output.text("cdk.Fn_ConditionEachMemberIn(");
+                to_check.emit_golang(context, output, true)?;
+                output.text(", ");
+                to_match.emit_golang(context, output, true)?;
+                output.text(")");
+            }
+        }
+        if let Some(trailer) = trailer {
+            output.text(trailer.to_owned());
+        }
+        Ok(())
+    }
+}
+
+impl RuleValueIr {
+    /// `as_list` is set where the CDK expects a `*[]*string`. List parameters are typed as
+    /// `interface{}` in the stack props, and `Fn_ValueOf` returns a `*string`, so both need to be
+    /// converted.
+    fn emit_golang(
+        &self,
+        context: &mut GoContext,
+        output: &CodeBuffer,
+        as_list: bool,
+    ) -> Result<(), Error> {
+        match self {
+            Self::Str(str) => output.text(format!("jsii.String({str:?})")),
+            Self::List(list) => {
+                output.text("&[]*string{");
+                for (idx, value) in list.iter().enumerate() {
+                    if idx > 0 {
+                        output.text(", ");
+                    }
+                    value.emit_golang(context, output, false)?;
+                }
+                output.text("}");
+            }
+            Self::Ref(reference) => match reference.origin {
+                Origin::Parameter if as_list => output.text(format!(
+                    "props.{name}.(*[]*string)",
+                    name = golang_identifier(&reference.name, IdentifierKind::Exported)
+                )),
+                _ => reference.emit_golang(context, output, None)?,
+            },
+            Self::RefAll(parameter_type) => {
+                output.text(format!("cdk.Fn_RefAll(jsii.String({parameter_type:?}))"))
+            }
+            Self::ValueOf(name, attribute) if as_list => output.text(format!(
+                "cdk.Token_AsList(cdk.Fn_ValueOf(jsii.String({name:?}), jsii.String({attribute:?})), nil)"
+            )),
+            Self::ValueOf(name, attribute) => output.text(format!(
+                "cdk.Fn_ValueOf(jsii.String({name:?}), jsii.String({attribute:?}))"
+            )),
+            Self::ValueOfAll(parameter_type, attribute) => output.text(format!(
+                "cdk.Fn_ValueOfAll(jsii.String({parameter_type:?}), jsii.String({attribute:?}))"
+            )),
+        }
+        Ok(())
+    }
+}
+
 impl GolangEmitter for ResourceIr {
     fn emit_golang(
         &self,
@@ -732,10 +1590,10 @@ impl GolangEmitter for ResourceIr {
                         Primitive::Unknown => "cdk.IResolvable".into(),
                     },
                     TypeReference::List(item_type) => {
-                        format!("[]{}", item_type.as_golang(context.schema)).into()
+                        format!("[]{}", item_type.as_golang(context.schema)?).into()
                     }
                     TypeReference::Map(item_type) => {
-                        format!("map[string]{}", item_type.as_golang(context.schema)).into()
+                        format!("map[string]{}", item_type.as_golang(context.schema)?).into()
                     }
                     TypeReference::Union(item_type) => "interface{}".into(),
                 };
@@ -761,11 +1619,8 @@ impl GolangEmitter for ResourceIr {
                         | TypeReference::List(ItemType::Static(TypeReference::Named(name))) => {
                             match name.as_ref() {
                                 "CfnTag" => "&cdk.CfnTag{".into(),
-                                name => {
-                                    let name =
-                                        &context.schema.type_named(name).unwrap().name.golang.name;
-                                    format!("&{}{{", name.split('_').next_back().unwrap()).into()
-                                }
+                                name => format!("&{}{{", golang_type_name(context.schema, name)?)
+                                    .into(),
                             }
                         }
                         TypeReference::Primitive(cfn) => match cfn {
@@ -782,29 +1637,28 @@ impl GolangEmitter for ResourceIr {
                             }
                         },
                         TypeReference::List(item_type) => {
-                            format!("[]{}", item_type.as_golang(context.schema)).into()
+                            format!("[]{}", item_type.as_golang(context.schema)?).into()
                         }
                         TypeReference::Map(_) => {
                             structure_is_map = true;
                             "map[string]interface{} {".into()
                         }
-                        other => unimplemented!("{other:?}"),
-                        // TypeReference::Map(item_type) => {
-                        //     format!("map[string]{}", item_type.as_golang(context.schema)).into()
-                        // }
-                        // TypeReference::Union(item_type) => "interface{}".into(),
+                        other @ TypeReference::Union(_) => {
+                            return Err(Error::TypeReferenceError {
+                                message: format!(
+                                    "Type reference {other:#?} not implemented for ResourceIr::Object"
+                                ),
+                            })
+                        }
                     }),
                     trailing: Some("}".into()),
                     trailing_newline: false,
                 });
                 for (name, val) in properties {
-                    if structure_is_simple_json {
-                        props.text(format!(
-                            "\"{name}\": ",
-                            name = golang_identifier(name, IdentifierKind::Exported)
-                        ));
-                    } else if structure_is_map {
-                        props.text(format!("\"{name}\": "));
+                    // The keys of JSON values and maps are kept as they are in the template
+                    // (e.g: `aws:SourceAccount`), and quoted as Go strings.
+                    if structure_is_simple_json || structure_is_map {
+                        props.text(format!("{}: ", golang_string(name)));
                     } else {
                         props.text(format!(
                             "{name}: ",
@@ -913,7 +1767,7 @@ impl GolangEmitter for ResourceIr {
                         ResourceIr::Bool(val) => val.to_string(),
                         ResourceIr::Double(val) => val.to_string(),
                         ResourceIr::Number(val) => val.to_string(),
-                        ResourceIr::String(val) => val.clone(),
+                        ResourceIr::String(val) => val.replace('%', "%%"),
                         _ => "%v".into(),
                     })
                     .collect::<String>();
@@ -934,6 +1788,25 @@ impl GolangEmitter for ResourceIr {
                 output.text("))");
             }
 
+            Self::Length(list) => match list.as_ref() {
+                ResourceIr::Array(_, items) => {
+                    output.text(format!("jsii.Number({})", items.len()));
+                }
+                list => {
+                    output.text("cdk.Fn_Len(");
+                    list.emit_golang(context, output, None)?;
+                    output.text(")");
+                }
+            },
+            Self::ToJsonString(value) => match value.to_json() {
+                Some(json) => output.text(format!("jsii.String({:?})", json.to_string())),
+                None => {
+                    output.text("cdk.Fn_ToJsonString(");
+                    value.emit_golang(context, output, None)?;
+                    output.text(")");
+                }
+            },
+
             // References
             Self::Ref(reference) => reference.emit_golang(context, output, None)?,
         }
@@ -946,25 +1819,21 @@ impl GolangEmitter for ResourceIr {
 }
 
 impl AsGolang for TypeReference {
-    fn as_golang(&self, schema: &Schema) -> Cow<'static, str> {
-        match self {
+    fn as_golang(&self, schema: &Schema) -> Result<Cow<'static, str>, Error> {
+        Ok(match self {
             Self::Named(name) if name == "CfnTag" => "*cdk.CfnTag".into(),
-            Self::Named(name) => {
-                let spec = schema.type_named(name).unwrap();
-                let name = &spec.name.golang;
-                format!("*{}.{}", name.package, name.name).into()
-            }
-            Self::Primitive(primitive) => primitive.as_golang(schema),
-            Self::List(items) => format!("*[]{}", items.as_golang(schema)).into(),
-            Self::Map(items) => format!("*map[string]{}", items.as_golang(schema)).into(),
+            Self::Named(name) => format!("*{}", golang_type_name(schema, name)?).into(),
+            Self::Primitive(primitive) => primitive.as_golang(schema)?,
+            Self::List(items) => format!("*[]{}", items.as_golang(schema)?).into(),
+            Self::Map(items) => format!("*map[string]{}", items.as_golang(schema)?).into(),
             Self::Union(_) => "interface{}{".into(),
-        }
+        })
     }
 }
 
 impl AsGolang for Primitive {
-    fn as_golang(&self, _schema: &Schema) -> Cow<'static, str> {
-        match self {
+    fn as_golang(&self, _schema: &Schema) -> Result<Cow<'static, str>, Error> {
+        Ok(match self {
             Self::Boolean => "*bool",
             Self::Number => "*float64",
             Self::String => "*string",
@@ -972,10 +1841,141 @@ impl AsGolang for Primitive {
             Self::Json => "interface{}{",
             Self::Unknown => "cdk.IResolvable",
         }
-        .into()
+        .into())
     }
 }
 
+/// The qualified Go name of a named property type (e.g: `sam.CfnFunction_S3LocationProperty`),
+/// using the same import alias as the resource constructors.
+fn golang_type_name(schema: &Schema, name: &str) -> Result<String, Error> {
+    let spec = schema
+        .type_named(name)
+        .ok_or_else(|| Error::TypeReferenceError {
+            message: format!("{name} is not a known property type"),
+        })?;
+    let name = &spec.name.golang;
+    let alias = match name.package.as_ref() {
+        "awscdk" => "cdk",
+        package => package
+            .strip_prefix("aws")
+            .or_else(|| package.strip_prefix("alexa"))
+            .unwrap_or(package),
+    };
+    Ok(format!("{alias}.{}", name.name))
+}
+
+// Go needs every argument, so left out options are passed as nil.
+impl GolangEmitter for L2Value {
+    fn emit_golang(
+        &self,
+        context: &mut GoContext,
+        output: &CodeBuffer,
+        trailer: Option<&str>,
+    ) -> Result<(), Error> {
+        let module = |service: &str| golang_identifier(service, IdentifierKind::ModuleName);
+        match self {
+            // L2 props type maps of strings, unlike the properties of L1 resources.
+            L2Value::Value(ResourceIr::Object(TypeReference::Map(item_type), entries))
+                if **item_type == TypeReference::Primitive(Primitive::String) =>
+            {
+                let map = output.indent_with_options(IndentOptions {
+                    indent: INDENT,
+                    leading: Some("&map[string]*string{".into()),
+                    trailing: Some("}".into()),
+                    trailing_newline: false,
+                });
+                for (name, value) in entries {
+                    map.text(format!("{name:?}: "));
+                    value.emit_golang(context, &map, Some(","))?;
+                }
+            }
+            L2Value::Value(value) => value.emit_golang(context, output, None)?,
+            L2Value::Seconds(value) => {
+                output.text("cdk.Duration_Seconds(");
+                value.emit_golang(context, output, None)?;
+                output.text(")");
+            }
+            L2Value::Enum {
+                service,
+                name,
+                member,
+            } => output.text(format!("{}.{name}_{member}", module(service))),
+            L2Value::Struct {
+                service,
+                name,
+                fields,
+            } => {
+                let props = output.indent_with_options(IndentOptions {
+                    indent: INDENT,
+                    leading: Some(format!("&{}.{name}{{", module(service)).into()),
+                    trailing: Some("}".into()),
+                    trailing_newline: false,
+                });
+                for (name, value) in fields {
+                    props.text(format!(
+                        "{}: ",
+                        golang_identifier(name, IdentifierKind::Exported)
+                    ));
+                    value.emit_golang(context, &props, None)?;
+                    props.line(",");
+                }
+            }
+            L2Value::New {
+                service,
+                class,
+                args,
+                options,
+            } => {
+                output.text(format!("{}.New{class}", module(service)));
+                emit_l2_args(context, output, args, *options)?;
+            }
+            L2Value::Call {
+                service,
+                class,
+                method,
+                args,
+                options,
+            } => {
+                output.text(format!(
+                    "{}.{class}_{}",
+                    module(service),
+                    golang_identifier(method, IdentifierKind::Exported)
+                ));
+                emit_l2_args(context, output, args, *options)?;
+            }
+            L2Value::Scope => output.text(context.scope()),
+            L2Value::Construct(name) => {
+                output.text(golang_identifier(name, IdentifierKind::Unexported))
+            }
+        }
+
+        if let Some(trailer) = trailer {
+            output.text(trailer.to_owned());
+        }
+        Ok(())
+    }
+}
+
+fn emit_l2_args(
+    context: &mut GoContext,
+    output: &CodeBuffer,
+    args: &[L2Value],
+    options: bool,
+) -> Result<(), Error> {
+    output.text("(");
+    for (index, arg) in args.iter().enumerate() {
+        if index > 0 {
+            output.text(", ");
+        }
+        arg.emit_golang(context, output, None)?;
+    }
+    if options {
+        output.text(", nil");
+    }
+    output.text(")");
+    Ok(())
+}
+
 impl GolangEmitter for Reference {
     fn emit_golang(
         &self,
@@ -995,11 +1995,29 @@ impl GolangEmitter for Reference {
                 name = golang_identifier(&self.name, IdentifierKind::Unexported),
                 attribute = golang_identifier(attribute, IdentifierKind::Exported),
             )),
+            Origin::NestedStackOutput {
+                output: name,
+                conditional,
+            } => output.text(format!(
+                "{var_name}.{name}.(*string)",
+                var_name = golang_identifier(&self.name, IdentifierKind::Unexported),
+                name = golang_identifier(name, IdentifierKind::Exported),
+            )),
+            Origin::ConstructAttribute { attribute } => output.text(format!(
+                "{name}.{attribute}()",
+                name = golang_identifier(&self.name, IdentifierKind::Unexported),
+                attribute = golang_identifier(attribute, IdentifierKind::Exported),
+            )),
             Origin::LogicalId { conditional } => output.text(format!(
                 "{name}.Ref()",
                 name = golang_identifier(&self.name, IdentifierKind::Unexported)
             )),
-            Origin::CfnParameter | Origin::Parameter => output.text(format!(
+            Origin::CfnParameter => {
+                let name = golang_identifier(&self.name, IdentifierKind::Unexported);
+                output.text(name.clone());
+                context.used_parameters.insert(name);
+            }
+            Origin::Parameter => output.text(format!(
                 "props.{name}",
                 name = golang_identifier(&self.name, IdentifierKind::Exported)
             )),
@@ -1013,7 +2031,7 @@ impl GolangEmitter for Reference {
                     PseudoParameter::URLSuffix => "UrlSuffix",
                     PseudoParameter::NotificationArns => "NotificationArns",
                 };
-                output.text(format!("stack.{pseudo}()"));
+                output.text(format!("{}.{pseudo}()", context.stack()));
             }
         }
 
@@ -1025,7 +2043,7 @@ impl GolangEmitter for Reference {
 }
 
 #[derive(Clone, Copy)]
-enum IdentifierKind {
+pub(super) enum IdentifierKind {
     /// The identifier is exported. It'll be named using PascalCase.
     Exported,
     /// The identifier is unexported. It'll be named using camelCase.
@@ -1034,9 +2052,32 @@ enum IdentifierKind {
     ModuleName,
 }
 
+// Quotes a string as a Go literal. Control characters are written as `\xNN` or `\uNNNN`, where
+// `escape_debug` would write `\u{..}`, which Go does not understand.
+fn golang_string(value: &str) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '"' => literal.push_str("\\\""),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c if c.is_ascii_control() => literal.push_str(&format!("\\x{:02x}", u32::from(c))),
+            c if c.is_control() || c == '\u{feff}' => {
+                literal.push_str(&format!("\\u{:04x}", u32::from(c)))
+            }
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
 /// Computes a go identifier name that is a suitable representation of the given
 /// name.
-fn golang_identifier(text: &str, kind: IdentifierKind) -> String {
+pub(super) fn golang_identifier(text: &str, kind: IdentifierKind) -> String {
     let text_string = text.replace('.', "");
     match kind {
         IdentifierKind::Exported => pascal_case(&text_string),
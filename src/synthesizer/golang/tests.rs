@@ -9,6 +9,7 @@ use std::borrow::Cow;
 use crate::cdk::Schema;
 use crate::code::CodeBuffer;
 use crate::ir::conditions::ConditionIr;
+use crate::ir::constructor::Constructor;
 use crate::ir::importer::ImportInstruction;
 use crate::primitives::WrapperF64;
 
@@ -54,6 +55,8 @@ fn test_condition_ir_map() {
         output.section(false),
         output.section(false),
         output.section(false),
+        output.section(false),
+        output.section(false),
     );
     let result = condition_ir.emit_golang(context, &output, Some(","));
     assert_eq!((), result.unwrap());
@@ -70,6 +73,8 @@ fn test_resource_ir_double() {
         output.section(false),
         output.section(false),
         output.section(false),
+        output.section(false),
+        output.section(false),
     );
     let result = resource_ir.emit_golang(context, &output, Some(","));
     assert_eq!((), result.unwrap());
@@ -89,6 +94,8 @@ fn test_resource_ir_object_primitive_error() {
         output.section(false),
         output.section(false),
         output.section(false),
+        output.section(false),
+        output.section(false),
     );
     let result = resource_ir
         .emit_golang(context, &output, Option::None)
@@ -99,6 +106,32 @@ fn test_resource_ir_object_primitive_error() {
     );
 }
 
+#[test]
+fn test_resource_ir_object_unknown_type_error() {
+    let output = CodeBuffer::default();
+    let schema = Cow::Borrowed(Schema::builtin());
+    let resource_ir = ResourceIr::Object(
+        TypeReference::Named("AWS::Nope::Thing.Property".into()),
+        IndexMap::new(),
+    );
+    let context = &mut GoContext::new(
+        &schema,
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+    );
+    let result = resource_ir
+        .emit_golang(context, &output, Option::None)
+        .unwrap_err();
+    assert_eq!(
+        "AWS::Nope::Thing.Property is not a known property type",
+        result.to_string(),
+    );
+}
+
 #[test]
 fn test_resource_ir_object_list_structure() {
     let output = CodeBuffer::default();
@@ -115,11 +148,60 @@ fn test_resource_ir_object_list_structure() {
         output.section(false),
         output.section(false),
         output.section(false),
+        output.section(false),
+        output.section(false),
     );
     let result = resource_ir.emit_golang(context, &output, Option::None);
     assert_eq!((), result.unwrap());
 }
 
+#[test]
+fn test_resource_ir_json_keys_are_kept() {
+    let output = CodeBuffer::default();
+    let schema = Cow::Borrowed(Schema::builtin());
+    let context = &mut GoContext::new(
+        &schema,
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+    );
+    ResourceIr::Object(
+        TypeReference::Primitive(Primitive::Json),
+        IndexMap::from_iter([
+            (
+                "aws:SourceAccount".to_string(),
+                ResourceIr::String("123456789012".into()),
+            ),
+            ("say \"hi\"".to_string(), ResourceIr::Bool(true)),
+        ]),
+    )
+    .emit_golang(context, &output, None)
+    .unwrap();
+
+    let mut result = Vec::new();
+    output.write(&mut result).unwrap();
+    assert_eq!(
+        "map[string]interface{} {\n\t\"aws:SourceAccount\": jsii.String(\"123456789012\"),\n\t\"say \\\"hi\\\"\": jsii.Bool(true),\n}",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_golang_string() {
+    assert_eq!(golang_string("plain"), "\"plain\"");
+    assert_eq!(
+        golang_string("it's \"quoted\"\n\\d+"),
+        r#""it's \"quoted\"\n\\d+""#
+    );
+    assert_eq!(
+        golang_string("a\u{7}b\u{85}é\u{feff}"),
+        "\"a\\x07b\\u0085é\\ufeff\""
+    );
+}
+
 #[test]
 fn test_resource_ir_cidr_null_mask() {
     let output = CodeBuffer::default();
@@ -135,6 +217,8 @@ fn test_resource_ir_cidr_null_mask() {
         output.section(false),
         output.section(false),
         output.section(false),
+        output.section(false),
+        output.section(false),
     );
     let result = resource_ir.emit_golang(context, &output, Option::None);
     assert_eq!((), result.unwrap());
@@ -155,6 +239,8 @@ fn test_resource_ir_cidr_string_mask() {
         output.section(false),
         output.section(false),
         output.section(false),
+        output.section(false),
+        output.section(false),
     );
     let result = resource_ir.emit_golang(context, &output, Option::None);
     assert_eq!((), result.unwrap());
@@ -170,6 +256,8 @@ fn test_reference_with_trailer() {
         output.section(false),
         output.section(false),
         output.section(false),
+        output.section(false),
+        output.section(false),
     );
     let reference = Reference {
         origin: Origin::Condition {},
@@ -183,7 +271,7 @@ fn test_reference_with_trailer() {
 fn test_boolean_primitive() {
     let schema = Cow::Borrowed(Schema::builtin());
     let primitive = Primitive::Boolean;
-    let result = primitive.as_golang(&schema);
+    let result = primitive.as_golang(&schema).unwrap();
     assert_eq!(Cow::from("*bool"), result);
 }
 
@@ -191,7 +279,7 @@ fn test_boolean_primitive() {
 fn test_string_primitive() {
     let schema = Cow::Borrowed(Schema::builtin());
     let primitive = Primitive::String;
-    let result = primitive.as_golang(&schema);
+    let result = primitive.as_golang(&schema).unwrap();
     assert_eq!(Cow::from("*string"), result);
 }
 
@@ -199,7 +287,7 @@ fn test_string_primitive() {
 fn test_timestamp_primitive() {
     let schema = Cow::Borrowed(Schema::builtin());
     let primitive = Primitive::Timestamp;
-    let result = primitive.as_golang(&schema);
+    let result = primitive.as_golang(&schema).unwrap();
     assert_eq!(Cow::from("*time.Time"), result);
 }
 
@@ -207,7 +295,7 @@ fn test_timestamp_primitive() {
 fn test_json_primitive() {
     let schema = Cow::Borrowed(Schema::builtin());
     let primitive = Primitive::Json;
-    let result = primitive.as_golang(&schema);
+    let result = primitive.as_golang(&schema).unwrap();
     assert_eq!(Cow::from("interface{}{"), result);
 }
 
@@ -215,6 +303,331 @@ fn test_json_primitive() {
 fn test_unknown_primitive() {
     let schema = Cow::Borrowed(Schema::builtin());
     let primitive = Primitive::Unknown;
-    let result = primitive.as_golang(&schema);
+    let result = primitive.as_golang(&schema).unwrap();
     assert_eq!(Cow::from("cdk.IResolvable"), result);
 }
+
+#[test]
+fn test_resource_ir_length() {
+    let output = CodeBuffer::default();
+    let schema = Cow::Borrowed(Schema::builtin());
+    let context = &mut GoContext::new(
+        &schema,
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+    );
+    ResourceIr::Length(Box::new(ResourceIr::Array(
+        TypeReference::Primitive(Primitive::String),
+        vec![
+            ResourceIr::String("a".into()),
+            ResourceIr::String("b".into()),
+        ],
+    )))
+    .emit_golang(context, &output, Some(","))
+    .unwrap();
+    ResourceIr::Length(Box::new(ResourceIr::Ref(Reference::new(
+        "List",
+        Origin::Parameter,
+    ))))
+    .emit_golang(context, &output, None)
+    .unwrap();
+
+    let mut result = Vec::new();
+    output.write(&mut result).unwrap();
+    assert_eq!(
+        "jsii.Number(2),\ncdk.Fn_Len(props.List)",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_resource_ir_to_json_string() {
+    let output = CodeBuffer::default();
+    let schema = Cow::Borrowed(Schema::builtin());
+    let context = &mut GoContext::new(
+        &schema,
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+    );
+    ResourceIr::ToJsonString(Box::new(ResourceIr::Object(
+        TypeReference::Primitive(Primitive::Json),
+        IndexMap::from_iter([("Key".to_string(), ResourceIr::String("Value".into()))]),
+    )))
+    .emit_golang(context, &output, None)
+    .unwrap();
+
+    let mut result = Vec::new();
+    output.write(&mut result).unwrap();
+    assert_eq!(
+        "jsii.String(\"{\\\"Key\\\":\\\"Value\\\"}\")",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_rule_each_member_equals() {
+    use crate::ir::rules::{RuleIr, RuleValueIr};
+
+    let output = CodeBuffer::default();
+    let schema = Cow::Borrowed(Schema::builtin());
+    let context = &mut GoContext::new(
+        &schema,
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+    );
+    RuleIr::Not(Box::new(RuleIr::EachMemberEquals(
+        RuleValueIr::Ref(Reference::new("Subnets", Origin::Parameter)),
+        RuleValueIr::Str("vpc-123".into()),
+    )))
+    .emit_golang(context, &output, None)
+    .unwrap();
+
+    let mut result = Vec::new();
+    output.write(&mut result).unwrap();
+    assert_eq!(
+        "cdk.Fn_ConditionNot(cdk.Fn_ConditionEachMemberEquals(props.Subnets.(*[]*string), jsii.String(\"vpc-123\")))",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_creation_policy() {
+    use crate::ir::resources::{CreationPolicyIr, ResourceSignalIr};
+
+    let output = CodeBuffer::default();
+    let schema = Cow::Borrowed(Schema::builtin());
+    let context = &mut GoContext::new(
+        &schema,
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+    );
+    CreationPolicyIr {
+        auto_scaling_creation_policy: None,
+        resource_signal: Some(ResourceSignalIr {
+            count: Some(ResourceIr::Number(3)),
+            timeout: Some(ResourceIr::String("PT15M".into())),
+        }),
+        start_fleet: None,
+    }
+    .emit_golang(context, &output, None)
+    .unwrap();
+
+    let mut result = Vec::new();
+    output.write(&mut result).unwrap();
+    assert_eq!(
+        "ResourceSignal: &cdk.CfnResourceSignal{\n\tCount: jsii.Number(3),\n\tTimeout: jsii.String(\"PT15M\"),\n},\n",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_parameter_validation() {
+    let output = CodeBuffer::default();
+    let schema = Cow::Borrowed(Schema::builtin());
+    let context = &mut GoContext::new(
+        &schema,
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+        output.section(false),
+    );
+    let constructor = Constructor {
+        inputs: vec![ConstructorParameter {
+            name: "port".into(),
+            constructor_type: ConstructorType::Number,
+            allowed_values: Some(vec!["80".into(), "443".into()]),
+            max_value: Some(65535.0),
+            ..Default::default()
+        }],
+    };
+    emit_parameter_validation(context, &output, &constructor.validations());
+
+    let mut result = Vec::new();
+    output.write(&mut result).unwrap();
+    assert_eq!(
+        "\"strconv\"
+
+if props != nil {
+\tif value := props.Port; value != nil && !*cdk.Token_IsUnresolved(value) {
+\t\tswitch *value {
+\t\tcase \"80\", \"443\":
+\t\tdefault:
+\t\t\tpanic(\"port must be one of: 80, 443\")
+\t\t}
+\t\tif number, err := strconv.ParseFloat(*value, 64); err != nil || number > 65535 {
+\t\t\tpanic(\"port must be at most 65535\")
+\t\t}
+\t}
+}
+
+",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_default_value_literals() {
+    assert_eq!(
+        DefaultValue::String("say \"hi\"".into()).to_golang(),
+        "jsii.String(\"say \\\"hi\\\"\")"
+    );
+    assert_eq!(
+        DefaultValue::Number(0.5).to_golang(),
+        "jsii.String(\"0.5\")"
+    );
+    assert_eq!(DefaultValue::Bool(false).to_golang(), "jsii.Bool(false)");
+    assert_eq!(
+        DefaultValue::List(vec![
+            DefaultValue::String("a".into()),
+            DefaultValue::String("b".into()),
+        ])
+        .to_golang(),
+        "&[]*string{jsii.String(\"a\"), jsii.String(\"b\")}"
+    );
+    assert_eq!(
+        DefaultValue::List(vec![DefaultValue::Number(80.0)]).to_golang(),
+        "&[]*float64{jsii.Number(80)}"
+    );
+}
+
+#[test]
+fn test_l2_constructs() {
+    let template: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Resources:
+          Role:
+            Type: AWS::IAM::Role
+            Properties:
+              AssumeRolePolicyDocument:
+                Statement:
+                  - Effect: Allow
+                    Principal:
+                      Service: lambda.amazonaws.com
+                    Action: sts:AssumeRole
+          Function:
+            Type: AWS::Lambda::Function
+            Properties:
+              Runtime: nodejs20.x
+              Handler: index.handler
+              Role: !GetAtt Role.Arn
+              Code:
+                ZipFile: exports.handler = async () => {};
+              Environment:
+                Variables:
+                  ROLE: !Ref Role
+              Architectures: [arm64]
+        "#,
+    )
+    .unwrap();
+    let mut ir = CloudformationProgramIr::from(template, Schema::builtin()).unwrap();
+    ir.use_l2_constructs(&crate::ir::constructs::L2Mappings::builtin());
+    let mut output = Vec::new();
+    Golang::new(Schema::builtin())
+        .synthesize(ir, &mut output, "Stack")
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    // Left out options are passed as nil.
+    assert!(
+        output.contains(
+            "\t\t&iam.RoleProps{
+\t\t\tAssumedBy: iam.NewServicePrincipal(jsii.String(\"lambda.amazonaws.com\"), nil),
+\t\t},
+\t)
+\tcfnRole := role.Node().DefaultChild().(iam.CfnRole)
+\tcfnRole.OverrideLogicalId(jsii.String(\"Role\"))
+"
+        ),
+        "{output}"
+    );
+    assert!(
+        output.contains(
+            "\t\t\tRole: role,
+\t\t\tHandler: jsii.String(\"index.handler\"),
+\t\t\tEnvironment: &map[string]*string{
+\t\t\t\t\"ROLE\": role.RoleName(),
+\t\t\t},
+\t\t},
+\t)
+\tcfnFunction := function.Node().DefaultChild().(lambda.CfnFunction)
+\tcfnFunction.OverrideLogicalId(jsii.String(\"Function\"))
+\tcfnFunction.AddPropertyOverride(jsii.String(\"Architectures\"), &[]interface{}{
+\t\tjsii.String(\"arm64\"),
+\t})
+"
+        ),
+        "{output}"
+    );
+}
+
+#[test]
+fn test_union_properties() {
+    let template: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Transform: AWS::Serverless-2016-10-31
+        Resources:
+          Function:
+            Type: AWS::Serverless::Function
+            Properties:
+              Runtime: nodejs20.x
+              Handler: index.handler
+              CodeUri:
+                Bucket: my-bucket
+                Key: code.zip
+        "#,
+    )
+    .unwrap();
+    let ir = CloudformationProgramIr::from(template, Schema::builtin()).unwrap();
+    let mut output = Vec::new();
+    Golang::new(Schema::builtin())
+        .synthesize(ir, &mut output, "Stack")
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(
+        output.contains("CodeUri: &sam.CfnFunction_S3LocationProperty{"),
+        "{output}"
+    );
+}
+
+#[test]
+fn test_package_other_than_main_has_no_entry_point() {
+    let template: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Resources:
+          Queue:
+            Type: AWS::SQS::Queue
+        "#,
+    )
+    .unwrap();
+    let ir = CloudformationProgramIr::from(template, Schema::builtin()).unwrap();
+    let mut output = Vec::new();
+    Golang::new(Schema::builtin())
+        .in_package("infra")
+        .synthesize(ir, &mut output, "QueueStack")
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.starts_with("package infra\n"), "{output}");
+    assert!(output.contains("func NewQueueStack("), "{output}");
+    assert!(!output.contains("func main()"), "{output}");
+    assert!(!output.contains("func env()"), "{output}");
+}
@@ -1,17 +1,22 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
-use super::Synthesizer;
+use super::{ConstructOptions, JavaOptions, Synthesizer, SynthesizerOptions};
 use crate::cdk::{ItemType, Schema, TypeReference};
 use crate::code::{CodeBuffer, IndentOptions};
 use crate::ir::conditions::ConditionIr;
+use crate::ir::constructor::{ConstructorType, DefaultValue, ParameterConstraint, PropValidation};
+use crate::ir::constructs::{L2Construct, L2Value};
 use crate::ir::importer::ImportInstruction;
+use crate::ir::nested_stacks::NestedStackInstruction;
 use crate::ir::reference::{Origin, PseudoParameter, Reference};
-use crate::ir::resources::{ResourceInstruction, ResourceIr};
+use crate::ir::resources::{CreationPolicyIr, ResourceInstruction, ResourceIr};
+use crate::ir::rules::{RuleIr, RuleValueIr};
 use crate::ir::CloudformationProgramIr;
 use crate::parser::lookup_table::MappingInnerValue;
 use crate::parser::resource::DeletionPolicy;
 use crate::Error;
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::rc::Rc;
 use std::{io, vec};
 use voca_rs::case::{camel_case, pascal_case};
@@ -37,6 +42,7 @@ macro_rules! fill {
 pub struct Java<'a> {
     package_name: String,
     schema: &'a Schema,
+    construct: Option<ConstructOptions>,
 }
 
 impl<'a> Java<'a> {
@@ -44,11 +50,18 @@ impl<'a> Java<'a> {
         Self {
             package_name: package_name.into(),
             schema,
+            construct: None,
         }
     }
 
+    /// Declares a construct instead of a stack (nested stacks are still declared as such).
+    pub fn as_construct(mut self, options: ConstructOptions) -> Self {
+        self.construct = Some(options);
+        self
+    }
+
     //noinspection ALL
-    fn write_header(&self, code: &CodeBuffer) {
+    fn write_header(&self, code: &CodeBuffer, nested_stacks: bool) {
         code.line(format!("package {};", self.package_name));
 
         // base imports
@@ -58,11 +71,65 @@ impl<'a> Java<'a> {
         code.line("import java.util.*;");
         code.line("import software.amazon.awscdk.CfnMapping;");
         code.line("import software.amazon.awscdk.CfnTag;");
+        if nested_stacks {
+            code.line("import software.amazon.awscdk.NestedStack;");
+            code.line("import software.amazon.awscdk.NestedStackProps;");
+        }
         code.line("import software.amazon.awscdk.Stack;");
         code.line("import software.amazon.awscdk.StackProps;");
         code.newline();
     }
 
+    // Declares the class of a stack. Nested stacks extend `NestedStack` rather than `Stack`, and
+    // constructs extend `Construct`, leaving the stack-wide settings of the template to the stack
+    // they are added to.
+    fn write_stack(
+        &self,
+        code: &CodeBuffer,
+        ir: &CloudformationProgramIr,
+        stack_name: &str,
+        nested: bool,
+        construct: Option<ConstructOptions>,
+    ) -> Result<(), Error> {
+        let base_class = match (nested, construct) {
+            (_, Some(_)) => "Construct",
+            (true, None) => "NestedStack",
+            (false, None) => "Stack",
+        };
+        let class = code.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some(format!("class {stack_name} extends {base_class} {{").into()),
+            trailing: Some("}".into()),
+            trailing_newline: true,
+        });
+
+        let props = Self::emit_props(ir);
+        Self::write_output_fields(ir, &class);
+
+        let definitions = match construct {
+            Some(_) => Self::write_construct_definitions(&props, &class, stack_name),
+            None => Self::write_stack_definitions(&props, &class, stack_name, base_class),
+        };
+        Self::write_props(&props, &definitions);
+        Self::write_parameter_validation(&ir.constructor.validations(), &definitions);
+        // The template options are those of the stack, which a construct does not own.
+        if construct.is_none() {
+            Self::write_template_options(ir, &definitions, self.schema)?;
+        }
+        Self::write_transforms(ir, &definitions, construct.is_some());
+
+        Self::write_mappings(ir, &definitions);
+        Self::write_conditions(ir, &definitions);
+        Self::write_rules(ir, &definitions);
+        Self::write_resources(ir, &definitions, self.schema)?;
+        let cfn_outputs = construct.is_none_or(|construct| construct.cfn_outputs);
+        Self::write_outputs(ir, &definitions, self.schema, cfn_outputs)?;
+        if construct.is_some() {
+            Self::write_pseudo_parameters(ir, &class);
+        }
+        Ok(())
+    }
+
     fn emit_mappings(mapping: &MappingInnerValue, output: &CodeBuffer) {
         match mapping {
             MappingInnerValue::Number(num) => output.text(format!("{num}")),
@@ -99,24 +166,31 @@ impl<'a> Java<'a> {
     fn emit_props(ir: &CloudformationProgramIr) -> Vec<JavaConstructorParameter> {
         let mut v = Vec::new();
         for input in &ir.constructor.inputs {
-            let java_type = match input.constructor_type.as_str() {
-                "List<Number>" => "List<Number>",
-                t if t.contains("List") => "String[]",
-                "Boolean" => "Boolean",
+            let java_type = match &input.constructor_type {
+                ConstructorType::ListOfNumbers => "List<Number>",
+                t if t.is_list() => "String[]",
+                ConstructorType::Boolean => "Boolean",
                 _ => "String",
             };
 
             v.push(JavaConstructorParameter {
                 name: input.name.clone(),
+                logical_id: input.logical_id.clone(),
                 description: input.description.clone(),
+                comments: input.comments.clone(),
                 java_type: java_type.into(),
-                constructor_type: if java_type == "Boolean" {
-                    "Boolean".to_string()
-                } else {
-                    input.constructor_type.clone()
-                },
+                constructor_type: input.constructor_type.clone(),
+                cfn_parameter: input.is_cfn_parameter(),
+                ssm_lookup: input.ssm_lookup,
                 default_value: input.default_value.clone(),
                 no_echo: input.no_echo.clone(),
+                allowed_values: input.allowed_values.clone(),
+                allowed_pattern: input.allowed_pattern.clone(),
+                constraint_description: input.constraint_description.clone(),
+                min_length: input.min_length,
+                max_length: input.max_length,
+                min_value: input.min_value,
+                max_value: input.max_value,
             });
         }
         v
@@ -126,6 +200,7 @@ impl<'a> Java<'a> {
         props: &[JavaConstructorParameter],
         writer: &CodeBuffer,
         stack_name: &str,
+        base_class: &str,
     ) -> Rc<CodeBuffer> {
         fill!(writer;
             format!("public {}(final Construct scope, final String id) {{", stack_name);
@@ -137,7 +212,7 @@ impl<'a> Java<'a> {
                 indent: INDENT,
                 leading: Some(
                     format!(
-                        "public {stack_name}(final Construct scope, final String id, final StackProps props) {{",
+                        "public {stack_name}(final Construct scope, final String id, final {base_class}Props props) {{",
                     )
                     .into(),
                 ),
@@ -157,20 +232,18 @@ impl<'a> Java<'a> {
             writer.newline();
             let definitions_with_props = writer.indent_with_options(IndentOptions {
                 indent: INDENT,
-                leading: Some(format!("public {stack_name}(final Construct scope, final String id, final StackProps props,").into()),
+                leading: Some(format!("public {stack_name}(final Construct scope, final String id, final {base_class}Props props,").into()),
                 trailing: Some("}".into()),
                 trailing_newline: true,
             });
             let mut prop = props.iter().peekable();
             while let Some(p) = prop.next() {
+                let argument = definitions_with_props.indent(INDENT);
+                argument.comments("//", &p.comments);
                 if prop.peek().is_none() {
-                    definitions_with_props
-                        .indent(INDENT)
-                        .line(format!("{} {}) {{", p.java_type, p.name));
+                    argument.line(format!("{} {}) {{", p.java_type, p.name));
                 } else {
-                    definitions_with_props
-                        .indent(INDENT)
-                        .line(format!("{} {},", p.java_type, p.name));
+                    argument.line(format!("{} {},", p.java_type, p.name));
                 }
             }
             definitions_with_props.line("super(scope, id, props);");
@@ -179,17 +252,81 @@ impl<'a> Java<'a> {
         }
     }
 
+    // Constructs have no props of their own, so their parameters are all the constructor takes.
+    fn write_construct_definitions(
+        props: &[JavaConstructorParameter],
+        writer: &CodeBuffer,
+        stack_name: &str,
+    ) -> Rc<CodeBuffer> {
+        if props.is_empty() {
+            let definitions = writer.indent_with_options(IndentOptions {
+                indent: INDENT,
+                leading: Some(
+                    format!("public {stack_name}(final Construct scope, final String id) {{")
+                        .into(),
+                ),
+                trailing: Some("}".into()),
+                trailing_newline: true,
+            });
+            definitions.line("super(scope, id);");
+            return definitions;
+        }
+
+        let delegating = writer.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some(
+                format!("public {stack_name}(final Construct scope, final String id) {{").into(),
+            ),
+            trailing: Some("}".into()),
+            trailing_newline: true,
+        });
+        delegating.line(format!("this(scope, id{});", ", null".repeat(props.len())));
+
+        writer.newline();
+        let definitions = writer.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some(
+                format!("public {stack_name}(final Construct scope, final String id,").into(),
+            ),
+            trailing: Some("}".into()),
+            trailing_newline: true,
+        });
+        let mut prop = props.iter().peekable();
+        while let Some(p) = prop.next() {
+            let argument = definitions.indent(INDENT);
+            argument.comments("//", &p.comments);
+            if prop.peek().is_none() {
+                argument.line(format!("{} {}) {{", p.java_type, p.name));
+            } else {
+                argument.line(format!("{} {},", p.java_type, p.name));
+            }
+        }
+        definitions.line("super(scope, id);");
+        definitions.newline();
+        definitions
+    }
+
     fn write_props(props: &[JavaConstructorParameter], writer: &CodeBuffer) {
         for prop in props {
             match &prop.default_value {
-                None => writer.newline(),
-                Some(v)
-                    if prop.constructor_type.contains("AWS::")
-                        || prop.no_echo.as_ref().is_some_and(|x| x == "true") =>
-                {
-                    let value_as = match &prop.constructor_type {
-                        t if t.contains("List") => "getValueAsList",
-                        _ => "getValueAsString",
+                default if prop.ssm_lookup => {
+                    let name_value = match default {
+                        Some(v) => format!(
+                            "Optional.ofNullable({}).orElse(\"{}\")",
+                            prop.name,
+                            v.to_string().escape_debug()
+                        ),
+                        None => prop.name.clone(),
+                    };
+                    writer.line(format!(
+                        "{} = StringParameter.valueForStringParameter(this, {name_value});",
+                        prop.name
+                    ));
+                }
+                default if prop.cfn_parameter => {
+                    let value_as = match prop.constructor_type.is_list() {
+                        true => "getValueAsList().toArray(new String[0])",
+                        false => "getValueAsString()",
                     };
                     let prop_options = writer.indent_with_options(IndentOptions {
                         indent: DOUBLE_INDENT,
@@ -209,7 +346,7 @@ impl<'a> Java<'a> {
                         leading: Some(
                             format!(
                                 ": CfnParameter.Builder.create(this, \"{}\")",
-                                pascal_case(&prop.name)
+                                prop.logical_id
                             )
                             .into(),
                         ),
@@ -217,25 +354,111 @@ impl<'a> Java<'a> {
                         trailing_newline: false,
                     });
                     prop_details.line(format!(".type(\"{}\")", prop.constructor_type));
-                    prop_details.line(format!(".defaultValue(\"{v}\")"));
+                    if let Some(v) = default {
+                        prop_details.line(format!(
+                            ".defaultValue(\"{}\")",
+                            v.to_string().escape_debug()
+                        ));
+                    }
+                    Self::write_parameter_constraints(prop, &prop_details);
                     if let Some(v) = &prop.no_echo {
                         prop_details.line(format!(".noEcho({v})"))
                     }
                     prop_details.line(".build()");
-                    prop_details.line(format!(".{value_as}();"));
+                    prop_details.line(format!(".{value_as};"));
                 }
-                Some(v) if prop.constructor_type == ("Boolean") => writer.line(format!(
-                    "{} = Optional.ofNullable({}).isPresent() ? {}\n{DOUBLE_INDENT}: {v};",
-                    prop.name, prop.name, prop.name
-                )),
+                None => writer.newline(),
                 Some(v) => writer.line(format!(
-                    "{} = Optional.ofNullable({}).isPresent() ? {}\n{DOUBLE_INDENT}: \"{v}\";",
-                    prop.name, prop.name, prop.name
+                    "{} = Optional.ofNullable({}).isPresent() ? {}\n{DOUBLE_INDENT}: {};",
+                    prop.name,
+                    prop.name,
+                    prop.name,
+                    v.to_java(&prop.java_type)
                 )),
             }
         }
     }
 
+    fn write_parameter_constraints(prop: &JavaConstructorParameter, writer: &CodeBuffer) {
+        if let Some(values) = &prop.allowed_values {
+            let values = values
+                .iter()
+                .map(|v| format!("\"{}\"", v.escape_debug()))
+                .collect::<Vec<_>>();
+            writer.line(format!(
+                ".allowedValues(Arrays.asList({}))",
+                values.join(", ")
+            ));
+        }
+        if let Some(v) = &prop.allowed_pattern {
+            writer.line(format!(".allowedPattern(\"{}\")", v.escape_debug()));
+        }
+        if let Some(v) = &prop.constraint_description {
+            writer.line(format!(".constraintDescription(\"{}\")", v.escape_debug()));
+        }
+        if let Some(v) = prop.min_length {
+            writer.line(format!(".minLength({v})"));
+        }
+        if let Some(v) = prop.max_length {
+            writer.line(format!(".maxLength({v})"));
+        }
+        if let Some(v) = prop.min_value {
+            writer.line(format!(".minValue({v})"));
+        }
+        if let Some(v) = prop.max_value {
+            writer.line(format!(".maxValue({v})"));
+        }
+    }
+
+    // Tokens can only be checked at deploy time, so they are skipped.
+    fn write_parameter_validation(validations: &[PropValidation], writer: &CodeBuffer) {
+        for validation in validations {
+            let name = &validation.param.name;
+            writer.newline();
+            let block = writer.indent_with_options(IndentOptions {
+                indent: INDENT,
+                leading: Some(
+                    format!("if ({name} != null && !Token.isUnresolved({name})) {{").into(),
+                ),
+                trailing: Some("}".into()),
+                trailing_newline: true,
+            });
+            for check in &validation.checks {
+                let violated = match &check.constraint {
+                    ParameterConstraint::AllowedValues(values) => {
+                        let values = values
+                            .iter()
+                            .map(|v| format!("\"{}\"", v.escape_debug()))
+                            .collect::<Vec<_>>();
+                        format!("!Arrays.asList({}).contains({name})", values.join(", "))
+                    }
+                    ParameterConstraint::AllowedPattern(pattern) => {
+                        format!("!{name}.matches(\"{}\")", pattern.escape_debug())
+                    }
+                    ParameterConstraint::MinLength(min) => format!("{name}.length() < {min}"),
+                    ParameterConstraint::MaxLength(max) => format!("{name}.length() > {max}"),
+                    ParameterConstraint::MinValue(min) => {
+                        format!("Double.parseDouble({name}) < {min}")
+                    }
+                    ParameterConstraint::MaxValue(max) => {
+                        format!("Double.parseDouble({name}) > {max}")
+                    }
+                };
+                block
+                    .indent_with_options(IndentOptions {
+                        indent: INDENT,
+                        leading: Some(format!("if ({violated}) {{").into()),
+                        trailing: Some("}".into()),
+                        trailing_newline: true,
+                    })
+                    .line(format!(
+                        "throw new IllegalArgumentException(\"{}\");",
+                        check.message.escape_debug()
+                    ));
+            }
+        }
+    }
+
     fn write_resource(
         resource: &ResourceInstruction,
         writer: &Rc<CodeBuffer>,
@@ -248,6 +471,9 @@ impl<'a> Java<'a> {
             writer.line(format!("Optional<Cfn{class}> {} = {} ? Optional.of(Cfn{class}.Builder.create(this, \"{res_name}\")", name(res_name), camel_case(cond)));
             let properties = writer.indent(DOUBLE_INDENT);
             for (name, prop) in &resource.properties {
+                if let Some(comments) = resource.property_comments.get(name) {
+                    properties.comments("//", comments);
+                }
                 properties.text(format!(".{}(", camel_case(name)));
                 emit_java(prop.clone(), &properties, Some(class), schema)?;
                 properties.text(")\n");
@@ -261,6 +487,9 @@ impl<'a> Java<'a> {
             ));
             let properties = writer.indent(DOUBLE_INDENT);
             for (name, prop) in &resource.properties {
+                if let Some(comments) = resource.property_comments.get(name) {
+                    properties.comments("//", comments);
+                }
                 properties.text(format!(".{}(", camel_case(name)));
                 emit_java(prop.clone(), &properties, Some(class), schema)?;
                 properties.text(")\n");
@@ -270,11 +499,45 @@ impl<'a> Java<'a> {
         }
     }
 
-    fn write_transforms(ir: &CloudformationProgramIr, writer: &Rc<CodeBuffer>) {
+    fn write_template_options(
+        ir: &CloudformationProgramIr,
+        writer: &Rc<CodeBuffer>,
+        schema: &Schema,
+    ) -> Result<(), Error> {
+        let metadata = ir.metadata.as_ref().filter(|metadata| !metadata.is_empty());
+        if ir.template_format_version.is_none() && metadata.is_none() {
+            return Ok(());
+        }
+
+        writer.newline();
+        if let Some(version) = &ir.template_format_version {
+            writer.line(format!(
+                "this.getTemplateOptions().setTemplateFormatVersion(\"{}\");",
+                version.escape_debug()
+            ));
+        }
+        if let Some(metadata) = metadata {
+            writer.text("this.getTemplateOptions().setMetadata(");
+            emit_java(
+                metadata.to_ir(|name| ir.constructor.logical_id(name)),
+                writer,
+                None,
+                schema,
+            )?;
+            writer.text(");\n");
+        }
+        Ok(())
+    }
+
+    fn write_transforms(ir: &CloudformationProgramIr, writer: &Rc<CodeBuffer>, construct: bool) {
         if !ir.transforms.is_empty() {
             writer.newline();
+            let stack = match construct {
+                true => "Stack.of(this)",
+                false => "this",
+            };
             for transform in &ir.transforms {
-                writer.line(format!("this.addTransform(\"{transform}\");"));
+                writer.line(format!("{stack}.addTransform(\"{transform}\");"));
             }
         }
     }
@@ -284,29 +547,164 @@ impl<'a> Java<'a> {
         writer: &Rc<CodeBuffer>,
         schema: &Schema,
     ) -> Result<(), Error> {
+        let nested_stacks: HashSet<&str> = ir
+            .resources
+            .iter()
+            .filter(|resource| resource.nested_stack.is_some())
+            .map(|resource| resource.name.as_str())
+            .collect();
         for resource in &ir.resources {
+            writer.comments("//", &resource.comments);
+            if let Some(nested) = &resource.nested_stack {
+                Self::write_nested_stack(resource, nested, writer, schema)?;
+                continue;
+            }
+            if let Some(construct) = &resource.l2_construct {
+                let cfn_var_name = Self::write_l2_construct(resource, construct, writer, schema)?;
+                writer.newline();
+                Self::write_resource_attributes(
+                    resource,
+                    &cfn_var_name,
+                    writer,
+                    false,
+                    &nested_stacks,
+                    schema,
+                )?;
+                continue;
+            }
             let maybe_undefined = Self::write_resource(resource, writer, schema)?;
             writer.newline();
-            Self::write_resource_attributes(resource, writer, maybe_undefined, schema)?;
+            Self::write_resource_attributes(
+                resource,
+                &camel_case(&resource.name),
+                writer,
+                maybe_undefined,
+                &nested_stacks,
+                schema,
+            )?;
         }
         Ok(())
     }
 
+    // Nested stacks take their parameters as positional constructor arguments, see
+    // `write_stack_definitions`.
+    fn write_nested_stack(
+        resource: &ResourceInstruction,
+        nested: &NestedStackInstruction,
+        writer: &Rc<CodeBuffer>,
+        schema: &Schema,
+    ) -> Result<(), Error> {
+        let class = &nested.class_name;
+        let var_name = name(&resource.name);
+        let props = Self::emit_props(&nested.program);
+        if props.is_empty() {
+            writer.line(format!(
+                "{class} {var_name} = new {class}(this, \"{}\");",
+                resource.name
+            ));
+        } else {
+            writer.line(format!(
+                "{class} {var_name} = new {class}(this, \"{}\", null,",
+                resource.name
+            ));
+            let arguments = writer.indent(DOUBLE_INDENT);
+            let mut props = props.iter().peekable();
+            while let Some(prop) = props.next() {
+                match nested.parameters.get(&prop.name) {
+                    Some(value) => {
+                        emit_java(value.clone(), &arguments, None, schema)?;
+                        if prop.java_type == "String[]" {
+                            arguments.text(".toArray(new String[0])");
+                        }
+                    }
+                    None => arguments.text("null"),
+                }
+                arguments.text(if props.peek().is_some() {
+                    ",\n"
+                } else {
+                    ");\n"
+                });
+            }
+        }
+        for dependency in &resource.dependencies {
+            writer.line(format!(
+                "{var_name}.getNode().addDependency({});",
+                camel_case(dependency)
+            ));
+        }
+        writer.newline();
+        // The other attributes apply to the CfnStack deploying the nested stack.
+        let attributes = ResourceInstruction {
+            dependencies: Vec::new(),
+            ..resource.clone()
+        };
+        Self::write_resource_attributes(
+            &attributes,
+            &format!("{var_name}.getNestedStackResource()"),
+            writer,
+            false,
+            &HashSet::new(),
+            schema,
+        )
+    }
+
+    // Emits the L2 construct of a resource, and returns the name of the variable holding its L1
+    // resource. The classes of the construct library are fully qualified, as some of their names
+    // are taken by `java.lang` (e.g: `Runtime`).
+    fn write_l2_construct(
+        resource: &ResourceInstruction,
+        construct: &L2Construct,
+        writer: &Rc<CodeBuffer>,
+        schema: &Schema,
+    ) -> Result<String, Error> {
+        let service = resource.resource_type.service();
+        let class = l2_class(service, &construct.class);
+        let res_name = &resource.name;
+        let var_name = name(res_name);
+        writer.line(format!(
+            "{class} {var_name} = {class}.Builder.create(this, \"{res_name}\")"
+        ));
+        let properties = writer.indent(DOUBLE_INDENT);
+        for (name, value) in &construct.props {
+            if let Some(comments) = resource.property_comments.get(name) {
+                properties.comments("//", comments);
+            }
+            properties.text(format!(".{name}("));
+            emit_l2_value(value, &properties, schema)?;
+            properties.text(")\n");
+        }
+        properties.line(".build();");
+
+        // The L1 resource keeps the logical ID of the template, and what the construct can't express
+        let cfn_class = format!("Cfn{}", resource.resource_type.type_name());
+        let cfn_var_name = name(&L2Construct::escape_hatch(res_name));
+        writer.line(format!(
+            "{cfn_class} {cfn_var_name} = ({cfn_class}) {var_name}.getNode().getDefaultChild();"
+        ));
+        writer.line(format!("{cfn_var_name}.overrideLogicalId(\"{res_name}\");"));
+        for (name, value) in &construct.overrides {
+            if let Some(comments) = resource.property_comments.get(name) {
+                writer.comments("//", comments);
+            }
+            writer.text(format!("{cfn_var_name}.addPropertyOverride(\"{name}\", "));
+            emit_java(value.clone(), writer, None, schema)?;
+            writer.text(");\n");
+        }
+        Ok(cfn_var_name)
+    }
+
     fn write_resource_attributes(
         resource: &ResourceInstruction,
+        var_name: &str,
         writer: &Rc<CodeBuffer>,
         maybe_undefined: bool,
+        nested_stacks: &HashSet<&str>,
         schema: &Schema,
     ) -> Result<(), Error> {
         let res_name = if maybe_undefined {
-            format!(
-                "{}.ifPresent(_{} -> _{}",
-                camel_case(&resource.name),
-                camel_case(&resource.name),
-                camel_case(&resource.name)
-            )
+            format!("{var_name}.ifPresent(_{var_name} -> _{var_name}")
         } else {
-            camel_case(&resource.name)
+            var_name.to_string()
         };
         let trailer = if maybe_undefined { ");\n" } else { ";\n" };
         let mut extra_line = false;
@@ -328,8 +726,13 @@ impl<'a> Java<'a> {
         }
 
         for dependency in &resource.dependencies {
+            // Nested stacks are deployed by a CfnStack resource of their parent.
+            let nested_stack_resource = match nested_stacks.contains(dependency.as_str()) {
+                true => ".getNestedStackResource()",
+                false => "",
+            };
             writer.text(format!(
-                "{res_name}.addDependency({}){}",
+                "{res_name}.addDependency({}{nested_stack_resource}){}",
                 camel_case(dependency),
                 trailer
             ));
@@ -371,6 +774,20 @@ impl<'a> Java<'a> {
             writer.text(format!("){trailer}"));
             extra_line = true;
         }
+
+        if let Some(update_replace_policy) = &resource.update_replace_policy {
+            writer.text(format!(
+                "{res_name}.getCfnOptions().setUpdateReplacePolicy(CfnDeletionPolicy.{update_replace_policy}){trailer}"
+            ));
+            extra_line = true;
+        }
+
+        if let Some(creation_policy) = &resource.creation_policy {
+            writer.text(format!("{res_name}.getCfnOptions().setCreationPolicy("));
+            Self::emit_creation_policy(creation_policy, writer, schema)?;
+            writer.text(format!("){trailer}"));
+            extra_line = true;
+        }
         if extra_line {
             writer.newline();
         }
@@ -378,6 +795,45 @@ impl<'a> Java<'a> {
         Ok(())
     }
 
+    fn emit_creation_policy(
+        creation_policy: &CreationPolicyIr,
+        writer: &Rc<CodeBuffer>,
+        schema: &Schema,
+    ) -> Result<(), Error> {
+        writer.text("CfnCreationPolicy.builder()");
+        if let Some(asg) = &creation_policy.auto_scaling_creation_policy {
+            writer
+                .text(".autoScalingCreationPolicy(CfnResourceAutoScalingCreationPolicy.builder()");
+            if let Some(percent) = &asg.min_successful_instances_percent {
+                writer.text(".minSuccessfulInstancesPercent(");
+                emit_java(percent.clone(), writer, None, schema)?;
+                writer.text(")");
+            }
+            writer.text(".build())");
+        }
+        if let Some(signal) = &creation_policy.resource_signal {
+            writer.text(".resourceSignal(CfnResourceSignal.builder()");
+            if let Some(count) = &signal.count {
+                writer.text(".count(");
+                emit_java(count.clone(), writer, None, schema)?;
+                writer.text(")");
+            }
+            if let Some(timeout) = &signal.timeout {
+                writer.text(".timeout(");
+                emit_java(timeout.clone(), writer, None, schema)?;
+                writer.text(")");
+            }
+            writer.text(".build())");
+        }
+        if let Some(start_fleet) = &creation_policy.start_fleet {
+            writer.text(".startFleet(");
+            emit_java(start_fleet.clone(), writer, None, schema)?;
+            writer.text(")");
+        }
+        writer.text(".build()");
+        Ok(())
+    }
+
     fn write_conditions(ir: &CloudformationProgramIr, writer: &Rc<CodeBuffer>) {
         for condition in &ir.conditions {
             let name = &*condition.name;
@@ -391,6 +847,32 @@ impl<'a> Java<'a> {
         writer.newline();
     }
 
+    fn write_rules(ir: &CloudformationProgramIr, writer: &Rc<CodeBuffer>) {
+        for rule in &ir.rules {
+            writer.line(format!("CfnRule.Builder.create(this, {:?})", rule.name));
+            let properties = writer.indent(DOUBLE_INDENT);
+            if let Some(condition) = &rule.condition {
+                properties.line(format!(".ruleCondition({})", emit_rule(condition)));
+            }
+            if !rule.assertions.is_empty() {
+                properties.line(".assertions(Arrays.asList(");
+                let assertions = properties.indent(DOUBLE_INDENT);
+                let mut iter = rule.assertions.iter().peekable();
+                while let Some(assertion) = iter.next() {
+                    // The CDK requires a description, even though CloudFormation does not.
+                    assertions.line(format!(
+                        "CfnRuleAssertion.builder().assertValue({}).assertDescription({:?}).build(){}",
+                        emit_rule(&assertion.assert),
+                        assertion.description.as_deref().unwrap_or_default(),
+                        if iter.peek().is_some() { "," } else { "))" }
+                    ));
+                }
+            }
+            properties.line(".build();");
+            writer.newline();
+        }
+    }
+
     fn match_field_type(condition: Option<String>) -> String {
         String::from(match condition {
             None => "Object",
@@ -398,6 +880,26 @@ impl<'a> Java<'a> {
         })
     }
 
+    // Declares the getters of the pseudo parameters a construct uses, so that they are referred to
+    // the same way as in a stack, but resolved by the stack the construct is in.
+    fn write_pseudo_parameters(ir: &CloudformationProgramIr, writer: &Rc<CodeBuffer>) {
+        for pseudo in ir.pseudo_parameters() {
+            let value_type = match pseudo {
+                PseudoParameter::NotificationArns => "List<String>",
+                _ => "String",
+            };
+            let getter = get_pseudo_param(pseudo).replace("this.", "");
+            writer.newline();
+            let indented = writer.indent_with_options(IndentOptions {
+                indent: INDENT,
+                leading: Some(format!("private {value_type} {getter} {{").into()),
+                trailing: Some("}".into()),
+                trailing_newline: true,
+            });
+            indented.line(format!("return Stack.of(this).{getter};"));
+        }
+    }
+
     fn write_output_fields(ir: &CloudformationProgramIr, writer: &Rc<CodeBuffer>) {
         for output in &ir.outputs {
             writer.line(format!(
@@ -429,14 +931,19 @@ impl<'a> Java<'a> {
         ir: &CloudformationProgramIr,
         writer: &Rc<CodeBuffer>,
         schema: &Schema,
+        cfn_outputs: bool,
     ) -> Result<(), Error> {
         for output in &ir.outputs {
             let var_name = camel_case(&output.name);
+            writer.comments("//", &output.comments);
             let output_writer = match &output.condition {
                 None => {
                     writer.text(format!("this.{var_name} = "));
                     emit_java(output.value.clone(), writer, None, schema)?;
                     writer.text(";\n");
+                    if !cfn_outputs {
+                        continue;
+                    }
                     let output_writer = writer.indent_with_options(IndentOptions {
                         indent: DOUBLE_INDENT,
                         leading: Some(
@@ -461,6 +968,9 @@ impl<'a> Java<'a> {
                     ));
                     emit_java(output.value.clone(), writer, None, schema)?;
                     writer.text(" : Optional.empty();\n");
+                    if !cfn_outputs {
+                        continue;
+                    }
                     let output_writer = writer.indent_with_options(IndentOptions {
                         indent: DOUBLE_INDENT,
                         leading: Some(
@@ -497,7 +1007,17 @@ impl<'a> Java<'a> {
 
 impl Default for Java<'_> {
     fn default() -> Self {
-        Self::new("com.myorg", Schema::builtin())
+        Self::new(JavaOptions::default().package, Schema::builtin())
+    }
+}
+
+impl From<&SynthesizerOptions> for Java<'_> {
+    fn from(options: &SynthesizerOptions) -> Self {
+        let java = Self::new(options.java.package.clone(), Schema::builtin());
+        match options.construct {
+            Some(construct) => java.as_construct(construct),
+            None => java,
+        }
     }
 }
 
@@ -510,31 +1030,19 @@ impl Synthesizer for Java<'_> {
     ) -> Result<(), Error> {
         let code = CodeBuffer::default();
 
-        self.write_header(&code);
+        let nested_stacks = ir.nested_stacks();
+        self.write_header(&code, !nested_stacks.is_empty());
 
         for import in &ir.imports {
             code.line(import.to_java_import()?);
         }
         code.newline();
 
-        let class = code.indent_with_options(IndentOptions {
-            indent: INDENT,
-            leading: Some(format!("class {stack_name} extends Stack {{").into()),
-            trailing: Some("}".into()),
-            trailing_newline: true,
-        });
-
-        let props = Self::emit_props(&ir);
-        Self::write_output_fields(&ir, &class);
-
-        let definitions = Self::write_stack_definitions(&props, &class, stack_name);
-        Self::write_props(&props, &definitions);
-        Self::write_transforms(&ir, &definitions);
-
-        Self::write_mappings(&ir, &definitions);
-        Self::write_conditions(&ir, &definitions);
-        Self::write_resources(&ir, &definitions, self.schema)?;
-        Self::write_outputs(&ir, &definitions, self.schema)?;
+        for nested in nested_stacks {
+            self.write_stack(&code, &nested.program, &nested.class_name, true, None)?;
+            code.newline();
+        }
+        self.write_stack(&code, &ir, stack_name, false, self.construct)?;
 
         Ok(code.write(into)?)
     }
@@ -612,6 +1120,69 @@ fn emit_conditions(condition: ConditionIr) -> String {
     }
 }
 
+fn emit_rule(rule: &RuleIr) -> String {
+    match rule {
+        RuleIr::And(list) => format!(
+            "Fn.conditionAnd({})",
+            list.iter().map(emit_rule).collect::<Vec<_>>().join(", ")
+        ),
+        RuleIr::Or(list) => format!(
+            "Fn.conditionOr({})",
+            list.iter().map(emit_rule).collect::<Vec<_>>().join(", ")
+        ),
+        RuleIr::Not(cond) => format!("Fn.conditionNot({})", emit_rule(cond)),
+        RuleIr::Equals(lhs, rhs) => format!(
+            "Fn.conditionEquals({}, {})",
+            emit_rule_value(lhs, false),
+            emit_rule_value(rhs, false)
+        ),
+        RuleIr::Contains(list, value) => format!(
+            "Fn.conditionContains({}, {})",
+            emit_rule_value(list, true),
+            emit_rule_value(value, false)
+        ),
+        RuleIr::EachMemberEquals(list, value) => format!(
+            "Fn.conditionEachMemberEquals({}, {})",
+            emit_rule_value(list, true),
+            emit_rule_value(value, false)
+        ),
+        RuleIr::EachMemberIn(to_check, to_match) => format!(
+            "Fn.conditionEachMemberIn({}, {})",
+            emit_rule_value(to_check, true),
+            emit_rule_value(to_match, true)
+        ),
+    }
+}
+
+/// Renders a rule operand. `as_list` is set where the CDK expects a `List<String>`, which list
+/// parameters (`String[]`) and `Fn.valueOf` (`String`) must be converted to.
+fn emit_rule_value(value: &RuleValueIr, as_list: bool) -> String {
+    match value {
+        RuleValueIr::Str(str) => format!("{str:?}"),
+        RuleValueIr::List(list) => format!(
+            "Arrays.asList({})",
+            list.iter()
+                .map(|value| emit_rule_value(value, false))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        RuleValueIr::Ref(reference) => match reference.origin {
+            Origin::CfnParameter | Origin::Parameter if as_list => {
+                format!("Arrays.asList({})", emit_reference(reference.clone()))
+            }
+            _ => emit_reference(reference.clone()),
+        },
+        RuleValueIr::RefAll(parameter_type) => format!("Fn.refAll({parameter_type:?})"),
+        RuleValueIr::ValueOf(name, attribute) if as_list => {
+            format!("Token.asList(Fn.valueOf({name:?}, {attribute:?}))")
+        }
+        RuleValueIr::ValueOf(name, attribute) => format!("Fn.valueOf({name:?}, {attribute:?})"),
+        RuleValueIr::ValueOfAll(parameter_type, attribute) => {
+            format!("Fn.valueOfAll({parameter_type:?}, {attribute:?})")
+        }
+    }
+}
+
 fn emit_reference(reference: Reference) -> String {
     let origin = reference.origin;
     let name = reference.name;
@@ -646,6 +1217,18 @@ fn emit_reference(reference: Reference) -> String {
                 )
             }
         }
+        Origin::NestedStackOutput {
+            output,
+            conditional,
+        } => format!(
+            "{}.get{}(){}.toString()",
+            camel_case(&name),
+            pascal_case(&output),
+            if conditional { ".get()" } else { "" }
+        ),
+        Origin::ConstructAttribute { attribute } => {
+            format!("{}.get{}()", camel_case(&name), pascal_case(&attribute))
+        }
         Origin::PseudoParameter(param) => get_pseudo_param(param),
         Origin::CfnParameter | Origin::Parameter => camel_case(&name),
         Origin::Condition => name,
@@ -759,7 +1342,14 @@ fn emit_java(
                         Ok(())
                     }
                     name => {
-                        let name = &schema.type_named(name).unwrap().name.java.name;
+                        let name = &schema
+                            .type_named(name)
+                            .ok_or_else(|| Error::TypeReferenceError {
+                                message: format!("{name} is not a known property type"),
+                            })?
+                            .name
+                            .java
+                            .name;
                         let obj = output.indent_with_options(IndentOptions {
                             indent: DOUBLE_INDENT,
                             leading: Some(format!("{name}.builder()").into()),
@@ -895,7 +1485,7 @@ fn emit_java(
                 Ok(())
             }
             other => {
-                output.text(format!("Fn.split({separator}, "));
+                output.text(format!("Fn.split({separator:?}, "));
                 emit_java(other.clone(), output, class, schema)?;
                 output.text(")");
                 Ok(())
@@ -914,6 +1504,31 @@ fn emit_java(
             }
             Ok(())
         }
+        ResourceIr::Length(list) => match *list {
+            array @ ResourceIr::Array(..) => {
+                emit_java(array, output, class, schema)?;
+                output.text(".size()");
+                Ok(())
+            }
+            other => {
+                output.text("Fn.len(");
+                emit_java(other, output, class, schema)?;
+                output.text(")");
+                Ok(())
+            }
+        },
+        ResourceIr::ToJsonString(value) => match value.to_json() {
+            Some(json) => {
+                output.text(java_string(&json.to_string()));
+                Ok(())
+            }
+            None => {
+                output.text("Fn.toJsonString(");
+                emit_java(*value, output, class, schema)?;
+                output.text(")");
+                Ok(())
+            }
+        },
         ResourceIr::Ref(reference) => {
             output.text(emit_reference(reference));
             Ok(())
@@ -921,6 +1536,106 @@ fn emit_java(
     }
 }
 
+// The fully qualified name of a class of the module of `service`.
+fn l2_class(service: &str, class: &str) -> String {
+    format!(
+        "software.amazon.awscdk.services.{}.{class}",
+        service.to_lowercase()
+    )
+}
+
+fn emit_l2_value(value: &L2Value, output: &CodeBuffer, schema: &Schema) -> Result<(), Error> {
+    let emit_args = |args: &[L2Value]| -> Result<(), Error> {
+        output.text("(");
+        for (index, arg) in args.iter().enumerate() {
+            if index > 0 {
+                output.text(", ");
+            }
+            emit_l2_value(arg, output, schema)?;
+        }
+        output.text(")");
+        Ok(())
+    };
+    match value {
+        L2Value::Value(value) => emit_java(value.clone(), output, None, schema)?,
+        L2Value::Seconds(value) => {
+            output.text("Duration.seconds(");
+            emit_java(value.clone(), output, None, schema)?;
+            output.text(")");
+        }
+        L2Value::Enum {
+            service,
+            name,
+            member,
+        } => output.text(format!("{}.{member}", l2_class(service, name))),
+        L2Value::Struct {
+            service,
+            name,
+            fields,
+        } => {
+            let obj = output.indent_with_options(IndentOptions {
+                indent: DOUBLE_INDENT,
+                leading: Some(format!("{}.builder()", l2_class(service, name)).into()),
+                trailing: Some(format!("{DOUBLE_INDENT}.build()").into()),
+                trailing_newline: false,
+            });
+            for (name, value) in fields {
+                obj.text(format!(".{name}("));
+                emit_l2_value(value, &obj, schema)?;
+                obj.text(")\n");
+            }
+        }
+        L2Value::New {
+            service,
+            class,
+            args,
+            ..
+        } => {
+            output.text(format!("new {}", l2_class(service, class)));
+            emit_args(args)?;
+        }
+        L2Value::Call {
+            service,
+            class,
+            method,
+            args,
+            ..
+        } => {
+            output.text(format!("{}.{method}", l2_class(service, class)));
+            emit_args(args)?;
+        }
+        L2Value::Scope => output.text("this"),
+        L2Value::Construct(logical_id) => output.text(name(logical_id)),
+    }
+    Ok(())
+}
+
+// Quotes a string as a Java literal. Control characters are written as octal escapes and other
+// non-ASCII characters as `\uNNNN`, where `escape_debug` would write `\u{..}`, which Java does not
+// understand. Java reads `\uNNNN` before the literal, so it cannot be used for line breaks.
+fn java_string(value: &str) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '"' => literal.push_str("\\\""),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c if c.is_control() => literal.push_str(&format!("\\{:03o}", u32::from(c))),
+            c if c.is_ascii() => literal.push(c),
+            c => {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    literal.push_str(&format!("\\u{unit:04x}"));
+                }
+            }
+        }
+    }
+    literal.push('"');
+    literal
+}
+
 fn name(key: &str) -> String {
     camel_case(key)
         .chars()
@@ -928,13 +1643,47 @@ fn name(key: &str) -> String {
         .collect()
 }
 
+impl DefaultValue {
+    // Number props are typed as Strings, except for the items of a List<Number>.
+    fn to_java(&self, java_type: &str) -> String {
+        match self {
+            DefaultValue::String(value) => format!("\"{}\"", value.escape_debug()),
+            DefaultValue::Number(value) if java_type == "List<Number>" => value.to_string(),
+            DefaultValue::Number(value) => format!("\"{value}\""),
+            DefaultValue::Bool(value) => value.to_string(),
+            DefaultValue::List(items) => {
+                let items = items
+                    .iter()
+                    .map(|item| item.to_java(java_type))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                match java_type {
+                    "List<Number>" => format!("Arrays.asList({items})"),
+                    _ => format!("new String[] {{{items}}}"),
+                }
+            }
+        }
+    }
+}
+
 pub struct JavaConstructorParameter {
     pub name: String,
+    pub logical_id: String,
     pub description: Option<String>,
-    pub constructor_type: String,
+    pub comments: Vec<String>,
+    pub constructor_type: ConstructorType,
     pub java_type: String,
-    pub default_value: Option<String>,
+    pub default_value: Option<DefaultValue>,
     pub no_echo: Option<String>,
+    pub allowed_values: Option<Vec<String>>,
+    pub allowed_pattern: Option<String>,
+    pub constraint_description: Option<String>,
+    pub min_length: Option<u64>,
+    pub max_length: Option<u64>,
+    pub min_value: Option<f64>,
+    pub max_value: Option<f64>,
+    pub cfn_parameter: bool,
+    pub ssm_lookup: bool,
 }
 
 pub struct JavaResourceInstruction {}
@@ -6,7 +6,7 @@ use super::*;
 
 use std::borrow::Cow;
 
-use crate::cdk::{Schema, TypeUnion};
+use crate::cdk::{Primitive, Schema, TypeUnion};
 use crate::code::CodeBuffer;
 use crate::ir::importer::ImportInstruction;
 use crate::ir::resources::ResourceIr;
@@ -106,6 +106,21 @@ fn test_resource_ir_object_type_reference_error() {
     );
 }
 
+#[test]
+fn test_resource_ir_object_unknown_type_error() {
+    let output = CodeBuffer::default();
+    let schema = Cow::Borrowed(Schema::builtin());
+    let resource_ir = ResourceIr::Object(
+        TypeReference::Named("AWS::Nope::Thing.Property".into()),
+        IndexMap::new(),
+    );
+    let result = emit_tag_value(resource_ir, &output, Option::None, &schema).unwrap_err();
+    assert_eq!(
+        "AWS::Nope::Thing.Property is not a known property type",
+        result.to_string(),
+    );
+}
+
 #[test]
 fn test_resource_ir_select_idx_greater_than_list_len() {
     let output = CodeBuffer::default();
@@ -156,3 +171,58 @@ fn test_resource_ir_cidr_string_mask() {
     let result = emit_java(resource_ir, &output, Option::None, &schema);
     assert_eq!((), result.unwrap());
 }
+
+#[test]
+fn test_rule_contains_list_parameter() {
+    use crate::ir::reference::{Origin, Reference};
+    use crate::ir::rules::{RuleIr, RuleValueIr};
+
+    let rule_ir = RuleIr::And(vec![
+        RuleIr::Contains(
+            RuleValueIr::Ref(Reference::new("Zones", Origin::Parameter)),
+            RuleValueIr::Str("us-east-1a".into()),
+        ),
+        RuleIr::EachMemberEquals(
+            RuleValueIr::ValueOfAll("AWS::EC2::Subnet::Id".into(), "VpcId".into()),
+            RuleValueIr::Ref(Reference::new("VpcId", Origin::Parameter)),
+        ),
+    ]);
+    assert_eq!(
+        "Fn.conditionAnd(Fn.conditionContains(Arrays.asList(zones), \"us-east-1a\"), Fn.conditionEachMemberEquals(Fn.valueOfAll(\"AWS::EC2::Subnet::Id\", \"VpcId\"), vpcId))",
+        emit_rule(&rule_ir)
+    );
+}
+
+#[test]
+fn test_java_string() {
+    assert_eq!(java_string("plain"), "\"plain\"");
+    assert_eq!(
+        java_string("it's \"quoted\"\n\\d+"),
+        r#""it's \"quoted\"\n\\d+""#
+    );
+    assert_eq!(
+        java_string("a\u{7}b\u{85}é😀"),
+        "\"a\\007b\\205\\u00e9\\ud83d\\ude00\""
+    );
+}
+
+#[test]
+fn test_resource_ir_to_json_string_literal() {
+    let output = CodeBuffer::default();
+    let schema = Cow::Borrowed(Schema::builtin());
+    let resource_ir = ResourceIr::ToJsonString(Box::new(ResourceIr::Object(
+        TypeReference::Primitive(Primitive::Json),
+        IndexMap::from_iter([(
+            "Name".to_string(),
+            ResourceIr::String("café\u{2028}".into()),
+        )]),
+    )));
+    emit_java(resource_ir, &output, None, &schema).unwrap();
+
+    let mut result = Vec::new();
+    output.write(&mut result).unwrap();
+    assert_eq!(
+        "\"{\\\"Name\\\":\\\"caf\\u00e9\\u2028\\\"}\"",
+        String::from_utf8(result).unwrap()
+    );
+}
@@ -34,6 +34,18 @@ mod python;
 #[doc(inline)]
 pub use python::*;
 
+mod options;
+#[doc(inline)]
+pub use options::*;
+
+mod project;
+#[doc(inline)]
+pub use project::*;
+
+mod report;
+#[doc(inline)]
+pub use report::*;
+
 pub trait Synthesizer {
     fn synthesize(
         &self,
@@ -50,21 +62,34 @@ impl CloudformationProgramIr {
         language: &str,
         into: &mut impl io::Write,
         stack_name: &str,
+    ) -> Result<(), Error> {
+        self.synthesize_with(language, into, &SynthesizerOptions::new(stack_name))
+    }
+
+    pub fn synthesize_with(
+        self,
+        language: &str,
+        into: &mut impl io::Write,
+        options: &SynthesizerOptions,
     ) -> Result<(), Error> {
         let synthesizer: Box<dyn Synthesizer> = match language {
             #[cfg(feature = "csharp")]
-            "csharp" => Box::<CSharp>::default(),
+            "csharp" => Box::new(CSharp::from(options)),
             #[cfg(feature = "golang")]
-            "go" => Box::<Golang>::default(),
+            "go" => Box::new(Golang::from(options)),
             #[cfg(feature = "java")]
-            "java" => Box::<Java>::default(),
+            "java" => Box::new(Java::from(options)),
             #[cfg(feature = "python")]
-            "python" => Box::new(Python {}),
+            "python" => Box::new(Python::from(options)),
             #[cfg(feature = "typescript")]
-            "typescript" => Box::new(Typescript {}),
-            _ => panic!("Unsupported language: {}", language),
+            "typescript" => Box::new(Typescript::from(options)),
+            _ => {
+                return Err(Error::UnsupportedLanguageError {
+                    language: language.to_string(),
+                })
+            }
         };
-        synthesizer.synthesize(self, into, stack_name)
+        synthesizer.synthesize(self, into, &options.stack_name)
     }
 }
 
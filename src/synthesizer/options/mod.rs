@@ -0,0 +1,86 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+/// How a program is synthesized (see [`crate::ir::CloudformationProgramIr::synthesize_with`]).
+/// Besides the shared fields, each language only reads its own section.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SynthesizerOptions {
+    /// The name of the generated stack class.
+    pub stack_name: String,
+    /// Synthesize the template as a construct to add to an existing stack, rather than as a stack
+    /// of its own.
+    pub construct: Option<ConstructOptions>,
+    pub java: JavaOptions,
+    pub golang: GolangOptions,
+    pub csharp: CSharpOptions,
+    pub python: PythonOptions,
+}
+
+impl SynthesizerOptions {
+    /// The default options for a stack named `stack_name`.
+    pub fn new(stack_name: impl Into<String>) -> Self {
+        Self {
+            stack_name: stack_name.into(),
+            construct: None,
+            java: JavaOptions::default(),
+            golang: GolangOptions::default(),
+            csharp: CSharpOptions::default(),
+            python: PythonOptions::default(),
+        }
+    }
+}
+
+/// How a template is synthesized as a construct. Its parameters become plain props (except for
+/// those CloudFormation has to resolve, which stay `CfnParameter`s), its outputs public fields, and
+/// the pseudo parameters refer to the stack the construct is added to.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ConstructOptions {
+    /// Also declare the outputs as `CfnOutput`s of the stack.
+    pub cfn_outputs: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JavaOptions {
+    /// The package of the stack classes (and of the entry point of a project).
+    pub package: String,
+}
+
+impl Default for JavaOptions {
+    fn default() -> Self {
+        Self {
+            package: "com.myorg".into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GolangOptions {
+    /// The path of the module declared in the `go.mod` of a project. Defaults to the name of the
+    /// project.
+    pub module: Option<String>,
+    /// The package of the stack files (and of the entry point of a project). Only the `main`
+    /// package can be run as an application.
+    pub package: String,
+}
+
+impl Default for GolangOptions {
+    fn default() -> Self {
+        Self {
+            module: None,
+            package: "main".into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CSharpOptions {
+    /// The namespace of the stack classes. Defaults to the name of each stack.
+    pub namespace: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PythonOptions {
+    /// The module the stack modules of a project are created in (e.g. `infra.stacks`), instead of
+    /// next to the entry point.
+    pub module: Option<String>,
+}
@@ -0,0 +1,424 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use voca_rs::case::{camel_case, kebab_case, pascal_case, snake_case};
+
+use crate::ir::cross_stack::CrossStackImport;
+use crate::{ir::CloudformationProgramIr, Error};
+
+use super::SynthesizerOptions;
+
+#[cfg(feature = "golang")]
+use super::golang::{golang_identifier, IdentifierKind};
+#[cfg(feature = "typescript")]
+use super::typescript::pretty_name;
+#[cfg(feature = "golang")]
+use super::{Golang, Synthesizer};
+
+/// A file of a generated CDK application. The path is relative to the project directory, and
+/// always uses `/` as a separator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectFile {
+    pub path: String,
+    pub contents: String,
+}
+
+/// A stack instantiated by the entry point of a generated CDK application.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProjectStack {
+    pub name: String,
+    // The constructor parameters of the stack, in order (Java passes them positionally).
+    parameters: Vec<String>,
+    // The props receiving an output of a stack instantiated before this one.
+    imports: Vec<CrossStackImport>,
+}
+
+impl ProjectStack {
+    /// Describes the stack named `name`, whose program is `ir`.
+    pub fn new(name: &str, ir: &CloudformationProgramIr) -> Self {
+        Self {
+            name: name.to_string(),
+            parameters: ir
+                .constructor
+                .inputs
+                .iter()
+                .map(|input| input.name.clone())
+                .collect(),
+            imports: ir.cross_stack_imports.clone(),
+        }
+    }
+}
+
+impl CloudformationProgramIr {
+    /// Synthesizes the stack along with everything needed to build and deploy it as a standalone
+    /// CDK application: an entry point instantiating the stack as `options.stack_name`, the
+    /// `cdk.json` file and the language's package manifest.
+    pub fn synthesize_project(
+        self,
+        language: &str,
+        options: &SynthesizerOptions,
+    ) -> Result<Vec<ProjectFile>, Error> {
+        let stack_name = &options.stack_name;
+        let stack = ProjectStack::new(stack_name, &self);
+        let mut files = vec![self.synthesize_project_stack(language, options)?];
+        files.extend(project_files(language, stack_name, &[stack], options)?);
+        Ok(files)
+    }
+
+    /// Synthesizes the stack as one of the stacks of an application created by [`project_files`].
+    pub fn synthesize_project_stack(
+        self,
+        language: &str,
+        options: &SynthesizerOptions,
+    ) -> Result<ProjectFile, Error> {
+        let stack_name = &options.stack_name;
+        let layout = Layout::for_language(language)?;
+
+        let mut stack = Vec::new();
+        match language {
+            // The entry point of a Go application lives in its own file, so that several stacks
+            // can share it.
+            #[cfg(feature = "golang")]
+            "go" | "golang" => {
+                Golang::from(options)
+                    .without_entry_point()
+                    .synthesize(self, &mut stack, stack_name)?;
+            }
+            _ => self.synthesize_with(language, &mut stack, options)?,
+        }
+        let stack = String::from_utf8(stack).map_err(|err| Error::IOError {
+            err: std::io::Error::new(std::io::ErrorKind::InvalidData, err),
+        })?;
+
+        Ok(ProjectFile {
+            path: render_stack(layout.stack_file, stack_name, options),
+            contents: stack,
+        })
+    }
+}
+
+/// Returns the files of a CDK application named `project_name` that instantiates each of `stacks`
+/// in order, without the stacks themselves (see
+/// [`CloudformationProgramIr::synthesize_project_stack`]). Stacks importing values from other
+/// stacks (see [`crate::ir::cross_stack::link_stacks`]) must come after them. The stacks must have
+/// been synthesized with the same `options`, whose `stack_name` is not used.
+pub fn project_files(
+    language: &str,
+    project_name: &str,
+    stacks: &[ProjectStack],
+    options: &SynthesizerOptions,
+) -> Result<Vec<ProjectFile>, Error> {
+    let layout = Layout::for_language(language)?;
+
+    let imports: String = stacks
+        .iter()
+        .map(|stack| render_stack(layout.import, &stack.name, options))
+        .collect();
+    let stacks: String = stacks
+        .iter()
+        .map(|stack| {
+            // Only the stacks other stacks import from are kept in a variable.
+            let variable = match stacks
+                .iter()
+                .flat_map(|other| &other.imports)
+                .any(|import| import.stack == stack.name)
+            {
+                true => layout.variable,
+                false => "",
+            };
+            render_stack(
+                &layout
+                    .instantiation
+                    .replace("{{VARIABLE}}", variable)
+                    .replace("{{IMPORTS}}", &(layout.imports)(stack)),
+                &stack.name,
+                options,
+            )
+        })
+        .collect();
+
+    Ok(layout
+        .files
+        .iter()
+        .map(|(path, contents)| ProjectFile {
+            path: render_project(path, project_name, options),
+            contents: render_project(contents, project_name, options)
+                .replace("{{IMPORTS}}", &imports)
+                .replace("{{STACKS}}", &stacks),
+        })
+        .collect())
+}
+
+/// The construct ID of the stack named `stack_name` in the application, which is also the name it
+/// is deployed as.
+pub fn stack_id(language: &str, stack_name: &str) -> String {
+    match language {
+        // See `emit_instantiation` in the Go synthesizer.
+        "go" | "golang" => stack_name
+            .split("Stack")
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+        _ => stack_name.to_string(),
+    }
+}
+
+/// Derives the class name of a stack from the name of the template it was converted from, e.g.
+/// `my-bucket.yaml` becomes `MyBucketStack`.
+pub fn stack_class_name(file_name: &str) -> String {
+    let stem = file_name
+        .rsplit_once('.')
+        .map_or(file_name, |(stem, _)| stem);
+    let mut name = pascal_case(&stem.replace(|c: char| !c.is_ascii_alphanumeric(), " "));
+    // Class names can't start with a digit.
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name.insert_str(0, "Template");
+    }
+    if !name.ends_with("Stack") {
+        name.push_str("Stack");
+    }
+    name
+}
+
+// A Layout lists the files of a project, besides the stacks. The boilerplate is adapted from the
+// one used by the end-to-end tests (in `cdk-from-cfn-testing/boilerplate`), without anything that
+// only matters when running in that environment.
+struct Layout {
+    stack_file: &'static str,
+    // Rendered once per stack to replace `{{IMPORTS}}` and `{{STACKS}}` in the entry point. The
+    // instantiation starts with `variable` if other stacks import values from the stack, and the
+    // `{{IMPORTS}}` of the instantiation are the arguments passing these values.
+    import: &'static str,
+    instantiation: &'static str,
+    variable: &'static str,
+    imports: fn(&ProjectStack) -> String,
+    files: &'static [(&'static str, &'static str)],
+}
+
+impl Layout {
+    fn for_language(language: &str) -> Result<Self, Error> {
+        match language {
+            #[cfg(feature = "csharp")]
+            "csharp" => Ok(Self {
+                stack_file: "{{STACK_CLASS_NAME}}.cs",
+                import: "",
+                instantiation: "        {{VARIABLE}}new {{CSHARP_NAMESPACE}}.{{STACK_CLASS_NAME}}(app, \"{{STACK_NAME}}\"{{IMPORTS}});\n",
+                variable: "var {{STACK_VARIABLE}} = ",
+                imports: csharp_imports,
+                files: &[
+                    ("cdk.json", include_str!("csharp/cdk.json.template")),
+                    (
+                        "{{PROJECT_CLASS_NAME}}.csproj",
+                        include_str!("csharp/Project.csproj"),
+                    ),
+                    ("Program.cs", include_str!("csharp/Program.cs.template")),
+                ],
+            }),
+            #[cfg(feature = "golang")]
+            "go" | "golang" => Ok(Self {
+                stack_file: "{{STACK_FILE_NAME}}.go",
+                import: "",
+                instantiation: "\t{{VARIABLE}}add{{STACK_CLASS_NAME}}(app{{IMPORTS}})\n",
+                variable: "{{STACK_VARIABLE}} := ",
+                imports: golang_imports,
+                files: &[
+                    ("cdk.json", include_str!("golang/cdk.json")),
+                    ("go.mod", include_str!("golang/go.mod.template")),
+                    ("main.go", include_str!("golang/main.go.template")),
+                ],
+            }),
+            #[cfg(feature = "java")]
+            "java" => Ok(Self {
+                stack_file: "src/main/java/{{JAVA_PACKAGE_DIR}}/{{STACK_CLASS_NAME}}.java",
+                import: "",
+                instantiation: "        {{VARIABLE}}new {{STACK_CLASS_NAME}}(app, \"{{STACK_NAME}}\", StackProps.builder()\n            .build(){{IMPORTS}});\n",
+                variable: "{{STACK_CLASS_NAME}} {{STACK_VARIABLE}} = ",
+                imports: java_imports,
+                files: &[
+                    ("cdk.json", include_str!("java/cdk.json")),
+                    ("pom.xml", include_str!("java/pom.xml.template")),
+                    (
+                        "src/main/java/{{JAVA_PACKAGE_DIR}}/MyApp.java",
+                        include_str!("java/MyApp.java.template"),
+                    ),
+                ],
+            }),
+            #[cfg(feature = "python")]
+            "python" => Ok(Self {
+                stack_file: "{{PYTHON_MODULE_DIR}}{{STACK_CLASS_NAME}}.py",
+                import: "from {{PYTHON_MODULE_PREFIX}}{{STACK_CLASS_NAME}} import {{STACK_CLASS_NAME}}\n",
+                instantiation: "{{VARIABLE}}{{STACK_CLASS_NAME}}(app, '{{STACK_NAME}}'{{IMPORTS}})\n",
+                variable: "{{STACK_FILE_NAME}} = ",
+                imports: python_imports,
+                files: &[
+                    ("cdk.json", include_str!("python/cdk.json")),
+                    ("requirements.txt", include_str!("python/requirements.txt")),
+                    ("app.py", include_str!("python/app.py.template")),
+                ],
+            }),
+            #[cfg(feature = "typescript")]
+            "typescript" => Ok(Self {
+                stack_file: "{{STACK_CLASS_NAME}}.ts",
+                import: "import { {{STACK_CLASS_NAME}} } from \"./{{STACK_CLASS_NAME}}\";\n",
+                instantiation: "{{VARIABLE}}new {{STACK_CLASS_NAME}}(app, \"{{STACK_NAME}}\"{{IMPORTS}});\n",
+                variable: "const {{STACK_VARIABLE}} = ",
+                imports: typescript_imports,
+                files: &[
+                    ("cdk.json", include_str!("typescript/cdk.json")),
+                    (
+                        "package.json",
+                        include_str!("typescript/package.json.template"),
+                    ),
+                    ("tsconfig.json", include_str!("typescript/tsconfig.json")),
+                    ("app.ts", include_str!("typescript/app.ts.template")),
+                ],
+            }),
+            _ => Err(Error::UnsupportedLanguageError {
+                language: language.to_string(),
+            }),
+        }
+    }
+}
+
+/// Replaces the `{{VARIABLE}}` placeholders that refer to a stack.
+fn render_stack(template: &str, stack_name: &str, options: &SynthesizerOptions) -> String {
+    let namespace = options.csharp.namespace.as_deref().unwrap_or(stack_name);
+    render_options(template, options)
+        .replace("{{CSHARP_NAMESPACE}}", namespace)
+        .replace("{{STACK_CLASS_NAME}}", stack_name)
+        .replace("{{STACK_FILE_NAME}}", &snake_case(stack_name))
+        .replace("{{STACK_VARIABLE}}", &camel_case(stack_name))
+        .replace("{{STACK_NAME}}", stack_name)
+}
+
+#[cfg(feature = "csharp")]
+fn csharp_imports(stack: &ProjectStack) -> String {
+    if stack.imports.is_empty() {
+        return String::new();
+    }
+    // The namespace is replaced along with the other placeholders of the instantiation.
+    let mut props = format!(
+        ", new {{{{CSHARP_NAMESPACE}}}}.{name}Props\n        {{\n",
+        name = stack.name
+    );
+    for import in &stack.imports {
+        props.push_str(&format!(
+            "            {} = {}.{} as string,\n",
+            pascal_case(&import.parameter),
+            camel_case(&import.stack),
+            import.output
+        ));
+    }
+    props.push_str("        }");
+    props
+}
+
+// The add function of a Go stack takes the values it imports as arguments, in order.
+#[cfg(feature = "golang")]
+fn golang_imports(stack: &ProjectStack) -> String {
+    stack
+        .imports
+        .iter()
+        .map(|import| {
+            format!(
+                ", {}.{}.(*string)",
+                camel_case(&import.stack),
+                golang_identifier(&import.output, IdentifierKind::Exported)
+            )
+        })
+        .collect()
+}
+
+// Java stacks take their props positionally, after the StackProps.
+#[cfg(feature = "java")]
+fn java_imports(stack: &ProjectStack) -> String {
+    if stack.imports.is_empty() {
+        return String::new();
+    }
+    stack
+        .parameters
+        .iter()
+        .map(|parameter| {
+            match stack
+                .imports
+                .iter()
+                .find(|import| &import.parameter == parameter)
+            {
+                Some(import) => format!(
+                    ",\n            {}.get{}().toString()",
+                    camel_case(&import.stack),
+                    pascal_case(&import.output)
+                ),
+                None => ",\n            null".to_string(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(feature = "python")]
+fn python_imports(stack: &ProjectStack) -> String {
+    stack
+        .imports
+        .iter()
+        .map(|import| {
+            format!(
+                ",\n    {}={}.{}",
+                import.parameter,
+                snake_case(&import.stack),
+                snake_case(&import.output)
+            )
+        })
+        .collect()
+}
+
+#[cfg(feature = "typescript")]
+fn typescript_imports(stack: &ProjectStack) -> String {
+    if stack.imports.is_empty() {
+        return String::new();
+    }
+    let mut props = ", {\n".to_string();
+    for import in &stack.imports {
+        props.push_str(&format!(
+            "  {}: {}.{},\n",
+            pretty_name(&import.parameter),
+            camel_case(&import.stack),
+            pretty_name(&import.output)
+        ));
+    }
+    props.push('}');
+    props
+}
+
+/// Replaces the `{{VARIABLE}}` placeholders that refer to the whole application.
+fn render_project(template: &str, project_name: &str, options: &SynthesizerOptions) -> String {
+    let module = match &options.golang.module {
+        Some(module) => module.clone(),
+        None => kebab_case(project_name),
+    };
+    render_options(template, options)
+        .replace("{{GO_MODULE}}", &module)
+        .replace("{{PROJECT_CLASS_NAME}}", project_name)
+        .replace("{{PROJECT_NAME}}", &kebab_case(project_name))
+}
+
+/// Replaces the `{{VARIABLE}}` placeholders that refer to the options of a language.
+fn render_options(template: &str, options: &SynthesizerOptions) -> String {
+    let (python_prefix, python_dir) = match &options.python.module {
+        Some(module) => (
+            format!("{module}."),
+            format!("{}/", module.replace('.', "/")),
+        ),
+        None => (String::new(), String::new()),
+    };
+    template
+        .replace("{{JAVA_PACKAGE}}", &options.java.package)
+        .replace(
+            "{{JAVA_PACKAGE_DIR}}",
+            &options.java.package.replace('.', "/"),
+        )
+        .replace("{{GO_PACKAGE}}", &options.golang.package)
+        .replace("{{PYTHON_MODULE_PREFIX}}", &python_prefix)
+        .replace("{{PYTHON_MODULE_DIR}}", &python_dir)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,285 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use crate::cdk::Schema;
+use crate::ir::cross_stack::link_stacks;
+use crate::CloudformationParseTree;
+
+use super::*;
+
+fn template(source: &str) -> CloudformationProgramIr {
+    let template: CloudformationParseTree = serde_yaml::from_str(source).unwrap();
+    CloudformationProgramIr::from(template, Schema::builtin()).unwrap()
+}
+
+fn project(language: &str) -> Vec<ProjectFile> {
+    template(
+        r#"
+        Resources:
+          Queue:
+            Type: AWS::SQS::Queue
+        "#,
+    )
+    .synthesize_project(language, &SynthesizerOptions::new("QueueStack"))
+    .unwrap()
+}
+
+fn paths(files: &[ProjectFile]) -> Vec<&str> {
+    files.iter().map(|file| file.path.as_str()).collect()
+}
+
+#[test]
+fn typescript() {
+    let files = project("typescript");
+    assert_eq!(
+        paths(&files),
+        vec![
+            "QueueStack.ts",
+            "cdk.json",
+            "package.json",
+            "tsconfig.json",
+            "app.ts"
+        ]
+    );
+    assert!(files[0]
+        .contents
+        .contains("export class QueueStack extends cdk.Stack"));
+    assert!(files[2].contents.contains(r#""name": "queue-stack","#));
+    assert!(files[4]
+        .contents
+        .contains(r#"import { QueueStack } from "./QueueStack";"#));
+    assert!(files[4]
+        .contents
+        .contains(r#"new QueueStack(app, "QueueStack");"#));
+}
+
+#[test]
+fn golang() {
+    let files = project("go");
+    assert_eq!(
+        paths(&files),
+        vec!["queue_stack.go", "cdk.json", "go.mod", "main.go"]
+    );
+    assert!(files[0]
+        .contents
+        .contains("func addQueueStack(app cdk.App) *QueueStack {"));
+    assert!(!files[0].contents.contains("func main() {"));
+    assert!(files[2].contents.starts_with("module queue-stack\n"));
+    assert!(files[3].contents.contains("\taddQueueStack(app)\n"));
+}
+
+#[test]
+fn java() {
+    let files = project("java");
+    assert_eq!(
+        paths(&files),
+        vec![
+            "src/main/java/com/myorg/QueueStack.java",
+            "cdk.json",
+            "pom.xml",
+            "src/main/java/com/myorg/MyApp.java"
+        ]
+    );
+    assert!(files[2]
+        .contents
+        .contains("<artifactId>queue-stack</artifactId>"));
+    assert!(files[3]
+        .contents
+        .contains(r#"new QueueStack(app, "QueueStack", StackProps.builder()"#));
+}
+
+#[test]
+fn csharp() {
+    let files = project("csharp");
+    assert_eq!(
+        paths(&files),
+        vec![
+            "QueueStack.cs",
+            "cdk.json",
+            "QueueStack.csproj",
+            "Program.cs"
+        ]
+    );
+    assert!(files[1]
+        .contents
+        .contains("dotnet run --project ./QueueStack.csproj"));
+    assert!(files[3]
+        .contents
+        .contains(r#"new QueueStack.QueueStack(app, "QueueStack");"#));
+}
+
+#[test]
+fn python() {
+    let files = project("python");
+    assert_eq!(
+        paths(&files),
+        vec!["QueueStack.py", "cdk.json", "requirements.txt", "app.py"]
+    );
+    assert!(files[3]
+        .contents
+        .contains("from QueueStack import QueueStack\n"));
+}
+
+#[test]
+fn language_options() {
+    let mut options = SynthesizerOptions::new("QueueStack");
+    options.java.package = "com.example.infra".into();
+    options.golang.module = Some("example.com/infra".into());
+    options.golang.package = "infra".into();
+    options.csharp.namespace = Some("Example.Infra".into());
+    options.python.module = Some("infra.stacks".into());
+    let project = |language: &str| {
+        template(
+            r#"
+            Resources:
+              Queue:
+                Type: AWS::SQS::Queue
+            "#,
+        )
+        .synthesize_project(language, &options)
+        .unwrap()
+    };
+
+    let files = project("java");
+    assert_eq!(
+        paths(&files),
+        vec![
+            "src/main/java/com/example/infra/QueueStack.java",
+            "cdk.json",
+            "pom.xml",
+            "src/main/java/com/example/infra/MyApp.java"
+        ]
+    );
+    assert!(files[0]
+        .contents
+        .starts_with("package com.example.infra;\n"));
+    assert!(files[2]
+        .contents
+        .contains("<mainClass>com.example.infra.MyApp</mainClass>"));
+    assert!(files[3]
+        .contents
+        .starts_with("package com.example.infra;\n"));
+
+    let files = project("go");
+    assert!(files[0].contents.starts_with("package infra\n"));
+    assert!(files[2].contents.starts_with("module example.com/infra\n"));
+    assert!(files[3].contents.starts_with("package infra\n"));
+
+    let files = project("csharp");
+    assert!(files[0].contents.contains("namespace Example.Infra\n{"));
+    assert!(files[3]
+        .contents
+        .contains(r#"new Example.Infra.QueueStack(app, "QueueStack");"#));
+
+    let files = project("python");
+    assert_eq!(files[0].path, "infra/stacks/QueueStack.py");
+    assert!(files[3]
+        .contents
+        .contains("from infra.stacks.QueueStack import QueueStack\n"));
+}
+
+#[test]
+fn several_stacks() {
+    let stacks = [
+        ProjectStack::new("BucketStack", &CloudformationProgramIr::default()),
+        ProjectStack::new("QueueStack", &CloudformationProgramIr::default()),
+    ];
+    let options = SynthesizerOptions::new("");
+    let files = project_files("typescript", "Infra", &stacks, &options).unwrap();
+    assert!(files[1].contents.contains(r#""name": "infra","#));
+    assert!(files[3].contents.contains(
+        "import { BucketStack } from \"./BucketStack\";\nimport { QueueStack } from \"./QueueStack\";\n"
+    ));
+    assert!(files[3].contents.contains(
+        "new BucketStack(app, \"BucketStack\");\nnew QueueStack(app, \"QueueStack\");\n"
+    ));
+
+    let files = project_files("csharp", "Infra", &stacks, &options).unwrap();
+    assert_eq!(
+        paths(&files),
+        vec!["cdk.json", "Infra.csproj", "Program.cs"]
+    );
+}
+
+fn linked_stacks() -> Vec<ProjectStack> {
+    let mut stacks = vec![
+        (
+            "QueueStack".to_string(),
+            template(
+                r#"
+            Parameters:
+              Retention:
+                Type: Number
+                Default: 60
+            Resources:
+              Queue:
+                Type: AWS::SQS::Queue
+                Properties:
+                  KmsMasterKeyId: !ImportValue key-arn
+            "#,
+            ),
+        ),
+        (
+            "KeyStack".to_string(),
+            template(
+                r#"
+            Resources:
+              Key:
+                Type: AWS::KMS::Key
+            Outputs:
+              KeyArn:
+                Value: !GetAtt Key.Arn
+                Export:
+                  Name: key-arn
+            "#,
+            ),
+        ),
+    ];
+    assert!(link_stacks(&mut stacks, str::to_string).is_empty());
+    stacks
+        .iter()
+        .map(|(name, ir)| ProjectStack::new(name, ir))
+        .collect()
+}
+
+#[test]
+fn cross_stack_imports() {
+    let stacks = linked_stacks();
+    let options = SynthesizerOptions::new("");
+    let entry_point = |language: &str| {
+        project_files(language, "Infra", &stacks, &options)
+            .unwrap()
+            .pop()
+            .unwrap()
+            .contents
+    };
+
+    assert!(entry_point("typescript").contains(
+        "const keyStack = new KeyStack(app, \"KeyStack\");\nnew QueueStack(app, \"QueueStack\", {\n  keyArn: keyStack.keyArn,\n});\n"
+    ));
+    assert!(entry_point("python").contains(
+        "key_stack = KeyStack(app, 'KeyStack')\nQueueStack(app, 'QueueStack',\n    keyArn=key_stack.key_arn)\n"
+    ));
+    assert!(entry_point("java").contains(
+        "        new QueueStack(app, \"QueueStack\", StackProps.builder()\n            .build(),\n            null,\n            keyStack.getKeyArn().toString());\n"
+    ));
+    assert!(entry_point("csharp").contains("            KeyArn = keyStack.KeyArn as string,\n"));
+    assert!(entry_point("go").contains(
+        "\tkeyStack := addKeyStack(app)\n\taddQueueStack(app, keyStack.KeyArn.(*string))\n"
+    ));
+}
+
+#[test]
+fn stack_class_names() {
+    assert_eq!(stack_class_name("my-bucket.yaml"), "MyBucketStack");
+    assert_eq!(stack_class_name("network_stack.json"), "NetworkStack");
+    assert_eq!(stack_class_name("vpc.v2.template"), "VpcV2Stack");
+    assert_eq!(stack_class_name("01-queue.yml"), "Template01QueueStack");
+}
+
+#[test]
+fn unsupported_language() {
+    let err = CloudformationProgramIr::default()
+        .synthesize_project("cobol", &SynthesizerOptions::new("Stack"))
+        .unwrap_err();
+    assert_eq!(err.to_string(), "cobol is not a supported language");
+}
@@ -3,23 +3,29 @@
 use crate::cdk::TypeReference;
 use crate::code::{CodeBuffer, IndentOptions};
 use crate::ir::conditions::ConditionIr;
-use crate::ir::constructor::ConstructorParameter;
+use crate::ir::constructor::{
+    ConstructorParameter, ConstructorType, DefaultValue, ParameterConstraint, PropValidation,
+};
+use crate::ir::constructs::{L2Construct, L2Value};
 use crate::ir::importer::ImportInstruction;
 use crate::ir::mappings::MappingInstruction;
 use crate::ir::outputs::OutputInstruction;
 use crate::ir::reference::{Origin, PseudoParameter, Reference};
-use crate::ir::resources::{ResourceInstruction, ResourceIr};
+use crate::ir::resources::{CreationPolicyIr, ResourceInstruction, ResourceIr};
+use crate::ir::rules::{RuleInstruction, RuleIr, RuleValueIr};
 use crate::ir::CloudformationProgramIr;
 use crate::parser::lookup_table::MappingInnerValue;
+use crate::util::Hasher;
 use crate::Error;
 use indexmap::IndexMap;
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::io;
 use std::rc::Rc;
-use voca_rs::case::{camel_case, pascal_case, snake_case};
+use voca_rs::case::{camel_case, snake_case};
 use voca_rs::Voca;
 
-use super::Synthesizer;
+use super::{ConstructOptions, Synthesizer, SynthesizerOptions};
 
 const INDENT: Cow<'static, str> = Cow::Borrowed("  ");
 
@@ -32,7 +38,26 @@ const KEYWORDS: &[&str] = &[
     "or", "yield",
 ];
 
-pub struct Python {}
+#[derive(Default)]
+pub struct Python {
+    construct: Option<ConstructOptions>,
+}
+
+impl Python {
+    /// Declares a construct instead of a stack (nested stacks are still declared as such).
+    pub fn as_construct(mut self, options: ConstructOptions) -> Self {
+        self.construct = Some(options);
+        self
+    }
+}
+
+impl From<&SynthesizerOptions> for Python {
+    fn from(options: &SynthesizerOptions) -> Self {
+        Self {
+            construct: options.construct,
+        }
+    }
+}
 
 impl Synthesizer for Python {
     fn synthesize(
@@ -43,8 +68,12 @@ impl Synthesizer for Python {
     ) -> Result<(), Error> {
         let code = CodeBuffer::default();
 
+        let nested_stacks = ir.nested_stacks();
         let imports = code.section(true);
-        imports.line("from aws_cdk import Stack");
+        imports.line(match nested_stacks.is_empty() {
+            true => "from aws_cdk import Stack",
+            false => "from aws_cdk import NestedStack, Stack",
+        });
         for import in &ir.imports {
             imports.line(import.to_python()?);
         }
@@ -52,175 +81,284 @@ impl Synthesizer for Python {
 
         let context = &mut PythonContext::with_imports(imports);
 
-        if let Some(description) = &ir.description {
-            let comment = code.pydoc();
-            comment.line(description.to_owned());
+        for nested in nested_stacks {
+            emit_stack(
+                context,
+                &code,
+                &nested.program,
+                &nested.class_name,
+                true,
+                None,
+            );
         }
-        let class = code.indent_with_options(IndentOptions {
+        emit_stack(context, &code, &ir, stack_name, false, self.construct);
+
+        Ok(code.write(output)?)
+    }
+}
+
+// emit_stack declares the class of a stack. Nested stacks extend `NestedStack` rather than `Stack`,
+// and don't forward their props to it. Constructs extend `Construct`, and leave the stack-wide
+// settings of the template to the stack they are added to.
+fn emit_stack(
+    context: &mut PythonContext,
+    code: &CodeBuffer,
+    ir: &CloudformationProgramIr,
+    stack_name: &str,
+    nested: bool,
+    construct: Option<ConstructOptions>,
+) {
+    let base_class = match (nested, construct) {
+        (_, Some(_)) => "Construct",
+        (true, None) => "NestedStack",
+        (false, None) => "Stack",
+    };
+    context.nested_stacks = ir
+        .resources
+        .iter()
+        .filter(|resource| resource.nested_stack.is_some())
+        .map(|resource| resource.name.clone())
+        .collect();
+
+    if let Some(description) = &ir.description {
+        let comment = code.pydoc();
+        comment.line(pydoc_text(description));
+    }
+    let class = code.indent_with_options(IndentOptions {
+        indent: INDENT,
+        leading: Some(format!("class {stack_name}({base_class}):").into()),
+        trailing: Some("".into()),
+        trailing_newline: true,
+    });
+
+    let ctor = class.indent_with_options(IndentOptions {
+        indent: INDENT,
+        leading: Some(
+            "def __init__(self, scope: Construct, construct_id: str, **kwargs) -> None:"
+                .to_string()
+                .into(),
+        ),
+        trailing: Some("".into()),
+        trailing_newline: true,
+    });
+    // Required props are not accepted by Stack, so they are taken out of kwargs before it is
+    // initialized.
+    let required_params = ir
+        .constructor
+        .inputs
+        .iter()
+        .filter(|p| !p.is_cfn_parameter() && p.default_value.is_none())
+        .collect::<Vec<&ConstructorParameter>>();
+    if !required_params.is_empty() {
+        ctor.line("# Required props");
+        let obj = ctor.indent_with_options(IndentOptions {
             indent: INDENT,
-            leading: Some(format!("class {stack_name}(Stack):").into()),
-            trailing: Some("".into()),
+            leading: Some("props = {".into()),
+            trailing: Some("}".into()),
             trailing_newline: true,
         });
+        for param in &required_params {
+            obj.comments("#", &param.comments);
+            obj.line(format!(
+                "'{name}': kwargs.pop('{name}'),",
+                name = param.name
+            ));
+        }
+    }
+    ctor.line(match nested || construct.is_some() {
+        true => "super().__init__(scope, construct_id)",
+        false => "super().__init__(scope, construct_id, **kwargs)",
+    });
 
-        let ctor = class.indent_with_options(IndentOptions {
+    let have_default_or_special_type_params = &ir
+        .constructor
+        .inputs
+        .iter()
+        .filter(|p| p.is_cfn_parameter() || p.default_value.is_some())
+        .collect::<Vec<&ConstructorParameter>>();
+    if !have_default_or_special_type_params.is_empty() {
+        ctor.newline();
+        // props are handled weirdly in python. Python doesn't have interfaces so we try and retrieve
+        // the props from kwargs, and default to None or a default value if one is given.
+        ctor.line("# Applying default props");
+        let (leading, trailing) = match required_params.is_empty() {
+            true => ("props = {", "}"),
+            false => ("props.update({", "})"),
+        };
+        let obj = ctor.indent_with_options(IndentOptions {
             indent: INDENT,
-            leading: Some(
-                "def __init__(self, scope: Construct, construct_id: str, **kwargs) -> None:"
-                    .to_string()
-                    .into(),
-            ),
-            trailing: Some("".into()),
+            leading: Some(leading.into()),
+            trailing: Some(trailing.into()),
             trailing_newline: true,
         });
-        ctor.line("super().__init__(scope, construct_id, **kwargs)");
-
-        let have_default_or_special_type_params = &ir
-            .constructor
-            .inputs
-            .iter()
-            .filter(|p| p.constructor_type.contains("AWS::") || p.default_value.is_some())
-            .collect::<Vec<&ConstructorParameter>>();
-        if !have_default_or_special_type_params.is_empty() {
-            ctor.newline();
-            // props are handled weirdly in python. Python doesn't have interfaces so we try and retrieve
-            // the props from kwargs, and default to None or a default value if one is given.
-            ctor.line("# Applying default props");
-            let obj = ctor.indent_with_options(IndentOptions {
-                indent: INDENT,
-                leading: Some("props = {".into()),
-                trailing: Some("}".into()),
-                trailing_newline: true,
-            });
-            for param in have_default_or_special_type_params {
-                let name = &param.name;
-                if param.constructor_type.contains("AWS::")
-                    || param.no_echo.as_ref().is_some_and(|x| x == "true")
-                {
-                    let cfn_param = obj.indent_with_options(IndentOptions {
-                        indent: INDENT,
-                        leading: Some(
-                            format!(
-                                "'{name}': cdk.CfnParameter(self, '{}', ",
-                                camel_case(&param.name)
-                            )
-                            .into(),
-                        ),
-                        trailing: Some("),".into()),
-                        trailing_newline: true,
-                    });
-                    cfn_param.line(format!("type = '{}',", param.constructor_type));
-                    if let Some(v) = &param.default_value {
-                        cfn_param.line(format!(
-                            "default = str(kwargs.get('{name}', '{}')),",
-                            v.escape_debug()
-                        ));
-                    } else {
-                        cfn_param.line(format!("default = str(kwargs.get('{name}')),"));
-                    };
-                    if let Some(v) = &param.description {
-                        cfn_param.line(format!("description = '{v}',"));
-                    };
-                    if let Some(v) = &param.no_echo {
-                        cfn_param.line(format!("no_echo = {},", v._pascal_case()));
-                    };
+        for param in have_default_or_special_type_params {
+            let name = &param.name;
+            obj.comments("#", &param.comments);
+            if param.ssm_lookup {
+                let name_value = match &param.default_value {
+                    Some(v) => {
+                        format!("kwargs.get('{name}', '{}')", v.to_string().escape_debug())
+                    }
+                    None => format!("kwargs.get('{name}')"),
+                };
+                obj.line(format!(
+                    "'{name}': ssm.StringParameter.value_for_string_parameter(self, {name_value}),"
+                ));
+            } else if param.is_cfn_parameter() {
+                let value_as = match param.constructor_type.is_list() {
+                    true => "value_as_list",
+                    false => "value_as_string",
+                };
+                let cfn_param = obj.indent_with_options(IndentOptions {
+                    indent: INDENT,
+                    leading: Some(
+                        format!("'{name}': cdk.CfnParameter(self, '{}',", param.logical_id).into(),
+                    ),
+                    trailing: Some(format!(").{value_as},").into()),
+                    trailing_newline: true,
+                });
+                cfn_param.line(format!("type = '{}',", param.constructor_type));
+                if let Some(v) = &param.default_value {
+                    cfn_param.line(format!(
+                        "default = str(kwargs.get('{name}', '{}')),",
+                        v.to_string().escape_debug()
+                    ));
                 } else {
-                    let value = match &param.default_value {
-                        None => "".to_owned(),
-                        Some(value) => {
-                            let value = match param.constructor_type.as_str() {
-                                "String" => format!("'{}'", value.escape_debug()),
-                                "List<Number>" => format!("[{value}]"),
-                                "CommaDelimitedList" => format!(
-                                    "[{}]",
-                                    value
-                                        .split(',')
-                                        .map(|v| format!("'{}'", v.escape_debug()))
-                                        .collect::<Vec<String>>()
-                                        .join(",")
-                                ),
-                                "Boolean" => pascal_case(value),
-                                _ => value.clone(),
-                            };
-                            value
-                        }
-                    };
-
-                    obj.line(format!("'{name}': kwargs.get('{name}', {value}),"));
+                    cfn_param.line(format!("default = str(kwargs.get('{name}')),"));
+                };
+                if let Some(v) = &param.description {
+                    cfn_param.line(format!("description = '{v}',"));
+                };
+                emit_parameter_constraints(&cfn_param, param);
+                if let Some(v) = &param.no_echo {
+                    cfn_param.line(format!("no_echo = {},", v._pascal_case()));
+                };
+            } else {
+                let value = match &param.default_value {
+                    None => "".to_owned(),
+                    Some(value) => value.to_python(),
                 };
-            }
-        }
-
-        if !ir.transforms.is_empty() {
-            ctor.newline();
-            ctor.line("# Transforms");
 
-            for transform in &ir.transforms {
-                ctor.line(format!("Stack.add_transform(self, '{transform}')"))
-            }
+                obj.line(format!("'{name}': kwargs.get('{name}', {value}),"));
+            };
         }
+    }
 
-        emit_mappings(&ctor, &ir.mappings);
+    emit_parameter_validation(context, &ctor, &ir.constructor.validations());
 
-        if !ir.conditions.is_empty() {
-            ctor.newline();
-            ctor.line("# Conditions");
+    // The template options are those of the stack, which a construct does not own.
+    if construct.is_none() {
+        emit_template_options(context, &ctor, ir);
+    }
 
-            for cond in &ir.conditions {
-                let synthed = synthesize_condition_recursive(&cond.value);
-                ctor.line(format!("{} = {}", snake_case(&cond.name), synthed));
-            }
+    if !ir.transforms.is_empty() {
+        ctor.newline();
+        ctor.line("# Transforms");
+
+        for transform in &ir.transforms {
+            ctor.line(match construct {
+                Some(_) => format!("Stack.of(self).add_transform('{transform}')"),
+                None => format!("Stack.add_transform(self, '{transform}')"),
+            })
         }
+    }
 
+    emit_mappings(&ctor, &ir.mappings);
+
+    if !ir.conditions.is_empty() {
         ctor.newline();
-        ctor.line("# Resources");
+        ctor.line("# Conditions");
 
-        let mut is_first_resource = true;
-        for reference in &ir.resources {
-            if is_first_resource {
-                is_first_resource = false;
-            } else {
-                ctor.newline();
-            }
-            emit_resource(context, &ctor, reference);
+        for cond in &ir.conditions {
+            let synthed = synthesize_condition_recursive(&cond.value);
+            ctor.line(format!("{} = {}", snake_case(&cond.name), synthed));
         }
+    }
+
+    emit_rules(&ctor, &ir.rules);
 
-        if !ir.outputs.is_empty() {
+    ctor.newline();
+    ctor.line("# Resources");
+
+    let mut is_first_resource = true;
+    for reference in &ir.resources {
+        if is_first_resource {
+            is_first_resource = false;
+        } else {
             ctor.newline();
-            ctor.line("# Outputs");
+        }
+        emit_resource(context, &ctor, reference);
+    }
+
+    if !ir.outputs.is_empty() {
+        ctor.newline();
+        ctor.line("# Outputs");
 
-            for op in &ir.outputs {
-                let var_name = snake_case(&op.name);
-                let cond = op.condition.as_ref().map(|s| snake_case(s));
+        for op in &ir.outputs {
+            let var_name = snake_case(&op.name);
+            let cond = op.condition.as_ref().map(|s| snake_case(s));
+            if op.description.is_some() || !op.comments.is_empty() {
+                let comment = ctor.pydoc();
                 if let Some(description) = &op.description {
-                    let comment = ctor.pydoc();
-                    comment.line(description.to_owned());
+                    comment.line(pydoc_text(description));
                 }
-                if let Some(cond) = &cond {
-                    ctor.text(format!("self.{var_name} = "));
-                    emit_resource_ir(context, &ctor, &op.value, Some(""));
-                    ctor.line(format!(" if {cond} else None"));
-                } else {
-                    ctor.text(format!("self.{var_name} = "));
-                    emit_resource_ir(context, &ctor, &op.value, Some("\n"));
+                for line in &op.comments {
+                    comment.line(pydoc_text(line));
                 }
+            }
+            if let Some(cond) = &cond {
+                ctor.text(format!("self.{var_name} = "));
+                emit_resource_ir(context, &ctor, &op.value, Some(""));
+                ctor.line(format!(" if {cond} else None"));
+            } else {
+                ctor.text(format!("self.{var_name} = "));
+                emit_resource_ir(context, &ctor, &op.value, Some("\n"));
+            }
 
-                if let Some(cond) = cond {
-                    let indented = ctor.indent_with_options(IndentOptions {
-                        indent: INDENT,
-                        leading: Some(format!("if ({cond}):").into()),
-                        trailing: Some("".into()),
-                        trailing_newline: true,
-                    });
-                    emit_cfn_output(context, &indented, op, &var_name);
-                } else {
-                    emit_cfn_output(context, &ctor, op, &var_name);
-                }
-                ctor.newline();
+            if construct.is_some_and(|construct| !construct.cfn_outputs) {
+                continue;
             }
+            if let Some(cond) = cond {
+                let indented = ctor.indent_with_options(IndentOptions {
+                    indent: INDENT,
+                    leading: Some(format!("if ({cond}):").into()),
+                    trailing: Some("".into()),
+                    trailing_newline: true,
+                });
+                emit_cfn_output(context, &indented, op, &var_name);
+            } else {
+                emit_cfn_output(context, &ctor, op, &var_name);
+            }
+            ctor.newline();
         }
+    }
 
-        Ok(code.write(output)?)
+    if construct.is_some() {
+        emit_pseudo_parameters(&class, ir);
+    }
+}
+
+// emit_pseudo_parameters declares the pseudo parameters a construct uses as properties, so that
+// they are referred to the same way as in a stack, but resolved by the stack the construct is in.
+fn emit_pseudo_parameters(class: &CodeBuffer, ir: &CloudformationProgramIr) {
+    for pseudo in ir.pseudo_parameters() {
+        let (name, value_type) = match pseudo {
+            PseudoParameter::Partition => ("partition", "str"),
+            PseudoParameter::Region => ("region", "str"),
+            PseudoParameter::StackId => ("stack_id", "str"),
+            PseudoParameter::StackName => ("stack_name", "str"),
+            PseudoParameter::URLSuffix => ("url_suffix", "str"),
+            PseudoParameter::AccountId => ("account", "str"),
+            PseudoParameter::NotificationArns => ("notification_arns", "list[str]"),
+        };
+        class.line("@property");
+        let property = class.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some(format!("def {name}(self) -> {value_type}:").into()),
+            trailing: Some("".into()),
+            trailing_newline: true,
+        });
+        property.line(format!("return Stack.of(self).{name}"));
     }
 }
 
@@ -279,13 +417,20 @@ impl ImportInstruction {
 struct PythonContext {
     imports: Rc<CodeBuffer>,
     imports_base64: bool,
+    imports_json: bool,
+    imports_re: bool,
+    // The resources of the stack being emitted that are nested stacks.
+    nested_stacks: HashSet<String>,
 }
 
 impl PythonContext {
-    const fn with_imports(imports: Rc<CodeBuffer>) -> Self {
+    fn with_imports(imports: Rc<CodeBuffer>) -> Self {
         Self {
             imports,
             imports_base64: false,
+            imports_json: false,
+            imports_re: false,
+            nested_stacks: HashSet::new(),
         }
     }
 
@@ -296,6 +441,28 @@ impl PythonContext {
         self.imports.line("import base64");
         self.imports_base64 = true;
     }
+
+    fn import_json(&mut self) {
+        if self.imports_json {
+            return;
+        }
+        self.imports.line("import json");
+        self.imports_json = true;
+    }
+
+    fn import_re(&mut self) {
+        if self.imports_re {
+            return;
+        }
+        self.imports.line("import re");
+        self.imports_re = true;
+    }
+}
+
+// Keeps `text` from closing the docstring it is written in, and its backslashes from being read as
+// escape sequences.
+fn pydoc_text(text: &str) -> String {
+    text.replace('\\', "\\\\").replace("\"\"\"", "\\\"\\\"\\\"")
 }
 
 trait PythonCodeBuffer {
@@ -314,6 +481,113 @@ impl PythonCodeBuffer for CodeBuffer {
     }
 }
 
+fn emit_parameter_constraints(output: &CodeBuffer, param: &ConstructorParameter) {
+    if let Some(values) = &param.allowed_values {
+        let values = values
+            .iter()
+            .map(|v| format!("'{}'", v.escape_debug()))
+            .collect::<Vec<_>>();
+        output.line(format!("allowed_values = [{}],", values.join(", ")));
+    }
+    if let Some(v) = &param.allowed_pattern {
+        output.line(format!("allowed_pattern = '{}',", v.escape_debug()));
+    }
+    if let Some(v) = &param.constraint_description {
+        output.line(format!("constraint_description = '{}',", v.escape_debug()));
+    }
+    if let Some(v) = param.min_length {
+        output.line(format!("min_length = {v},"));
+    }
+    if let Some(v) = param.max_length {
+        output.line(format!("max_length = {v},"));
+    }
+    if let Some(v) = param.min_value {
+        output.line(format!("min_value = {v},"));
+    }
+    if let Some(v) = param.max_value {
+        output.line(format!("max_value = {v},"));
+    }
+}
+
+fn emit_parameter_validation(
+    context: &mut PythonContext,
+    output: &CodeBuffer,
+    validations: &[PropValidation],
+) {
+    if validations.is_empty() {
+        return;
+    }
+
+    output.newline();
+    output.line("# Validating props");
+    for validation in validations {
+        let param = validation.param;
+        let value = format!("props['{}']", param.name);
+        let block = output.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some(
+                format!("if {value} is not None and not cdk.Token.is_unresolved({value}):").into(),
+            ),
+            trailing: None,
+            trailing_newline: false,
+        });
+        for check in &validation.checks {
+            let violated = match &check.constraint {
+                ParameterConstraint::AllowedValues(values) => {
+                    let values = values
+                        .iter()
+                        .map(|v| python_string(v))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    match param.constructor_type {
+                        ConstructorType::Number => format!("str({value}) not in [{values}]"),
+                        _ => format!("{value} not in [{values}]"),
+                    }
+                }
+                ParameterConstraint::AllowedPattern(pattern) => {
+                    context.import_re();
+                    format!("not re.fullmatch({}, {value})", python_string(pattern))
+                }
+                ParameterConstraint::MinLength(min) => format!("len({value}) < {min}"),
+                ParameterConstraint::MaxLength(max) => format!("len({value}) > {max}"),
+                ParameterConstraint::MinValue(min) => format!("float({value}) < {min}"),
+                ParameterConstraint::MaxValue(max) => format!("float({value}) > {max}"),
+            };
+            block
+                .indent_with_options(IndentOptions {
+                    indent: INDENT,
+                    leading: Some(format!("if {violated}:").into()),
+                    trailing: None,
+                    trailing_newline: false,
+                })
+                .line(format!(
+                    "raise ValueError({})",
+                    python_string(&check.message)
+                ));
+        }
+    }
+}
+
+// Quotes a string as a Python literal. Control characters are written as `\xNN`, where
+// `escape_debug` would write `\u{..}`, which Python does not understand.
+fn python_string(value: &str) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push('\'');
+    for c in value.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '\'' => literal.push_str("\\'"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c if c.is_control() => literal.push_str(&format!("\\x{:02x}", u32::from(c))),
+            c => literal.push(c),
+        }
+    }
+    literal.push('\'');
+    literal
+}
+
 fn emit_mappings(output: &CodeBuffer, mappings: &[MappingInstruction]) {
     if mappings.is_empty() {
         return;
@@ -425,13 +699,163 @@ fn synthesize_condition_recursive(val: &ConditionIr) -> String {
     }
 }
 
+fn emit_rules(output: &CodeBuffer, rules: &[RuleInstruction]) {
+    if rules.is_empty() {
+        return;
+    }
+
+    output.newline();
+    output.line("# Rules");
+
+    for rule in rules {
+        let output = output.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some(format!("cdk.CfnRule(self, '{}',", rule.name.escape_debug()).into()),
+            trailing: Some(")".into()),
+            trailing_newline: true,
+        });
+        if let Some(condition) = &rule.condition {
+            output.line(format!(
+                "rule_condition = {},",
+                synthesize_rule_recursive(condition)
+            ));
+        }
+        if rule.assertions.is_empty() {
+            continue;
+        }
+        let assertions = output.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some("assertions = [".into()),
+            trailing: Some("],".into()),
+            trailing_newline: true,
+        });
+        for assertion in &rule.assertions {
+            let assertion_output = assertions.indent_with_options(IndentOptions {
+                indent: INDENT,
+                leading: Some("cdk.CfnRuleAssertion(".into()),
+                trailing: Some("),".into()),
+                trailing_newline: true,
+            });
+            assertion_output.line(format!(
+                "assert_ = {},",
+                synthesize_rule_recursive(&assertion.assert)
+            ));
+            // The CDK requires a description, even though CloudFormation does not.
+            assertion_output.line(format!(
+                "assert_description = '{}',",
+                assertion
+                    .description
+                    .as_deref()
+                    .unwrap_or_default()
+                    .escape_debug()
+            ));
+        }
+    }
+}
+
+fn synthesize_rule_recursive(val: &RuleIr) -> String {
+    match val {
+        RuleIr::And(x) => format!(
+            "cdk.Fn.condition_and({})",
+            x.iter()
+                .map(synthesize_rule_recursive)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        RuleIr::Or(x) => format!(
+            "cdk.Fn.condition_or({})",
+            x.iter()
+                .map(synthesize_rule_recursive)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        RuleIr::Not(x) => format!("cdk.Fn.condition_not({})", synthesize_rule_recursive(x)),
+        RuleIr::Equals(a, b) => format!(
+            "cdk.Fn.condition_equals({}, {})",
+            synthesize_rule_value(a, false),
+            synthesize_rule_value(b, false)
+        ),
+        RuleIr::Contains(a, b) => format!(
+            "cdk.Fn.condition_contains({}, {})",
+            synthesize_rule_value(a, true),
+            synthesize_rule_value(b, false)
+        ),
+        RuleIr::EachMemberEquals(a, b) => format!(
+            "cdk.Fn.condition_each_member_equals({}, {})",
+            synthesize_rule_value(a, true),
+            synthesize_rule_value(b, false)
+        ),
+        RuleIr::EachMemberIn(a, b) => format!(
+            "cdk.Fn.condition_each_member_in({}, {})",
+            synthesize_rule_value(a, true),
+            synthesize_rule_value(b, true)
+        ),
+    }
+}
+
+/// Renders a rule operand. `as_list` is set where the CDK expects a list, as `Fn::ValueOf` is
+/// typed as a string even when the attribute holds a list.
+fn synthesize_rule_value(val: &RuleValueIr, as_list: bool) -> String {
+    match val {
+        RuleValueIr::Str(x) => format!("'{}'", x.escape_debug()),
+        RuleValueIr::List(x) => format!(
+            "[{}]",
+            x.iter()
+                .map(|val| synthesize_rule_value(val, false))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        RuleValueIr::Ref(x) => x.to_python().into(),
+        RuleValueIr::RefAll(x) => format!("cdk.Fn.ref_all('{}')", x.escape_debug()),
+        RuleValueIr::ValueOf(name, attribute) => {
+            let value = format!(
+                "cdk.Fn.value_of('{}', '{}')",
+                name.escape_debug(),
+                attribute.escape_debug()
+            );
+            if as_list {
+                format!("cdk.Token.as_list({value})")
+            } else {
+                value
+            }
+        }
+        RuleValueIr::ValueOfAll(x, attribute) => format!(
+            "cdk.Fn.value_of_all('{}', '{}')",
+            x.escape_debug(),
+            attribute.escape_debug()
+        ),
+    }
+}
+
+impl DefaultValue {
+    fn to_python(&self) -> String {
+        match self {
+            DefaultValue::String(value) => format!("'{}'", value.escape_debug()),
+            // Number props are strings, as in the other languages, but List<Number> props are
+            // lists of numbers
+            DefaultValue::Number(value) => format!("'{value}'"),
+            DefaultValue::Bool(true) => "True".into(),
+            DefaultValue::Bool(false) => "False".into(),
+            DefaultValue::List(items) => {
+                let items = items
+                    .iter()
+                    .map(|item| match item {
+                        DefaultValue::Number(value) => value.to_string(),
+                        other => other.to_python(),
+                    })
+                    .collect::<Vec<_>>();
+                format!("[{}]", items.join(", "))
+            }
+        }
+    }
+}
+
 impl Reference {
     fn to_python(&self) -> Cow<'static, str> {
         match &self.origin {
-            Origin::CfnParameter => {
-                format!("props['{}'].value_as_string", camel_case(&self.name)).into()
+            Origin::CfnParameter | Origin::Parameter => {
+                format!("props['{}']", camel_case(&self.name)).into()
             }
-            Origin::Parameter => format!("props['{}']", camel_case(&self.name)).into(),
             Origin::LogicalId { conditional: _ } => {
                 format!("{var}{chain}ref", var = camel_case(&self.name), chain = ".").into()
             }
@@ -455,6 +879,21 @@ impl Reference {
                 name = snake_case(attribute)
             )
             .into(),
+            Origin::NestedStackOutput {
+                output,
+                conditional: _,
+            } => format!(
+                "{var_name}.{name}",
+                var_name = camel_case(&self.name),
+                name = snake_case(output)
+            )
+            .into(),
+            Origin::ConstructAttribute { attribute } => format!(
+                "{var_name}.{name}",
+                var_name = camel_case(&self.name),
+                name = snake_case(attribute)
+            )
+            .into(),
         }
     }
 }
@@ -465,10 +904,73 @@ fn emit_resource(
     reference: &ResourceInstruction,
 ) {
     let var_name = camel_case(&reference.name);
-    // lambda is a reserved keyword in python. If we encounter it or another keyword, we prepend 'aws_'
-    let mut service: String = reference.resource_type.service().to_lowercase();
-    if KEYWORDS.contains(&service.as_str()) {
-        service = format!("aws_{}", reference.resource_type.service().to_lowercase());
+    output.comments("#", &reference.comments);
+    if let Some(nested) = &reference.nested_stack {
+        output.line(format!(
+            "{var_name} = {}(self, '{}',",
+            nested.class_name,
+            reference.name.escape_debug(),
+        ));
+        let mid_output = output.indent(INDENT);
+        // The nested stack reads its props from kwargs, by their camelCase name.
+        let props = mid_output.indent(INDENT);
+        for (name, value) in &nested.parameters {
+            props.text(format!("{name} = "));
+            emit_resource_ir(context, &props, value, Some(",\n"));
+        }
+        mid_output.line(")");
+        // The resource attributes apply to the CfnStack deploying the nested stack.
+        emit_cfn_options(
+            context,
+            output,
+            reference,
+            &format!("{var_name}.nested_stack_resource"),
+        );
+        for dependency in &reference.dependencies {
+            output.line(format!(
+                "{var_name}.node.add_dependency({})",
+                camel_case(dependency)
+            ));
+        }
+        return;
+    }
+    let service = module_name(reference.resource_type.service());
+    if let Some(construct) = &reference.l2_construct {
+        output.line(format!(
+            "{var_name} = {service}.{}(self, '{}',",
+            construct.class,
+            reference.name.escape_debug(),
+        ));
+        let mid_output = output.indent(INDENT);
+        let props = mid_output.indent(INDENT);
+        for (name, value) in &construct.props {
+            if let Some(comments) = reference.property_comments.get(name) {
+                props.comments("#", comments);
+            }
+            props.text(format!("{} = ", snake_case(name)));
+            emit_l2_value(context, &props, value, Some(",\n"));
+        }
+        mid_output.line(")");
+
+        // The L1 resource keeps the logical ID of the template, and what the construct can't express
+        let cfn_var_name = camel_case(&L2Construct::escape_hatch(&reference.name));
+        output.line(format!("{cfn_var_name} = {var_name}.node.default_child"));
+        output.line(format!(
+            "{cfn_var_name}.override_logical_id('{}')",
+            reference.name.escape_debug()
+        ));
+        for (name, value) in &construct.overrides {
+            if let Some(comments) = reference.property_comments.get(name) {
+                output.comments("#", comments);
+            }
+            output.text(format!(
+                "{cfn_var_name}.add_property_override('{}', ",
+                name.escape_debug()
+            ));
+            emit_resource_ir(context, output, value, Some(")\n"));
+        }
+        emit_resource_attributes(context, output, reference, &cfn_var_name);
+        return;
     }
     let maybe_undefined = if let Some(cond) = &reference.condition {
         output.line(format!(
@@ -480,7 +982,12 @@ fn emit_resource(
         let output = output.indent(INDENT);
 
         let mid_output = output.indent(INDENT);
-        emit_resource_props(context, mid_output.indent(INDENT), &reference.properties);
+        emit_resource_props(
+            context,
+            mid_output.indent(INDENT),
+            &reference.properties,
+            &reference.property_comments,
+        );
         mid_output.line(format!(") if {} else None", snake_case(cond)));
 
         true
@@ -494,7 +1001,12 @@ fn emit_resource(
         let output = output.indent(INDENT);
 
         let mid_output = output.indent(INDENT);
-        emit_resource_props(context, mid_output.indent(INDENT), &reference.properties);
+        emit_resource_props(
+            context,
+            mid_output.indent(INDENT),
+            &reference.properties,
+            &reference.property_comments,
+        );
         mid_output.line(")");
 
         false
@@ -509,11 +1021,134 @@ fn emit_resource(
     }
 }
 
+// The name a service's module is imported as. lambda is a reserved keyword in python: if we
+// encounter it or another keyword, we prepend 'aws_'
+fn module_name(service: &str) -> String {
+    let service = service.to_lowercase();
+    if KEYWORDS.contains(&service.as_str()) {
+        format!("aws_{service}")
+    } else {
+        service
+    }
+}
+
+fn emit_l2_value(
+    context: &mut PythonContext,
+    output: &CodeBuffer,
+    value: &L2Value,
+    trailer: Option<&str>,
+) {
+    // Structs are passed as keyword arguments, which come last.
+    let emit_args = |context: &mut PythonContext, args: &[L2Value]| {
+        output.text("(");
+        for (index, arg) in args.iter().enumerate() {
+            if index > 0 {
+                output.text(", ");
+            }
+            match arg {
+                L2Value::Struct { fields, .. } if index == args.len() - 1 => {
+                    emit_l2_fields(context, output, fields)
+                }
+                arg => emit_l2_value(context, output, arg, None),
+            }
+        }
+        output.text(")");
+    };
+    match value {
+        L2Value::Value(value) => emit_resource_ir(context, output, value, None),
+        L2Value::Seconds(value) => {
+            output.text("cdk.Duration.seconds(");
+            emit_resource_ir(context, output, value, None);
+            output.text(")");
+        }
+        L2Value::Enum {
+            service,
+            name,
+            member,
+        } => output.text(format!("{}.{name}.{member}", module_name(service))),
+        L2Value::Struct {
+            service,
+            name,
+            fields,
+        } => {
+            output.text(format!("{}.{name}(", module_name(service)));
+            emit_l2_fields(context, output, fields);
+            output.text(")");
+        }
+        L2Value::New {
+            service,
+            class,
+            args,
+            ..
+        } => {
+            output.text(format!("{}.{class}", module_name(service)));
+            emit_args(context, args);
+        }
+        L2Value::Call {
+            service,
+            class,
+            method,
+            args,
+            ..
+        } => {
+            output.text(format!(
+                "{}.{class}.{}",
+                module_name(service),
+                snake_case(method)
+            ));
+            emit_args(context, args);
+        }
+        L2Value::Scope => output.text("self"),
+        L2Value::Construct(name) => output.text(camel_case(name)),
+    }
+
+    if let Some(trailer) = trailer {
+        output.text(trailer.to_owned())
+    }
+}
+
+fn emit_l2_fields<S>(
+    context: &mut PythonContext,
+    output: &CodeBuffer,
+    fields: &IndexMap<String, L2Value, S>,
+) {
+    for (index, (name, value)) in fields.iter().enumerate() {
+        if index > 0 {
+            output.text(", ");
+        }
+        output.text(format!("{} = ", snake_case(name)));
+        emit_l2_value(context, output, value, None);
+    }
+}
+
 fn emit_resource_attributes(
     context: &mut PythonContext,
     output: &CodeBuffer,
     reference: &ResourceInstruction,
     var_name: &str,
+) {
+    emit_cfn_options(context, output, reference, var_name);
+
+    if !reference.dependencies.is_empty() {
+        for dependency in &reference.dependencies {
+            // Nested stacks are deployed by a CfnStack resource of their parent.
+            let nested_stack_resource = match context.nested_stacks.contains(dependency) {
+                true => ".nested_stack_resource",
+                false => "",
+            };
+            output.line(format!(
+                "{var_name}.add_dependency({}{nested_stack_resource})",
+                camel_case(dependency)
+            ));
+        }
+    }
+}
+
+fn emit_cfn_options(
+    context: &mut PythonContext,
+    output: &CodeBuffer,
+    reference: &ResourceInstruction,
+    var_name: &str,
 ) {
     if let Some(metadata) = &reference.metadata {
         let md = output.indent_with_options(IndentOptions {
@@ -527,7 +1162,7 @@ fn emit_resource_attributes(
 
     if let Some(update_policy) = &reference.update_policy {
         output.text(format!("{var_name}.cfn_options.update_policy = "));
-        emit_resource_ir(context, output, update_policy, Some(""));
+        emit_resource_ir(context, output, update_policy, Some("\n"));
     }
 
     if let Some(deletion_policy) = &reference.deletion_policy {
@@ -536,13 +1171,92 @@ fn emit_resource_attributes(
         ));
     }
 
-    if !reference.dependencies.is_empty() {
-        for dependency in &reference.dependencies {
-            output.line(format!(
-                "{var_name}.add_dependency({})",
-                camel_case(dependency)
-            ));
+    if let Some(update_replace_policy) = &reference.update_replace_policy {
+        output.line(format!(
+            "{var_name}.cfn_options.update_replace_policy = cdk.CfnDeletionPolicy.{update_replace_policy}"
+        ));
+    }
+
+    if let Some(creation_policy) = &reference.creation_policy {
+        let cp = output.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some(
+                format!("{var_name}.cfn_options.creation_policy = cdk.CfnCreationPolicy(").into(),
+            ),
+            trailing: Some(")".into()),
+            trailing_newline: true,
+        });
+        emit_creation_policy(context, cp, creation_policy);
+    }
+}
+
+fn emit_creation_policy(
+    context: &mut PythonContext,
+    output: Rc<CodeBuffer>,
+    creation_policy: &CreationPolicyIr,
+) {
+    if let Some(asg) = &creation_policy.auto_scaling_creation_policy {
+        let asg_output = output.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some(
+                "auto_scaling_creation_policy = cdk.CfnResourceAutoScalingCreationPolicy(".into(),
+            ),
+            trailing: Some("),".into()),
+            trailing_newline: true,
+        });
+        if let Some(percent) = &asg.min_successful_instances_percent {
+            asg_output.text("min_successful_instances_percent = ");
+            emit_resource_ir(context, &asg_output, percent, Some(",\n"));
+        }
+    }
+    if let Some(signal) = &creation_policy.resource_signal {
+        let signal_output = output.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some("resource_signal = cdk.CfnResourceSignal(".into()),
+            trailing: Some("),".into()),
+            trailing_newline: true,
+        });
+        if let Some(count) = &signal.count {
+            signal_output.text("count = ");
+            emit_resource_ir(context, &signal_output, count, Some(",\n"));
         }
+        if let Some(timeout) = &signal.timeout {
+            signal_output.text("timeout = ");
+            emit_resource_ir(context, &signal_output, timeout, Some(",\n"));
+        }
+    }
+    if let Some(start_fleet) = &creation_policy.start_fleet {
+        output.text("start_fleet = ");
+        emit_resource_ir(context, &output, start_fleet, Some(",\n"));
+    }
+}
+
+fn emit_template_options(
+    context: &mut PythonContext,
+    output: &CodeBuffer,
+    ir: &CloudformationProgramIr,
+) {
+    let metadata = ir.metadata.as_ref().filter(|metadata| !metadata.is_empty());
+    if ir.template_format_version.is_none() && metadata.is_none() {
+        return;
+    }
+
+    output.newline();
+    output.line("# Template options");
+    if let Some(version) = &ir.template_format_version {
+        output.line(format!(
+            "self.template_options.template_format_version = '{}'",
+            version.escape_debug()
+        ));
+    }
+    if let Some(metadata) = metadata {
+        output.text("self.template_options.metadata = ");
+        emit_resource_ir(
+            context,
+            output,
+            &metadata.to_ir(|name| ir.constructor.logical_id(name)),
+            Some("\n"),
+        );
     }
 }
 
@@ -566,8 +1280,12 @@ fn emit_resource_props<S>(
     context: &mut PythonContext,
     output: Rc<CodeBuffer>,
     props: &IndexMap<String, ResourceIr, S>,
+    comments: &IndexMap<String, Vec<String>, Hasher>,
 ) {
     for (name, prop) in props {
+        if let Some(comments) = comments.get(name) {
+            output.comments("#", comments);
+        }
         output.text(format!("{} = ", snake_case(name)));
         emit_resource_ir(context, &output, prop, Some(",\n"));
     }
@@ -700,12 +1418,37 @@ fn emit_resource_ir(
                 output.text(")")
             }
         },
+        ResourceIr::Length(list) => match list.as_ref() {
+            array @ ResourceIr::Array(..) => {
+                output.text("len(");
+                emit_resource_ir(context, output, array, None);
+                output.text(")")
+            }
+            other => {
+                output.text("cdk.Fn.len(");
+                emit_resource_ir(context, output, other, None);
+                output.text(")")
+            }
+        },
+        ResourceIr::ToJsonString(value) => {
+            if value.to_json().is_some() {
+                context.import_json();
+                output.text("json.dumps(");
+            } else {
+                output.text("cdk.Fn.to_json_string(");
+            }
+            emit_resource_ir(context, output, value, None);
+            output.text(")")
+        }
         ResourceIr::Sub(parts) => {
             output.text("f\"\"\"");
             for part in parts {
                 match part {
                     ResourceIr::String(lit) => {
-                        let escaped_lit = lit.replace('{', "{{").replace('}', "}}");
+                        let escaped_lit = lit
+                            .replace('\\', "\\\\")
+                            .replace('{', "{{")
+                            .replace('}', "}}");
                         output.text(escaped_lit);
                     }
                     other => {
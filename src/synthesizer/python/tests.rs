@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 use crate::ir::{conditions::ConditionIr, importer::ImportInstruction};
 
-use super::synthesize_condition_recursive;
+use super::{python_string, synthesize_condition_recursive};
 
 #[test]
 fn test_invalid_organization() {
@@ -32,3 +32,75 @@ fn test_condition_ir_not_simple() {
     let result = synthesize_condition_recursive(&condition_ir);
     assert_eq!("not (condition)", result);
 }
+
+#[test]
+fn test_rule_each_member_in() {
+    use crate::ir::rules::{RuleIr, RuleValueIr};
+
+    let rule_ir = RuleIr::Not(Box::new(RuleIr::EachMemberIn(
+        RuleValueIr::ValueOf("Subnets".into(), "VpcId".into()),
+        RuleValueIr::RefAll("AWS::EC2::VPC::Id".into()),
+    )));
+    let result = super::synthesize_rule_recursive(&rule_ir);
+    assert_eq!(
+        "cdk.Fn.condition_not(cdk.Fn.condition_each_member_in(cdk.Token.as_list(cdk.Fn.value_of('Subnets', 'VpcId')), cdk.Fn.ref_all('AWS::EC2::VPC::Id')))",
+        result
+    );
+}
+
+#[test]
+fn test_required_props_not_forwarded() {
+    use crate::cdk::Schema;
+    use crate::ir::CloudformationProgramIr;
+    use crate::CloudformationParseTree;
+
+    let template: CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Parameters:
+          QueueName:
+            Type: String
+          Delay:
+            Type: Number
+            Default: 5
+        Resources:
+          Queue:
+            Type: AWS::SQS::Queue
+            Properties:
+              QueueName: !Ref QueueName
+              DelaySeconds: !Ref Delay
+        "#,
+    )
+    .unwrap();
+    let mut output = Vec::new();
+    CloudformationProgramIr::from(template, Schema::builtin())
+        .unwrap()
+        .synthesize("python", &mut output, "QueueStack")
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains(
+        "    # Required props
+    props = {
+      'queueName': kwargs.pop('queueName'),
+    }
+    super().__init__(scope, construct_id, **kwargs)
+
+    # Applying default props
+    props.update({
+      'delay': kwargs.get('delay', '5'),
+    })
+"
+    ));
+}
+
+#[test]
+fn test_python_string() {
+    assert_eq!(python_string("plain"), "'plain'");
+    assert_eq!(
+        python_string("it's \"quoted\"\n\\d+"),
+        r#"'it\'s "quoted"\n\\d+'"#
+    );
+    assert_eq!(
+        python_string("a\u{7}b\u{85}c\u{ad}é"),
+        "'a\\x07b\\x85c\u{ad}é'"
+    );
+}
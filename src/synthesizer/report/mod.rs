@@ -0,0 +1,264 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use serde::Serialize;
+use voca_rs::case::camel_case;
+
+use crate::cdk::{Primitive, PropertyBag, Schema, TypeReference};
+use crate::ir::diagnostics::{Diagnostic, Diagnostics};
+use crate::ir::resources::{ResourceInstruction, ResourceIr};
+use crate::ir::CloudformationProgramIr;
+use crate::parser::span::SourceMap;
+use crate::synthesizer::SynthesizerOptions;
+use crate::CloudformationParseTree;
+
+/// The sections of a template the IR knows about. Any other is ignored when parsing.
+const KNOWN_SECTIONS: [&str; 10] = [
+    "AWSTemplateFormatVersion",
+    "Description",
+    "Metadata",
+    "Transform",
+    "Conditions",
+    "Mappings",
+    "Outputs",
+    "Parameters",
+    "Rules",
+    "Resources",
+];
+
+/// A machine-readable description of how a template was converted: what each resource became,
+/// what each parameter became, what the synthesized code has no equivalent for, and the problems
+/// found along the way. It serializes to JSON (e.g: with `serde_json::to_string_pretty`).
+#[derive(Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversionReport {
+    pub resources: Vec<ResourceReport>,
+    pub parameters: Vec<ParameterReport>,
+    /// The template entries that are not in the synthesized code, by path (e.g: `Hooks`, or
+    /// `Conditions.Unused` once pruned).
+    pub dropped: Vec<String>,
+    pub diagnostics: Vec<Diagnostic>,
+    /// The paths of the entries declared by the template, compared to the IR by `describe`.
+    #[serde(skip)]
+    declared: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceReport {
+    pub logical_id: String,
+    #[serde(rename = "type")]
+    pub resource_type: String,
+    /// The class the resource is created with: its L1 class (e.g: `CfnBucket`), its L2 construct
+    /// (e.g: `Bucket`), or the class of the nested stack it was converted to.
+    pub construct: String,
+    /// The properties that are passed as raw JSON rather than typed values, by path from the
+    /// resource (e.g: `Properties.PolicyDocument`).
+    pub json_properties: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParameterReport {
+    pub logical_id: String,
+    /// The name of the prop the parameter is read from.
+    pub prop: String,
+    pub kind: ParameterKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ParameterKind {
+    /// A plain prop of the stack or construct.
+    Prop,
+    /// A prop that is also declared as a `CfnParameter`, for CloudFormation to check or resolve.
+    CfnParameter,
+    /// A prop holding the name of an SSM parameter, which is read with `ssm.StringParameter`.
+    SsmLookup,
+}
+
+impl ConversionReport {
+    /// Starts the report of `template`, recording the entries it declares. Sections the IR does
+    /// not know about are dropped right away.
+    pub fn new(template: &CloudformationParseTree, source_map: &SourceMap) -> Self {
+        let mut report = Self::default();
+        for section in source_map.sections() {
+            if !KNOWN_SECTIONS.contains(&section) {
+                report.dropped.push(section.to_string());
+            }
+        }
+
+        if template.aws_template_format_version.is_some() {
+            report.declared.push("AWSTemplateFormatVersion".into());
+        }
+        if template.metadata.is_some() {
+            report.declared.push("Metadata".into());
+        }
+        let sections = [
+            ("Parameters", template.parameters.keys().collect::<Vec<_>>()),
+            ("Mappings", template.mappings.keys().collect()),
+            ("Conditions", template.conditions.keys().collect()),
+            ("Rules", template.rules.keys().collect()),
+            ("Resources", template.resources.keys().collect()),
+            ("Outputs", template.outputs.keys().collect()),
+        ];
+        for (section, names) in sections {
+            for name in names {
+                report.declared.push(format!("{section}.{name}"));
+            }
+        }
+        report
+    }
+
+    /// Records `diagnostics`, which should already be located.
+    pub fn record(&mut self, diagnostics: &Diagnostics) {
+        self.diagnostics.extend(diagnostics.iter().cloned());
+    }
+
+    /// Describes the resources and parameters of `ir`, as synthesized with `options`, and lists
+    /// the declared entries it no longer has.
+    pub fn describe(
+        &mut self,
+        ir: &CloudformationProgramIr,
+        schema: &Schema,
+        options: &SynthesizerOptions,
+    ) {
+        self.resources = ir
+            .resources
+            .iter()
+            .map(|resource| ResourceReport::new(resource, schema))
+            .collect();
+
+        self.parameters = ir
+            .constructor
+            .inputs
+            .iter()
+            .map(|input| {
+                let logical_id = self
+                    .declared
+                    .iter()
+                    .filter_map(|path| path.strip_prefix("Parameters."))
+                    .find(|name| camel_case(name) == input.name)
+                    .unwrap_or(&input.name);
+                let kind = if input.ssm_lookup {
+                    ParameterKind::SsmLookup
+                } else if input.is_cfn_parameter() {
+                    ParameterKind::CfnParameter
+                } else {
+                    ParameterKind::Prop
+                };
+                ParameterReport {
+                    logical_id: logical_id.to_string(),
+                    prop: input.name.clone(),
+                    kind,
+                }
+            })
+            .collect();
+
+        for path in &self.declared {
+            let kept = match path.split_once('.') {
+                Some(("Parameters", name)) => {
+                    let name = camel_case(name);
+                    ir.constructor.inputs.iter().any(|input| input.name == name)
+                }
+                Some(("Mappings", name)) => ir.mappings.iter().any(|m| m.name == name),
+                Some(("Conditions", name)) => ir.conditions.iter().any(|c| c.name == name),
+                Some(("Rules", name)) => ir.rules.iter().any(|r| r.name == name),
+                Some(("Resources", name)) => ir.resources.iter().any(|r| r.name == name),
+                Some(("Outputs", name)) => ir.outputs.iter().any(|o| o.name == name),
+                // A construct has no template options of its own.
+                _ => options.construct.is_none(),
+            };
+            if !kept && !self.dropped.contains(path) {
+                self.dropped.push(path.clone());
+            }
+        }
+    }
+}
+
+impl ResourceReport {
+    fn new(resource: &ResourceInstruction, schema: &Schema) -> Self {
+        let resource_type = resource.resource_type.to_string();
+        let (construct, json_properties) = if let Some(nested) = &resource.nested_stack {
+            (nested.class_name.clone(), Vec::new())
+        } else if let Some(l2) = &resource.l2_construct {
+            // Overrides are set with addPropertyOverride, which takes untyped values.
+            let overrides = l2.overrides.keys();
+            (
+                l2.class.clone(),
+                overrides.map(|name| format!("Properties.{name}")).collect(),
+            )
+        } else {
+            let mut json_properties = Vec::new();
+            // Properties are only translated when the schema declares them.
+            let spec = schema.resource_type(&resource_type);
+            for (name, value) in &resource.properties {
+                if let Some(property) = spec.and_then(|spec| spec.property(name)) {
+                    let path = format!("Properties.{name}");
+                    json_paths(
+                        schema,
+                        &path,
+                        &property.value_type,
+                        value,
+                        &mut json_properties,
+                    );
+                }
+            }
+            (
+                format!("Cfn{}", resource.resource_type.type_name()),
+                json_properties,
+            )
+        };
+        Self {
+            logical_id: resource.name.clone(),
+            resource_type,
+            construct,
+            json_properties,
+        }
+    }
+}
+
+// Adds to `paths` the paths within `value` whose declared type is untyped JSON, or a type the
+// schema does not describe, both of which the synthesizers emit as raw JSON.
+fn json_paths(
+    schema: &Schema,
+    path: &str,
+    expected: &TypeReference,
+    value: &ResourceIr,
+    paths: &mut Vec<String>,
+) {
+    match (expected, value) {
+        (TypeReference::Primitive(Primitive::Json | Primitive::Unknown), _) => {
+            paths.push(path.to_string());
+        }
+        (_, ResourceIr::If(_, when_true, when_false)) => {
+            json_paths(schema, path, expected, when_true, paths);
+            json_paths(schema, path, expected, when_false, paths);
+        }
+        (TypeReference::List(item_type), ResourceIr::Array(_, items)) => {
+            for (index, item) in items.iter().enumerate() {
+                json_paths(schema, &format!("{path}[{index}]"), item_type, item, paths);
+            }
+        }
+        (TypeReference::Map(item_type), ResourceIr::Object(_, entries)) => {
+            for (key, value) in entries {
+                json_paths(schema, &format!("{path}.{key}"), item_type, value, paths);
+            }
+        }
+        (TypeReference::Named(name), ResourceIr::Object(_, entries)) => {
+            let Some(data_type) = schema.type_named(name) else {
+                paths.push(path.to_string());
+                return;
+            };
+            for (key, value) in entries {
+                if let Some(property) = data_type.property(key) {
+                    let path = format!("{path}.{key}");
+                    json_paths(schema, &path, &property.value_type, value, paths);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests;
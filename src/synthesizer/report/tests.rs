@@ -0,0 +1,154 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use crate::ir::diagnostics::Diagnostics;
+use crate::synthesizer::ConstructOptions;
+
+use super::*;
+
+const TEMPLATE: &str = r#"
+AWSTemplateFormatVersion: "2010-09-09"
+Parameters:
+  BucketName:
+    Type: String
+  KeyName:
+    Type: AWS::EC2::KeyPair::KeyName
+  Unused:
+    Type: String
+Conditions:
+  Never: !Equals [a, b]
+Resources:
+  Role:
+    Type: AWS::IAM::Role
+    Properties:
+      AssumeRolePolicyDocument:
+        Statement: []
+      RoleName: !Ref BucketName
+  Bucket:
+    Type: AWS::S3::Bucket
+    Properties:
+      BucketName: !Ref BucketName
+      Tags:
+        - Key: key
+          Value: !Ref KeyName
+  Custom:
+    Type: AWS::CloudFormation::CustomResource
+    Properties:
+      ServiceToken: arn
+Hooks:
+  Check:
+    Type: AWS::Hook::Something
+"#;
+
+fn report(options: &SynthesizerOptions) -> ConversionReport {
+    let template: CloudformationParseTree = serde_yaml::from_str(TEMPLATE).unwrap();
+    let mut report = ConversionReport::new(&template, &SourceMap::new(TEMPLATE));
+    let mut ir = CloudformationProgramIr::from(template, Schema::builtin()).unwrap();
+    let mut diagnostics = Diagnostics::default();
+    ir.prune(&mut diagnostics);
+    report.record(&diagnostics);
+    report.describe(&ir, Schema::builtin(), options);
+    report
+}
+
+#[test]
+fn describes_the_conversion() {
+    let report = report(&SynthesizerOptions::new("Stack"));
+
+    let resources: Vec<(&str, &str, &str, Vec<&str>)> = report
+        .resources
+        .iter()
+        .map(|resource| {
+            (
+                resource.logical_id.as_str(),
+                resource.resource_type.as_str(),
+                resource.construct.as_str(),
+                resource
+                    .json_properties
+                    .iter()
+                    .map(String::as_str)
+                    .collect(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        resources,
+        vec![
+            ("Bucket", "AWS::S3::Bucket", "CfnBucket", vec![]),
+            (
+                "Custom",
+                "AWS::CloudFormation::CustomResource",
+                "CfnCustomResource",
+                vec![]
+            ),
+            (
+                "Role",
+                "AWS::IAM::Role",
+                "CfnRole",
+                vec!["Properties.AssumeRolePolicyDocument"]
+            ),
+        ]
+    );
+
+    assert_eq!(
+        report.parameters,
+        vec![
+            ParameterReport {
+                logical_id: "BucketName".into(),
+                prop: "bucketName".into(),
+                kind: ParameterKind::Prop,
+            },
+            ParameterReport {
+                logical_id: "KeyName".into(),
+                prop: "keyName".into(),
+                kind: ParameterKind::CfnParameter,
+            },
+        ]
+    );
+    assert_eq!(
+        report.dropped,
+        vec!["Hooks", "Parameters.Unused", "Conditions.Never"]
+    );
+    assert_eq!(report.diagnostics.len(), 2);
+}
+
+#[test]
+fn construct_drops_template_options() {
+    let mut options = SynthesizerOptions::new("Stack");
+    options.construct = Some(ConstructOptions::default());
+    let report = report(&options);
+    assert_eq!(
+        report.dropped,
+        vec![
+            "Hooks",
+            "AWSTemplateFormatVersion",
+            "Parameters.Unused",
+            "Conditions.Never"
+        ]
+    );
+}
+
+#[test]
+fn serializes_to_json() {
+    let report = report(&SynthesizerOptions::new("Stack"));
+    let json = serde_json::to_value(&report).unwrap();
+
+    assert_eq!(
+        json["resources"][0],
+        serde_json::json!({
+            "logicalId": "Bucket",
+            "type": "AWS::S3::Bucket",
+            "construct": "CfnBucket",
+            "jsonProperties": [],
+        })
+    );
+    assert_eq!(json["parameters"][1]["kind"], "cfnParameter");
+    assert_eq!(
+        json["diagnostics"][0],
+        serde_json::json!({
+            "severity": "warning",
+            "path": "Conditions.Never",
+            "message": "unused condition, it was removed",
+            "span": null,
+        })
+    );
+}
@@ -12,3 +12,556 @@ mod test;
 use stack::IrStack;
 
 generate_ir_tests!();
+
+#[test]
+fn test_unsupported_language() {
+    let ir = crate::ir::CloudformationProgramIr::default();
+    let mut output = Vec::new();
+    let error = ir.synthesize("cobol", &mut output, "Stack").unwrap_err();
+    assert_eq!(error.to_string(), "cobol is not a supported language");
+}
+
+#[test]
+fn test_nested_stacks() {
+    let child: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Resources:
+          Topic:
+            Type: AWS::SNS::Topic
+        Outputs:
+          TopicArn:
+            Value: !Ref Topic
+        "#,
+    )
+    .unwrap();
+    let child =
+        crate::ir::CloudformationProgramIr::from(child, crate::cdk::Schema::builtin()).unwrap();
+    let template: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Resources:
+          Topics:
+            Type: AWS::CloudFormation::Stack
+            Properties:
+              TemplateURL: ./topics.yaml
+          Queue:
+            Type: AWS::SQS::Queue
+            Properties:
+              QueueName: !GetAtt Topics.Outputs.TopicArn
+        "#,
+    )
+    .unwrap();
+    let mut diagnostics = crate::ir::diagnostics::Diagnostics::default();
+    let ir = crate::ir::CloudformationProgramIr::from_with_nested_stacks(
+        template,
+        crate::cdk::Schema::builtin(),
+        std::collections::HashMap::from([("Topics".to_string(), child)]),
+        &mut diagnostics,
+    );
+    assert!(diagnostics.is_empty(), "{diagnostics}");
+
+    // Every language declares the nested stack's class and instantiates it instead of a CfnStack.
+    for (language, class, output) in [
+        (
+            "csharp",
+            "public class TopicsNestedStack : NestedStack",
+            "topics.TopicArn as string",
+        ),
+        (
+            "go",
+            "type TopicsNestedStack struct {\n\tcdk.NestedStack",
+            "topics.TopicArn.(*string)",
+        ),
+        (
+            "java",
+            "class TopicsNestedStack extends NestedStack {",
+            "topics.getTopicArn().toString()",
+        ),
+        (
+            "python",
+            "class TopicsNestedStack(NestedStack):",
+            "topics.topic_arn",
+        ),
+        (
+            "typescript",
+            "export class TopicsNestedStack extends cdk.NestedStack {",
+            "topics.topicArn",
+        ),
+    ] {
+        let mut code = Vec::new();
+        ir.clone().synthesize(language, &mut code, "Stack").unwrap();
+        let code = String::from_utf8(code).unwrap();
+        assert!(code.contains(class), "{language}: {code}");
+        assert!(code.contains(output), "{language}: {code}");
+        assert!(!code.contains("CfnStack"), "{language}: {code}");
+    }
+}
+
+#[test]
+fn test_nested_stack_attributes() {
+    let child: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Resources:
+          Topic:
+            Type: AWS::SNS::Topic
+        "#,
+    )
+    .unwrap();
+    let child =
+        crate::ir::CloudformationProgramIr::from(child, crate::cdk::Schema::builtin()).unwrap();
+    let template: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Resources:
+          Topics:
+            Type: AWS::CloudFormation::Stack
+            DeletionPolicy: Retain
+            UpdateReplacePolicy: Snapshot
+            Metadata:
+              Owner: team-a
+            Properties:
+              TemplateURL: ./topics.yaml
+        "#,
+    )
+    .unwrap();
+    let mut diagnostics = crate::ir::diagnostics::Diagnostics::default();
+    let ir = crate::ir::CloudformationProgramIr::from_with_nested_stacks(
+        template,
+        crate::cdk::Schema::builtin(),
+        std::collections::HashMap::from([("Topics".to_string(), child)]),
+        &mut diagnostics,
+    );
+    assert!(diagnostics.is_empty(), "{diagnostics}");
+
+    // Every language sets the attributes on the CfnStack deploying the nested stack.
+    for (language, attributes) in [
+        (
+            "csharp",
+            [
+                "topics.NestedStackResource.AddMetadata(\"Owner\", \"team-a\");",
+                "topics.NestedStackResource.CfnOptions.DeletionPolicy = CfnDeletionPolicy.RETAIN;",
+                "topics.NestedStackResource.CfnOptions.UpdateReplacePolicy = CfnDeletionPolicy.SNAPSHOT;",
+            ],
+        ),
+        (
+            "go",
+            [
+                "topics.NestedStackResource().AddMetadata(jsii.String(\"Owner\"), jsii.String(\"team-a\"))",
+                "topics.NestedStackResource().CfnOptions().SetDeletionPolicy(cdk.CfnDeletionPolicy_RETAIN)",
+                "topics.NestedStackResource().CfnOptions().SetUpdateReplacePolicy(cdk.CfnDeletionPolicy_SNAPSHOT)",
+            ],
+        ),
+        (
+            "java",
+            [
+                "topics.getNestedStackResource().addMetadata(\"Owner\", \"team-a\");",
+                "topics.getNestedStackResource().applyRemovalPolicy(RemovalPolicy.RETAIN);",
+                "topics.getNestedStackResource().getCfnOptions().setUpdateReplacePolicy(CfnDeletionPolicy.SNAPSHOT);",
+            ],
+        ),
+        (
+            "python",
+            [
+                "topics.nested_stack_resource.cfn_options.metadata = {",
+                "topics.nested_stack_resource.cfn_options.deletion_policy = cdk.CfnDeletionPolicy.RETAIN",
+                "topics.nested_stack_resource.cfn_options.update_replace_policy = cdk.CfnDeletionPolicy.SNAPSHOT",
+            ],
+        ),
+        (
+            "typescript",
+            [
+                "topics.nestedStackResource!.cfnOptions.metadata = {",
+                "topics.nestedStackResource!.cfnOptions.deletionPolicy = cdk.CfnDeletionPolicy.RETAIN;",
+                "topics.nestedStackResource!.cfnOptions.updateReplacePolicy = cdk.CfnDeletionPolicy.SNAPSHOT;",
+            ],
+        ),
+    ] {
+        let mut code = Vec::new();
+        ir.clone().synthesize(language, &mut code, "Stack").unwrap();
+        let code = String::from_utf8(code).unwrap();
+        for attribute in attributes {
+            assert!(code.contains(attribute), "{language}: {code}");
+        }
+    }
+}
+
+#[test]
+fn test_constructs() {
+    let template: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Transform: AWS::Serverless-2016-10-31
+        Resources:
+          Queue:
+            Type: AWS::SQS::Queue
+            Properties:
+              QueueName: !Sub ${AWS::Region}-queue
+        Outputs:
+          QueueArn:
+            Value: !GetAtt Queue.Arn
+            Export:
+              Name: queue-arn
+        "#,
+    )
+    .unwrap();
+    let ir =
+        crate::ir::CloudformationProgramIr::from(template, crate::cdk::Schema::builtin()).unwrap();
+
+    // Every language extends Construct, and gets the region and the transforms from the stack.
+    for (language, class, region, transform) in [
+        (
+            "csharp",
+            "public class Stack : Construct",
+            "private string Region => Stack.Of(this).Region;",
+            "Stack.Of(this).AddTransform(",
+        ),
+        (
+            "go",
+            "type Stack struct {\n\tconstructs.Construct",
+            "cdk.Stack_Of(construct).Region()",
+            "cdk.Stack_Of(construct).AddTransform(",
+        ),
+        (
+            "java",
+            "class Stack extends Construct {",
+            "return Stack.of(this).getRegion();",
+            "Stack.of(this).addTransform(",
+        ),
+        (
+            "python",
+            "class Stack(Construct):",
+            "return Stack.of(self).region",
+            "Stack.of(self).add_transform(",
+        ),
+        (
+            "typescript",
+            "export class Stack extends Construct {",
+            "return cdk.Stack.of(this).region;",
+            "cdk.Stack.of(this).addTransform(",
+        ),
+    ] {
+        let mut options = super::SynthesizerOptions::new("Stack");
+        options.construct = Some(super::ConstructOptions::default());
+        let mut code = Vec::new();
+        ir.clone()
+            .synthesize_with(language, &mut code, &options)
+            .unwrap();
+        let code = String::from_utf8(code).unwrap();
+        assert!(code.contains(class), "{language}: {code}");
+        assert!(code.contains(region), "{language}: {code}");
+        assert!(code.contains(transform), "{language}: {code}");
+        assert!(!code.contains("CfnOutput"), "{language}: {code}");
+
+        options.construct = Some(super::ConstructOptions { cfn_outputs: true });
+        let mut code = Vec::new();
+        ir.clone()
+            .synthesize_with(language, &mut code, &options)
+            .unwrap();
+        let code = String::from_utf8(code).unwrap();
+        assert!(code.contains("CfnOutput"), "{language}: {code}");
+    }
+}
+
+#[test]
+fn test_comments() {
+    let source = r#"
+Parameters:
+  # The name of the bucket.
+  BucketName:
+    Type: String
+Resources:
+  # Holds the assets.
+  Bucket:
+    Type: AWS::S3::Bucket
+    Properties:
+      BucketName: !Ref BucketName # set by the caller
+Outputs:
+  # The bucket's ARN.
+  BucketArn:
+    Value: !GetAtt Bucket.Arn
+"#;
+    let template: crate::CloudformationParseTree = source.parse().unwrap();
+    let ir =
+        crate::ir::CloudformationProgramIr::from(template, crate::cdk::Schema::builtin()).unwrap();
+
+    // Every language emits the comments of the parameter, the resource, its property and the output.
+    for (language, marker, doc) in [
+        ("csharp", "//", "///"),
+        ("go", "//", "///"),
+        ("java", "//", "//"),
+        ("python", "#", ""),
+        ("typescript", "//", "*"),
+    ] {
+        let mut code = Vec::new();
+        ir.clone().synthesize(language, &mut code, "Stack").unwrap();
+        let code = String::from_utf8(code).unwrap();
+        assert!(
+            code.contains(&format!("{marker} Holds the assets.")),
+            "{language}: {code}"
+        );
+        assert!(
+            code.contains(&format!("{marker} set by the caller")),
+            "{language}: {code}"
+        );
+        assert!(
+            code.contains(&format!("{doc} The bucket's ARN.")),
+            "{language}: {code}"
+        );
+        assert!(
+            code.contains("The name of the bucket."),
+            "{language}: {code}"
+        );
+    }
+}
+
+#[test]
+fn test_comments_in_doc_blocks_are_escaped() {
+    let source = r#"
+Parameters:
+  # Ends */ the block and """ the string.
+  BucketName:
+    Type: String
+Resources:
+  Bucket:
+    Type: AWS::S3::Bucket
+Outputs:
+  # Ends */ the block and """ the string.
+  BucketArn:
+    Value: !GetAtt Bucket.Arn
+"#;
+    let template: crate::CloudformationParseTree = source.parse().unwrap();
+    let ir =
+        crate::ir::CloudformationProgramIr::from(template, crate::cdk::Schema::builtin()).unwrap();
+
+    let mut code = Vec::new();
+    ir.clone()
+        .synthesize("typescript", &mut code, "Stack")
+        .unwrap();
+    let code = String::from_utf8(code).unwrap();
+    assert!(code.contains("Ends *\\/ the block"), "{code}");
+    assert!(!code.contains("Ends */"), "{code}");
+
+    let mut code = Vec::new();
+    ir.synthesize("python", &mut code, "Stack").unwrap();
+    let code = String::from_utf8(code).unwrap();
+    assert!(code.contains("and \\\"\\\"\\\" the string."), "{code}");
+    // Only the line comment of the parameter keeps the quotes as they are.
+    assert_eq!(code.matches("and \"\"\" the string.").count(), 1, "{code}");
+}
+
+#[test]
+fn test_l2_prop_comments() {
+    let source = r#"
+Resources:
+  Queue:
+    Type: AWS::SQS::Queue
+    Properties:
+      # Named by the caller.
+      QueueName: orders
+      # Kept as an override.
+      RedriveAllowPolicy:
+        redrivePermission: denyAll
+"#;
+    let template: crate::CloudformationParseTree = source.parse().unwrap();
+    let mut ir =
+        crate::ir::CloudformationProgramIr::from(template, crate::cdk::Schema::builtin()).unwrap();
+    ir.use_l2_constructs(&crate::ir::constructs::L2Mappings::builtin());
+
+    // The comments of the props and of the overrides are emitted before them.
+    for (language, marker, prop, over) in [
+        (
+            "csharp",
+            "//",
+            "QueueName = ",
+            "AddPropertyOverride(\"RedriveAllowPolicy\"",
+        ),
+        (
+            "go",
+            "//",
+            "QueueName: ",
+            "AddPropertyOverride(jsii.String(\"RedriveAllowPolicy\")",
+        ),
+        (
+            "java",
+            "//",
+            ".queueName(",
+            "addPropertyOverride(\"RedriveAllowPolicy\"",
+        ),
+        (
+            "python",
+            "#",
+            "queue_name = ",
+            "add_property_override('RedriveAllowPolicy'",
+        ),
+        (
+            "typescript",
+            "//",
+            "queueName: ",
+            "addPropertyOverride('RedriveAllowPolicy'",
+        ),
+    ] {
+        let mut code = Vec::new();
+        ir.clone().synthesize(language, &mut code, "Stack").unwrap();
+        let code = String::from_utf8(code).unwrap();
+        let comment = code
+            .find(&format!("{marker} Named by the caller."))
+            .unwrap_or_else(|| panic!("{language}: {code}"));
+        assert!(comment < code.find(prop).unwrap(), "{language}: {code}");
+        let comment = code
+            .find(&format!("{marker} Kept as an override."))
+            .unwrap_or_else(|| panic!("{language}: {code}"));
+        assert!(comment < code.find(over).unwrap(), "{language}: {code}");
+    }
+}
+
+#[test]
+fn test_resource_options() {
+    let template: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Resources:
+          Asg:
+            Type: AWS::AutoScaling::AutoScalingGroup
+            DeletionPolicy: Retain
+            UpdateReplacePolicy: Retain
+            UpdatePolicy:
+              AutoScalingRollingUpdate:
+                MinInstancesInService: 1
+            CreationPolicy:
+              ResourceSignal:
+                Count: 3
+                Timeout: PT15M
+            Properties:
+              MinSize: '1'
+              MaxSize: '3'
+        "#,
+    )
+    .unwrap();
+    let ir =
+        crate::ir::CloudformationProgramIr::from(template, crate::cdk::Schema::builtin()).unwrap();
+
+    // Every option is a statement of its own.
+    for (language, options) in [
+        (
+            "go",
+            [
+                "asg.AddOverride(jsii.String(\"UpdatePolicy\"), ",
+                "asg.CfnOptions().SetDeletionPolicy(cdk.CfnDeletionPolicy_RETAIN)",
+                "asg.CfnOptions().SetUpdateReplacePolicy(cdk.CfnDeletionPolicy_RETAIN)",
+                "asg.CfnOptions().SetCreationPolicy(&cdk.CfnCreationPolicy{",
+            ],
+        ),
+        (
+            "python",
+            [
+                "asg.cfn_options.update_policy = {",
+                "asg.cfn_options.deletion_policy = cdk.CfnDeletionPolicy.RETAIN",
+                "asg.cfn_options.update_replace_policy = cdk.CfnDeletionPolicy.RETAIN",
+                "asg.cfn_options.creation_policy = cdk.CfnCreationPolicy(",
+            ],
+        ),
+        (
+            "typescript",
+            [
+                "asg.cfnOptions.updatePolicy = {",
+                "asg.cfnOptions.deletionPolicy = cdk.CfnDeletionPolicy.RETAIN;",
+                "asg.cfnOptions.updateReplacePolicy = cdk.CfnDeletionPolicy.RETAIN;",
+                "asg.cfnOptions.creationPolicy = {",
+            ],
+        ),
+    ] {
+        let mut code = Vec::new();
+        ir.clone().synthesize(language, &mut code, "Stack").unwrap();
+        let code = String::from_utf8(code).unwrap();
+        for option in options {
+            assert!(
+                code.lines()
+                    .any(|line| line.trim_start().starts_with(option)),
+                "{language}: {option}\n{code}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_cfn_parameter_logical_ids() {
+    let template: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Parameters:
+          KeyName:
+            Type: AWS::EC2::KeyPair::KeyName
+          VPCId:
+            Type: AWS::EC2::VPC::Id
+        Resources:
+          Queue:
+            Type: AWS::SQS::Queue
+        "#,
+    )
+    .unwrap();
+    let ir =
+        crate::ir::CloudformationProgramIr::from(template, crate::cdk::Schema::builtin()).unwrap();
+
+    // The CfnParameters keep the names of the template, so that deployments can still set them.
+    for (language, key_name, vpc_id) in [
+        (
+            "csharp",
+            "new CfnParameter(this, \"KeyName\",",
+            "new CfnParameter(this, \"VPCId\",",
+        ),
+        (
+            "go",
+            "cdk.NewCfnParameter(stack, jsii.String(\"KeyName\"),",
+            "cdk.NewCfnParameter(stack, jsii.String(\"VPCId\"),",
+        ),
+        (
+            "java",
+            "CfnParameter.Builder.create(this, \"KeyName\")",
+            "CfnParameter.Builder.create(this, \"VPCId\")",
+        ),
+        (
+            "python",
+            "cdk.CfnParameter(self, 'KeyName',",
+            "cdk.CfnParameter(self, 'VPCId',",
+        ),
+        (
+            "typescript",
+            "new cdk.CfnParameter(this, 'KeyName', {",
+            "new cdk.CfnParameter(this, 'VPCId', {",
+        ),
+    ] {
+        let mut code = Vec::new();
+        ir.clone().synthesize(language, &mut code, "Stack").unwrap();
+        let code = String::from_utf8(code).unwrap();
+        assert!(code.contains(key_name), "{language}: {code}");
+        assert!(code.contains(vpc_id), "{language}: {code}");
+        assert!(!code.contains("CfnParameter(self, 'KeyName', "), "{code}");
+    }
+}
+
+#[test]
+fn test_number_parameter_defaults() {
+    let template: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Parameters:
+          Port:
+            Type: Number
+            Default: 8080
+        Resources:
+          Queue:
+            Type: AWS::SQS::Queue
+        "#,
+    )
+    .unwrap();
+    let ir =
+        crate::ir::CloudformationProgramIr::from(template, crate::cdk::Schema::builtin()).unwrap();
+
+    // Number props are strings in every language, so their defaults are string literals.
+    for (language, default) in [
+        ("csharp", "props.Port ??= \"8080\";"),
+        ("go", "Port: jsii.String(\"8080\"),"),
+        ("java", ": \"8080\";"),
+        ("python", "'port': kwargs.get('port', '8080'),"),
+        ("typescript", "port: props.port ?? '8080',"),
+    ] {
+        let mut code = Vec::new();
+        ir.clone().synthesize(language, &mut code, "Stack").unwrap();
+        let code = String::from_utf8(code).unwrap();
+        assert!(code.contains(default), "{language}: {code}");
+    }
+}
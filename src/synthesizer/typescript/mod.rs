@@ -1,6 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::io;
 use std::rc::Rc;
 
@@ -10,22 +11,45 @@ use voca_rs::case::{camel_case, pascal_case};
 use crate::cdk::TypeReference;
 use crate::code::{CodeBuffer, IndentOptions};
 use crate::ir::conditions::ConditionIr;
-use crate::ir::constructor::ConstructorParameter;
+use crate::ir::constructor::{
+    ConstructorParameter, ConstructorType, DefaultValue, ParameterConstraint, PropValidation,
+};
+use crate::ir::constructs::{L2Construct, L2Value};
 use crate::ir::importer::ImportInstruction;
 use crate::ir::mappings::{MappingInstruction, OutputType};
 use crate::ir::outputs::OutputInstruction;
 use crate::ir::reference::{Origin, PseudoParameter, Reference};
-use crate::ir::resources::{ResourceInstruction, ResourceIr};
+use crate::ir::resources::{CreationPolicyIr, ResourceInstruction, ResourceIr};
+use crate::ir::rules::{RuleInstruction, RuleIr, RuleValueIr};
 use crate::ir::CloudformationProgramIr;
 use crate::parser::lookup_table::MappingInnerValue;
 use crate::util::Hasher;
 use crate::Error;
 
-use super::Synthesizer;
+use super::{ConstructOptions, Synthesizer, SynthesizerOptions};
 
 const INDENT: Cow<'static, str> = Cow::Borrowed("  ");
 
-pub struct Typescript {}
+#[derive(Default)]
+pub struct Typescript {
+    construct: Option<ConstructOptions>,
+}
+
+impl Typescript {
+    /// Declares a construct instead of a stack (nested stacks are still declared as such).
+    pub fn as_construct(mut self, options: ConstructOptions) -> Self {
+        self.construct = Some(options);
+        self
+    }
+}
+
+impl From<&SynthesizerOptions> for Typescript {
+    fn from(options: &SynthesizerOptions) -> Self {
+        Self {
+            construct: options.construct,
+        }
+    }
+}
 
 impl Synthesizer for Typescript {
     fn synthesize(
@@ -43,246 +67,333 @@ impl Synthesizer for Typescript {
 
         let context = &mut TypescriptContext::with_imports(imports);
 
-        let iface_props = code.indent_with_options(IndentOptions {
-            indent: INDENT,
-            leading: Some(
-                format!("export interface {stack_name}Props extends cdk.StackProps {{").into(),
-            ),
-            trailing: Some("}".into()),
-            trailing_newline: true,
-        });
-        for param in &ir.constructor.inputs {
-            let comment = iface_props.tsdoc();
-            if let Some(description) = &param.description {
-                comment.line(description.to_owned());
-            }
-            let question_mark_token = match &param.default_value {
-                None => "",
-                Some(value) => {
-                    let value = match param.constructor_type.as_str() {
-                        "Number" => value.clone(),
-                        _ => format!("'{}'", value.escape_debug()),
-                    };
-                    comment.line(format!("@default {value}"));
-                    "?"
-                }
-            };
-            let constructor_type = match param.constructor_type.as_str() {
-                "List<Number>" => "number[]",
-                t if t.contains("List") => "string[]",
-                "Boolean" => "boolean",
-                _ => "string",
-            };
-            iface_props.line(format!(
-                "readonly {}{question_mark_token}: {};",
-                pretty_name(&param.name),
-                constructor_type,
-            ));
+        for nested in ir.nested_stacks() {
+            emit_stack(
+                context,
+                &code,
+                &nested.program,
+                &nested.class_name,
+                true,
+                None,
+            );
+            code.newline();
         }
-        code.newline();
+        emit_stack(context, &code, &ir, stack_name, false, self.construct);
+
+        Ok(code.write(output)?)
+    }
+}
 
-        if let Some(description) = &ir.description {
-            let comment = code.tsdoc();
-            comment.line(description.to_owned());
+// emit_stack declares the class of a stack, and the interface of its props. Nested stacks extend
+// `cdk.NestedStack` rather than `cdk.Stack`, and constructs extend `Construct`, leaving the
+// stack-wide settings of the template to the stack they are added to.
+fn emit_stack(
+    context: &mut TypescriptContext,
+    code: &CodeBuffer,
+    ir: &CloudformationProgramIr,
+    stack_name: &str,
+    nested: bool,
+    construct: Option<ConstructOptions>,
+) {
+    let base_class = match (nested, construct) {
+        (_, Some(_)) => "Construct",
+        (true, None) => "cdk.NestedStack",
+        (false, None) => "cdk.Stack",
+    };
+    let scope_type = if nested || construct.is_some() {
+        context.import_construct();
+        "Construct"
+    } else {
+        "cdk.App"
+    };
+    context.nested_stacks = ir
+        .resources
+        .iter()
+        .filter(|resource| resource.nested_stack.is_some())
+        .map(|resource| resource.name.clone())
+        .collect();
+
+    let extends = match construct {
+        Some(_) => String::new(),
+        None => format!(" extends {base_class}Props"),
+    };
+    let iface_props = code.indent_with_options(IndentOptions {
+        indent: INDENT,
+        leading: Some(format!("export interface {stack_name}Props{extends} {{").into()),
+        trailing: Some("}".into()),
+        trailing_newline: true,
+    });
+    for param in &ir.constructor.inputs {
+        let comment = iface_props.tsdoc();
+        if let Some(description) = &param.description {
+            comment.line(tsdoc_text(description));
         }
-        let class = code.indent_with_options(IndentOptions {
-            indent: INDENT,
-            leading: Some(format!("export class {stack_name} extends cdk.Stack {{").into()),
-            trailing: Some("}".into()),
-            trailing_newline: true,
-        });
-        if !ir.outputs.is_empty() {
-            for op in &ir.outputs {
+        for line in &param.comments {
+            comment.line(tsdoc_text(line));
+        }
+        let question_mark_token = match &param.default_value {
+            None => "",
+            Some(value) => {
+                comment.line(tsdoc_text(&format!("@default {}", value.to_typescript())));
+                "?"
+            }
+        };
+        let constructor_type = match &param.constructor_type {
+            ConstructorType::ListOfNumbers => "number[]",
+            t if t.is_list() => "string[]",
+            ConstructorType::Boolean => "boolean",
+            _ => "string",
+        };
+        iface_props.line(format!(
+            "readonly {}{question_mark_token}: {};",
+            pretty_name(&param.name),
+            constructor_type,
+        ));
+    }
+    code.newline();
+
+    if let Some(description) = &ir.description {
+        let comment = code.tsdoc();
+        comment.line(tsdoc_text(description));
+    }
+    let class = code.indent_with_options(IndentOptions {
+        indent: INDENT,
+        leading: Some(format!("export class {stack_name} extends {base_class} {{").into()),
+        trailing: Some("}".into()),
+        trailing_newline: true,
+    });
+    if !ir.outputs.is_empty() {
+        for op in &ir.outputs {
+            if op.description.is_some() || !op.comments.is_empty() {
+                let comment = class.tsdoc();
                 if let Some(description) = &op.description {
-                    let comment = class.tsdoc();
-                    comment.line(description.to_owned());
+                    comment.line(tsdoc_text(description));
+                }
+                for line in &op.comments {
+                    comment.line(tsdoc_text(line));
                 }
-                // NOTE: the property type can be inferred by the compiler...
-                class.line(format!(
-                    "public readonly {name}{option};",
-                    name = pretty_name(&op.name),
-                    option = match &op.condition {
-                        Some(_) => "?",
-                        None => "",
-                    }
-                ));
             }
-            class.newline();
+            // NOTE: the property type can be inferred by the compiler...
+            class.line(format!(
+                "public readonly {name}{option};",
+                name = pretty_name(&op.name),
+                option = match &op.condition {
+                    Some(_) => "?",
+                    None => "",
+                }
+            ));
         }
+        class.newline();
+    }
 
-        let default_empty = if ir
-            .constructor
-            .inputs
-            .iter()
-            .all(|param| param.default_value.is_some())
-        {
-            " = {}"
-        } else {
-            ""
-        };
+    let default_empty = if ir
+        .constructor
+        .inputs
+        .iter()
+        .all(|param| param.default_value.is_some())
+    {
+        " = {}"
+    } else {
+        ""
+    };
 
-        let  ctor = class.indent_with_options(IndentOptions{
+    let  ctor = class.indent_with_options(IndentOptions{
+        indent: INDENT,
+        leading: Some(format!("public constructor(scope: {scope_type}, id: string, props: {stack_name}Props{default_empty}) {{").into()),
+        trailing: Some("}".into()),
+        trailing_newline: true,
+    });
+    ctor.line(match construct {
+        Some(_) => "super(scope, id);",
+        None => "super(scope, id, props);",
+    });
+
+    let have_default_or_special_type_params = &ir
+        .constructor
+        .inputs
+        .iter()
+        .filter(|p| p.is_cfn_parameter() || p.default_value.is_some())
+        .collect::<Vec<&ConstructorParameter>>();
+    if !have_default_or_special_type_params.is_empty() {
+        ctor.newline();
+        ctor.line("// Applying default props");
+        let obj = ctor.indent_with_options(IndentOptions {
             indent: INDENT,
-            leading: Some(format!("public constructor(scope: cdk.App, id: string, props: {stack_name}Props{default_empty}) {{").into()),
-            trailing: Some("}".into()),
+            leading: Some("props = {".into()),
+            trailing: Some("};".into()),
             trailing_newline: true,
         });
-        ctor.line("super(scope, id, props);");
-
-        let have_default_or_special_type_params = &ir
-            .constructor
-            .inputs
-            .iter()
-            .filter(|p| p.constructor_type.contains("AWS::") || p.default_value.is_some())
-            .collect::<Vec<&ConstructorParameter>>();
-        if !have_default_or_special_type_params.is_empty() {
-            ctor.newline();
-            ctor.line("// Applying default props");
-            let obj = ctor.indent_with_options(IndentOptions {
-                indent: INDENT,
-                leading: Some("props = {".into()),
-                trailing: Some("};".into()),
-                trailing_newline: true,
-            });
-            obj.line("...props,");
-            for param in have_default_or_special_type_params {
-                let name = &param.name;
-                // example: AWS::EC2::Image::Id, List<AWS::EC2::VPC::Id>, AWS::SSM::Parameter::Value<List<String>>
-                if param.constructor_type.contains("AWS::")
-                    || param.no_echo.as_ref().is_some_and(|x| x == "true")
-                {
-                    let value_as = match &param.constructor_type {
-                        t if t.contains("List") => "valueAsList",
-                        _ => "valueAsString",
-                    };
-                    let cfn_param = obj.indent_with_options(IndentOptions {
-                        indent: INDENT,
-                        leading: Some(
-                            format!(
-                                "{name}: new cdk.CfnParameter(this, '{}', {{",
-                                pascal_case(&param.name)
-                            )
-                            .into(),
-                        ),
-                        trailing: Some(format!("}}).{value_as},").into()),
-                        trailing_newline: true,
-                    });
-                    cfn_param.line(format!("type: '{}',", param.constructor_type));
-                    let to_string = match &param.constructor_type {
-                        t if t.contains("List") => "join(',')",
-                        _ => "toString()",
-                    };
-                    if let Some(v) = &param.default_value {
-                        cfn_param.line(format!(
-                            "default: props.{name}?.{to_string} ?? '{}',",
-                            v.escape_debug()
-                        ));
-                    } else {
-                        cfn_param.line(format!("default: props.{name}.{to_string},"));
-                    };
-                    if let Some(v) = &param.description {
-                        cfn_param.line(format!("description: '{v}',"));
-                    };
-                    if let Some(v) = &param.no_echo {
-                        cfn_param.line(format!("noEcho: {v},"));
-                    }
+        obj.line("...props,");
+        for param in have_default_or_special_type_params {
+            let name = &param.name;
+            if param.ssm_lookup {
+                let name_value = match &param.default_value {
+                    Some(v) => format!("props.{name} ?? '{}'", v.to_string().escape_debug()),
+                    None => format!("props.{name}!"),
+                };
+                obj.line(format!(
+                    "{name}: ssm.StringParameter.valueForStringParameter(this, {name_value}),"
+                ));
+            } else if param.is_cfn_parameter() {
+                let value_as = match param.constructor_type.is_list() {
+                    true => "valueAsList",
+                    false => "valueAsString",
+                };
+                let cfn_param = obj.indent_with_options(IndentOptions {
+                    indent: INDENT,
+                    leading: Some(
+                        format!(
+                            "{name}: new cdk.CfnParameter(this, '{}', {{",
+                            param.logical_id
+                        )
+                        .into(),
+                    ),
+                    trailing: Some(format!("}}).{value_as},").into()),
+                    trailing_newline: true,
+                });
+                cfn_param.line(format!("type: '{}',", param.constructor_type));
+                let to_string = match param.constructor_type.is_list() {
+                    true => "join(',')",
+                    false => "toString()",
+                };
+                if let Some(v) = &param.default_value {
+                    cfn_param.line(format!(
+                        "default: props.{name}?.{to_string} ?? '{}',",
+                        v.to_string().escape_debug()
+                    ));
                 } else {
-                    let value = match &param.default_value {
-                        None => "".to_owned(),
-                        Some(value) => {
-                            let value = match param.constructor_type.as_str() {
-                                "String" => format!("'{}'", value.escape_debug()),
-                                "List<Number>" => format!("[{value}]"),
-                                "CommaDelimitedList" => format!(
-                                    "[{}]",
-                                    value
-                                        .split(',')
-                                        .map(|v| format!("'{}'", v.escape_debug()))
-                                        .collect::<Vec<String>>()
-                                        .join(",")
-                                ),
-                                _ => value.clone(),
-                            };
-                            value
-                        }
-                    };
-
-                    obj.line(format!("{name}: props.{name} ?? {value},"));
+                    cfn_param.line(format!("default: props.{name}.{to_string},"));
+                };
+                if let Some(v) = &param.description {
+                    cfn_param.line(format!("description: '{v}',"));
+                };
+                emit_parameter_constraints(&cfn_param, param);
+                if let Some(v) = &param.no_echo {
+                    cfn_param.line(format!("noEcho: {v},"));
+                }
+            } else {
+                let value = match &param.default_value {
+                    None => "".to_owned(),
+                    Some(value) => value.to_typescript(),
                 };
-            }
-        }
-
-        if !ir.transforms.is_empty() {
-            ctor.newline();
-            ctor.line("// Transforms");
 
-            for transform in &ir.transforms {
-                ctor.line(format!("this.addTransform('{transform}');"));
-            }
+                obj.line(format!("{name}: props.{name} ?? {value},"));
+            };
         }
+    }
 
-        emit_mappings(&ctor, &ir.mappings);
+    emit_parameter_validation(&ctor, &ir.constructor.validations());
 
-        if !ir.conditions.is_empty() {
-            ctor.newline();
-            ctor.line("// Conditions");
+    // The template options are those of the stack, which a construct does not own.
+    if construct.is_none() {
+        emit_template_options(context, &ctor, ir);
+    }
 
-            for cond in &ir.conditions {
-                let synthed = synthesize_condition_recursive(&cond.value);
-                ctor.line(format!("const {} = {};", pretty_name(&cond.name), synthed));
-            }
+    if !ir.transforms.is_empty() {
+        ctor.newline();
+        ctor.line("// Transforms");
+
+        let stack = match construct {
+            Some(_) => "cdk.Stack.of(this)",
+            None => "this",
+        };
+        for transform in &ir.transforms {
+            ctor.line(format!("{stack}.addTransform('{transform}');"));
         }
+    }
+
+    emit_mappings(&ctor, &ir.mappings);
 
+    if !ir.conditions.is_empty() {
         ctor.newline();
-        ctor.line("// Resources");
+        ctor.line("// Conditions");
 
-        let mut is_first_resource = true;
-        for reference in &ir.resources {
-            if is_first_resource {
-                is_first_resource = false;
-            } else {
-                ctor.newline();
-            }
-            emit_resource(context, &ctor, reference);
+        for cond in &ir.conditions {
+            let synthed = synthesize_condition_recursive(&cond.value);
+            ctor.line(format!("const {} = {};", pretty_name(&cond.name), synthed));
         }
+    }
+
+    emit_rules(&ctor, &ir.rules);
 
-        if !ir.outputs.is_empty() {
+    ctor.newline();
+    ctor.line("// Resources");
+
+    let mut is_first_resource = true;
+    for reference in &ir.resources {
+        if is_first_resource {
+            is_first_resource = false;
+        } else {
             ctor.newline();
-            ctor.line("// Outputs");
+        }
+        emit_resource(context, &ctor, reference);
+    }
+
+    if !ir.outputs.is_empty() {
+        ctor.newline();
+        ctor.line("// Outputs");
 
-            for op in &ir.outputs {
-                let var_name = pretty_name(&op.name);
-                let cond = op.condition.as_ref().map(|s| pretty_name(s));
+        for op in &ir.outputs {
+            let var_name = pretty_name(&op.name);
+            let cond = op.condition.as_ref().map(|s| pretty_name(s));
 
-                if let Some(cond) = &cond {
-                    ctor.line(format!(
-                        "this.{var_name} = {cond}",
-                        cond = pretty_name(cond)
-                    ));
-                    ctor.text(format!("{INDENT}? "));
-                    let indented = ctor.indent(INDENT);
-                    emit_resource_ir(context, &indented, &op.value, Some("\n"));
-                    ctor.line(format!("{INDENT}: undefined;"));
-                } else {
-                    ctor.text(format!("this.{var_name} = "));
-                    emit_resource_ir(context, &ctor, &op.value, Some(";\n"));
-                }
+            if let Some(cond) = &cond {
+                ctor.line(format!(
+                    "this.{var_name} = {cond}",
+                    cond = pretty_name(cond)
+                ));
+                ctor.text(format!("{INDENT}? "));
+                let indented = ctor.indent(INDENT);
+                emit_resource_ir(context, &indented, &op.value, Some("\n"));
+                ctor.line(format!("{INDENT}: undefined;"));
+            } else {
+                ctor.text(format!("this.{var_name} = "));
+                emit_resource_ir(context, &ctor, &op.value, Some(";\n"));
+            }
 
-                if let Some(cond) = cond {
-                    let indented = ctor.indent_with_options(IndentOptions {
-                        indent: INDENT,
-                        leading: Some(format!("if ({cond}) {{").into()),
-                        trailing: Some("}".into()),
-                        trailing_newline: true,
-                    });
-                    emit_cfn_output(context, &indented, op, &var_name);
-                } else {
-                    emit_cfn_output(context, &ctor, op, &var_name);
-                }
+            if construct.is_some_and(|construct| !construct.cfn_outputs) {
+                continue;
+            }
+            if let Some(cond) = cond {
+                let indented = ctor.indent_with_options(IndentOptions {
+                    indent: INDENT,
+                    leading: Some(format!("if ({cond}) {{").into()),
+                    trailing: Some("}".into()),
+                    trailing_newline: true,
+                });
+                emit_cfn_output(context, &indented, op, &var_name);
+            } else {
+                emit_cfn_output(context, &ctor, op, &var_name);
             }
         }
+    }
 
-        Ok(code.write(output)?)
+    if construct.is_some() {
+        emit_pseudo_parameters(&class, ir);
+    }
+}
+
+// emit_pseudo_parameters declares the pseudo parameters a construct uses as getters, so that they
+// are referred to the same way as in a stack, but resolved by the stack the construct is in.
+fn emit_pseudo_parameters(class: &CodeBuffer, ir: &CloudformationProgramIr) {
+    for pseudo in ir.pseudo_parameters() {
+        let (name, value_type) = match pseudo {
+            PseudoParameter::Partition => ("partition", "string"),
+            PseudoParameter::Region => ("region", "string"),
+            PseudoParameter::StackId => ("stackId", "string"),
+            PseudoParameter::StackName => ("stackName", "string"),
+            PseudoParameter::URLSuffix => ("urlSuffix", "string"),
+            PseudoParameter::AccountId => ("account", "string"),
+            PseudoParameter::NotificationArns => ("notificationArns", "string[]"),
+        };
+        class.newline();
+        let getter = class.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some(format!("private get {name}(): {value_type} {{").into()),
+            trailing: Some("}".into()),
+            trailing_newline: true,
+        });
+        getter.line(format!("return cdk.Stack.of(this).{name};"));
     }
 }
 
@@ -320,15 +431,28 @@ impl ImportInstruction {
 struct TypescriptContext {
     imports: Rc<CodeBuffer>,
     imports_buffer: bool,
+    imports_construct: bool,
+    // The resources of the stack being emitted that are nested stacks.
+    nested_stacks: HashSet<String>,
 }
 impl TypescriptContext {
-    const fn with_imports(imports: Rc<CodeBuffer>) -> Self {
+    fn with_imports(imports: Rc<CodeBuffer>) -> Self {
         Self {
             imports,
             imports_buffer: false,
+            imports_construct: false,
+            nested_stacks: HashSet::new(),
         }
     }
 
+    fn import_construct(&mut self) {
+        if self.imports_construct {
+            return;
+        }
+        self.imports.line("import { Construct } from 'constructs';");
+        self.imports_construct = true;
+    }
+
     fn import_buffer(&mut self) {
         if self.imports_buffer {
             return;
@@ -338,6 +462,27 @@ impl TypescriptContext {
     }
 }
 
+impl DefaultValue {
+    fn to_typescript(&self) -> String {
+        match self {
+            DefaultValue::String(value) => format!("'{}'", value.escape_debug()),
+            // Number props are typed as strings, but List<Number> props as number[]
+            DefaultValue::Number(value) => format!("'{value}'"),
+            DefaultValue::Bool(value) => value.to_string(),
+            DefaultValue::List(items) => {
+                let items = items
+                    .iter()
+                    .map(|item| match item {
+                        DefaultValue::Number(value) => value.to_string(),
+                        other => other.to_typescript(),
+                    })
+                    .collect::<Vec<_>>();
+                format!("[{}]", items.join(", "))
+            }
+        }
+    }
+}
+
 impl Reference {
     fn to_typescript(&self) -> Cow<'static, str> {
         match &self.origin {
@@ -370,6 +515,19 @@ impl Reference {
                 name = pascal_case(&attribute.replace('.', ""))
             )
             .into(),
+            Origin::NestedStackOutput {
+                output,
+                conditional,
+            } => format!(
+                "{var_name}.{name}{bang}",
+                var_name = camel_case(&self.name),
+                name = pretty_name(output),
+                bang = if *conditional { "!" } else { "" }
+            )
+            .into(),
+            Origin::ConstructAttribute { attribute } => {
+                format!("{}.{attribute}", camel_case(&self.name)).into()
+            }
         }
     }
 }
@@ -404,7 +562,76 @@ fn emit_resource(
     reference: &ResourceInstruction,
 ) {
     let var_name = pretty_name(&reference.name);
+    output.comments("//", &reference.comments);
+    if let Some(nested) = &reference.nested_stack {
+        output.line(format!(
+            "const {var_name} = new {}(this, '{}', {{",
+            nested.class_name,
+            reference.name.escape_debug(),
+        ));
+        let comments = IndexMap::default();
+        emit_resource_props(
+            context,
+            output.indent(INDENT),
+            &nested.parameters,
+            &comments,
+        );
+        output.line("});");
+        // The resource attributes apply to the CfnStack deploying the nested stack.
+        emit_cfn_options(
+            context,
+            output,
+            reference,
+            &format!("{var_name}.nestedStackResource!"),
+        );
+        for dependency in &reference.dependencies {
+            output.line(format!(
+                "{var_name}.node.addDependency({});",
+                pretty_name(dependency)
+            ));
+        }
+        return;
+    }
     let service = reference.resource_type.service().to_lowercase();
+    if let Some(construct) = &reference.l2_construct {
+        output.line(format!(
+            "const {var_name} = new {service}.{}(this, '{}', {{",
+            construct.class,
+            reference.name.escape_debug(),
+        ));
+        let props = output.indent(INDENT);
+        for (name, value) in &construct.props {
+            if let Some(comments) = reference.property_comments.get(name) {
+                props.comments("//", comments);
+            }
+            props.text(format!("{name}: "));
+            emit_l2_value(context, &props, value, Some(",\n"));
+        }
+        output.line("});");
+
+        // The L1 resource keeps the logical ID of the template, and what the construct can't express
+        let cfn_var_name = pretty_name(&L2Construct::escape_hatch(&reference.name));
+        output.line(format!(
+            "const {cfn_var_name} = {var_name}.node.defaultChild as {service}.Cfn{};",
+            reference.resource_type.type_name(),
+        ));
+        output.line(format!(
+            "{cfn_var_name}.overrideLogicalId('{}');",
+            reference.name.escape_debug()
+        ));
+        for (name, value) in &construct.overrides {
+            if let Some(comments) = reference.property_comments.get(name) {
+                output.comments("//", comments);
+            }
+            output.text(format!(
+                "{cfn_var_name}.addPropertyOverride('{}', ",
+                name.escape_debug()
+            ));
+            emit_resource_ir(context, output, value, Some(");\n"));
+        }
+        emit_resource_attributes(context, output, reference, &cfn_var_name);
+        return;
+    }
 
     let maybe_undefined = if let Some(cond) = &reference.condition {
         output.line(format!(
@@ -421,7 +648,12 @@ fn emit_resource(
         ));
 
         let mid_output = output.indent(INDENT);
-        emit_resource_props(context, mid_output.indent(INDENT), &reference.properties);
+        emit_resource_props(
+            context,
+            mid_output.indent(INDENT),
+            &reference.properties,
+            &reference.property_comments,
+        );
         mid_output.line("})");
 
         output.line(": undefined;");
@@ -434,7 +666,12 @@ fn emit_resource(
             rtype = reference.resource_type.type_name(),
         ));
 
-        emit_resource_props(context, output.indent(INDENT), &reference.properties);
+        emit_resource_props(
+            context,
+            output.indent(INDENT),
+            &reference.properties,
+            &reference.property_comments,
+        );
 
         output.line("});");
 
@@ -451,11 +688,102 @@ fn emit_resource(
     }
 }
 
+fn emit_l2_value(
+    context: &mut TypescriptContext,
+    output: &CodeBuffer,
+    value: &L2Value,
+    trailer: Option<&str>,
+) {
+    let emit_args = |context: &mut TypescriptContext, args: &[L2Value]| {
+        output.text("(");
+        for (index, arg) in args.iter().enumerate() {
+            if index > 0 {
+                output.text(", ");
+            }
+            emit_l2_value(context, output, arg, None);
+        }
+        output.text(")");
+    };
+    match value {
+        L2Value::Value(value) => emit_resource_ir(context, output, value, None),
+        L2Value::Seconds(value) => {
+            output.text("cdk.Duration.seconds(");
+            emit_resource_ir(context, output, value, None);
+            output.text(")");
+        }
+        L2Value::Enum {
+            service,
+            name,
+            member,
+        } => output.text(format!("{}.{name}.{member}", service.to_lowercase())),
+        L2Value::Struct { fields, .. } => {
+            let obj = output.indent_with_options(IndentOptions {
+                indent: INDENT,
+                leading: Some("{".into()),
+                trailing: Some("}".into()),
+                trailing_newline: false,
+            });
+            for (name, value) in fields {
+                obj.text(format!("{name}: "));
+                emit_l2_value(context, &obj, value, Some(",\n"));
+            }
+        }
+        L2Value::New {
+            service,
+            class,
+            args,
+            ..
+        } => {
+            output.text(format!("new {}.{class}", service.to_lowercase()));
+            emit_args(context, args);
+        }
+        L2Value::Call {
+            service,
+            class,
+            method,
+            args,
+            ..
+        } => {
+            output.text(format!("{}.{class}.{method}", service.to_lowercase()));
+            emit_args(context, args);
+        }
+        L2Value::Scope => output.text("this"),
+        L2Value::Construct(name) => output.text(camel_case(name)),
+    }
+
+    if let Some(trailer) = trailer {
+        output.text(trailer.to_owned())
+    }
+}
+
 fn emit_resource_attributes(
     context: &mut TypescriptContext,
     output: &CodeBuffer,
     reference: &ResourceInstruction,
     var_name: &str,
+) {
+    emit_cfn_options(context, output, reference, var_name);
+
+    if !reference.dependencies.is_empty() {
+        for dependency in &reference.dependencies {
+            // Nested stacks are deployed by a CfnStack resource of their parent.
+            let nested_stack_resource = match context.nested_stacks.contains(dependency) {
+                true => ".nestedStackResource!",
+                false => "",
+            };
+            output.line(format!(
+                "{var_name}.addDependency({}{nested_stack_resource});",
+                pretty_name(dependency)
+            ));
+        }
+    }
+}
+
+fn emit_cfn_options(
+    context: &mut TypescriptContext,
+    output: &CodeBuffer,
+    reference: &ResourceInstruction,
+    var_name: &str,
 ) {
     if let Some(metadata) = &reference.metadata {
         let md = output.indent_with_options(IndentOptions {
@@ -469,7 +797,7 @@ fn emit_resource_attributes(
 
     if let Some(update_policy) = &reference.update_policy {
         output.text(format!("{var_name}.cfnOptions.updatePolicy = "));
-        emit_resource_ir(context, output, update_policy, Some(";"));
+        emit_resource_ir(context, output, update_policy, Some(";\n"));
     }
 
     if let Some(deletion_policy) = &reference.deletion_policy {
@@ -478,13 +806,49 @@ fn emit_resource_attributes(
         ));
     }
 
-    if !reference.dependencies.is_empty() {
-        for dependency in &reference.dependencies {
-            output.line(format!(
-                "{var_name}.addDependency({});",
-                pretty_name(dependency)
-            ));
-        }
+    if let Some(update_replace_policy) = &reference.update_replace_policy {
+        output.line(format!(
+            "{var_name}.cfnOptions.updateReplacePolicy = cdk.CfnDeletionPolicy.{update_replace_policy};"
+        ));
+    }
+
+    if let Some(creation_policy) = &reference.creation_policy {
+        let cp = output.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some(format!("{var_name}.cfnOptions.creationPolicy = {{").into()),
+            trailing: Some("};".into()),
+            trailing_newline: true,
+        });
+        emit_creation_policy(context, cp, creation_policy);
+    }
+}
+
+fn emit_template_options(
+    context: &mut TypescriptContext,
+    output: &CodeBuffer,
+    ir: &CloudformationProgramIr,
+) {
+    let metadata = ir.metadata.as_ref().filter(|metadata| !metadata.is_empty());
+    if ir.template_format_version.is_none() && metadata.is_none() {
+        return;
+    }
+
+    output.newline();
+    output.line("// Template options");
+    if let Some(version) = &ir.template_format_version {
+        output.line(format!(
+            "this.templateOptions.templateFormatVersion = '{}';",
+            version.escape_debug()
+        ));
+    }
+    if let Some(metadata) = metadata {
+        output.text("this.templateOptions.metadata = ");
+        emit_resource_ir(
+            context,
+            output,
+            &metadata.to_ir(|name| ir.constructor.logical_id(name)),
+            Some(";\n"),
+        );
     }
 }
 
@@ -504,12 +868,55 @@ fn emit_resource_metadata(
     }
 }
 
+fn emit_creation_policy(
+    context: &mut TypescriptContext,
+    output: Rc<CodeBuffer>,
+    creation_policy: &CreationPolicyIr,
+) {
+    if let Some(asg) = &creation_policy.auto_scaling_creation_policy {
+        let asg_output = output.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some("autoScalingCreationPolicy: {".into()),
+            trailing: Some("},".into()),
+            trailing_newline: true,
+        });
+        if let Some(percent) = &asg.min_successful_instances_percent {
+            asg_output.text("minSuccessfulInstancesPercent: ");
+            emit_resource_ir(context, &asg_output, percent, Some(",\n"));
+        }
+    }
+    if let Some(signal) = &creation_policy.resource_signal {
+        let signal_output = output.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some("resourceSignal: {".into()),
+            trailing: Some("},".into()),
+            trailing_newline: true,
+        });
+        if let Some(count) = &signal.count {
+            signal_output.text("count: ");
+            emit_resource_ir(context, &signal_output, count, Some(",\n"));
+        }
+        if let Some(timeout) = &signal.timeout {
+            signal_output.text("timeout: ");
+            emit_resource_ir(context, &signal_output, timeout, Some(",\n"));
+        }
+    }
+    if let Some(start_fleet) = &creation_policy.start_fleet {
+        output.text("startFleet: ");
+        emit_resource_ir(context, &output, start_fleet, Some(",\n"));
+    }
+}
+
 fn emit_resource_props(
     context: &mut TypescriptContext,
     output: Rc<CodeBuffer>,
     props: &IndexMap<String, ResourceIr, Hasher>,
+    comments: &IndexMap<String, Vec<String>, Hasher>,
 ) {
     for (name, prop) in props {
+        if let Some(comments) = comments.get(name) {
+            output.comments("//", comments);
+        }
         output.text(format!("{}: ", pretty_name(name)));
         emit_resource_ir(context, &output, prop, Some(",\n"));
     }
@@ -656,7 +1063,11 @@ fn emit_resource_ir(
             output.text("`");
             for part in parts {
                 match part {
-                    ResourceIr::String(lit) => output.text(lit.clone()),
+                    ResourceIr::String(lit) => output.text(
+                        lit.replace('\\', "\\\\")
+                            .replace('`', "\\`")
+                            .replace("${", "\\${"),
+                    ),
                     other => {
                         output.text("${");
                         emit_resource_ir(context, output, other, None);
@@ -667,6 +1078,27 @@ fn emit_resource_ir(
             output.text("`")
         }
 
+        ResourceIr::Length(list) => match list.as_ref() {
+            array @ ResourceIr::Array(..) => {
+                emit_resource_ir(context, output, array, None);
+                output.text(".length")
+            }
+            other => {
+                output.text("cdk.Fn.len(");
+                emit_resource_ir(context, output, other, None);
+                output.text(")")
+            }
+        },
+        ResourceIr::ToJsonString(value) => {
+            if value.to_json().is_some() {
+                output.text("JSON.stringify(");
+            } else {
+                output.text("cdk.Fn.toJsonString(");
+            }
+            emit_resource_ir(context, output, value, None);
+            output.text(")")
+        }
+
         // References
         ResourceIr::Ref(reference) => output.text(reference.to_typescript()),
     }
@@ -676,6 +1108,103 @@ fn emit_resource_ir(
     }
 }
 
+fn emit_parameter_constraints(output: &CodeBuffer, param: &ConstructorParameter) {
+    if let Some(values) = &param.allowed_values {
+        let values = values
+            .iter()
+            .map(|v| format!("'{}'", v.escape_debug()))
+            .collect::<Vec<_>>();
+        output.line(format!("allowedValues: [{}],", values.join(", ")));
+    }
+    if let Some(v) = &param.allowed_pattern {
+        output.line(format!("allowedPattern: '{}',", v.escape_debug()));
+    }
+    if let Some(v) = &param.constraint_description {
+        output.line(format!("constraintDescription: '{}',", v.escape_debug()));
+    }
+    if let Some(v) = param.min_length {
+        output.line(format!("minLength: {v},"));
+    }
+    if let Some(v) = param.max_length {
+        output.line(format!("maxLength: {v},"));
+    }
+    if let Some(v) = param.min_value {
+        output.line(format!("minValue: {v},"));
+    }
+    if let Some(v) = param.max_value {
+        output.line(format!("maxValue: {v},"));
+    }
+}
+
+fn emit_parameter_validation(output: &CodeBuffer, validations: &[PropValidation]) {
+    if validations.is_empty() {
+        return;
+    }
+
+    output.newline();
+    output.line("// Validating props");
+    for validation in validations {
+        let param = validation.param;
+        let value = format!("props.{}!", param.name);
+        let number = format!("Number({value})");
+        let block = output.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some(format!("if (!cdk.Token.isUnresolved(props.{})) {{", param.name).into()),
+            trailing: Some("}".into()),
+            trailing_newline: true,
+        });
+        for check in &validation.checks {
+            let violated = match &check.constraint {
+                ParameterConstraint::AllowedValues(values) => {
+                    let values = values
+                        .iter()
+                        .map(|v| format!("'{}'", v.escape_debug()))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    match param.constructor_type {
+                        ConstructorType::Number => format!("![{values}].includes(String({value}))"),
+                        _ => format!("![{values}].includes({value})"),
+                    }
+                }
+                ParameterConstraint::AllowedPattern(pattern) => {
+                    format!("!/^(?:{})$/.test({value})", regex_literal(pattern))
+                }
+                ParameterConstraint::MinLength(min) => format!("{value}.length < {min}"),
+                ParameterConstraint::MaxLength(max) => format!("{value}.length > {max}"),
+                ParameterConstraint::MinValue(min) => format!("{number} < {min}"),
+                ParameterConstraint::MaxValue(max) => format!("{number} > {max}"),
+            };
+            block
+                .indent_with_options(IndentOptions {
+                    indent: INDENT,
+                    leading: Some(format!("if ({violated}) {{").into()),
+                    trailing: Some("}".into()),
+                    trailing_newline: true,
+                })
+                .line(format!(
+                    "throw new Error('{}');",
+                    check.message.escape_debug()
+                ));
+        }
+    }
+}
+
+// Escapes the forward slashes a pattern may contain, so it can be used in a regular expression
+// literal.
+fn regex_literal(pattern: &str) -> String {
+    let mut literal = String::with_capacity(pattern.len());
+    let mut escaped = false;
+    for c in pattern.chars() {
+        match c {
+            '/' if !escaped => literal.push_str("\\/"),
+            '\n' => literal.push_str("\\n"),
+            _ => literal.push(c),
+        }
+        escaped = c == '\\' && !escaped;
+    }
+    literal
+}
+
 fn emit_mappings(output: &CodeBuffer, mappings: &[MappingInstruction]) {
     if mappings.is_empty() {
         return;
@@ -768,6 +1297,136 @@ fn synthesize_condition_recursive(val: &ConditionIr) -> String {
     }
 }
 
+fn emit_rules(output: &CodeBuffer, rules: &[RuleInstruction]) {
+    if rules.is_empty() {
+        return;
+    }
+
+    output.newline();
+    output.line("// Rules");
+
+    for rule in rules {
+        let output = output.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some(
+                format!("new cdk.CfnRule(this, '{}', {{", rule.name.escape_debug()).into(),
+            ),
+            trailing: Some("});".into()),
+            trailing_newline: true,
+        });
+        if let Some(condition) = &rule.condition {
+            output.line(format!(
+                "ruleCondition: {},",
+                synthesize_rule_recursive(condition)
+            ));
+        }
+        if rule.assertions.is_empty() {
+            continue;
+        }
+        let assertions = output.indent_with_options(IndentOptions {
+            indent: INDENT,
+            leading: Some("assertions: [".into()),
+            trailing: Some("],".into()),
+            trailing_newline: true,
+        });
+        for assertion in &rule.assertions {
+            let assertion_output = assertions.indent_with_options(IndentOptions {
+                indent: INDENT,
+                leading: Some("{".into()),
+                trailing: Some("},".into()),
+                trailing_newline: true,
+            });
+            assertion_output.line(format!(
+                "assert: {},",
+                synthesize_rule_recursive(&assertion.assert)
+            ));
+            // The CDK requires a description, even though CloudFormation does not.
+            assertion_output.line(format!(
+                "assertDescription: '{}',",
+                assertion
+                    .description
+                    .as_deref()
+                    .unwrap_or_default()
+                    .escape_debug()
+            ));
+        }
+    }
+}
+
+fn synthesize_rule_recursive(val: &RuleIr) -> String {
+    match val {
+        RuleIr::And(x) => format!(
+            "cdk.Fn.conditionAnd({})",
+            x.iter()
+                .map(synthesize_rule_recursive)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        RuleIr::Or(x) => format!(
+            "cdk.Fn.conditionOr({})",
+            x.iter()
+                .map(synthesize_rule_recursive)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        RuleIr::Not(x) => format!("cdk.Fn.conditionNot({})", synthesize_rule_recursive(x)),
+        RuleIr::Equals(a, b) => format!(
+            "cdk.Fn.conditionEquals({}, {})",
+            synthesize_rule_value(a, false),
+            synthesize_rule_value(b, false)
+        ),
+        RuleIr::Contains(a, b) => format!(
+            "cdk.Fn.conditionContains({}, {})",
+            synthesize_rule_value(a, true),
+            synthesize_rule_value(b, false)
+        ),
+        RuleIr::EachMemberEquals(a, b) => format!(
+            "cdk.Fn.conditionEachMemberEquals({}, {})",
+            synthesize_rule_value(a, true),
+            synthesize_rule_value(b, false)
+        ),
+        RuleIr::EachMemberIn(a, b) => format!(
+            "cdk.Fn.conditionEachMemberIn({}, {})",
+            synthesize_rule_value(a, true),
+            synthesize_rule_value(b, true)
+        ),
+    }
+}
+
+/// Renders a rule operand. `as_list` is set where the CDK expects a `string[]`, as `Fn::ValueOf`
+/// is typed as a string even when the attribute holds a list.
+fn synthesize_rule_value(val: &RuleValueIr, as_list: bool) -> String {
+    match val {
+        RuleValueIr::Str(x) => format!("'{}'", x.escape_debug()),
+        RuleValueIr::List(x) => format!(
+            "[{}]",
+            x.iter()
+                .map(|val| synthesize_rule_value(val, false))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        RuleValueIr::Ref(x) => x.to_typescript().into(),
+        RuleValueIr::RefAll(x) => format!("cdk.Fn.refAll('{}')", x.escape_debug()),
+        RuleValueIr::ValueOf(name, attribute) => {
+            let value = format!(
+                "cdk.Fn.valueOf('{}', '{}')",
+                name.escape_debug(),
+                attribute.escape_debug()
+            );
+            if as_list {
+                format!("cdk.Token.asList({value})")
+            } else {
+                value
+            }
+        }
+        RuleValueIr::ValueOfAll(x, attribute) => format!(
+            "cdk.Fn.valueOfAll('{}', '{}')",
+            x.escape_debug(),
+            attribute.escape_debug()
+        ),
+    }
+}
+
 fn emit_mapping_instruction(output: Rc<CodeBuffer>, mapping_instruction: &MappingInstruction) {
     for (name, inner_mapping) in &mapping_instruction.map {
         let output = output.indent_with_options(IndentOptions {
@@ -814,7 +1473,7 @@ static SUFFIX_FIXES: &[SuffixFix] = &[
     },
 ];
 
-fn pretty_name(name: &str) -> String {
+pub(super) fn pretty_name(name: &str) -> String {
     // hardcoded consts that always need love.
     if name == "VPCs" {
         return "vpcs".to_string();
@@ -838,6 +1497,11 @@ fn pretty_name(name: &str) -> String {
     camel_case(&end_str)
 }
 
+// Keeps `text` from closing the TSDoc comment it is written in.
+fn tsdoc_text(text: &str) -> String {
+    text.replace("*/", "*\\/")
+}
+
 trait TypescriptCodeBuffer {
     fn tsdoc(&self) -> Rc<CodeBuffer>;
 }
@@ -37,3 +37,396 @@ fn test_alexa_organization() {
         result.unwrap()
     );
 }
+
+#[test]
+fn test_parameter_constraints() {
+    let template: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Parameters:
+          Port:
+            Type: Number
+            Default: 8080
+            MinValue: 1
+            MaxValue: 65535
+          KeyName:
+            Type: AWS::EC2::KeyPair::KeyName
+            AllowedPattern: '[\w/-]+'
+            ConstraintDescription: must be an existing key pair
+            MaxLength: 255
+        Resources: {}
+        "#,
+    )
+    .unwrap();
+    let ir = CloudformationProgramIr::from(template, crate::cdk::Schema::builtin()).unwrap();
+    let mut output = Vec::new();
+    Typescript::default()
+        .synthesize(ir, &mut output, "Stack")
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(
+        output.contains(
+            "      keyName: new cdk.CfnParameter(this, 'KeyName', {
+        type: 'AWS::EC2::KeyPair::KeyName',
+        default: props.keyName.toString(),
+        allowedPattern: '[\\\\w/-]+',
+        constraintDescription: 'must be an existing key pair',
+        maxLength: 255,
+      }).valueAsString,
+"
+        ),
+        "{output}"
+    );
+    assert!(
+        output.contains(
+            "    // Validating props
+    if (!cdk.Token.isUnresolved(props.port)) {
+      if (Number(props.port!) < 1) {
+        throw new Error('port must be at least 1');
+      }
+      if (Number(props.port!) > 65535) {
+        throw new Error('port must be at most 65535');
+      }
+    }
+"
+        ),
+        "{output}"
+    );
+}
+
+#[test]
+fn test_parameter_default_values() {
+    let template: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Parameters:
+          Subnets:
+            Type: CommaDelimitedList
+            Default: "subnet-a , subnet-'b'"
+          Ports:
+            Type: List<Number>
+            Default: [80, 443]
+          Enabled:
+            Type: Boolean
+            Default: true
+          Size:
+            Type: Number
+            Default: 5
+        Resources: {}
+        "#,
+    )
+    .unwrap();
+    let ir = CloudformationProgramIr::from(template, crate::cdk::Schema::builtin()).unwrap();
+    let mut output = Vec::new();
+    Typescript::default()
+        .synthesize(ir, &mut output, "Stack")
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(
+        output.contains(
+            "      subnets: props.subnets ?? ['subnet-a', 'subnet-\\'b\\''],
+      ports: props.ports ?? [80, 443],
+      enabled: props.enabled ?? true,
+      size: props.size ?? '5',
+"
+        ),
+        "{output}"
+    );
+    assert!(output.contains("   * @default [80, 443]\n"), "{output}");
+}
+
+#[test]
+fn test_ssm_parameter_lookups() {
+    let template: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Parameters:
+          Ami:
+            Type: AWS::SSM::Parameter::Value<AWS::EC2::Image::Id>
+            Default: /aws/service/ami-amazon-linux-latest/al2023-ami-kernel-default-x86_64
+          Zones:
+            Type: AWS::SSM::Parameter::Value<List<String>>
+        Resources: {}
+        "#,
+    )
+    .unwrap();
+    let mut ir = CloudformationProgramIr::from(template, crate::cdk::Schema::builtin()).unwrap();
+    ir.lookup_ssm_parameters();
+    let mut output = Vec::new();
+    Typescript::default()
+        .synthesize(ir, &mut output, "Stack")
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(
+        output.contains("import * as ssm from 'aws-cdk-lib/aws-ssm';\n"),
+        "{output}"
+    );
+    assert!(
+        output.contains(
+            "      ami: ssm.StringParameter.valueForStringParameter(this, props.ami ?? '/aws/service/ami-amazon-linux-latest/al2023-ami-kernel-default-x86_64'),
+      zones: new cdk.CfnParameter(this, 'Zones', {
+        type: 'AWS::SSM::Parameter::Value<List<String>>',
+        default: props.zones.join(','),
+      }).valueAsList,
+"
+        ),
+        "{output}"
+    );
+}
+
+#[test]
+fn test_template_options() {
+    let template: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        AWSTemplateFormatVersion: "2010-09-09"
+        Metadata:
+          AWS::CloudFormation::Interface:
+            ParameterLabels:
+              DBPassword:
+                default: Database password
+        Parameters:
+          DBPassword:
+            Type: String
+            NoEcho: true
+        Resources: {}
+        "#,
+    )
+    .unwrap();
+    let ir = CloudformationProgramIr::from(template, crate::cdk::Schema::builtin()).unwrap();
+    let mut output = Vec::new();
+    Typescript::default()
+        .synthesize(ir, &mut output, "Stack")
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(
+        output.contains(
+            "    // Template options
+    this.templateOptions.templateFormatVersion = '2010-09-09';
+    this.templateOptions.metadata = {
+      'AWS::CloudFormation::Interface': {
+        ParameterLabels: {
+          DBPassword: {
+            default: 'Database password',
+          },
+        },
+      },
+    };
+"
+        ),
+        "{output}"
+    );
+    assert!(
+        output.contains("dbPassword: new cdk.CfnParameter(this, 'DBPassword', {"),
+        "{output}"
+    );
+}
+
+#[test]
+fn test_regex_literal() {
+    assert_eq!(regex_literal("a/b\\/c"), "a\\/b\\/c");
+}
+
+#[test]
+fn test_rules() {
+    let template: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Parameters:
+          Environment:
+            Type: String
+        Rules:
+          ProdInstanceType:
+            RuleCondition: !Equals [!Ref Environment, prod]
+            Assertions:
+              - Assert: !Contains [[m5.large, m5.xlarge], !Ref Environment]
+              - Assert: !EachMemberIn [!ValueOf [Environment, Tags], !RefAll AWS::EC2::VPC::Id]
+                AssertDescription: Subnets must be in an existing VPC
+        Resources: {}
+        "#,
+    )
+    .unwrap();
+    let ir = CloudformationProgramIr::from(template, crate::cdk::Schema::builtin()).unwrap();
+    let mut output = Vec::new();
+    Typescript::default()
+        .synthesize(ir, &mut output, "Stack")
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(
+        output.contains(
+            "    // Rules
+    new cdk.CfnRule(this, 'ProdInstanceType', {
+      ruleCondition: cdk.Fn.conditionEquals(props.environment!, 'prod'),
+      assertions: [
+        {
+          assert: cdk.Fn.conditionContains(['m5.large', 'm5.xlarge'], props.environment!),
+          assertDescription: '',
+        },
+        {
+          assert: cdk.Fn.conditionEachMemberIn(cdk.Token.asList(cdk.Fn.valueOf('Environment', 'Tags')), cdk.Fn.refAll('AWS::EC2::VPC::Id')),
+          assertDescription: 'Subnets must be in an existing VPC',
+        },
+      ],
+    });
+"
+        ),
+        "{output}"
+    );
+}
+
+#[test]
+fn test_nested_stacks() {
+    let child: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Parameters:
+          VpcId:
+            Type: String
+        Resources:
+          Group:
+            Type: AWS::EC2::SecurityGroup
+            Properties:
+              GroupDescription: test
+              VpcId: !Ref VpcId
+        Outputs:
+          GroupId:
+            Value: !GetAtt Group.GroupId
+        "#,
+    )
+    .unwrap();
+    let child = CloudformationProgramIr::from(child, crate::cdk::Schema::builtin()).unwrap();
+    let template: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Resources:
+          Vpc:
+            Type: AWS::EC2::VPC
+            Properties:
+              CidrBlock: 10.0.0.0/16
+          Network:
+            Type: AWS::CloudFormation::Stack
+            DependsOn: Vpc
+            Properties:
+              TemplateURL: ./network.yaml
+              Parameters:
+                VpcId: !Ref Vpc
+          Queue:
+            Type: AWS::SQS::Queue
+            DependsOn: Network
+            Properties:
+              QueueName: !GetAtt Network.Outputs.GroupId
+        "#,
+    )
+    .unwrap();
+    let mut diagnostics = crate::ir::diagnostics::Diagnostics::default();
+    let ir = CloudformationProgramIr::from_with_nested_stacks(
+        template,
+        crate::cdk::Schema::builtin(),
+        std::collections::HashMap::from([("Network".to_string(), child)]),
+        &mut diagnostics,
+    );
+    assert!(diagnostics.is_empty(), "{diagnostics}");
+    let mut output = Vec::new();
+    Typescript::default()
+        .synthesize(ir, &mut output, "Stack")
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(
+        output.contains("import { Construct } from 'constructs';\n"),
+        "{output}"
+    );
+    assert!(
+        output.contains(
+            "export class NetworkNestedStack extends cdk.NestedStack {
+  public readonly groupId;
+
+  public constructor(scope: Construct, id: string, props: NetworkNestedStackProps) {"
+        ),
+        "{output}"
+    );
+    assert!(
+        output.contains(
+            "    const network = new NetworkNestedStack(this, 'Network', {
+      vpcId: vpc.ref,
+    });
+    network.node.addDependency(vpc);
+
+    const queue = new sqs.CfnQueue(this, 'Queue', {
+      queueName: network.groupId,
+    });
+    queue.addDependency(network.nestedStackResource!);
+"
+        ),
+        "{output}"
+    );
+    // The nested stack is declared before the stack that uses it.
+    assert!(output.find("class NetworkNestedStack") < output.find("class Stack"));
+}
+
+#[test]
+fn test_l2_constructs() {
+    let template: crate::CloudformationParseTree = serde_yaml::from_str(
+        r#"
+        Resources:
+          Table:
+            Type: AWS::DynamoDB::Table
+            Properties:
+              BillingMode: PAY_PER_REQUEST
+              AttributeDefinitions:
+                - AttributeName: id
+                  AttributeType: S
+              KeySchema:
+                - AttributeName: id
+                  KeyType: HASH
+              StreamSpecification:
+                StreamViewType: NEW_IMAGE
+          Queue:
+            Type: AWS::SQS::Queue
+            DependsOn: Table
+            Properties:
+              QueueName: !Sub "${Table}-queue"
+              KmsMasterKeyId: !GetAtt Table.StreamArn
+        "#,
+    )
+    .unwrap();
+    let mut ir = CloudformationProgramIr::from(template, crate::cdk::Schema::builtin()).unwrap();
+    ir.use_l2_constructs(&crate::ir::constructs::L2Mappings::builtin());
+    let mut output = Vec::new();
+    Typescript::default()
+        .synthesize(ir, &mut output, "Stack")
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(
+        output.contains(
+            "    const table = new dynamodb.Table(this, 'Table', {
+      partitionKey: {
+        name: 'id',
+        type: dynamodb.AttributeType.STRING,
+      },
+      billingMode: dynamodb.BillingMode.PAY_PER_REQUEST,
+    });
+    const cfnTable = table.node.defaultChild as dynamodb.CfnTable;
+    cfnTable.overrideLogicalId('Table');
+    cfnTable.addPropertyOverride('StreamSpecification', {
+      StreamViewType: 'NEW_IMAGE',
+    });
+    cfnTable.cfnOptions.deletionPolicy = cdk.CfnDeletionPolicy.DELETE;
+    cfnTable.cfnOptions.updateReplacePolicy = cdk.CfnDeletionPolicy.DELETE;
+"
+        ),
+        "{output}"
+    );
+    assert!(
+        output.contains(
+            "    const queue = new sqs.Queue(this, 'Queue', {
+      queueName: `${table.tableName}-queue`,
+    });
+    const cfnQueue = queue.node.defaultChild as sqs.CfnQueue;
+    cfnQueue.overrideLogicalId('Queue');
+    cfnQueue.addPropertyOverride('KmsMasterKeyId', cfnTable.attrStreamArn);
+    cfnQueue.addDependency(cfnTable);
+"
+        ),
+        "{output}"
+    );
+}
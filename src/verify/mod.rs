@@ -0,0 +1,402 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use serde_json::{Map, Value};
+
+use crate::ir::sub::{sub_parse_tree, SubValue};
+use crate::Error;
+
+/// What `cdk synth` adds to every stack, by section.
+const CDK_ADDITIONS: [(&str, &str); 4] = [
+    ("Resources", "CDKMetadata"),
+    ("Conditions", "CDKMetadataAvailable"),
+    ("Parameters", "BootstrapVersion"),
+    ("Rules", "CheckBootstrapVersion"),
+];
+
+/// The prefixes of the resource metadata keys the CDK adds (e.g: `aws:cdk:path`).
+const CDK_METADATA_PREFIXES: [&str; 2] = ["aws:cdk:", "aws:asset:"];
+
+/// Parses a JSON or YAML template into JSON, expanding the short form of intrinsic functions
+/// (e.g: `!Ref Bucket` becomes `{"Ref": "Bucket"}`).
+pub fn parse(source: &str) -> Result<Value, Error> {
+    let value: serde_yaml::Value = serde_yaml::from_str(source)?;
+    Ok(yaml_to_json(value))
+}
+
+fn yaml_to_json(value: serde_yaml::Value) -> Value {
+    match value {
+        serde_yaml::Value::Null => Value::Null,
+        serde_yaml::Value::Bool(value) => Value::Bool(value),
+        serde_yaml::Value::Number(number) => {
+            if let Some(number) = number.as_i64() {
+                number.into()
+            } else if let Some(number) = number.as_u64() {
+                number.into()
+            } else {
+                number.as_f64().map_or(Value::Null, Value::from)
+            }
+        }
+        serde_yaml::Value::String(value) => Value::String(value),
+        serde_yaml::Value::Sequence(items) => {
+            Value::Array(items.into_iter().map(yaml_to_json).collect())
+        }
+        serde_yaml::Value::Mapping(entries) => Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (scalar_text(yaml_to_json(key)), yaml_to_json(value)))
+                .collect(),
+        ),
+        serde_yaml::Value::Tagged(tagged) => {
+            let tag = tagged.tag.to_string();
+            let name = tag.trim_start_matches('!');
+            let key = match name {
+                "Ref" | "Condition" => name.to_string(),
+                _ => format!("Fn::{name}"),
+            };
+            Value::Object(Map::from_iter([(key, yaml_to_json(tagged.value))]))
+        }
+    }
+}
+
+/// Normalizes `template` so that templates that deploy the same resources compare equal: what
+/// `cdk synth` adds (the `CDKMetadata` resource, bootstrap checks, `aws:cdk:*` metadata) and
+/// empty sections are removed, scalars are compared as the strings CloudFormation sees, tags and
+/// `DependsOn` are sorted, `Fn::GetAtt` uses its list form, and `Fn::Sub` and `Fn::Join` are
+/// rewritten as the concatenation of their literal text and references (which is how the CDK
+/// renders interpolated strings). Key order never matters.
+pub fn normalize(template: &Value) -> Value {
+    let mut template = template.clone();
+    if let Value::Object(sections) = &mut template {
+        for (section, name) in CDK_ADDITIONS {
+            if let Some(Value::Object(entries)) = sections.get_mut(section) {
+                entries.remove(name);
+            }
+        }
+        if let Some(Value::Object(resources)) = sections.get_mut("Resources") {
+            for resource in resources.values_mut() {
+                if let Value::Object(attributes) = resource {
+                    normalize_attributes(attributes);
+                }
+            }
+        }
+        sections.retain(|_, value| !is_empty(value));
+    }
+    expression(template)
+}
+
+fn normalize_attributes(attributes: &mut Map<String, Value>) {
+    if let Some(Value::Object(metadata)) = attributes.get_mut("Metadata") {
+        metadata.retain(|key, _| {
+            !CDK_METADATA_PREFIXES
+                .iter()
+                .any(|prefix| key.starts_with(prefix))
+        });
+    }
+    if let Some(depends_on) = attributes.get_mut("DependsOn") {
+        let mut names: Vec<Value> = match depends_on.take() {
+            Value::Array(names) => names,
+            name => vec![name],
+        };
+        names.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+        names.dedup();
+        *depends_on = Value::Array(names);
+    }
+    attributes.retain(|_, value| !is_empty(value));
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Object(entries) => entries.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+// Normalizes a value and the intrinsic functions within it.
+fn expression(value: Value) -> Value {
+    match value {
+        Value::Bool(_) | Value::Number(_) => Value::String(scalar_text(value)),
+        Value::Array(items) => Value::Array(items.into_iter().map(expression).collect()),
+        Value::Object(entries) if entries.len() == 1 => {
+            let (key, value) = entries.into_iter().next().unwrap();
+            match (key.as_str(), value) {
+                ("Fn::GetAtt", Value::String(attribute)) => {
+                    let names: Vec<Value> = match attribute.split_once('.') {
+                        Some((name, attribute)) => vec![name.into(), attribute.into()],
+                        None => vec![attribute.into()],
+                    };
+                    function("Fn::GetAtt", Value::Array(names))
+                }
+                ("Fn::Sub", value) => sub(value),
+                ("Fn::Join", Value::Array(arguments)) => join(arguments),
+                (_, value) => function(&key, value),
+            }
+        }
+        Value::Object(entries) => Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| {
+                    let mut value = expression(value);
+                    if key == "Tags" {
+                        sort_tags(&mut value);
+                    }
+                    (key, value)
+                })
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+fn function(name: &str, arguments: Value) -> Value {
+    Value::Object(Map::from_iter([(name.to_string(), expression(arguments))]))
+}
+
+fn scalar_text(value: Value) -> String {
+    match value {
+        Value::String(text) => text,
+        other => other.to_string(),
+    }
+}
+
+// Sorts a list of `{Key, Value}` tags by key, as the CDK renders them.
+fn sort_tags(tags: &mut Value) {
+    let Value::Array(tags) = tags else {
+        return;
+    };
+    if tags
+        .iter()
+        .all(|tag| tag.get("Key").is_some_and(Value::is_string))
+    {
+        tags.sort_by(|a, b| a["Key"].as_str().cmp(&b["Key"].as_str()));
+    }
+}
+
+// Rewrites `Fn::Sub` as the concatenation of its text and of the references it holds.
+fn sub(arguments: Value) -> Value {
+    let (text, variables) = match arguments {
+        Value::String(text) => (text, Map::new()),
+        Value::Array(arguments) => match <[Value; 2]>::try_from(arguments) {
+            Ok([Value::String(text), Value::Object(variables)]) => (text, variables),
+            Ok(arguments) => return function("Fn::Sub", Value::Array(arguments.into())),
+            Err(arguments) => return function("Fn::Sub", Value::Array(arguments)),
+        },
+        arguments => return function("Fn::Sub", arguments),
+    };
+    let Ok(parts) = sub_parse_tree(&text) else {
+        return function("Fn::Sub", Value::String(text));
+    };
+    let parts = parts
+        .into_iter()
+        .map(|part| match part {
+            SubValue::String(text) => Value::String(text),
+            SubValue::Variable(name) => match variables.get(&name) {
+                Some(value) => value.clone(),
+                None => match name.split_once('.') {
+                    Some((name, attribute)) => Value::Object(Map::from_iter([(
+                        "Fn::GetAtt".to_string(),
+                        Value::Array(vec![name.into(), attribute.into()]),
+                    )])),
+                    None => Value::Object(Map::from_iter([("Ref".to_string(), name.into())])),
+                },
+            },
+        })
+        .collect();
+    join(vec![Value::String(String::new()), Value::Array(parts)])
+}
+
+// Folds the literal items of `Fn::Join`, and flattens the nested concatenations it holds.
+fn join(arguments: Vec<Value>) -> Value {
+    let (delimiter, items) = match <[Value; 2]>::try_from(arguments) {
+        Ok([Value::String(delimiter), Value::Array(items)]) => (delimiter, items),
+        Ok(arguments) => return function("Fn::Join", Value::Array(arguments.into())),
+        Err(arguments) => return function("Fn::Join", Value::Array(arguments)),
+    };
+    let items: Vec<Value> = items.into_iter().map(expression).collect();
+
+    if delimiter.is_empty() {
+        let mut parts: Vec<Value> = Vec::with_capacity(items.len());
+        for part in items.into_iter().flat_map(concatenated) {
+            match (parts.last_mut(), part) {
+                (_, Value::String(text)) if text.is_empty() => {}
+                (Some(Value::String(previous)), Value::String(text)) => previous.push_str(&text),
+                (_, part) => parts.push(part),
+            }
+        }
+        return match parts.len() {
+            0 => Value::String(String::new()),
+            1 => parts.pop().unwrap(),
+            _ => concatenation("", parts),
+        };
+    }
+
+    if items.iter().all(Value::is_string) {
+        let texts: Vec<&str> = items.iter().filter_map(Value::as_str).collect();
+        return Value::String(texts.join(&delimiter));
+    }
+    concatenation(&delimiter, items)
+}
+
+// The parts of `value` if it is a normalized `Fn::Join` with an empty delimiter, or `value` itself.
+fn concatenated(value: Value) -> Vec<Value> {
+    if let Value::Object(entries) = &value {
+        if let (1, Some(Value::Array(arguments))) = (entries.len(), entries.get("Fn::Join")) {
+            if let [Value::String(delimiter), Value::Array(parts)] = arguments.as_slice() {
+                if delimiter.is_empty() {
+                    return parts.clone();
+                }
+            }
+        }
+    }
+    vec![value]
+}
+
+fn concatenation(delimiter: &str, items: Vec<Value>) -> Value {
+    Value::Object(Map::from_iter([(
+        "Fn::Join".to_string(),
+        Value::Array(vec![delimiter.into(), Value::Array(items)]),
+    )]))
+}
+
+/// Semantic diff of JSON templates: the keys the actual template lacks or has in excess, and the
+/// values that differ, each located by its path (e.g: `Resources.Bucket.Type`). The test harness
+/// compares synthesized templates with it too.
+pub struct TemplateDiff;
+
+impl TemplateDiff {
+    /// Compares the `original` template with the one `cdk synth` produced from its conversion,
+    /// both JSON or YAML, once they are normalized (see [`normalize`]). No differences means the
+    /// migrated stack deploys the same thing.
+    pub fn verify(original: &str, synthesized: &str) -> Result<Vec<String>, Error> {
+        Ok(Self::differences(
+            &normalize(&parse(original)?),
+            &normalize(&parse(synthesized)?),
+        ))
+    }
+
+    /// Generates a report of the differences between `expected` and `actual`.
+    pub fn report(expected: &Value, actual: &Value) -> String {
+        Self::format(&Self::differences(expected, actual))
+    }
+
+    /// Formats the `differences` found by [`TemplateDiff::differences`] as a report.
+    pub fn format(differences: &[String]) -> String {
+        if differences.is_empty() {
+            "No differences found".to_string()
+        } else {
+            format!("Differences found:\n{}", differences.join("\n\n"))
+        }
+    }
+
+    /// Describes each difference between `expected` and `actual`.
+    pub fn differences(expected: &Value, actual: &Value) -> Vec<String> {
+        let mut diff_lines = Vec::new();
+        Self::find_differences(expected, actual, "", &mut diff_lines);
+        diff_lines
+    }
+
+    fn find_differences(
+        expected: &Value,
+        actual: &Value,
+        path: &str,
+        diff_lines: &mut Vec<String>,
+    ) {
+        match (expected, actual) {
+            (Value::Object(exp_map), Value::Object(act_map)) => {
+                for (key, exp_val) in exp_map {
+                    let current_path = Self::build_path(path, key);
+                    match act_map.get(key) {
+                        Some(act_val) => {
+                            Self::find_differences(exp_val, act_val, &current_path, diff_lines)
+                        }
+                        None => {
+                            Self::add_missing_key_diff(&current_path, exp_val, diff_lines);
+                        }
+                    }
+                }
+                for (key, act_val) in act_map {
+                    if !exp_map.contains_key(key) {
+                        let current_path = Self::build_path(path, key);
+                        Self::add_extra_key_diff(&current_path, act_val, diff_lines);
+                    }
+                }
+            }
+            (Value::Array(exp_arr), Value::Array(act_arr)) => {
+                if exp_arr.len() != act_arr.len() {
+                    diff_lines.push(format!(
+                        "Array length mismatch at {}: expected {}, got {}",
+                        path,
+                        exp_arr.len(),
+                        act_arr.len()
+                    ));
+                }
+                for (i, (exp_item, act_item)) in exp_arr.iter().zip(act_arr.iter()).enumerate() {
+                    let current_path = format!("{path}[{i}]");
+                    Self::find_differences(exp_item, act_item, &current_path, diff_lines);
+                }
+            }
+            (exp, act) if exp != act => {
+                Self::add_value_mismatch_diff(path, exp, act, diff_lines);
+            }
+            _ => {}
+        }
+    }
+
+    // The dot-notation path of `key` within `base`.
+    fn build_path(base: &str, key: &str) -> String {
+        if base.is_empty() {
+            key.to_string()
+        } else {
+            format!("{base}.{key}")
+        }
+    }
+
+    fn pretty_print_json(value: &Value) -> String {
+        serde_json::to_string_pretty(value).unwrap_or_else(|_| format!("{value:?}"))
+    }
+
+    fn indent(text: &str, spaces: usize) -> String {
+        let indent_str = " ".repeat(spaces);
+        text.lines()
+            .map(|line| format!("{indent_str}{line}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn add_missing_key_diff(path: &str, value: &Value, diff_lines: &mut Vec<String>) {
+        let pretty = Self::pretty_print_json(value);
+        diff_lines.push(format!(
+            "- Missing key: {}\n  Expected:\n{}",
+            path,
+            Self::indent(&pretty, 4)
+        ));
+    }
+
+    fn add_extra_key_diff(path: &str, value: &Value, diff_lines: &mut Vec<String>) {
+        let pretty = Self::pretty_print_json(value);
+        diff_lines.push(format!(
+            "+ Extra key: {}\n  Actual:\n{}",
+            path,
+            Self::indent(&pretty, 4)
+        ));
+    }
+
+    fn add_value_mismatch_diff(
+        path: &str,
+        expected: &Value,
+        actual: &Value,
+        diff_lines: &mut Vec<String>,
+    ) {
+        let exp_pretty = Self::pretty_print_json(expected);
+        let act_pretty = Self::pretty_print_json(actual);
+        diff_lines.push(format!(
+            "Value mismatch at {}:\n  Expected:\n{}\n  Actual:\n{}",
+            path,
+            Self::indent(&exp_pretty, 4),
+            Self::indent(&act_pretty, 4)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests;
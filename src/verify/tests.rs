@@ -0,0 +1,201 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+use serde_json::json;
+
+use super::*;
+
+const ORIGINAL: &str = r#"
+Parameters:
+  Env:
+    Type: String
+Resources:
+  Queue:
+    Type: AWS::SQS::Queue
+    DependsOn: Topic
+    Properties:
+      DelaySeconds: 5
+      QueueName: !Sub ${Env}-queue-${AWS::Region}
+      Tags:
+        - Key: team
+          Value: core
+        - Key: owner
+          Value: !GetAtt Topic.TopicName
+  Topic:
+    Type: AWS::SNS::Topic
+    Properties:
+      TopicName: !Join ['-', [my, topic]]
+"#;
+
+#[test]
+fn equivalent_templates() {
+    let synthesized = json!({
+        "Parameters": {
+            "Env": { "Type": "String" },
+            "BootstrapVersion": { "Type": "AWS::SSM::Parameter::Value<String>" },
+        },
+        "Resources": {
+            "Topic": {
+                "Type": "AWS::SNS::Topic",
+                "Properties": { "TopicName": "my-topic" },
+                "Metadata": { "aws:cdk:path": "Stack/Topic" },
+            },
+            "Queue": {
+                "Type": "AWS::SQS::Queue",
+                "Properties": {
+                    "QueueName": {
+                        "Fn::Join": ["", [{ "Ref": "Env" }, "-queue-", { "Ref": "AWS::Region" }]],
+                    },
+                    "DelaySeconds": "5",
+                    "Tags": [
+                        { "Key": "owner", "Value": { "Fn::GetAtt": ["Topic", "TopicName"] } },
+                        { "Key": "team", "Value": "core" },
+                    ],
+                },
+                "DependsOn": ["Topic"],
+                "Metadata": { "aws:cdk:path": "Stack/Queue" },
+            },
+            "CDKMetadata": {
+                "Type": "AWS::CDK::Metadata",
+                "Properties": { "Analytics": "v2:deflate64:..." },
+                "Condition": "CDKMetadataAvailable",
+            },
+        },
+        "Conditions": {
+            "CDKMetadataAvailable": { "Fn::Equals": [{ "Ref": "AWS::Region" }, "us-east-1"] },
+        },
+        "Rules": {
+            "CheckBootstrapVersion": { "Assertions": [] },
+        },
+    });
+
+    let differences = TemplateDiff::verify(ORIGINAL, &synthesized.to_string()).unwrap();
+    assert!(differences.is_empty(), "{differences:?}");
+    assert_eq!(TemplateDiff::format(&differences), "No differences found");
+}
+
+#[test]
+fn rewrites_keeping_the_logical_id() {
+    // The CDK renders each resource under the same logical ID, but orders the resources and
+    // their keys its own way, and writes the intrinsic functions in their long form.
+    let original = r#"
+    Resources:
+      Topic:
+        Type: AWS::SNS::Topic
+        Properties:
+          TopicName: !Sub ${AWS::StackName}-topic
+      Queue:
+        Type: AWS::SQS::Queue
+        DependsOn: [Topic, Key]
+        Properties:
+          KmsMasterKeyId: !GetAtt Key.Arn
+          MaximumMessageSize: 1024
+          FifoQueue: true
+      Key:
+        Type: AWS::KMS::Key
+    "#;
+    let synthesized = r#"{
+        "Resources": {
+            "Key": {
+                "Type": "AWS::KMS::Key",
+                "Metadata": { "aws:cdk:path": "Stack/Key" }
+            },
+            "Queue": {
+                "Properties": {
+                    "FifoQueue": true,
+                    "KmsMasterKeyId": { "Fn::GetAtt": ["Key", "Arn"] },
+                    "MaximumMessageSize": "1024"
+                },
+                "DependsOn": ["Key", "Topic"],
+                "Type": "AWS::SQS::Queue"
+            },
+            "Topic": {
+                "Type": "AWS::SNS::Topic",
+                "Properties": {
+                    "TopicName": { "Fn::Join": ["", [{ "Ref": "AWS::StackName" }, "-topic"]] }
+                }
+            }
+        }
+    }"#;
+
+    let differences = TemplateDiff::verify(original, synthesized).unwrap();
+    assert!(differences.is_empty(), "{differences:?}");
+
+    // A resource renamed by the conversion is a difference, even if it is otherwise the same.
+    let renamed = synthesized.replace("\"Key\"", "\"Key7F3A\"");
+    let differences = TemplateDiff::verify(original, &renamed).unwrap();
+    assert!(differences
+        .iter()
+        .any(|difference| difference.starts_with("- Missing key: Resources.Key\n")));
+}
+
+#[test]
+fn different_templates() {
+    let synthesized = r#"{
+        "Parameters": { "Env": { "Type": "Number" } },
+        "Resources": {
+            "Queue": {
+                "Type": "AWS::SQS::Queue",
+                "DependsOn": "Topic",
+                "Properties": {
+                    "QueueName": { "Fn::Sub": "${Env}-queue-${AWS::Region}" },
+                    "Tags": [{ "Key": "team", "Value": "core" }]
+                }
+            },
+            "Topic": {
+                "Type": "AWS::SNS::Topic",
+                "Properties": { "TopicName": "my-topic", "DisplayName": "Topic" }
+            }
+        }
+    }"#;
+
+    let differences = TemplateDiff::verify(ORIGINAL, synthesized).unwrap();
+    assert_eq!(
+        TemplateDiff::format(&differences),
+        r#"Differences found:
+Value mismatch at Parameters.Env.Type:
+  Expected:
+    "String"
+  Actual:
+    "Number"
+
+- Missing key: Resources.Queue.Properties.DelaySeconds
+  Expected:
+    "5"
+
+Array length mismatch at Resources.Queue.Properties.Tags: expected 2, got 1
+
+Value mismatch at Resources.Queue.Properties.Tags[0].Key:
+  Expected:
+    "owner"
+  Actual:
+    "team"
+
+Value mismatch at Resources.Queue.Properties.Tags[0].Value:
+  Expected:
+    {
+      "Fn::GetAtt": [
+        "Topic",
+        "TopicName"
+      ]
+    }
+  Actual:
+    "core"
+
++ Extra key: Resources.Topic.Properties.DisplayName
+  Actual:
+    "Topic""#
+    );
+}
+
+#[test]
+fn sub_with_variables() {
+    let original = json!({
+        "Fn::Sub": ["${Name}.${Bucket.Arn}/${!Literal}", { "Name": { "Fn::Join": ["", ["a", "b"]] } }],
+    });
+    assert_eq!(
+        normalize(&original),
+        json!({
+            "Fn::Join": ["", ["ab.", { "Fn::GetAtt": ["Bucket", "Arn"] }, "/${Literal}"]],
+        })
+    );
+}